@@ -0,0 +1,682 @@
+// Typed bindings for the Intellex reputation contract.
+//
+// Consumer contracts call the reputation contract through `ext_reputation` instead of
+// hand-writing `json!` payloads, and decode its views and NEP-297 events with the types
+// below. The types mirror the contract's JSON ABI and must be kept in sync with it.
+
+// Methods mirror the contract's JSON ABI, so their argument counts follow it
+#![allow(clippy::too_many_arguments)]
+
+use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::serde_json::Value;
+use near_sdk::{ext_contract, AccountId, Balance, PromiseOrValue};
+
+// NEP-297 standard name and version of the events emitted by the contract
+pub const EVENT_STANDARD: &str = "intellex_reputation";
+pub const EVENT_STANDARD_VERSION: &str = "1.0.0";
+
+// Prefix of NEP-297 event logs
+const EVENT_LOG_PREFIX: &str = "EVENT_JSON:";
+
+#[ext_contract(ext_reputation)]
+pub trait Reputation {
+    // Agents
+    fn get_agent_reputation(&self, agent_id: AccountId) -> Option<AgentReputationView>;
+    fn get_agent_reputation_detailed(&self, agent_id: AccountId) -> Option<AgentReputationDetailedView>;
+    fn get_trust_level(&self, score: u32) -> TrustLevel;
+    fn get_capability_limits(&self, agent_id: AccountId, specialization: Option<String>) -> Option<CapabilityLimitsView>;
+    fn get_agents_bulk(&self, agent_ids: Vec<AccountId>) -> Vec<Option<AgentReputationDetailedView>>;
+    fn get_capability_limits_bulk(&self, agent_ids: Vec<AccountId>) -> Vec<Option<CapabilityLimitsView>>;
+    fn get_agent_count(&self) -> u64;
+    fn get_agents(&self, from_index: Option<u64>, limit: Option<u32>) -> Vec<AgentSummaryView>;
+    fn get_recently_updated_agents(&self, since_timestamp: u64, limit: Option<u32>) -> Vec<AgentSummaryView>;
+    fn get_reputation_history(&self, agent_id: AccountId, from_ts: u64, to_ts: u64) -> Vec<ReputationSnapshot>;
+    fn get_pending_rewards(&self, agent_id: AccountId) -> U128;
+    fn get_stake_health(&self, agent_id: AccountId) -> Option<StakeHealthView>;
+    fn resolve_identity(&self, identifier: String) -> Option<IdentityView>;
+    fn get_agent_status(&self, agent_id: AccountId) -> Option<AgentStatusView>;
+    fn get_agent_profile(&self, agent_id: AccountId) -> Option<AgentProfile>;
+    fn get_endorsements(&self, agent_id: AccountId) -> Vec<EndorsementView>;
+
+    // Numeric-code views; `get_code_map` lists what each code means
+    fn get_code_map(&self) -> CodeMapView;
+    fn get_agent_reputation_numeric(&self, agent_id: AccountId) -> Option<AgentReputationNumericView>;
+    fn get_intent_numeric(&self, intent_id: String) -> Option<IntentNumericView>;
+
+    // Authorization
+    fn can_perform_action(&self, agent_id: AccountId, action_type: String, value: Option<Balance>) -> bool;
+    fn authorize_action(&mut self, agent_id: AccountId, action: String, value: Option<U128>) -> bool;
+    fn simulate_authorization(
+        &self,
+        agent_id: AccountId,
+        action: String,
+        value: Option<U128>,
+        consumer_id: Option<AccountId>,
+    ) -> AuthorizationSimulationView;
+    fn consume_capability(&mut self, action_type: String, value: Option<U128>, agent_id: Option<AccountId>);
+    fn get_remaining_quota(&self, agent_id: AccountId) -> Vec<RemainingQuotaView>;
+    fn issue_capability_attestation(&mut self, agent_id: AccountId) -> IssuedAttestation;
+    fn verify_attestation(&self, payload: Base64VecU8, nonce: u64) -> bool;
+
+    // Discovery
+    fn get_top_agents(&self, limit: u32, offset: u32) -> Vec<RankedAgentView>;
+    fn get_top_agents_by_category(&self, category: FeedbackCategory, limit: u32) -> Vec<RankedAgentView>;
+    fn get_agents_by_trust_level(&self, level: TrustLevel, from_index: u32, limit: u32) -> Vec<RankedAgentView>;
+    fn find_agents_by_specialization(
+        &self,
+        tag: String,
+        min_score: Option<u32>,
+        audited_only: Option<bool>,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<RankedAgentView>;
+    fn recommend_agents(&self, intent_type: String, max_value: Option<U128>, limit: Option<u32>) -> Vec<AgentRecommendationView>;
+    fn get_audit_badge(&self, agent_id: AccountId) -> Option<u64>;
+
+    // Intents
+    fn record_intent(
+        &mut self,
+        intent_id: String,
+        agent_id: AccountId,
+        intent_type: String,
+        parameters: String,
+        deadline: Option<u64>,
+        referrer_id: Option<AccountId>,
+    );
+    fn record_intent_for_client(
+        &mut self,
+        intent_id: String,
+        agent_id: AccountId,
+        client_id: AccountId,
+        intent_type: String,
+        parameters: String,
+        deadline: Option<u64>,
+        referrer_id: Option<AccountId>,
+    );
+    fn update_intent_status(&mut self, intent_id: String, status: IntentStatus, result: Option<String>, nonce: Option<u64>);
+    fn accept_intent(&mut self, intent_id: String);
+    fn decline_intent(&mut self, intent_id: String, reason: Option<String>);
+    fn post_checkpoint(&mut self, intent_id: String, percentage: u8, note: String, hash: Option<Base64VecU8>);
+    fn approve_checkpoint(&mut self, intent_id: String, checkpoint_index: u32);
+    fn expire_intent(&mut self, intent_id: String);
+    fn fail_intent(&mut self, intent_id: String, category: FailureCategory, result: Option<String>);
+    fn record_delegation(&mut self, parent_intent_id: String, sub_agent_id: AccountId);
+    fn accept_delegation(&mut self, parent_intent_id: String);
+    fn decline_delegation(&mut self, parent_intent_id: String);
+    fn get_delegation_tree(&self, intent_id: String) -> Vec<TaskDelegationView>;
+    fn dispute_intent(&mut self, intent_id: String, reason: String);
+    fn finalize_intent(&mut self, intent_id: String);
+    fn get_intent(&self, intent_id: String) -> Option<IntentView>;
+    fn get_fee_rate(&self, agent_id: AccountId) -> FeeRateView;
+    fn get_fee_config(&self) -> FeeConfigView;
+    fn get_expired_intents(&self, limit: u32) -> Vec<String>;
+
+    // Insurance
+    fn file_insurance_claim(&mut self, incident_id: u64, intent_id: String, amount: U128, description: String) -> u64;
+    fn get_insurance_pool(&self) -> InsurancePoolView;
+    fn get_insurance_claim(&self, claim_id: u64) -> Option<InsuranceClaimView>;
+
+    // Cross-chain
+    fn verify_export(&self, payload: Base64VecU8, nonce: u64) -> bool;
+
+    // Feedback
+    fn add_feedback(
+        &mut self,
+        agent_id: AccountId,
+        intent_id: String,
+        rating: u8,
+        category_ratings: CategoryRatings,
+        message: Option<String>,
+        anonymous: Option<bool>,
+    );
+
+    fn get_query_credit(&self, consumer_id: AccountId) -> U128;
+
+    // NEP-141 receiver for staking, stake-and-register, ITLX intent escrow, query credit, audit
+    // bounties, stake delegation and reward funding; other accepted staking tokens can only be
+    // staked
+    fn ft_on_transfer(&mut self, sender_id: AccountId, amount: U128, msg: String) -> PromiseOrValue<U128>;
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum TrustLevel {
+    Novice,
+    Apprentice,
+    Trusted,
+    Expert,
+    Master,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum FeedbackCategory {
+    Accuracy,
+    ResponseTime,
+    Communication,
+    ProblemSolving,
+    Ethics,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum IntentStatus {
+    Created,
+    Accepted,
+    Declined,
+    InProgress,
+    Completed,
+    Failed,
+    Disputed,
+    Cancelled,
+    Expired,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum FailureCategory {
+    Timeout,
+    WrongResult,
+    PartialCompletion,
+    ClientCancelled,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum EscrowAsset {
+    Near,
+    Itlx,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum AgentStatus {
+    Active,
+    Probation,
+    Suspended,
+    Banned,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentProfile {
+    pub display_name: String,
+    pub description: String,
+    pub endpoint_url: Option<String>,
+    pub tags: Vec<String>,
+    pub icon_hash: Option<Base64VecU8>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentSummaryView {
+    pub agent_id: AccountId,
+    pub score: u32,
+    pub trust_level: TrustLevel,
+    pub status: AgentStatus,
+    pub total_interactions: u64,
+    pub successful_interactions: u64,
+    pub registered_at: u64,
+    pub updated_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StakeHealthView {
+    pub stake: U128,
+    pub min_stake: U128,
+    pub below_minimum: bool,
+    pub shortfall_since: Option<u64>,
+    pub grace_ends_at: Option<u64>,
+    pub grace_remaining: Option<u64>,
+    pub penalized: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EndorsementView {
+    pub endorser: AccountId,
+    pub specialization: String,
+    pub weight_bps: u32,
+    pub endorsed_at: u64,
+    pub current_weight_bps: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentStatusView {
+    pub status: AgentStatus,
+    pub since: u64,
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum AuthorizationRule {
+    AgentNotRegistered,
+    AgentSuspended,
+    UnknownAction,
+    MissingValue,
+    TrustLimit,
+    CapabilityNotGranted,
+    Allowed,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CategoryRatings {
+    pub accuracy: u8,
+    pub response_time: u8,
+    pub communication: u8,
+    pub problem_solving: u8,
+    pub ethics: u8,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentReputationView {
+    pub score: u32,
+    pub total_interactions: u64,
+    pub successful_interactions: u64,
+    pub specializations: Vec<String>,
+    pub last_update: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DisputeStatsView {
+    pub opened: u64,
+    pub won: u64,
+    pub lost: u64,
+    pub pending: u64,
+    pub finished_intents: u64,
+    pub average_resolution_time: u64,
+    pub dispute_rate_per_100_bps: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentReputationDetailedView {
+    pub score: u32,
+    // The score at full precision (basis points, 0-10000)
+    pub score_bps: u32,
+    // Approximate 95% credible interval of the score given how much feedback backs it
+    pub score_lower_bound: u32,
+    pub score_upper_bound: u32,
+    pub total_interactions: u64,
+    pub successful_interactions: u64,
+    pub specializations: Vec<String>,
+    pub last_update: u64,
+    pub trust_level: TrustLevel,
+    pub status: AgentStatus,
+    pub category_scores: CategoryRatings,
+    pub reliability_score: u32,
+    pub liveness_score: u32,
+    pub dispute_stats: DisputeStatsView,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CapabilityLimitsView {
+    pub max_complexity: u8,
+    pub max_transaction_value: Balance,
+    pub can_access_critical_systems: bool,
+    pub can_operate_autonomously: bool,
+    pub can_delegate: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReputationSnapshot {
+    pub score: u32,
+    pub trust_level: TrustLevel,
+    pub timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuthorizationSimulationView {
+    pub agent_id: AccountId,
+    pub action: String,
+    pub consumer_id: Option<AccountId>,
+    pub allowed: bool,
+    pub rule: AuthorizationRule,
+    pub message: String,
+    pub trust_level: Option<TrustLevel>,
+    pub limit: Option<U128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IssuedAttestation {
+    pub nonce: u64,
+    pub payload: Base64VecU8,
+    pub payload_hash: Base64VecU8,
+    pub expires_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RemainingQuotaView {
+    pub action_type: String,
+    pub used_actions: u32,
+    pub remaining_actions: u32,
+    pub used_value: U128,
+    pub remaining_value: Option<U128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RankedAgentView {
+    pub agent_id: AccountId,
+    pub score: u32,
+    pub trust_level: TrustLevel,
+    pub category_score: Option<u8>,
+    pub audited: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentRecommendationView {
+    pub agent_id: AccountId,
+    pub score: u32,
+    pub specialization_score: u32,
+    pub specialization_score_bps: u32,
+    pub trust_level: TrustLevel,
+    pub max_transaction_value: U128,
+    pub audited: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IntentView {
+    pub intent_id: String,
+    pub agent_id: AccountId,
+    pub client_id: AccountId,
+    pub intent_type: String,
+    pub parameters: String,
+    pub status: IntentStatus,
+    pub result: Option<String>,
+    pub timestamp: u64,
+    pub updated_at: u64,
+    pub response_deadline: u64,
+    pub deadline: u64,
+    pub accepted_at: Option<u64>,
+    pub feedback_submitted: bool,
+    pub escrow_amount: U128,
+    pub escrow_asset: EscrowAsset,
+    pub escrow_released: U128,
+    pub escrow_refunded: U128,
+    pub disputed_at: Option<u64>,
+    pub dispute_reason: Option<String>,
+    pub finalized: bool,
+    pub deadline_extension: u64,
+    pub agreement_id: Option<u64>,
+    pub failure_category: Option<FailureCategory>,
+    pub referrer_id: Option<AccountId>,
+    // Nonce the next status update must quote
+    pub nonce: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TaskDelegationView {
+    pub delegator: AccountId,
+    pub sub_agent: AccountId,
+    pub depth: u8,
+    pub delegated_at: u64,
+    pub accepted_at: Option<u64>,
+    pub outcome_share_bps: u32,
+    pub finalized: bool,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum ClaimStatus {
+    Pending,
+    Approved,
+    Paid,
+    Rejected,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InsuranceConfig {
+    pub pool_share_bps: u16,
+    pub claim_window: u64,
+    pub max_incident_payout: U128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InsurancePoolView {
+    pub balance: U128,
+    pub incident_count: u64,
+    pub claim_count: u64,
+    pub config: InsuranceConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InsuranceClaimView {
+    pub claim_id: u64,
+    pub incident_id: u64,
+    pub intent_id: String,
+    pub claimant: AccountId,
+    pub requested: U128,
+    pub description: String,
+    pub status: ClaimStatus,
+    pub payout: U128,
+    pub filed_at: u64,
+    pub resolution: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeRateView {
+    pub base_fee_bps: u16,
+    pub discount_bps: u16,
+    pub effective_fee_bps: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeDiscountTier {
+    pub min_trust_level: TrustLevel,
+    pub max_dispute_rate_bps: u32,
+    pub discount_bps: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeSplit {
+    pub treasury_bps: u16,
+    pub insurance_bps: u16,
+    pub referrer_bps: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeConfigView {
+    pub protocol_fee_bps: u16,
+    pub fee_discount_tiers: Vec<FeeDiscountTier>,
+    pub fee_split: FeeSplit,
+}
+
+// Canonical identity shared by an agent's NEAR account, linked foreign addresses, realm
+// memberships and DID
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IdentityView {
+    pub identity_id: u64,
+    pub near_account: AccountId,
+    pub foreign_addresses: Vec<ForeignAddress>,
+    pub realms: Vec<RealmMembership>,
+    pub did: Option<String>,
+    pub created_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ForeignAddress {
+    // CAIP-10 account ID, e.g. "eip155:8453:0xab…"
+    pub account_id: String,
+    pub linked_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RealmMembership {
+    pub realm: String,
+    pub member_id: String,
+    pub recorded_by: AccountId,
+    pub recorded_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CodeMapView {
+    pub trust_levels: Vec<(u8, String)>,
+    pub intent_statuses: Vec<(u8, String)>,
+    pub violation_types: Vec<(u8, String)>,
+    pub escrow_assets: Vec<(u8, String)>,
+    pub feedback_categories: Vec<(u8, String)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentReputationNumericView {
+    pub score: u32,
+    pub trust_level: u8,
+    pub total_interactions: u64,
+    pub successful_interactions: u64,
+    pub category_scores: [u8; 5],
+    pub reliability_score: u32,
+    pub violation_count: u32,
+    pub last_update: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IntentNumericView {
+    pub intent_id: String,
+    pub agent_id: AccountId,
+    pub client_id: AccountId,
+    pub status: u8,
+    pub timestamp: u64,
+    pub updated_at: u64,
+    pub deadline: u64,
+    pub escrow_amount: U128,
+    pub escrow_asset: u8,
+    pub finalized: bool,
+}
+
+// ft_transfer_call message understood by the contract's ft_on_transfer
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde", tag = "action", rename_all = "snake_case")]
+pub enum TransferMessage {
+    Stake,
+    Register { specializations: Vec<String> },
+    RecordIntent {
+        intent_id: String,
+        agent_id: AccountId,
+        intent_type: String,
+        parameters: String,
+        deadline: Option<u64>,
+        referrer_id: Option<AccountId>,
+    },
+    DepositQueryCredit,
+}
+
+// NEP-297 envelope of an event emitted by the contract
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EventLog {
+    pub standard: String,
+    pub version: String,
+    pub event: String,
+    pub data: Vec<Value>,
+}
+
+impl EventLog {
+    // Parse a receipt log line, returning None for logs that are not reputation events
+    pub fn parse(log: &str) -> Option<Self> {
+        let payload = log.strip_prefix(EVENT_LOG_PREFIX)?;
+        let event: EventLog = near_sdk::serde_json::from_str(payload).ok()?;
+        (event.standard == EVENT_STANDARD).then_some(event)
+    }
+
+    // Decode the first data entry as a typed event payload
+    pub fn data_as<T: for<'de> Deserialize<'de>>(&self) -> Option<T> {
+        self.data.first().and_then(|data| near_sdk::serde_json::from_value(data.clone()).ok())
+    }
+}
+
+// Payload of `agent_registered`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentRegisteredEvent {
+    pub agent_id: AccountId,
+}
+
+// Payload of `intent_created`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IntentCreatedEvent {
+    pub intent_id: String,
+    pub agent_id: AccountId,
+    pub client_id: AccountId,
+    pub intent_type: String,
+    pub escrow_amount: U128,
+    pub escrow_asset: EscrowAsset,
+    pub deadline: u64,
+}
+
+// Payload of `intent_status_updated`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IntentStatusUpdatedEvent {
+    pub intent_id: String,
+    pub agent_id: AccountId,
+    pub old_status: IntentStatus,
+    pub new_status: IntentStatus,
+    pub updated_by: AccountId,
+    // The intent's nonce after the update
+    pub nonce: u64,
+}
+
+// Payload of `escrow_released`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EscrowReleasedEvent {
+    pub intent_id: String,
+    pub agent_id: AccountId,
+    pub asset: EscrowAsset,
+    pub amount: U128,
+    pub protocol_fee: U128,
+    pub treasury_fee: U128,
+    pub insurance_fee: U128,
+    pub referrer_fee: U128,
+}
+
+// Payload of `escrow_refunded`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EscrowRefundedEvent {
+    pub intent_id: String,
+    pub client_id: AccountId,
+    pub asset: EscrowAsset,
+    pub amount: U128,
+}
+
+// Payload of `config_updated`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ConfigUpdatedEvent {
+    pub parameter: String,
+    pub old_value: Value,
+    pub new_value: Value,
+    pub updated_by: AccountId,
+    pub config_nonce: u64,
+}
@@ -0,0 +1,276 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Upper bound on intent types an agreement can cover
+const MAX_AGREEMENT_INTENT_TYPES: usize = 10;
+
+// Upper bound on active agreements an agent can be party to, and separately on proposals
+// awaiting acceptance
+const MAX_OPEN_AGREEMENTS_PER_AGENT: u32 = 50;
+
+// Upper bound on agreements returned per page
+const MAX_AGREEMENTS_PAGE: u32 = 50;
+
+// Standing terms two agents agree on once instead of negotiating every intent
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgreementTerms {
+    // NEAR escrowed with every intent delegated under the agreement
+    pub rate: U128,
+    // Time the provider has to finish each intent (nanoseconds)
+    pub sla_period: u64,
+    // Intent types the agreement covers (empty covers every type)
+    pub intent_types: Vec<String>,
+    // Whether covered intents are accepted on the provider's behalf when delegated
+    pub auto_accept: bool,
+    // Share of each payout to the provider routed to the delegating agent (basis points)
+    pub revenue_split_bps: u16,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum AgreementStatus {
+    // Proposed by the delegator, awaiting the provider
+    Proposed,
+    Active,
+    Terminated,
+}
+
+// Fulfillment record of intents delegated under an agreement
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Default, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgreementStats {
+    pub(crate) delegated: u64,
+    pub(crate) completed: u64,
+    pub(crate) failed: u64,
+    // Delegated intents that ended without an outcome (declined, cancelled or expired)
+    pub(crate) abandoned: u64,
+}
+
+// Counts behind an agent's agreement index. The agreement ids themselves are stored one per
+// key, by the agent and their position in the index.
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct AgentAgreements {
+    // Agreements the agent has been party to, in any status
+    pub(crate) total: u64,
+    pub(crate) proposed: u32,
+    pub(crate) active: u32,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ServiceAgreement {
+    // Agent delegating work and paying for it
+    pub(crate) delegator_id: AccountId,
+    // Agent performing the work
+    pub(crate) provider_id: AccountId,
+    pub(crate) terms: AgreementTerms,
+    pub(crate) status: AgreementStatus,
+    pub(crate) created_at: u64,
+    pub(crate) stats: AgreementStats,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Propose a standing agreement to a provider agent (delegating agent only)
+    pub fn propose_agreement(&mut self, provider_id: AccountId, terms: AgreementTerms) -> u64 {
+        let delegator_id = env::predecessor_account_id();
+        assert!(self.agent_reputations.get(&delegator_id).is_some(), "Only registered agents can propose agreements");
+        assert!(self.agent_reputations.get(&provider_id).is_some(), "Provider agent not registered");
+        assert!(delegator_id != provider_id, "An agent cannot contract with itself");
+        assert!(terms.sla_period > self.intent_response_period, "SLA period must exceed the intent response period");
+        assert!(terms.intent_types.len() <= MAX_AGREEMENT_INTENT_TYPES, "Too many intent types");
+        assert!(terms.revenue_split_bps <= 10_000, "Revenue split cannot exceed 10000 bps");
+        #[cfg(not(feature = "escrow"))]
+        assert_eq!(terms.rate.0, 0, "Intent escrow is not enabled in this build");
+
+        let agreement_id = self.next_agreement_id;
+        self.next_agreement_id += 1;
+
+        self.agreements.insert(&agreement_id, &ServiceAgreement {
+            delegator_id: delegator_id.clone(),
+            provider_id: provider_id.clone(),
+            terms: terms.clone(),
+            status: AgreementStatus::Proposed,
+            created_at: env::block_timestamp(),
+            stats: AgreementStats::default(),
+        });
+        for agent_id in [&delegator_id, &provider_id] {
+            let mut index = self.agent_agreements.get(agent_id).unwrap_or_default();
+            assert!(index.proposed < MAX_OPEN_AGREEMENTS_PER_AGENT, "Agent has too many proposed agreements");
+            index.proposed += 1;
+            self.agent_agreement_ids.insert(&(agent_id.clone(), index.total), &agreement_id);
+            index.total += 1;
+            self.agent_agreements.insert(agent_id, &index);
+        }
+
+        emit_event(
+            "agreement_proposed",
+            json!({
+                "agreement_id": agreement_id,
+                "delegator_id": delegator_id,
+                "provider_id": provider_id,
+                "terms": terms,
+            }),
+        );
+
+        agreement_id
+    }
+
+    // Accept a proposed agreement (provider only)
+    pub fn accept_agreement(&mut self, agreement_id: u64) {
+        let mut agreement = self.agreements.get(&agreement_id).expect("Agreement not found");
+        assert_eq!(env::predecessor_account_id(), agreement.provider_id, "Only the provider can accept the agreement");
+        assert!(agreement.status == AgreementStatus::Proposed, "Agreement is not awaiting acceptance");
+
+        for agent_id in [&agreement.delegator_id, &agreement.provider_id] {
+            let mut index = self.agent_agreements.get(agent_id).expect("Agreement not indexed");
+            assert!(index.active < MAX_OPEN_AGREEMENTS_PER_AGENT, "Agent has too many active agreements");
+            index.proposed -= 1;
+            index.active += 1;
+            self.agent_agreements.insert(agent_id, &index);
+        }
+
+        agreement.status = AgreementStatus::Active;
+        self.agreements.insert(&agreement_id, &agreement);
+
+        emit_event("agreement_accepted", json!({ "agreement_id": agreement_id }));
+    }
+
+    // End an agreement (either party). Intents already delegated under it run to completion.
+    pub fn terminate_agreement(&mut self, agreement_id: u64) {
+        let caller = env::predecessor_account_id();
+        let mut agreement = self.agreements.get(&agreement_id).expect("Agreement not found");
+        assert!(
+            caller == agreement.delegator_id || caller == agreement.provider_id,
+            "Only a party to the agreement can terminate it"
+        );
+        assert!(agreement.status != AgreementStatus::Terminated, "Agreement already terminated");
+
+        for agent_id in [&agreement.delegator_id, &agreement.provider_id] {
+            let mut index = self.agent_agreements.get(agent_id).expect("Agreement not indexed");
+            if agreement.status == AgreementStatus::Active {
+                index.active -= 1;
+            } else {
+                index.proposed -= 1;
+            }
+            self.agent_agreements.insert(agent_id, &index);
+        }
+
+        agreement.status = AgreementStatus::Terminated;
+        self.agreements.insert(&agreement_id, &agreement);
+
+        emit_event(
+            "agreement_terminated",
+            json!({
+                "agreement_id": agreement_id,
+                "terminated_by": caller,
+            }),
+        );
+    }
+
+    // Delegate an intent to the provider under an active agreement (delegator only). The
+    // attached deposit must equal the agreed rate; the deadline follows the agreed SLA and
+    // the intent is accepted immediately if the agreement auto-accepts.
+    #[payable]
+    pub fn delegate_under_agreement(&mut self, agreement_id: u64, intent_id: String, intent_type: String, parameters: String) {
+        let mut agreement = self.agreements.get(&agreement_id).expect("Agreement not found");
+        assert_eq!(env::predecessor_account_id(), agreement.delegator_id, "Only the delegator can delegate under the agreement");
+        assert!(agreement.status == AgreementStatus::Active, "Agreement is not active");
+        assert!(
+            agreement.terms.intent_types.is_empty() || agreement.terms.intent_types.contains(&intent_type),
+            "Intent type not covered by the agreement"
+        );
+        assert_eq!(env::attached_deposit(), agreement.terms.rate.0, "Deposit must equal the agreed rate");
+
+        self.internal_record_intent(
+            intent_id.clone(),
+            agreement.provider_id.clone(),
+            agreement.delegator_id.clone(),
+            intent_type,
+            parameters,
+            Some(env::block_timestamp() + agreement.terms.sla_period),
+            agreement.terms.rate.0,
+            EscrowAsset::Near,
+            None,
+            Some(&agreement.delegator_id),
+        );
+
+        let mut intent = self.intents.get(&intent_id).expect("Intent not found");
+        intent.agreement_id = Some(agreement_id);
+        self.intents.insert(&intent_id, &intent);
+
+        agreement.stats.delegated += 1;
+        self.agreements.insert(&agreement_id, &agreement);
+
+        if agreement.terms.auto_accept && intent.status == IntentStatus::Created {
+            self.internal_apply_intent_transition(intent, IntentStatus::Accepted, None);
+        }
+    }
+
+    // Get an agreement by ID
+    pub fn get_agreement(&self, agreement_id: u64) -> Option<AgreementView> {
+        self.agreements
+            .get(&agreement_id)
+            .map(|agreement| agreement_view(agreement_id, agreement))
+    }
+
+    // Get a page of the agreements an agent is party to, as delegator or provider, oldest
+    // first, with their fulfillment stats
+    pub fn get_agent_agreements(&self, agent_id: AccountId, from_index: u64, limit: u32) -> Vec<AgreementView> {
+        let total = self.agent_agreements.get(&agent_id).map_or(0, |index| index.total);
+        let to_index = std::cmp::min(from_index.saturating_add(std::cmp::min(limit, MAX_AGREEMENTS_PAGE) as u64), total);
+
+        (from_index..to_index)
+            .filter_map(|position| {
+                let agreement_id = self.agent_agreement_ids.get(&(agent_id.clone(), position))?;
+                self.agreements
+                    .get(&agreement_id)
+                    .map(|agreement| agreement_view(agreement_id, agreement))
+            })
+            .collect()
+    }
+
+    // Count a finalized intent toward the fulfillment stats of its agreement
+    pub(crate) fn internal_record_agreement_outcome(&mut self, intent: &IntentData) {
+        let agreement_id = match intent.agreement_id {
+            Some(agreement_id) => agreement_id,
+            None => return,
+        };
+        let mut agreement = match self.agreements.get(&agreement_id) {
+            Some(agreement) => agreement,
+            None => return,
+        };
+
+        match intent.status {
+            IntentStatus::Completed => agreement.stats.completed += 1,
+            IntentStatus::Failed => agreement.stats.failed += 1,
+            _ => agreement.stats.abandoned += 1,
+        }
+        self.agreements.insert(&agreement_id, &agreement);
+    }
+}
+
+fn agreement_view(agreement_id: u64, agreement: ServiceAgreement) -> AgreementView {
+    AgreementView {
+        agreement_id,
+        delegator_id: agreement.delegator_id,
+        provider_id: agreement.provider_id,
+        terms: agreement.terms,
+        status: agreement.status,
+        created_at: agreement.created_at,
+        stats: agreement.stats,
+    }
+}
+
+// View struct for service agreements
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgreementView {
+    agreement_id: u64,
+    delegator_id: AccountId,
+    provider_id: AccountId,
+    terms: AgreementTerms,
+    status: AgreementStatus,
+    created_at: u64,
+    stats: AgreementStats,
+}
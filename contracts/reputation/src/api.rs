@@ -0,0 +1,650 @@
+use crate::*;
+use crate::config::FULL_CONFIG_SCHEMA_VERSION;
+
+// Where the contract's source is published
+const SOURCE_LINK: &str = "https://github.com/brainstems/intellex-framework";
+
+// NEP-330 source metadata, listing the standards the contract implements
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractSourceMetadata {
+    version: Option<String>,
+    link: Option<String>,
+    standards: Vec<Standard>,
+}
+
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Standard {
+    standard: String,
+    version: String,
+}
+
+#[derive(near_sdk::serde::Serialize, Clone, Copy)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum MethodKind {
+    // Read-only; callable as a view without a transaction
+    View,
+    // Changes state; needs a transaction
+    Call,
+    // Changes state and accepts an attached deposit
+    Payable,
+    // Initializes the contract
+    Init,
+}
+
+// Public method of the contract. Argument and return types are the Rust types of the contract
+// and of the reputation-interface crate.
+pub struct ApiMethod {
+    name: &'static str,
+    kind: MethodKind,
+    args: &'static [(&'static str, &'static str)],
+    returns: Option<&'static str>,
+    // Cargo feature the method is compiled under, if it is optional
+    feature: Option<&'static str>,
+}
+
+impl ApiMethod {
+    const fn gated(self, feature: &'static str) -> Self {
+        Self { feature: Some(feature), ..self }
+    }
+
+    fn enabled(&self) -> bool {
+        match self.feature {
+            None => true,
+            Some(feature) => ENABLED_FEATURES.contains(&feature),
+        }
+    }
+}
+
+const fn view(name: &'static str, args: &'static [(&'static str, &'static str)], returns: &'static str) -> ApiMethod {
+    ApiMethod { name, kind: MethodKind::View, args, returns: Some(returns), feature: None }
+}
+
+const fn call(name: &'static str, args: &'static [(&'static str, &'static str)], returns: Option<&'static str>) -> ApiMethod {
+    ApiMethod { name, kind: MethodKind::Call, args, returns, feature: None }
+}
+
+const fn payable(name: &'static str, args: &'static [(&'static str, &'static str)], returns: Option<&'static str>) -> ApiMethod {
+    ApiMethod { name, kind: MethodKind::Payable, args, returns, feature: None }
+}
+
+const fn init(name: &'static str, args: &'static [(&'static str, &'static str)], returns: Option<&'static str>) -> ApiMethod {
+    ApiMethod { name, kind: MethodKind::Init, args, returns, feature: None }
+}
+
+// Optional subsystems compiled into this build
+const ENABLED_FEATURES: &[&str] = &[
+    #[cfg(feature = "cross-chain")]
+    "cross-chain",
+    #[cfg(feature = "escrow")]
+    "escrow",
+];
+
+// Every public method except private callbacks, in module order. Update this table with any
+// change to a method's name, arguments or return type.
+const API_METHODS: &[ApiMethod] = &[
+    // Core
+    init("new", &[("owner_id", "AccountId"), ("token_contract_id", "AccountId"), ("min_stake_amount", "Balance")], None),
+    payable("register_agent", &[("agent_id", "AccountId"), ("specializations", "Vec<String>")], Some("Promise")),
+    call("add_feedback", &[("agent_id", "AccountId"), ("intent_id", "String"), ("rating", "u8"), ("category_ratings", "CategoryRatings"), ("message", "Option<String>"), ("anonymous", "Option<bool>")], None),
+    call("update_reputation_on_stake_change", &[("agent_id", "AccountId")], None),
+    view("get_agent_reputation", &[("agent_id", "AccountId")], "Option<AgentReputationView>"),
+    view("get_trust_level", &[("score", "u32")], "TrustLevel"),
+    view("get_decline_quota", &[("trust_level", "TrustLevel")], "u32"),
+    view("get_capability_limits", &[("agent_id", "AccountId"), ("specialization", "Option<String>")], "Option<CapabilityLimits>"),
+    view("get_capability_limits_bulk", &[("agent_ids", "Vec<AccountId>")], "Vec<Option<CapabilityLimits>>"),
+    view("can_perform_action", &[("agent_id", "AccountId"), ("action_type", "String"), ("value", "Option<Balance>")], "bool"),
+    view("get_agent_reputation_detailed", &[("agent_id", "AccountId")], "Option<AgentReputationDetailedView>"),
+    view("get_agents_bulk", &[("agent_ids", "Vec<AccountId>")], "Vec<Option<AgentReputationDetailedView>>"),
+    call("report_violation", &[("agent_id", "AccountId"), ("violation_type", "ViolationType"), ("description", "String"), ("evidence", "Vec<Evidence>")], None),
+    call("appeal_violation", &[("violation_index", "u64"), ("justification", "String")], None),
+    call("restore_reputation", &[("agent_id", "AccountId"), ("points", "u32"), ("reason", "String")], None),
+
+    // Agreements
+    call("propose_agreement", &[("provider_id", "AccountId"), ("terms", "AgreementTerms")], Some("u64")),
+    call("accept_agreement", &[("agreement_id", "u64")], None),
+    call("terminate_agreement", &[("agreement_id", "u64")], None),
+    payable("delegate_under_agreement", &[("agreement_id", "u64"), ("intent_id", "String"), ("intent_type", "String"), ("parameters", "String")], None),
+    view("get_agreement", &[("agreement_id", "u64")], "Option<AgreementView>"),
+    view("get_agent_agreements", &[("agent_id", "AccountId"), ("from_index", "u64"), ("limit", "u32")], "Vec<AgreementView>"),
+
+    // API
+    view("contract_source_metadata", &[], "ContractSourceMetadata"),
+    view("contract_api", &[], "ContractApiView"),
+
+    // Attestations
+    call("issue_capability_attestation", &[("agent_id", "AccountId")], Some("IssuedAttestation")),
+    view("verify_attestation", &[("payload", "Base64VecU8"), ("nonce", "u64")], "bool"),
+    call("prune_capability_attestation", &[("nonce", "u64")], None),
+    payable("issue_capability_attestation_signed", &[("agent_id", "AccountId")], Some("Promise")).gated("cross-chain"),
+
+    // Audits
+    call("open_manipulation_audit", &[("agent_id", "AccountId"), ("lookback", "Option<u32>")], Some("ManipulationAuditView")),
+    call("resolve_manipulation_audit", &[("agent_id", "AccountId"), ("confirmed", "bool"), ("ruling_note", "Option<String>")], None),
+    view("get_manipulation_audit", &[("agent_id", "AccountId")], "Option<ManipulationAuditView>"),
+    view("is_under_audit", &[("agent_id", "AccountId")], "bool"),
+
+    // Authorization
+    view("simulate_authorization", &[("agent_id", "AccountId"), ("action", "String"), ("value", "Option<U128>"), ("consumer_id", "Option<AccountId>")], "AuthorizationSimulationView"),
+
+    // Badges
+    view("nft_metadata", &[], "BadgeContractMetadataView"),
+    view("nft_token", &[("token_id", "String")], "Option<BadgeTokenView>"),
+    payable("nft_transfer", &[("receiver_id", "AccountId"), ("token_id", "String"), ("approval_id", "Option<u64>"), ("memo", "Option<String>")], None),
+    payable("nft_transfer_call", &[("receiver_id", "AccountId"), ("token_id", "String"), ("approval_id", "Option<u64>"), ("memo", "Option<String>"), ("msg", "String")], Some("PromiseOrValue<bool>")),
+    view("nft_total_supply", &[], "U128"),
+    view("nft_tokens", &[("from_index", "Option<U128>"), ("limit", "Option<u64>")], "Vec<BadgeTokenView>"),
+    view("nft_supply_for_owner", &[("account_id", "AccountId")], "U128"),
+    view("nft_tokens_for_owner", &[("account_id", "AccountId"), ("from_index", "Option<U128>"), ("limit", "Option<u64>")], "Vec<BadgeTokenView>"),
+
+    // Bidding
+    payable("open_intent_auction", &[("intent_id", "String"), ("intent_type", "String"), ("parameters", "String")], None).gated("escrow"),
+    call("commit_bid", &[("intent_id", "String"), ("commitment", "Base64VecU8")], None).gated("escrow"),
+    call("reveal_bid", &[("intent_id", "String"), ("price", "U128"), ("eta", "u64"), ("salt", "String")], None).gated("escrow"),
+    call("close_intent_auction", &[("intent_id", "String")], None).gated("escrow"),
+    call("cancel_intent_auction", &[("intent_id", "String")], None).gated("escrow"),
+    view("get_intent_auction", &[("intent_id", "String")], "Option<IntentAuction>").gated("escrow"),
+    view("get_open_auctions", &[("from_index", "u64"), ("limit", "u32")], "Vec<IntentAuction>").gated("escrow"),
+    view("get_bidding_config", &[], "BiddingConfig").gated("escrow"),
+
+    // Blind ratings
+    call("rate_client", &[("intent_id", "String"), ("rating", "u8")], None),
+    call("release_blind_ratings", &[("intent_id", "String")], None),
+    view("get_blind_ratings", &[("intent_id", "String")], "Option<BlindRatingsView>"),
+
+    // Bounties
+    call("submit_audit_attestation", &[("bounty_id", "u64"), ("passed", "bool"), ("report", "Evidence")], None),
+    call("review_audit_attestation", &[("bounty_id", "u64"), ("auditor", "AccountId"), ("approved", "bool")], None),
+    call("cancel_audit_bounty", &[("bounty_id", "u64")], None),
+    view("get_audit_bounty", &[("bounty_id", "u64")], "Option<AuditBountyView>"),
+    view("get_audit_bounties", &[("from_index", "u64"), ("limit", "u32")], "Vec<AuditBountyView>"),
+    view("get_audit_badge", &[("agent_id", "AccountId")], "Option<u64>"),
+
+    // Bridge
+    call("check_bridge_allowance", &[("agent_id", "AccountId"), ("token_id", "AccountId"), ("amount", "U128")], Some("BridgeAllowanceView")).gated("cross-chain"),
+    call("add_bridge_account", &[("bridge_id", "AccountId")], None).gated("cross-chain"),
+    call("remove_bridge_account", &[("bridge_id", "AccountId")], None).gated("cross-chain"),
+    view("get_bridge_accounts", &[], "Vec<AccountId>").gated("cross-chain"),
+    call("set_bridge_token", &[("token_id", "AccountId"), ("decimals", "Option<u8>")], None).gated("cross-chain"),
+    view("get_bridge_tokens", &[], "Vec<BridgeTokenView>").gated("cross-chain"),
+
+    // Chain signatures
+    payable("request_evm_signature", &[("chain_id", "u64"), ("payload", "Base64VecU8")], Some("Promise")).gated("cross-chain"),
+    call("resync_evm_nonce", &[("chain_id", "u64"), ("next_nonce", "u64")], None).gated("cross-chain"),
+    call("set_chain_signer", &[("signer_id", "Option<AccountId>")], None).gated("cross-chain"),
+    call("set_evm_chain_budget", &[("chain_id", "u64"), ("budget", "Option<EvmChainBudget>")], None).gated("cross-chain"),
+    view("get_evm_chain_budgets", &[], "Vec<(u64, EvmChainBudget)>").gated("cross-chain"),
+    view("get_evm_delegation", &[("agent_id", "AccountId"), ("chain_id", "u64")], "EvmDelegationView").gated("cross-chain"),
+
+    // Checkpoints
+    call("post_checkpoint", &[("intent_id", "String"), ("percentage", "u8"), ("note", "String"), ("hash", "Option<Base64VecU8>")], None),
+    call("cancel_stalled_intent", &[("intent_id", "String")], None),
+    view("get_intent_checkpoints", &[("intent_id", "String")], "Vec<IntentCheckpoint>"),
+
+    // Clients
+    view("get_client_reputation", &[("client_id", "AccountId")], "Option<ClientReputationView>"),
+    call("set_client_auto_accept", &[("min_client_score", "Option<u32>")], None),
+    view("get_client_auto_accept", &[("agent_id", "AccountId")], "Option<u32>"),
+
+    // Codes
+    view("get_code_map", &[], "CodeMapView"),
+    view("get_agent_reputation_numeric", &[("agent_id", "AccountId")], "Option<AgentReputationNumericView>"),
+    view("get_intent_numeric", &[("intent_id", "String")], "Option<IntentNumericView>"),
+    view("get_violations_numeric", &[("agent_id", "AccountId"), ("from_index", "u32"), ("limit", "u32")], "Vec<ViolationNumericView>"),
+
+    // Configuration
+    call("set_min_stake", &[("min_stake_amount", "U128")], Some("u64")),
+    call("set_staking_rate_oracle", &[("staking_rate_oracle", "Option<AccountId>")], None),
+    call("set_feedback_expiry", &[("feedback_expiry_period", "u64")], None),
+    call("set_category_scoring", &[("configs", "Vec<CategoryScoringConfig>")], None),
+    call("set_bayesian_prior", &[("prior", "BayesianPrior")], None),
+    call("set_feedback_limit_config", &[("config", "FeedbackLimitConfig")], None),
+    call("set_feedback_amendment_window", &[("feedback_amendment_window", "u64")], None),
+    call("set_blind_rating_window", &[("blind_rating_window", "u64")], None),
+    call("set_failure_weights", &[("weights", "FailureWeights")], None),
+    call("set_performance_config", &[("config", "PerformanceConfig")], None),
+    call("set_score_epoch_length", &[("score_epoch_length", "u64")], None),
+    call("set_stake_grace_period", &[("stake_grace_period", "u64")], None),
+    call("set_endorsement_config", &[("config", "EndorsementConfig")], None),
+    call("set_max_task_delegation_depth", &[("max_task_delegation_depth", "u8")], None),
+    call("set_insurance_config", &[("config", "InsuranceConfig")], None),
+    call("set_violation_policy", &[("policy", "Vec<ViolationPolicyEntry>")], Some("u64")),
+    call("set_penalty_decay_config", &[("config", "PenaltyDecayConfig")], None),
+    call("set_remediation_verifier", &[("remediation_verifier", "Option<AccountId>")], None),
+    call("set_agent_status_config", &[("config", "AgentStatusConfig")], None),
+    call("set_registry", &[("near_ai_registry", "AccountId")], None),
+    call("set_intents_processor", &[("intents_processor", "AccountId")], None),
+    call("set_intent_response_period", &[("intent_response_period", "u64")], None),
+    call("set_intent_deadline_period", &[("intent_deadline_period", "u64")], None),
+    call("set_bidding_config", &[("config", "BiddingConfig")], None),
+    call("set_intent_dispute_window", &[("intent_dispute_window", "u64")], None),
+    call("set_checkpoint_stall_period", &[("checkpoint_stall_period", "u64")], None),
+    call("set_treasury", &[("treasury_id", "AccountId")], Some("u64")),
+    call("set_slash_burn_bps", &[("slash_burn_bps", "u16")], Some("u64")),
+    call("set_protocol_fee_bps", &[("protocol_fee_bps", "u16")], None),
+    call("set_fee_discount_tiers", &[("tiers", "Vec<FeeDiscountTier>")], None),
+    call("set_fee_split", &[("fee_split", "FeeSplit")], None),
+    call("set_usage_quota_config", &[("config", "UsageQuotaConfig")], None),
+    call("set_attestation_ttl", &[("attestation_ttl", "u64")], None),
+    call("set_query_rebate_amount", &[("query_rebate_amount", "U128")], None),
+    call("set_reviewer_weight_config", &[("config", "ReviewerWeightConfig")], None),
+    call("set_scoring_mode", &[("scoring_mode", "ScoringMode")], None),
+    call("set_retention_config", &[("config", "RetentionConfig")], None),
+    call("set_escheatment_config", &[("config", "EscheatmentConfig")], None),
+    call("set_delegation_config", &[("config", "DelegationConfig")], None),
+    call("set_rewards_config", &[("config", "RewardsConfig")], None),
+    call("set_history_config", &[("config", "HistoryConfig")], None),
+    view("get_config", &[], "ContractConfigView"),
+    view("get_full_config", &[], "Value"),
+    view("config_hash", &[], "String"),
+
+    // Delegation
+    call("undelegate", &[("agent_id", "AccountId"), ("amount", "Option<U128>")], None),
+    call("withdraw_delegation", &[("agent_id", "AccountId")], Some("Promise")),
+    view("get_delegations", &[("delegator_id", "AccountId")], "Vec<DelegationView>"),
+    view("get_delegated_stake", &[("agent_id", "AccountId")], "DelegatedStakeView"),
+    view("get_delegation_config", &[], "DelegationConfig"),
+
+    // Cross-chain
+    call("import_cross_chain_reputation", &[("agent_id", "AccountId"), ("source_chain", "String"), ("proof", "CrossChainProof")], None).gated("cross-chain"),
+    view("get_import_history", &[("agent_id", "AccountId")], "Vec<ImportRecord>").gated("cross-chain"),
+    call("set_chain_policy", &[("source_chain", "String"), ("policy", "Option<ChainPolicy>")], None).gated("cross-chain"),
+    view("list_supported_chains", &[], "Vec<(String, ChainPolicy)>").gated("cross-chain"),
+    call("add_trusted_attestor", &[("source_chain", "String"), ("key", "AttestorKey")], None).gated("cross-chain"),
+    call("remove_trusted_attestor", &[("source_chain", "String"), ("key", "AttestorKey")], None).gated("cross-chain"),
+    view("get_trusted_attestors", &[("source_chain", "String")], "Vec<AttestorKey>").gated("cross-chain"),
+    view("get_cross_chain_nonce", &[("agent_id", "AccountId"), ("source_chain", "String")], "Option<u64>").gated("cross-chain"),
+    call("export_reputation", &[], Some("ReputationExport")).gated("cross-chain"),
+    payable("export_reputation_signed", &[], Some("Promise")).gated("cross-chain"),
+    view("verify_export", &[("payload", "Base64VecU8"), ("nonce", "u64")], "bool").gated("cross-chain"),
+    view("get_signed_export", &[("agent_id", "AccountId"), ("nonce", "u64")], "Option<SignedExportView>").gated("cross-chain"),
+    view("get_export_signer", &[], "ExportSignerView").gated("cross-chain"),
+    call("set_aurora_mirror", &[("config", "Option<AuroraMirrorConfig>")], None).gated("cross-chain"),
+    view("get_aurora_mirror", &[], "Option<AuroraMirrorConfig>").gated("cross-chain"),
+    call("sync_aurora_mirror", &[("from_index", "Option<u64>"), ("limit", "Option<u32>")], Some("u32")).gated("cross-chain"),
+    call("retry_aurora_mirror_sync", &[("agent_id", "AccountId")], None).gated("cross-chain"),
+    view("get_aurora_mirror_sync", &[("agent_id", "AccountId")], "Option<MirrorSyncView>").gated("cross-chain"),
+    view("get_aurora_mirror_syncs", &[("from_index", "u64"), ("limit", "u32")], "Vec<MirrorSyncView>").gated("cross-chain"),
+    view("get_portability_lock", &[("agent_id", "AccountId")], "Option<String>"),
+
+    // DAO
+    call("set_governance_dao", &[("dao_id", "Option<AccountId>")], None),
+    view("get_governance_dao", &[], "Option<AccountId>"),
+    view("get_dao_violation_proposal", &[("agent_id", "AccountId"), ("violation_type", "ViolationType"), ("description", "String"), ("evidence", "Vec<Evidence>")], "DaoProposalArgs"),
+    view("get_dao_appeal_proposal", &[("agent_id", "AccountId"), ("violation_index", "u64"), ("overturn", "bool"), ("note", "Option<String>")], "DaoProposalArgs"),
+
+    // Disputes
+    call("dispute_intent", &[("intent_id", "String"), ("reason", "String")], None),
+    call("resolve_intent_dispute", &[("intent_id", "String"), ("agent_wins", "bool"), ("ruling_note", "Option<String>")], None),
+    call("add_arbiter", &[("arbiter_id", "AccountId")], None),
+    call("remove_arbiter", &[("arbiter_id", "AccountId")], None),
+    view("get_arbiters", &[], "Vec<AccountId>"),
+    view("get_dispute_stats", &[("agent_id", "AccountId")], "Option<DisputeStatsView>"),
+
+    // Endorsements
+    call("endorse_agent", &[("agent_id", "AccountId"), ("specialization", "String")], None),
+    call("revoke_endorsement", &[("agent_id", "AccountId"), ("specialization", "String")], None),
+    call("remove_endorsement", &[("endorser", "AccountId"), ("specialization", "String")], None),
+    view("get_endorsements", &[("agent_id", "AccountId")], "Vec<EndorsementView>"),
+    view("get_endorsement_config", &[], "EndorsementConfig"),
+
+    // Enumeration
+    view("get_agent_count", &[], "u64"),
+    view("get_agents", &[("from_index", "Option<u64>"), ("limit", "Option<u32>")], "Vec<AgentSummaryView>"),
+    view("get_recently_updated_agents", &[("since_timestamp", "u64"), ("limit", "Option<u32>")], "Vec<AgentSummaryView>"),
+
+    // Epochs
+    call("finalize_epoch", &[], Some("bool")),
+    view("get_score_at_epoch", &[("agent_id", "AccountId"), ("epoch", "u64")], "Option<EpochCheckpoint>"),
+    view("get_epoch_info", &[], "EpochInfoView"),
+
+    // Escheatment
+    call("heartbeat", &[], None),
+    call("issue_dormancy_notice", &[("account_id", "AccountId")], None),
+    call("escheat_stake", &[("account_id", "AccountId")], None),
+    call("reclaim_escheated_stake", &[], Some("U128")),
+    view("get_escheated_stake", &[("account_id", "AccountId")], "Option<EscheatedStakeView>"),
+    view("get_stake_dormancy", &[("account_id", "AccountId")], "Option<StakeDormancyView>"),
+    view("get_escheatment_config", &[], "EscheatmentConfig"),
+
+    // Escrow
+    call("approve_checkpoint", &[("intent_id", "String"), ("checkpoint_index", "u32")], None).gated("escrow"),
+
+    // Failures
+    call("fail_intent", &[("intent_id", "String"), ("category", "FailureCategory"), ("result", "Option<String>")], None),
+    view("get_failure_breakdown", &[("agent_id", "AccountId")], "Option<FailureBreakdownView>"),
+
+    // Fees
+    view("get_fee_config", &[], "FeeConfigView"),
+    view("get_fee_rate", &[("agent_id", "AccountId")], "FeeRateView"),
+    view("get_fee_discount_tiers", &[], "Vec<FeeDiscountTier>"),
+
+    // Feedback
+    view("get_feedback", &[("agent_id", "AccountId"), ("from_index", "u32"), ("limit", "u32")], "Vec<FeedbackView>"),
+    call("update_feedback", &[("agent_id", "AccountId"), ("feedback_index", "u64"), ("rating", "u8"), ("category_ratings", "CategoryRatings"), ("message", "Option<String>")], None),
+    call("revoke_feedback", &[("agent_id", "AccountId"), ("feedback_index", "u64")], None),
+    call("respond_to_feedback", &[("feedback_index", "u64"), ("response", "String")], None),
+    call("prune_expired_feedback", &[("agent_id", "AccountId"), ("max_entries", "u64")], Some("u64")),
+    view("get_rating_histogram", &[("agent_id", "AccountId")], "Option<RatingHistogramView>"),
+    call("reveal_pseudonym", &[("pseudonym", "String"), ("reason", "String")], Some("AccountId")),
+
+    // Feedback disputes
+    payable("dispute_feedback", &[("feedback_index", "u64"), ("reason", "String")], Some("u64")),
+    call("resolve_feedback_dispute", &[("dispute_id", "u64"), ("uphold", "bool"), ("ruling_note", "Option<String>")], Some("Promise")),
+    view("get_feedback_dispute", &[("dispute_id", "u64")], "Option<FeedbackDispute>"),
+    view("get_feedback_dispute_quota", &[("agent_id", "AccountId")], "Option<FeedbackDisputeQuotaView>"),
+
+    // Feedback limits
+    view("get_feedback_limit_config", &[], "FeedbackLimitConfig"),
+
+    // Force majeure
+    call("declare_force_majeure", &[("reason", "String"), ("start", "Option<u64>")], Some("u64")),
+    call("end_force_majeure", &[("window_id", "u64")], None),
+    call("extend_intent_for_force_majeure", &[("intent_id", "String"), ("window_id", "u64")], None),
+    view("get_force_majeure_windows", &[("from_index", "u64"), ("limit", "u32")], "Vec<ForceMajeureWindow>"),
+    view("is_force_majeure_active", &[], "bool"),
+    view("get_open_force_majeure_windows", &[], "Vec<u64>"),
+
+    // Health
+    view("health_check", &[], "HealthStatusView"),
+    view("get_network_stats", &[], "NetworkStatsView"),
+    call("refresh_token_balance", &[], Some("Promise")),
+
+    // History
+    view("get_reputation_history", &[("agent_id", "AccountId"), ("from_ts", "u64"), ("to_ts", "u64")], "Vec<ReputationSnapshot>"),
+
+    // Identity
+    view("resolve_identity", &[("identifier", "String")], "Option<IdentityView>"),
+    call("set_did", &[("did", "Option<String>"), ("signature", "Option<Base64VecU8>")], None),
+    view("get_did_link_message", &[("agent_id", "AccountId"), ("did", "String")], "String"),
+    call("unlink_foreign_address", &[("account_id", "String")], None),
+    call("set_realm_authority", &[("realm", "String"), ("authority_id", "Option<AccountId>")], None),
+    view("get_realm_authority", &[("realm", "String")], "Option<AccountId>"),
+    call("record_realm_membership", &[("realm", "String"), ("member_id", "String"), ("agent_id", "AccountId")], None),
+    call("remove_realm_membership", &[("realm", "String"), ("member_id", "String")], None),
+    call("link_evm_address", &[("chain_id", "u64"), ("address", "String"), ("signature", "Base64VecU8")], None).gated("cross-chain"),
+    view("get_evm_link_message", &[("agent_id", "AccountId"), ("chain_id", "u64"), ("address", "String")], "String").gated("cross-chain"),
+
+    // Insurance
+    call("file_insurance_claim", &[("incident_id", "u64"), ("intent_id", "String"), ("amount", "U128"), ("description", "String")], Some("u64")),
+    call("approve_insurance_claim", &[("claim_id", "u64"), ("payout", "Option<U128>")], Some("Promise")),
+    call("reject_insurance_claim", &[("claim_id", "u64"), ("reason", "String")], None),
+    view("get_insurance_pool", &[], "InsurancePoolView"),
+    view("get_insurance_incident", &[("incident_id", "u64")], "Option<InsuranceIncidentView>"),
+    view("get_insurance_claim", &[("claim_id", "u64")], "Option<InsuranceClaimView>"),
+
+    // Intent types
+    call("set_intent_type", &[("intent_type", "String"), ("metadata", "IntentTypeMetadata")], None),
+    call("remove_intent_type", &[("intent_type", "String")], None),
+    view("get_intent_type", &[("intent_type", "String")], "Option<IntentTypeMetadata>"),
+    view("get_intent_types", &[("from_index", "u64"), ("limit", "u32")], "Vec<(String, IntentTypeMetadata)>"),
+
+    // Intents
+    payable("record_intent", &[("intent_id", "String"), ("agent_id", "AccountId"), ("intent_type", "String"), ("parameters", "String"), ("deadline", "Option<u64>"), ("referrer_id", "Option<AccountId>")], None),
+    payable("record_intent_for_client", &[("intent_id", "String"), ("agent_id", "AccountId"), ("client_id", "AccountId"), ("intent_type", "String"), ("parameters", "String"), ("deadline", "Option<u64>"), ("referrer_id", "Option<AccountId>")], None),
+    call("update_intent_status", &[("intent_id", "String"), ("status", "IntentStatus"), ("result", "Option<String>"), ("nonce", "Option<u64>")], None),
+    call("accept_intent", &[("intent_id", "String")], None),
+    call("decline_intent", &[("intent_id", "String"), ("reason", "Option<String>")], None),
+    call("expire_unaccepted_intent", &[("intent_id", "String")], None),
+    call("expire_intent", &[("intent_id", "String")], None),
+    view("get_expired_intents", &[("limit", "u32")], "Vec<String>"),
+    call("finalize_intent", &[("intent_id", "String")], None),
+    view("get_decline_quota_status", &[("agent_id", "AccountId")], "Option<DeclineQuotaView>"),
+    view("get_intent", &[("intent_id", "String")], "Option<IntentView>"),
+
+    // Leaderboard
+    view("get_top_agents", &[("limit", "u32"), ("offset", "u32")], "Vec<RankedAgentView>"),
+    view("get_top_agents_by_category", &[("category", "FeedbackCategory"), ("limit", "u32")], "Vec<RankedAgentView>"),
+    view("get_agents_by_trust_level", &[("level", "TrustLevel"), ("from_index", "u32"), ("limit", "u32")], "Vec<RankedAgentView>"),
+
+    // Matching
+    view("recommend_agents", &[("intent_type", "String"), ("max_value", "Option<U128>"), ("limit", "Option<u32>")], "Vec<AgentRecommendationView>"),
+
+    // Migrate
+    view("contract_version", &[], "ContractVersionView"),
+
+    // Oracles
+    call("add_performance_oracle", &[("oracle_id", "AccountId"), ("public_key", "Base64VecU8")], None),
+    call("remove_performance_oracle", &[("oracle_id", "AccountId")], None),
+    call("submit_performance_metrics", &[("report", "PerformanceReport"), ("signature", "Base64VecU8")], None),
+    view("get_performance_oracles", &[], "Vec<(AccountId, Base64VecU8)>"),
+    view("get_performance_metrics", &[("agent_id", "AccountId")], "Option<PerformanceMetricsView>"),
+
+    // Ownership
+    view("owner", &[], "AccountId"),
+    view("get_ownership", &[], "OwnershipView"),
+    call("propose_owner", &[("new_owner", "Option<AccountId>")], None),
+    call("accept_ownership", &[], None),
+    call("set_recovery_account", &[("recovery_account", "Option<AccountId>")], None),
+    call("initiate_recovery", &[], None),
+    call("cancel_recovery", &[], None),
+    call("complete_recovery", &[], None),
+
+    // Pause
+    call("set_guardian", &[("guardian_id", "Option<AccountId>")], Some("u64")),
+    call("pause_features", &[("features", "Vec<PausableFeature>")], None),
+    call("unpause_features", &[("features", "Vec<PausableFeature>")], None),
+    view("get_paused_features", &[], "Vec<PausableFeature>"),
+    view("get_guardian", &[], "Option<AccountId>"),
+
+    // Payouts
+    call("claim_unpaid_payouts", &[("asset", "EscrowAsset")], Some("Promise")),
+    view("get_unpaid_payouts", &[("account_id", "AccountId"), ("asset", "EscrowAsset")], "U128"),
+
+    // Penalty decay
+    view("get_violation_status", &[("agent_id", "AccountId")], "Option<ViolationStatusView>"),
+
+    // Policy
+    call("set_capability_policy", &[("rules", "Vec<CapabilityRule>")], None),
+    view("get_capability_policy", &[], "Vec<CapabilityRule>"),
+    view("get_matching_capability_rule", &[("agent_id", "AccountId")], "Option<String>"),
+
+    // Privacy
+    call("set_privacy_config", &[("epsilon_milli", "u32")], None),
+    call("rotate_privacy_seed", &[], None),
+    view("is_privacy_noise_enabled", &[], "bool"),
+
+    // Profile
+    call("set_agent_profile", &[("profile", "AgentProfile")], None),
+    call("remove_agent_profile", &[], None),
+    view("get_agent_profile", &[("agent_id", "AccountId")], "Option<AgentProfile>"),
+
+    // Query rebates
+    call("authorize_action", &[("agent_id", "AccountId"), ("action", "String"), ("value", "Option<U128>")], Some("bool")),
+    call("withdraw_query_credit", &[("amount", "U128")], Some("Promise")),
+    call("claim_query_rebates", &[], Some("Promise")),
+    view("get_query_credit", &[("consumer_id", "AccountId")], "U128"),
+    view("get_query_rebates", &[("agent_id", "AccountId")], "U128"),
+
+    // Quotas
+    call("consume_capability", &[("action_type", "String"), ("value", "Option<U128>"), ("agent_id", "Option<AccountId>")], None),
+    view("get_remaining_quota", &[("agent_id", "AccountId")], "Vec<RemainingQuotaView>"),
+    view("get_usage_quota_config", &[], "UsageQuotaConfig"),
+    call("add_quota_dispatcher", &[("dispatcher_id", "AccountId")], None),
+    call("remove_quota_dispatcher", &[("dispatcher_id", "AccountId")], None),
+    view("get_quota_dispatchers", &[], "Vec<AccountId>"),
+
+    // Relationships
+    view("get_relationship_score", &[("client_id", "AccountId"), ("agent_id", "AccountId")], "Option<RelationshipScoreView>"),
+
+    // Remediation
+    call("create_remediation_task", &[("task_id", "String"), ("description", "String"), ("requirements", "String"), ("points", "u32")], None),
+    call("close_remediation_task", &[("task_id", "String")], None),
+    call("assign_remediation_task", &[("task_id", "String"), ("agent_id", "AccountId")], None),
+    call("complete_remediation_task", &[("task_id", "String"), ("proof", "String")], None),
+    call("verify_remediation", &[("agent_id", "AccountId"), ("task_id", "String"), ("approve", "bool"), ("note", "Option<String>")], None),
+    view("get_remediation_task", &[("task_id", "String")], "Option<RemediationTask>"),
+    view("get_open_remediation_tasks", &[("from_index", "u64"), ("limit", "u32")], "Vec<RemediationTask>"),
+    view("get_remediation_assignments", &[("agent_id", "AccountId"), ("status", "Option<RemediationStatus>")], "Vec<RemediationAssignment>"),
+    view("get_pending_remediation_reviews", &[("from_index", "u64"), ("limit", "u32")], "Vec<RemediationAssignment>"),
+
+    // Retention
+    view("get_retention_config", &[], "RetentionConfig"),
+
+    // Rewards
+    call("claim_rewards", &[], Some("Promise")),
+    view("get_pending_rewards", &[("agent_id", "AccountId")], "U128"),
+    view("get_reward_pool", &[], "RewardPoolView"),
+
+    // Shadow
+    call("enable_shadow_scoring", &[("params", "ScoringParams"), ("trial_period", "u64")], None),
+    call("disable_shadow_scoring", &[], None),
+    view("get_shadow_score", &[("agent_id", "AccountId")], "Option<ShadowScoreView>"),
+    view("get_shadow_comparison", &[], "Option<ShadowComparisonView>"),
+
+    // Specializations
+    call("update_specializations", &[("specializations", "Vec<String>")], None),
+    view("find_agents_by_specialization", &[("tag", "String"), ("min_score", "Option<u32>"), ("audited_only", "Option<bool>"), ("from_index", "Option<u32>"), ("limit", "Option<u32>")], "Vec<RankedAgentView>"),
+    view("get_specialization_agent_count", &[("tag", "String")], "u64"),
+    view("get_specialization_scores", &[("agent_id", "AccountId")], "Vec<SpecializationScoreView>"),
+    view("get_specialization_score", &[("agent_id", "AccountId"), ("tag", "String")], "Option<SpecializationScoreView>"),
+
+    // Stake health
+    call("enforce_stake_minimum", &[("agent_id", "AccountId")], None),
+    view("get_stake_health", &[("agent_id", "AccountId")], "Option<StakeHealthView>"),
+
+    // Staking tokens
+    call("set_staking_token", &[("token_id", "AccountId"), ("token", "StakingToken")], None),
+    call("set_staking_token_weight", &[("token_id", "AccountId"), ("weight_bps", "u32")], None),
+    call("unstake_token", &[("token_id", "AccountId"), ("amount", "U128")], Some("Promise")),
+    view("get_staking_tokens", &[], "Vec<StakingTokenView>"),
+    view("get_agent_stakes", &[("agent_id", "AccountId")], "AgentStakesView"),
+
+    // Status
+    call("suspend_agent", &[("agent_id", "AccountId"), ("reason", "String")], None),
+    call("ban_agent", &[("agent_id", "AccountId"), ("reason", "String")], None),
+    call("reinstate_agent", &[("agent_id", "AccountId"), ("reason", "String")], None),
+    view("get_agent_status", &[("agent_id", "AccountId")], "Option<AgentStatusView>"),
+    view("get_agent_status_config", &[], "AgentStatusConfig"),
+
+    // Storage
+    payable("storage_deposit", &[("account_id", "Option<AccountId>"), ("registration_only", "Option<bool>")], Some("StorageBalance")),
+    payable("storage_withdraw", &[("amount", "Option<U128>")], Some("StorageBalance")),
+    payable("storage_unregister", &[("force", "Option<bool>")], Some("bool")),
+    view("storage_balance_bounds", &[], "StorageBalanceBounds"),
+    view("storage_balance_of", &[("account_id", "AccountId")], "Option<StorageBalance>"),
+
+    // Task delegation
+    call("record_delegation", &[("parent_intent_id", "String"), ("sub_agent_id", "AccountId")], None),
+    call("accept_delegation", &[("parent_intent_id", "String")], None),
+    call("decline_delegation", &[("parent_intent_id", "String")], None),
+    view("get_delegation_tree", &[("intent_id", "String")], "Vec<TaskDelegationView>"),
+
+    // Timelock
+    call("execute_action", &[("action_id", "u64")], None),
+    call("cancel_action", &[("action_id", "u64")], None),
+    view("get_pending_actions", &[], "Vec<PendingActionView>"),
+    view("get_timelock_delay", &[], "u64"),
+    call("set_timelock_delay", &[("delay", "u64")], Some("u64")),
+
+    // Token integration
+    call("ft_on_transfer", &[("sender_id", "AccountId"), ("amount", "U128"), ("msg", "String")], Some("PromiseOrValue<U128>")),
+    call("unstake_itlx", &[("amount", "U128")], Some("Promise")),
+
+    // Treasury
+    view("get_slashing_totals", &[], "SlashingTotalsView"),
+
+    // Violation policy
+    view("get_violation_policy", &[], "Vec<ViolationPolicyEntry>"),
+
+    // Violations
+    call("resolve_appeal", &[("agent_id", "AccountId"), ("violation_index", "u64"), ("overturn", "bool"), ("note", "Option<String>")], None),
+    view("get_violations", &[("agent_id", "AccountId"), ("from_index", "u32"), ("limit", "u32")], "Vec<ViolationView>"),
+
+    // Whistleblower
+    payable("submit_report", &[("agent_id", "AccountId"), ("violation_type", "ViolationType"), ("description", "String"), ("evidence", "Vec<Evidence>")], Some("u64")),
+    call("resolve_report", &[("report_id", "u64"), ("ruling", "ReportStatus"), ("ruling_note", "Option<String>")], Some("Promise")),
+    view("get_report", &[("report_id", "u64")], "Option<WhistleblowerReport>"),
+    view("get_pending_reports", &[("from_index", "u64"), ("limit", "u32")], "Vec<u64>"),
+    view("get_report_terms", &[], "ReportTermsView"),
+];
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // NEP-330 source metadata
+    pub fn contract_source_metadata(&self) -> ContractSourceMetadata {
+        let standards = [
+            ("nep145", "1.0.0"),
+            ("nep171", "1.2.0"),
+            ("nep177", "2.0.0"),
+            ("nep181", "1.0.0"),
+            ("nep297", "1.0.0"),
+            ("nep330", "1.1.0"),
+        ];
+
+        ContractSourceMetadata {
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            link: Some(SOURCE_LINK.to_string()),
+            standards: standards
+                .iter()
+                .map(|(standard, version)| Standard {
+                    standard: standard.to_string(),
+                    version: version.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    // Describe the methods this build exposes, with the versions of the code, storage layout
+    // and configuration, so integrators can introspect the contract at runtime
+    pub fn contract_api(&self) -> ContractApiView {
+        ContractApiView {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            state_version: self.state_version,
+            config_schema_version: FULL_CONFIG_SCHEMA_VERSION,
+            config_nonce: self.config_nonce,
+            features: ENABLED_FEATURES.iter().map(|feature| feature.to_string()).collect(),
+            methods: API_METHODS
+                .iter()
+                .filter(|method| method.enabled())
+                .map(|method| ApiMethodView {
+                    name: method.name.to_string(),
+                    kind: method.kind,
+                    args: method
+                        .args
+                        .iter()
+                        .map(|(name, type_name)| ApiArgView {
+                            name: name.to_string(),
+                            type_name: type_name.to_string(),
+                        })
+                        .collect(),
+                    returns: method.returns.map(|returns| returns.to_string()),
+                })
+                .collect(),
+        }
+    }
+}
+
+// View struct for the contract's self-description
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractApiView {
+    version: String,
+    state_version: StateVersion,
+    // Version of the get_full_config document layout
+    config_schema_version: u32,
+    // Number of configuration changes so far
+    config_nonce: u64,
+    // Optional subsystems compiled into this build
+    features: Vec<String>,
+    methods: Vec<ApiMethodView>,
+}
+
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ApiMethodView {
+    name: String,
+    kind: MethodKind,
+    args: Vec<ApiArgView>,
+    // Return type, if the method returns a value
+    returns: Option<String>,
+}
+
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ApiArgView {
+    name: String,
+    #[serde(rename = "type")]
+    type_name: String,
+}
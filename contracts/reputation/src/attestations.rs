@@ -0,0 +1,217 @@
+use crate::*;
+#[cfg(feature = "cross-chain")]
+use crate::chain_signatures::{GAS_FOR_CHAIN_SIGNATURE, GAS_FOR_SIGNATURE_CALLBACK};
+use crate::events::emit_event;
+use near_sdk::json_types::Base64VecU8;
+#[cfg(feature = "cross-chain")]
+use near_sdk::serde_json::Value;
+
+// Upper bound on how long an attestation stays valid (7 days, in nanoseconds)
+pub(crate) const MAX_ATTESTATION_TTL: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
+// MPC derivation path of the contract's attestation signing key
+#[cfg(feature = "cross-chain")]
+const ATTESTATION_SIGNING_PATH: &str = "capability-attestation";
+
+// Snapshot of an agent's capabilities, Borsh-encoded into the attestation payload. Gateways
+// decode it, check the expiry and cache the limits until then.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct CapabilityAttestation {
+    pub agent_id: AccountId,
+    // Score finalized at the last epoch, or the live score for agents never finalized
+    pub score: u32,
+    pub trust_level: TrustLevel,
+    pub limits: CapabilityLimits,
+    pub issued_at: u64,
+    pub expires_at: u64,
+    pub nonce: u64,
+    // Finalized epoch the score was taken from; None for a live score
+    pub epoch: Option<u64>,
+}
+
+// Issued attestation: the Borsh-encoded CapabilityAttestation and its keccak256 hash
+#[derive(near_sdk::serde::Serialize, near_sdk::serde::Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IssuedAttestation {
+    pub nonce: u64,
+    pub payload: Base64VecU8,
+    pub payload_hash: Base64VecU8,
+    pub expires_at: u64,
+}
+
+// Issued attestation with the MPC signer's response, None if signing failed
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SignedCapabilityAttestation {
+    pub attestation: IssuedAttestation,
+    pub signature: Option<near_sdk::serde_json::Value>,
+}
+
+// On-chain record an attestation is verified against
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct AttestationRecord {
+    agent_id: AccountId,
+    // Account that paid for the record's storage
+    issuer: AccountId,
+    payload_hash: Vec<u8>,
+    expires_at: u64,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Attest the agent's current score, trust level and capability limits until the
+    // attestation TTL passes. The payload hash is recorded under a new nonce and logged, so
+    // gateways can validate it with verify_attestation once and cache it until expiry. The
+    // caller pays for the record's storage, released by prune_capability_attestation.
+    pub fn issue_capability_attestation(&mut self, agent_id: AccountId) -> IssuedAttestation {
+        let issuer = env::predecessor_account_id();
+        let agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+        self.assert_agent_in_good_standing(&agent_id);
+
+        let initial_storage = env::storage_usage();
+        let nonce = self.next_attestation_nonce;
+        self.next_attestation_nonce += 1;
+
+        let now = env::block_timestamp();
+        let expires_at = now + self.attestation_ttl;
+        let (epoch, score) = match self.internal_finalized_score(&agent_id) {
+            Some((epoch, score)) => (Some(epoch), score),
+            None => (None, agent_rep.score),
+        };
+        let payload = CapabilityAttestation {
+            agent_id: agent_id.clone(),
+            score,
+            trust_level: self.get_trust_level(score),
+            limits: self.internal_capability_limits(&agent_id, &agent_rep, None),
+            issued_at: now,
+            expires_at,
+            nonce,
+            epoch,
+        }
+        .try_to_vec()
+        .expect("Failed to serialize attestation payload");
+        let payload_hash = env::keccak256(&payload);
+
+        self.capability_attestations.insert(
+            &nonce,
+            &AttestationRecord {
+                agent_id: agent_id.clone(),
+                issuer: issuer.clone(),
+                payload_hash: payload_hash.clone(),
+                expires_at,
+            },
+        );
+        self.internal_charge_storage(&issuer, initial_storage);
+
+        let attestation = IssuedAttestation {
+            nonce,
+            payload: payload.into(),
+            payload_hash: payload_hash.into(),
+            expires_at,
+        };
+
+        emit_event(
+            "capability_attested",
+            json!({
+                "agent_id": agent_id,
+                "issuer": issuer,
+                "nonce": attestation.nonce,
+                "payload": attestation.payload,
+                "payload_hash": attestation.payload_hash,
+                "expires_at": expires_at,
+            }),
+        );
+
+        attestation
+    }
+
+    // Whether `payload` is exactly the attestation recorded under `nonce`, it has not expired
+    // and its agent has not since been suspended or banned
+    pub fn verify_attestation(&self, payload: Base64VecU8, nonce: u64) -> bool {
+        self.capability_attestations.get(&nonce).is_some_and(|record| {
+            record.payload_hash == env::keccak256(&payload.0)
+                && env::block_timestamp() < record.expires_at
+                && self.internal_agent_status(&record.agent_id).in_good_standing()
+        })
+    }
+
+    // Delete an expired attestation record, releasing its storage to the issuer. Callable by
+    // anyone.
+    pub fn prune_capability_attestation(&mut self, nonce: u64) {
+        let record = self.capability_attestations.get(&nonce).expect("Attestation not found");
+        assert!(env::block_timestamp() >= record.expires_at, "Attestation has not expired");
+
+        let initial_storage = env::storage_usage();
+        self.capability_attestations.remove(&nonce);
+        self.internal_release_storage(&record.issuer, initial_storage - env::storage_usage());
+    }
+}
+
+#[cfg(feature = "cross-chain")]
+#[near_bindgen]
+impl AgentReputationContract {
+    // Issue a capability attestation and have the MPC signer sign its payload hash with the
+    // contract's attestation key, so gateways can verify it without any NEAR call. Any
+    // deposit is forwarded to the signer as its fee.
+    #[payable]
+    pub fn issue_capability_attestation_signed(&mut self, agent_id: AccountId) -> Promise {
+        let signer_id = self.chain_signer_id.clone().expect("Chain signatures are not configured");
+        let attestation = self.issue_capability_attestation(agent_id);
+        let deposit = env::attached_deposit();
+
+        Promise::new(signer_id)
+            .function_call(
+                "sign".to_string(),
+                json!({
+                    "request": {
+                        "payload": attestation.payload_hash.0,
+                        "path": ATTESTATION_SIGNING_PATH,
+                        "key_version": 0,
+                    }
+                }).to_string().into_bytes(),
+                deposit,
+                GAS_FOR_CHAIN_SIGNATURE,
+            )
+            .then(
+                Promise::new(env::current_account_id()).function_call(
+                    "on_capability_attestation_signed".to_string(),
+                    json!({
+                        "issuer": env::predecessor_account_id(),
+                        "attestation": attestation,
+                        "deposit": U128(deposit),
+                    }).to_string().into_bytes(),
+                    0,
+                    GAS_FOR_SIGNATURE_CALLBACK,
+                ),
+            )
+    }
+
+    // Callback pairing the attestation with the MPC signature. The unsigned attestation stays
+    // valid through verify_attestation if signing failed.
+    #[private]
+    pub fn on_capability_attestation_signed(
+        &mut self,
+        issuer: AccountId,
+        attestation: IssuedAttestation,
+        deposit: U128,
+    ) -> SignedCapabilityAttestation {
+        let signature = match env::promise_result(0) {
+            PromiseResult::Successful(result) => near_sdk::serde_json::from_slice::<Value>(&result).ok(),
+            _ => None,
+        };
+
+        if signature.is_none() && deposit.0 > 0 {
+            Promise::new(issuer).transfer(deposit.0);
+        }
+
+        emit_event(
+            "capability_attestation_signed",
+            json!({
+                "nonce": attestation.nonce,
+                "signature": signature,
+            }),
+        );
+
+        SignedCapabilityAttestation { attestation, signature }
+    }
+}
@@ -0,0 +1,272 @@
+use crate::*;
+use crate::events::emit_event;
+use near_sdk::json_types::Base64VecU8;
+use std::collections::BTreeMap;
+
+// Feedback entries examined when an audit opens, by default and at most
+const DEFAULT_AUDIT_LOOKBACK: u32 = 50;
+const MAX_AUDIT_LOOKBACK: u32 = 100;
+
+// Smallest group of identical rating vectors worth reporting; pairs are too common among
+// honest reviewers to mean anything
+const MIN_IDENTICAL_RATINGS_GROUP: usize = 3;
+
+// Maximum length of an audit ruling note
+const MAX_AUDIT_NOTE_LENGTH: usize = 1_000;
+
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum AuditStatus {
+    // Suspect feedback is quarantined pending a ruling
+    Open,
+    // Manipulation confirmed; quarantined feedback stays out and a violation was recorded
+    Confirmed,
+    // No manipulation found; quarantined feedback counts again
+    Cleared,
+}
+
+// Suspicious patterns found in the audited feedback, by feedback index
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ManipulationReport {
+    // Range of feedback indexes examined
+    from_index: u64,
+    to_index: u64,
+    // Feedback whose intent is missing or was not the reviewer's with this agent; quarantined
+    unlinked: Vec<u64>,
+    // Feedback submitted in the same block
+    same_block: Vec<Vec<u64>>,
+    // Feedback with identical overall and category ratings
+    identical_ratings: Vec<Vec<u64>>,
+}
+
+// Governance investigation of suspected score manipulation for an agent
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ManipulationAudit {
+    opened_by: AccountId,
+    opened_at: u64,
+    report: ManipulationReport,
+    // Feedback this audit took out of scoring
+    quarantined: Vec<u64>,
+    status: AuditStatus,
+    resolved_at: Option<u64>,
+    ruling_note: Option<String>,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Put an agent under a manipulation audit (owner or governance). The last `lookback`
+    // feedback entries are examined: entries without a linked intent are quarantined from
+    // scoring, and same-block and identical-rating patterns are compiled into the report.
+    pub fn open_manipulation_audit(&mut self, agent_id: AccountId, lookback: Option<u32>) -> ManipulationAuditView {
+        self.assert_owner_or_governance();
+        if let Some(audit) = self.manipulation_audits.get(&agent_id) {
+            assert!(audit.status != AuditStatus::Open, "Agent is already under audit");
+        }
+        let lookback = lookback.unwrap_or(DEFAULT_AUDIT_LOOKBACK);
+        assert!(lookback > 0 && lookback <= MAX_AUDIT_LOOKBACK, "Audit lookback out of range");
+
+        let mut agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+        let to_index = agent_rep.feedback_count;
+        let from_index = std::cmp::max(to_index.saturating_sub(lookback as u64), agent_rep.feedback_pruned);
+
+        let mut unlinked = Vec::new();
+        let mut by_block: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+        let mut by_ratings: BTreeMap<[u8; 6], Vec<u64>> = BTreeMap::new();
+        for index in from_index..to_index {
+            let feedback = agent_rep.feedback_history.get(&index).expect("Feedback entry missing");
+
+            let linked = self
+                .intents
+                .get(&feedback.intent_id)
+                .is_some_and(|intent| intent.client_id == feedback.user_id && intent.agent_id == agent_id);
+            if !linked {
+                unlinked.push(index);
+            }
+
+            by_block.entry(feedback.timestamp).or_default().push(index);
+            let ratings = &feedback.category_ratings;
+            by_ratings
+                .entry([
+                    feedback.rating,
+                    ratings.accuracy,
+                    ratings.response_time,
+                    ratings.communication,
+                    ratings.problem_solving,
+                    ratings.ethics,
+                ])
+                .or_default()
+                .push(index);
+        }
+
+        // Quarantine unlinked feedback still counting toward the score
+        let mut quarantined = Vec::new();
+        for &index in &unlinked {
+            let mut feedback = agent_rep.feedback_history.get(&index).expect("Feedback entry missing");
+            if feedback.counts_toward_score() {
+                agent_rep.discount_feedback(index, &feedback);
+                feedback.quarantined = true;
+                agent_rep.feedback_history.insert(&index, &feedback);
+                quarantined.push(index);
+            }
+        }
+        if !quarantined.is_empty() {
+            self.internal_rescore_after_audit(&agent_id, &mut agent_rep);
+        }
+
+        let audit = ManipulationAudit {
+            opened_by: env::predecessor_account_id(),
+            opened_at: env::block_timestamp(),
+            report: ManipulationReport {
+                from_index,
+                to_index,
+                unlinked,
+                same_block: by_block.into_values().filter(|group| group.len() > 1).collect(),
+                identical_ratings: by_ratings
+                    .into_values()
+                    .filter(|group| group.len() >= MIN_IDENTICAL_RATINGS_GROUP)
+                    .collect(),
+            },
+            quarantined,
+            status: AuditStatus::Open,
+            resolved_at: None,
+            ruling_note: None,
+        };
+        self.manipulation_audits.insert(&agent_id, &audit);
+
+        emit_event(
+            "manipulation_audit_opened",
+            json!({
+                "agent_id": agent_id,
+                "opened_by": audit.opened_by,
+                "quarantined": audit.quarantined,
+            }),
+        );
+
+        audit_view(agent_id, audit)
+    }
+
+    // Rule on an open audit (owner or governance). Confirmed manipulation keeps the quarantined
+    // feedback out of scoring and records a terms violation with the report as evidence; a
+    // cleared audit restores the quarantined feedback.
+    pub fn resolve_manipulation_audit(&mut self, agent_id: AccountId, confirmed: bool, ruling_note: Option<String>) {
+        self.assert_owner_or_governance();
+        if let Some(note) = &ruling_note {
+            assert!(note.len() <= MAX_AUDIT_NOTE_LENGTH, "Ruling note too long");
+        }
+        let mut audit = self.manipulation_audits.get(&agent_id).expect("No audit for agent");
+        assert!(audit.status == AuditStatus::Open, "Audit already resolved");
+
+        let initial_storage = env::storage_usage();
+
+        if confirmed {
+            let report = near_sdk::serde_json::to_vec(&audit.report).expect("Failed to serialize audit report");
+            let evidence = vec![Evidence {
+                hash: Base64VecU8(env::sha256(&report)),
+                uri: format!("near:{}/get_manipulation_audit?agent_id={}", env::current_account_id(), agent_id),
+                media_type: "application/json".to_string(),
+            }];
+            self.internal_record_violation(
+                agent_id.clone(),
+                ViolationType::TermsViolation,
+                env::predecessor_account_id(),
+                format!(
+                    "Score manipulation confirmed by audit: {} unlinked, {} same-block and {} identical-rating groups",
+                    audit.report.unlinked.len(),
+                    audit.report.same_block.len(),
+                    audit.report.identical_ratings.len()
+                ),
+                evidence,
+                false,
+            );
+        } else if !audit.quarantined.is_empty() {
+            let mut agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+            for &index in &audit.quarantined {
+                let mut feedback = agent_rep.feedback_history.get(&index).expect("Feedback entry missing");
+                feedback.quarantined = false;
+                agent_rep.feedback_history.insert(&index, &feedback);
+                // Feedback upheld in a dispute since quarantine stays out
+                if feedback.counts_toward_score() {
+                    agent_rep.recount_feedback(index, &feedback);
+                }
+            }
+            self.internal_rescore_after_audit(&agent_id, &mut agent_rep);
+        }
+
+        audit.status = if confirmed { AuditStatus::Confirmed } else { AuditStatus::Cleared };
+        audit.resolved_at = Some(env::block_timestamp());
+        audit.ruling_note = ruling_note;
+        self.manipulation_audits.insert(&agent_id, &audit);
+
+        // The resolving member pays for the storage of the violation record
+        self.internal_charge_storage(&env::predecessor_account_id(), initial_storage);
+        if confirmed {
+            let mut agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+            self.internal_apply_retention(&agent_id, &mut agent_rep);
+        }
+
+        emit_event(
+            "manipulation_audit_resolved",
+            json!({
+                "agent_id": agent_id,
+                "status": audit.status,
+            }),
+        );
+    }
+
+    // Get the latest manipulation audit of an agent with its report
+    pub fn get_manipulation_audit(&self, agent_id: AccountId) -> Option<ManipulationAuditView> {
+        self.manipulation_audits
+            .get(&agent_id)
+            .map(|audit| audit_view(agent_id, audit))
+    }
+
+    // Whether an agent is under an open manipulation audit
+    pub fn is_under_audit(&self, agent_id: AccountId) -> bool {
+        self.manipulation_audits
+            .get(&agent_id)
+            .is_some_and(|audit| audit.status == AuditStatus::Open)
+    }
+
+    // First feedback index an open audit of the agent covers; entries from here on must be kept
+    pub(crate) fn internal_open_audit_from_index(&self, agent_id: &AccountId) -> Option<u64> {
+        self.manipulation_audits
+            .get(agent_id)
+            .filter(|audit| audit.status == AuditStatus::Open)
+            .map(|audit| audit.report.from_index)
+    }
+
+    fn internal_rescore_after_audit(&mut self, agent_id: &AccountId, agent_rep: &mut AgentReputation) {
+        self.recalculate_reputation_with_categories(agent_id, agent_rep);
+        agent_rep.last_update = env::block_timestamp();
+        self.internal_save_agent(agent_id, agent_rep);
+        self.internal_update_shadow_score(agent_id, agent_rep);
+    }
+}
+
+fn audit_view(agent_id: AccountId, audit: ManipulationAudit) -> ManipulationAuditView {
+    ManipulationAuditView {
+        agent_id,
+        opened_by: audit.opened_by,
+        opened_at: audit.opened_at,
+        report: audit.report,
+        quarantined: audit.quarantined,
+        status: audit.status,
+        resolved_at: audit.resolved_at,
+        ruling_note: audit.ruling_note,
+    }
+}
+
+// View struct for a manipulation audit
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ManipulationAuditView {
+    agent_id: AccountId,
+    opened_by: AccountId,
+    opened_at: u64,
+    report: ManipulationReport,
+    quarantined: Vec<u64>,
+    status: AuditStatus,
+    resolved_at: Option<u64>,
+    ruling_note: Option<String>,
+}
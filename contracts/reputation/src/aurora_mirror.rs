@@ -0,0 +1,389 @@
+use crate::*;
+#[cfg(feature = "cross-chain")]
+use crate::chain_signatures::to_hex;
+#[cfg(feature = "cross-chain")]
+use crate::events::emit_event;
+#[cfg(feature = "cross-chain")]
+use crate::identity::parse_evm_address;
+
+// Gas for the Aurora engine's call into the mirror contract
+#[cfg(feature = "cross-chain")]
+const GAS_FOR_AURORA_MIRROR_CALL: Gas = Gas(50_000_000_000_000);
+
+// Gas reserved for on_aurora_mirror_synced
+#[cfg(feature = "cross-chain")]
+const GAS_FOR_AURORA_MIRROR_CALLBACK: Gas = Gas(10_000_000_000_000);
+
+// Most score updates pushed per sync_aurora_mirror call, to stay within the gas limit
+#[cfg(feature = "cross-chain")]
+const MAX_MIRROR_SYNCS_PER_CALL: u32 = 4;
+
+// Most pending syncs looked at per sync_aurora_mirror call, skipping agents whose reputation
+// is locked
+#[cfg(feature = "cross-chain")]
+const MAX_MIRROR_SYNCS_SCANNED: usize = 50;
+
+// Most automatic attempts at pushing an update before the sync is marked failed
+#[cfg(feature = "cross-chain")]
+const MAX_MIRROR_ATTEMPTS: u32 = 10;
+
+// Upper bound on mirror syncs returned per page
+#[cfg(feature = "cross-chain")]
+const MAX_MIRROR_SYNCS_PAGE: u32 = 100;
+
+// Solidity signature of the mirror contract's update function
+#[cfg(feature = "cross-chain")]
+const MIRROR_UPDATE_SIGNATURE: &str = "updateReputation(bytes32,uint32,uint8,uint64)";
+
+// Contract on Aurora that score updates are mirrored to. The mirror sees the calls coming from
+// this contract's Aurora address (the last 20 bytes of keccak256 of its account ID) and should
+// accept updates only from it.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuroraMirrorConfig {
+    // Aurora engine account the EVM calls go through
+    pub engine_id: AccountId,
+    // 0x-prefixed address of the mirror contract
+    pub mirror_address: String,
+    // Smallest change in an agent's score (0-100) since it was last mirrored that is pushed
+    pub min_score_change: u32,
+    // Failed pushes of an update retried before its sync is marked failed
+    pub max_attempts: u32,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum MirrorSyncStatus {
+    // The mirror holds the agent's score, within the change threshold
+    Synced,
+    // An update is due and waits for the next sync_aurora_mirror call
+    Pending,
+    // An update was pushed and its result is not in yet
+    InFlight,
+    // Pushing the update failed max_attempts times; retry_aurora_mirror_sync requeues it
+    Failed,
+}
+
+// An agent's score as mirrored to Aurora
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct MirrorSync {
+    // Score the mirror confirmed last, if any
+    mirrored_score: Option<u32>,
+    // Score in the update being pushed
+    in_flight_score: Option<u32>,
+    status: MirrorSyncStatus,
+    // Failed pushes of the current update
+    attempts: u32,
+    queued_at: u64,
+    last_attempt_at: Option<u64>,
+}
+
+impl MirrorSync {
+    fn new() -> Self {
+        Self {
+            mirrored_score: None,
+            in_flight_score: None,
+            status: MirrorSyncStatus::Synced,
+            attempts: 0,
+            queued_at: env::block_timestamp(),
+            last_attempt_at: None,
+        }
+    }
+}
+
+// Arguments of the Aurora engine's `call` method (CallArgs::V2)
+#[cfg(feature = "cross-chain")]
+#[derive(BorshSerialize)]
+enum AuroraCallArgs {
+    V2(AuroraFunctionCallArgs),
+}
+
+#[cfg(feature = "cross-chain")]
+#[derive(BorshSerialize)]
+struct AuroraFunctionCallArgs {
+    contract: [u8; 20],
+    // Attached ETH (wei, big-endian u256)
+    value: [u8; 32],
+    input: Vec<u8>,
+}
+
+// Leading fields of the SubmitResult the Aurora engine's `call` returns; the gas used and logs
+// that follow are not read
+#[cfg(feature = "cross-chain")]
+#[derive(BorshDeserialize)]
+struct AuroraSubmitResult {
+    #[allow(dead_code)]
+    version: u8,
+    status: AuroraTransactionStatus,
+}
+
+// Outcome of the EVM call. The engine reports reverts and other EVM errors as a successful
+// NEAR call, so only Succeed means the mirror took the update.
+#[cfg(feature = "cross-chain")]
+#[derive(BorshDeserialize)]
+#[allow(dead_code)]
+enum AuroraTransactionStatus {
+    Succeed(Vec<u8>),
+    Revert(Vec<u8>),
+    OutOfGas,
+    OutOfFund,
+    OutOfOffset,
+    CallTooDeep,
+}
+
+#[cfg(feature = "cross-chain")]
+#[near_bindgen]
+impl AgentReputationContract {
+    // Set the Aurora contract agents' scores are mirrored to, or None to stop mirroring (owner
+    // or governance). Syncs already queued wait until a mirror is set again.
+    pub fn set_aurora_mirror(&mut self, config: Option<AuroraMirrorConfig>) {
+        self.assert_owner_or_governance();
+        if let Some(config) = &config {
+            parse_evm_address(&config.mirror_address);
+            assert!(config.min_score_change <= 100, "Score change threshold must be between 0 and 100");
+            assert!(
+                config.max_attempts > 0 && config.max_attempts <= MAX_MIRROR_ATTEMPTS,
+                "Max attempts out of range"
+            );
+        }
+
+        let old_value = std::mem::replace(&mut self.aurora_mirror, config.clone());
+
+        self.record_config_change("aurora_mirror", json!(old_value), json!(config));
+    }
+
+    // Get the Aurora mirror settings, if mirroring is enabled
+    pub fn get_aurora_mirror(&self) -> Option<AuroraMirrorConfig> {
+        self.aurora_mirror.clone()
+    }
+
+    // Push pending score updates to the Aurora mirror, up to `limit`, starting at `from_index`
+    // in the pending queue. Agents whose reputation is locked (see get_portability_lock) are
+    // skipped until the lock lifts. Callable by anyone; each push needs about 60 Tgas.
+    // Returns the number of updates pushed.
+    pub fn sync_aurora_mirror(&mut self, from_index: Option<u64>, limit: Option<u32>) -> u32 {
+        let config = self.aurora_mirror.clone().expect("Aurora mirror is not configured");
+        let mirror_address = parse_evm_address(&config.mirror_address);
+        let limit = std::cmp::min(limit.unwrap_or(MAX_MIRROR_SYNCS_PER_CALL), MAX_MIRROR_SYNCS_PER_CALL);
+
+        let candidates: Vec<AccountId> = self
+            .mirror_sync_queue
+            .iter()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(MAX_MIRROR_SYNCS_SCANNED)
+            .collect();
+
+        let now = env::block_timestamp();
+        let mut pushed = 0;
+        for agent_id in candidates {
+            if pushed == limit {
+                break;
+            }
+            if self.internal_portability_lock(&agent_id).is_some() {
+                continue;
+            }
+            let mut sync = self.mirror_syncs.get(&agent_id).unwrap();
+            let agent_rep = match self.agent_reputations.get(&agent_id) {
+                Some(agent_rep) => agent_rep,
+                None => {
+                    // Unregistered since it was queued
+                    self.mirror_sync_queue.remove(&agent_id);
+                    self.mirror_syncs.remove(&agent_id);
+                    continue;
+                },
+            };
+            let score = agent_rep.score;
+            let trust_level = self.get_trust_level(score);
+
+            sync.status = MirrorSyncStatus::InFlight;
+            sync.in_flight_score = Some(score);
+            sync.last_attempt_at = Some(now);
+            self.mirror_syncs.insert(&agent_id, &sync);
+            self.mirror_sync_queue.remove(&agent_id);
+
+            let args = AuroraCallArgs::V2(AuroraFunctionCallArgs {
+                contract: mirror_address,
+                value: [0; 32],
+                input: mirror_update_input(&agent_id, score, trust_level.code(), now),
+            });
+            Promise::new(config.engine_id.clone())
+                .function_call(
+                    "call".to_string(),
+                    args.try_to_vec().expect("Failed to serialize Aurora call"),
+                    0,
+                    GAS_FOR_AURORA_MIRROR_CALL,
+                )
+                .then(
+                    Promise::new(env::current_account_id()).function_call(
+                        "on_aurora_mirror_synced".to_string(),
+                        json!({ "agent_id": agent_id, "score": score }).to_string().into_bytes(),
+                        0,
+                        GAS_FOR_AURORA_MIRROR_CALLBACK,
+                    ),
+                );
+            pushed += 1;
+        }
+        pushed
+    }
+
+    // Callback recording a pushed update as mirrored, or counting the failed attempt. The
+    // update only counts as mirrored if the EVM call itself succeeded.
+    #[private]
+    pub fn on_aurora_mirror_synced(&mut self, agent_id: AccountId, score: u32) {
+        let mut sync = match self.mirror_syncs.get(&agent_id) {
+            Some(sync) => sync,
+            None => return,
+        };
+        sync.in_flight_score = None;
+
+        let succeeded = match env::promise_result(0) {
+            PromiseResult::Successful(result) => matches!(
+                AuroraSubmitResult::deserialize(&mut result.as_slice()),
+                Ok(AuroraSubmitResult { status: AuroraTransactionStatus::Succeed(_), .. })
+            ),
+            _ => false,
+        };
+        if succeeded {
+            sync.mirrored_score = Some(score);
+            sync.attempts = 0;
+            sync.status = MirrorSyncStatus::Synced;
+            emit_event("aurora_mirror_synced", json!({ "agent_id": agent_id, "score": score }));
+        } else {
+            sync.attempts += 1;
+            let max_attempts = self.aurora_mirror.as_ref().map_or(1, |config| config.max_attempts);
+            if sync.attempts >= max_attempts {
+                sync.status = MirrorSyncStatus::Failed;
+                self.failed_mirror_syncs.insert(&agent_id);
+            } else {
+                sync.status = MirrorSyncStatus::Pending;
+                self.mirror_sync_queue.insert(&agent_id);
+            }
+            emit_event(
+                "aurora_mirror_sync_failed",
+                json!({
+                    "agent_id": agent_id,
+                    "score": score,
+                    "attempts": sync.attempts,
+                    "status": sync.status,
+                }),
+            );
+        }
+        self.mirror_syncs.insert(&agent_id, &sync);
+
+        // The score may have moved on while the update was in flight; requeue it if so
+        if sync.status == MirrorSyncStatus::Synced {
+            if let Some(agent_rep) = self.agent_reputations.get(&agent_id) {
+                self.internal_queue_mirror_sync(&agent_id, agent_rep.score);
+            }
+        }
+    }
+
+    // Requeue an agent's failed mirror sync with a fresh set of attempts. Callable by anyone.
+    pub fn retry_aurora_mirror_sync(&mut self, agent_id: AccountId) {
+        let mut sync = self.mirror_syncs.get(&agent_id).expect("No mirror sync for this agent");
+        assert_eq!(sync.status, MirrorSyncStatus::Failed, "Mirror sync has not failed");
+
+        sync.status = MirrorSyncStatus::Pending;
+        sync.attempts = 0;
+        sync.queued_at = env::block_timestamp();
+        self.mirror_syncs.insert(&agent_id, &sync);
+        self.failed_mirror_syncs.remove(&agent_id);
+        self.mirror_sync_queue.insert(&agent_id);
+    }
+
+    // Get an agent's mirror sync state
+    pub fn get_aurora_mirror_sync(&self, agent_id: AccountId) -> Option<MirrorSyncView> {
+        self.mirror_syncs.get(&agent_id).map(|sync| mirror_sync_view(agent_id, sync))
+    }
+
+    // Get a page of the agents whose mirror sync is pending, then those whose sync failed
+    pub fn get_aurora_mirror_syncs(&self, from_index: u64, limit: u32) -> Vec<MirrorSyncView> {
+        self.mirror_sync_queue
+            .iter()
+            .chain(self.failed_mirror_syncs.iter())
+            .skip(from_index as usize)
+            .take(std::cmp::min(limit, MAX_MIRROR_SYNCS_PAGE) as usize)
+            .filter_map(|agent_id| {
+                let sync = self.mirror_syncs.get(&agent_id)?;
+                Some(mirror_sync_view(agent_id, sync))
+            })
+            .collect()
+    }
+}
+
+impl AgentReputationContract {
+    // Queue the agent's new score for the Aurora mirror if it moved by the change threshold
+    // since it was last mirrored, or drop a queued update it moved back from
+    pub(crate) fn internal_queue_mirror_sync(&mut self, agent_id: &AccountId, score: u32) {
+        let min_score_change = match &self.aurora_mirror {
+            Some(config) => config.min_score_change,
+            None => return,
+        };
+        let mut sync = self.mirror_syncs.get(agent_id).unwrap_or_else(MirrorSync::new);
+        let due = sync
+            .mirrored_score
+            .is_none_or(|mirrored| mirrored.abs_diff(score) >= min_score_change);
+
+        // In-flight updates are rechecked when their result lands; failed ones wait for a retry,
+        // which pushes the latest score
+        match (sync.status, due) {
+            (MirrorSyncStatus::Synced, true) => {
+                sync.status = MirrorSyncStatus::Pending;
+                sync.attempts = 0;
+                sync.queued_at = env::block_timestamp();
+                self.mirror_sync_queue.insert(agent_id);
+            },
+            (MirrorSyncStatus::Pending, false) => {
+                sync.status = MirrorSyncStatus::Synced;
+                self.mirror_sync_queue.remove(agent_id);
+            },
+            _ => return,
+        }
+        self.mirror_syncs.insert(agent_id, &sync);
+    }
+}
+
+// ABI-encoded call of the mirror's updateReputation(bytes32 agentKey, uint32 score,
+// uint8 trustLevel, uint64 updatedAt), where agentKey is keccak256 of the agent's account ID
+#[cfg(feature = "cross-chain")]
+fn mirror_update_input(agent_id: &AccountId, score: u32, trust_level: u8, updated_at: u64) -> Vec<u8> {
+    let mut input = env::keccak256(MIRROR_UPDATE_SIGNATURE.as_bytes())[..4].to_vec();
+    input.extend_from_slice(&env::keccak256(agent_id.as_bytes()));
+    for value in [score as u64, trust_level as u64, updated_at] {
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&value.to_be_bytes());
+        input.extend_from_slice(&word);
+    }
+    input
+}
+
+#[cfg(feature = "cross-chain")]
+fn mirror_sync_view(agent_id: AccountId, sync: MirrorSync) -> MirrorSyncView {
+    MirrorSyncView {
+        agent_key: to_hex(&env::keccak256(agent_id.as_bytes())),
+        agent_id,
+        status: sync.status,
+        mirrored_score: sync.mirrored_score,
+        in_flight_score: sync.in_flight_score,
+        attempts: sync.attempts,
+        queued_at: sync.queued_at,
+        last_attempt_at: sync.last_attempt_at,
+    }
+}
+
+// View struct for an agent's mirror sync
+#[cfg(feature = "cross-chain")]
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MirrorSyncView {
+    agent_id: AccountId,
+    // Key the agent is stored under in the mirror contract
+    agent_key: String,
+    status: MirrorSyncStatus,
+    mirrored_score: Option<u32>,
+    in_flight_score: Option<u32>,
+    // Failed pushes of the current update
+    attempts: u32,
+    queued_at: u64,
+    last_attempt_at: Option<u64>,
+}
@@ -0,0 +1,146 @@
+use crate::*;
+
+// Rule that decided an authorization request
+#[derive(near_sdk::serde::Serialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum AuthorizationRule {
+    // Agent is not in the registry
+    AgentNotRegistered,
+    // Agent is suspended or banned
+    AgentSuspended,
+    // Action type is not recognized
+    UnknownAction,
+    // Transaction requested without a value
+    MissingValue,
+    // Transaction value exceeds the trust level's limit
+    TrustLimit,
+    // Trust level does not grant the capability
+    CapabilityNotGranted,
+    // All checks passed
+    Allowed,
+}
+
+// Outcome of evaluating an action against an agent's capabilities
+pub struct AuthorizationDecision {
+    pub(crate) rule: AuthorizationRule,
+    pub(crate) trust_level: Option<TrustLevel>,
+    pub(crate) limit: Option<Balance>,
+}
+
+impl AuthorizationDecision {
+    pub fn allowed(&self) -> bool {
+        self.rule == AuthorizationRule::Allowed
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Dry-run an authorization check, reporting the exact rule that decided it
+    pub fn simulate_authorization(
+        &self,
+        agent_id: AccountId,
+        action: String,
+        value: Option<U128>,
+        consumer_id: Option<AccountId>,
+    ) -> AuthorizationSimulationView {
+        let decision = self.evaluate_authorization(&agent_id, &action, value.map(|v| v.0));
+
+        AuthorizationSimulationView {
+            agent_id,
+            action,
+            consumer_id,
+            allowed: decision.allowed(),
+            rule: decision.rule,
+            message: authorization_message(decision.rule).to_string(),
+            trust_level: decision.trust_level,
+            limit: decision.limit.map(U128),
+        }
+    }
+
+    // Evaluate an action against the agent's capability limits
+    pub(crate) fn evaluate_authorization(
+        &self,
+        agent_id: &AccountId,
+        action_type: &str,
+        value: Option<Balance>,
+    ) -> AuthorizationDecision {
+        let agent_rep = match self.agent_reputations.get(agent_id) {
+            Some(agent_rep) => agent_rep,
+            None => {
+                return AuthorizationDecision {
+                    rule: AuthorizationRule::AgentNotRegistered,
+                    trust_level: None,
+                    limit: None,
+                }
+            }
+        };
+
+        let trust_level = self.get_trust_level(agent_rep.score);
+        if !self.internal_agent_status(agent_id).in_good_standing() {
+            return AuthorizationDecision {
+                rule: AuthorizationRule::AgentSuspended,
+                trust_level: Some(trust_level),
+                limit: None,
+            };
+        }
+
+        let limits = self.get_capability_limits(agent_id.clone(), None).expect("Agent not registered");
+        let mut limit = None;
+
+        let rule = match action_type {
+            "transaction" => {
+                limit = Some(limits.max_transaction_value);
+                match value {
+                    None => AuthorizationRule::MissingValue,
+                    Some(tx_value) if tx_value > limits.max_transaction_value => AuthorizationRule::TrustLimit,
+                    Some(_) => AuthorizationRule::Allowed,
+                }
+            },
+            "critical_access" => granted(limits.can_access_critical_systems),
+            "autonomous_operation" => granted(limits.can_operate_autonomously),
+            "delegation" => granted(limits.can_delegate),
+            _ => AuthorizationRule::UnknownAction,
+        };
+
+        AuthorizationDecision {
+            rule,
+            trust_level: Some(trust_level),
+            limit,
+        }
+    }
+}
+
+fn granted(capability: bool) -> AuthorizationRule {
+    if capability {
+        AuthorizationRule::Allowed
+    } else {
+        AuthorizationRule::CapabilityNotGranted
+    }
+}
+
+// Human-readable explanation for each rule, suitable for integrator error messages
+pub(crate) fn authorization_message(rule: AuthorizationRule) -> &'static str {
+    match rule {
+        AuthorizationRule::AgentNotRegistered => "Agent is not registered",
+        AuthorizationRule::AgentSuspended => "Agent is suspended or banned",
+        AuthorizationRule::UnknownAction => "Unknown action type",
+        AuthorizationRule::MissingValue => "Transaction actions require a value",
+        AuthorizationRule::TrustLimit => "Transaction value exceeds the agent's trust level limit",
+        AuthorizationRule::CapabilityNotGranted => "Agent's trust level does not grant this capability",
+        AuthorizationRule::Allowed => "Action allowed",
+    }
+}
+
+// View struct for authorization simulations
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuthorizationSimulationView {
+    agent_id: AccountId,
+    action: String,
+    consumer_id: Option<AccountId>,
+    allowed: bool,
+    rule: AuthorizationRule,
+    message: String,
+    trust_level: Option<TrustLevel>,
+    limit: Option<U128>,
+}
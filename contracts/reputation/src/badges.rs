@@ -0,0 +1,255 @@
+use crate::*;
+use crate::events::{emit_event, emit_standard_event};
+use near_sdk::PromiseOrValue;
+
+// NEP-171 standard name and version of badge mint events
+const NFT_STANDARD: &str = "nep171";
+const NFT_STANDARD_VERSION: &str = "1.2.0";
+
+// NEP-177 metadata spec of the badge collection
+const NFT_METADATA_SPEC: &str = "nft-1.0.0";
+
+// Successful interactions that earn the interactions badge
+const INTERACTIONS_BADGE_THRESHOLD: u64 = 1000;
+
+// Upper bound on badges returned by one enumeration call
+const MAX_BADGE_PAGE: u64 = 100;
+
+// Reputation milestone a badge marks. Badges are kept once earned, even if the agent later
+// falls back below the milestone.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum BadgeKind {
+    TrustedTier,
+    ExpertTier,
+    MasterTier,
+    ThousandInteractions,
+}
+
+impl BadgeKind {
+    pub const ALL: [BadgeKind; 4] = [
+        BadgeKind::TrustedTier,
+        BadgeKind::ExpertTier,
+        BadgeKind::MasterTier,
+        BadgeKind::ThousandInteractions,
+    ];
+
+    fn code(&self) -> &'static str {
+        match self {
+            BadgeKind::TrustedTier => "trusted",
+            BadgeKind::ExpertTier => "expert",
+            BadgeKind::MasterTier => "master",
+            BadgeKind::ThousandInteractions => "interactions_1000",
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            BadgeKind::TrustedTier => "Trusted Agent",
+            BadgeKind::ExpertTier => "Expert Agent",
+            BadgeKind::MasterTier => "Master Agent",
+            BadgeKind::ThousandInteractions => "1000 Successful Interactions",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            BadgeKind::TrustedTier => "Reached the Trusted reputation tier",
+            BadgeKind::ExpertTier => "Reached the Expert reputation tier",
+            BadgeKind::MasterTier => "Reached the Master reputation tier",
+            BadgeKind::ThousandInteractions => "Completed 1000 successful interactions",
+        }
+    }
+
+    fn reached(&self, agent_rep: &AgentReputation, trust_level: TrustLevel) -> bool {
+        match self {
+            BadgeKind::TrustedTier => trust_level >= TrustLevel::Trusted,
+            BadgeKind::ExpertTier => trust_level >= TrustLevel::Expert,
+            BadgeKind::MasterTier => trust_level >= TrustLevel::Master,
+            BadgeKind::ThousandInteractions => agent_rep.successful_interactions >= INTERACTIONS_BADGE_THRESHOLD,
+        }
+    }
+}
+
+// A minted badge
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Badge {
+    owner_id: AccountId,
+    kind: BadgeKind,
+    issued_at: u64,
+}
+
+// Each agent holds at most one badge of each kind
+fn badge_token_id(agent_id: &AccountId, kind: BadgeKind) -> String {
+    format!("{}:{}", agent_id, kind.code())
+}
+
+// Minimal non-transferable NEP-171 with NEP-177 metadata and NEP-181 enumeration. Badges are
+// minted by the contract as agents reach milestones and can never be transferred.
+#[near_bindgen]
+impl AgentReputationContract {
+    pub fn nft_metadata(&self) -> BadgeContractMetadataView {
+        BadgeContractMetadataView {
+            spec: NFT_METADATA_SPEC.to_string(),
+            name: "Intellex Reputation Badges".to_string(),
+            symbol: "ITLXBADGE".to_string(),
+            icon: None,
+            base_uri: None,
+            reference: None,
+            reference_hash: None,
+        }
+    }
+
+    pub fn nft_token(&self, token_id: String) -> Option<BadgeTokenView> {
+        self.badges.get(&token_id).map(|badge| badge_view(token_id, badge))
+    }
+
+    #[payable]
+    #[allow(unused_variables)]
+    pub fn nft_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: String,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    ) {
+        env::panic_str("Reputation badges are soulbound and cannot be transferred");
+    }
+
+    #[payable]
+    #[allow(unused_variables)]
+    pub fn nft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: String,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<bool> {
+        env::panic_str("Reputation badges are soulbound and cannot be transferred");
+    }
+
+    pub fn nft_total_supply(&self) -> U128 {
+        U128(self.badges.len() as u128)
+    }
+
+    pub fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<BadgeTokenView> {
+        self.badges
+            .iter()
+            .skip(from_index.map_or(0, |index| index.0) as usize)
+            .take(std::cmp::min(limit.unwrap_or(MAX_BADGE_PAGE), MAX_BADGE_PAGE) as usize)
+            .map(|(token_id, badge)| badge_view(token_id, badge))
+            .collect()
+    }
+
+    pub fn nft_supply_for_owner(&self, account_id: AccountId) -> U128 {
+        U128(self.internal_badges_of(&account_id).count() as u128)
+    }
+
+    pub fn nft_tokens_for_owner(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<BadgeTokenView> {
+        self.internal_badges_of(&account_id)
+            .skip(from_index.map_or(0, |index| index.0) as usize)
+            .take(std::cmp::min(limit.unwrap_or(MAX_BADGE_PAGE), MAX_BADGE_PAGE) as usize)
+            .map(|(token_id, badge)| badge_view(token_id, badge))
+            .collect()
+    }
+
+    fn internal_badges_of<'a>(&'a self, account_id: &'a AccountId) -> impl Iterator<Item = (String, Badge)> + 'a {
+        BadgeKind::ALL.iter().filter_map(move |kind| {
+            let token_id = badge_token_id(account_id, *kind);
+            self.badges.get(&token_id).map(|badge| (token_id, badge))
+        })
+    }
+
+    // Mint the badges for milestones the agent has just crossed
+    pub(crate) fn internal_award_badges(
+        &mut self,
+        agent_id: &AccountId,
+        previous: Option<&AgentReputation>,
+        current: &AgentReputation,
+    ) {
+        let trust_level = self.get_trust_level(current.score);
+
+        for kind in BadgeKind::ALL {
+            let newly_reached = kind.reached(current, trust_level)
+                && !previous.is_some_and(|p| kind.reached(p, self.get_trust_level(p.score)));
+            let token_id = badge_token_id(agent_id, kind);
+            if !newly_reached || self.badges.get(&token_id).is_some() {
+                continue;
+            }
+
+            self.badges.insert(
+                &token_id,
+                &Badge { owner_id: agent_id.clone(), kind, issued_at: env::block_timestamp() },
+            );
+
+            emit_standard_event(
+                NFT_STANDARD,
+                NFT_STANDARD_VERSION,
+                "nft_mint",
+                json!({
+                    "owner_id": agent_id,
+                    "token_ids": [token_id],
+                }),
+            );
+            emit_event(
+                "badge_awarded",
+                json!({
+                    "agent_id": agent_id,
+                    "badge": kind,
+                    "token_id": token_id,
+                }),
+            );
+        }
+    }
+}
+
+fn badge_view(token_id: String, badge: Badge) -> BadgeTokenView {
+    BadgeTokenView {
+        token_id,
+        owner_id: badge.owner_id,
+        metadata: BadgeMetadataView {
+            title: badge.kind.title().to_string(),
+            description: badge.kind.description().to_string(),
+            // NEP-177 timestamps are milliseconds since the epoch, as strings
+            issued_at: (badge.issued_at / 1_000_000).to_string(),
+        },
+        badge: badge.kind,
+    }
+}
+
+// NEP-177 contract metadata
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BadgeContractMetadataView {
+    spec: String,
+    name: String,
+    symbol: String,
+    icon: Option<String>,
+    base_uri: Option<String>,
+    reference: Option<String>,
+    reference_hash: Option<String>,
+}
+
+// NEP-171 token with the subset of NEP-177 token metadata badges use
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BadgeTokenView {
+    token_id: String,
+    owner_id: AccountId,
+    metadata: BadgeMetadataView,
+    badge: BadgeKind,
+}
+
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BadgeMetadataView {
+    title: String,
+    description: String,
+    issued_at: String,
+}
@@ -0,0 +1,345 @@
+use crate::*;
+#[cfg(feature = "escrow")]
+use crate::events::emit_event;
+#[cfg(feature = "escrow")]
+use crate::fixed_point::mul_bps;
+#[cfg(feature = "escrow")]
+use crate::specializations::normalize_tag;
+
+// Shortest and longest commit or reveal window (1 minute and 7 days)
+pub(crate) const MIN_BIDDING_PERIOD: u64 = 60 * 1_000_000_000;
+pub(crate) const MAX_BIDDING_PERIOD: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
+// Most sealed bids an auction accepts
+#[cfg(feature = "escrow")]
+const MAX_BIDS_PER_AUCTION: usize = 50;
+
+// Upper bound on open auctions returned per page
+#[cfg(feature = "escrow")]
+const MAX_AUCTIONS_PAGE: u32 = 50;
+
+// Maximum length of a bid's salt
+#[cfg(feature = "escrow")]
+const MAX_BID_SALT_LENGTH: usize = 64;
+
+// Windows of intent auctions and how their winner is selected
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BiddingConfig {
+    // How long agents can commit sealed bids, then how long they have to reveal them (nanoseconds)
+    pub commit_period: u64,
+    pub reveal_period: u64,
+    // How far the bidder's score in the intent type discounts its price when bids are compared
+    // (basis points; a bid from an agent scoring 100 counts at this much less than its price).
+    // 0 selects the lowest price.
+    pub reputation_weight_bps: u32,
+}
+
+impl Default for BiddingConfig {
+    fn default() -> Self {
+        Self {
+            commit_period: 60 * 60 * 1_000_000_000, // 1 hour
+            reveal_period: 60 * 60 * 1_000_000_000, // 1 hour
+            reputation_weight_bps: 5_000,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum AuctionStatus {
+    // Taking sealed bids, then reveals, until closed
+    Open,
+    // Closed with a winner; the intent was recorded with it
+    Awarded,
+    // Closed without a valid revealed bid; the budget was refunded
+    Unfilled,
+    // Withdrawn by the client before reveals began; the budget was refunded
+    Cancelled,
+}
+
+// A sealed bid and, once revealed, its terms
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SealedBid {
+    pub(crate) agent_id: AccountId,
+    // sha256 of the Borsh encoding of (intent ID, agent ID, price as u128, ETA as u64, salt)
+    pub(crate) commitment: Base64VecU8,
+    pub(crate) committed_at: u64,
+    pub(crate) price: Option<U128>,
+    // Time the agent needs to finish the intent once awarded (nanoseconds)
+    pub(crate) eta: Option<u64>,
+    pub(crate) revealed_at: Option<u64>,
+}
+
+// An intent put out for bids before an agent is assigned. The client's budget is held until
+// the auction closes; the winning price becomes the intent's escrow and the rest is refunded.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IntentAuction {
+    pub(crate) intent_id: String,
+    pub(crate) client_id: AccountId,
+    pub(crate) intent_type: String,
+    pub(crate) parameters: String,
+    pub(crate) budget: U128,
+    pub(crate) created_at: u64,
+    pub(crate) commit_ends_at: u64,
+    pub(crate) reveal_ends_at: u64,
+    // Reputation weight in force when the auction opened
+    pub(crate) reputation_weight_bps: u32,
+    pub(crate) bids: Vec<SealedBid>,
+    pub(crate) status: AuctionStatus,
+    pub(crate) winner: Option<AccountId>,
+    pub(crate) winning_price: Option<U128>,
+}
+
+#[cfg(feature = "escrow")]
+#[near_bindgen]
+impl AgentReputationContract {
+    // Put an intent of a registered type out for bids. The attached deposit is the budget no
+    // bid may exceed; the client pays for the auction's storage.
+    #[payable]
+    pub fn open_intent_auction(&mut self, intent_id: String, intent_type: String, parameters: String) {
+        let client_id = env::predecessor_account_id();
+        let budget = env::attached_deposit();
+        assert!(budget > 0, "Attach the budget for the intent");
+        assert!(self.intents.get(&intent_id).is_none(), "Intent already exists");
+        assert!(self.intent_auctions.get(&intent_id).is_none(), "Auction already exists");
+        let intent_type = normalize_tag(&intent_type);
+        assert!(self.intent_types.get(&intent_type).is_some(), "Unknown intent type");
+
+        let now = env::block_timestamp();
+        let config = &self.bidding_config;
+        let auction = IntentAuction {
+            intent_id: intent_id.clone(),
+            client_id: client_id.clone(),
+            intent_type: intent_type.clone(),
+            parameters,
+            budget: U128(budget),
+            created_at: now,
+            commit_ends_at: now + config.commit_period,
+            reveal_ends_at: now + config.commit_period + config.reveal_period,
+            reputation_weight_bps: config.reputation_weight_bps,
+            bids: Vec::new(),
+            status: AuctionStatus::Open,
+            winner: None,
+            winning_price: None,
+        };
+
+        let initial_storage = env::storage_usage();
+        self.intent_auctions.insert(&intent_id, &auction);
+        self.open_auctions.insert(&intent_id);
+        self.internal_charge_storage(&client_id, initial_storage);
+
+        emit_event(
+            "intent_auction_opened",
+            json!({
+                "intent_id": intent_id,
+                "client_id": client_id,
+                "intent_type": intent_type,
+                "budget": U128(budget),
+                "commit_ends_at": auction.commit_ends_at,
+                "reveal_ends_at": auction.reveal_ends_at,
+            }),
+        );
+    }
+
+    // Commit a sealed bid while the commit window is open, replacing the agent's earlier one.
+    // The agent must be eligible for the intent type and pays for the bid's storage.
+    pub fn commit_bid(&mut self, intent_id: String, commitment: Base64VecU8) {
+        let agent_id = env::predecessor_account_id();
+        assert_eq!(commitment.0.len(), 32, "Commitment must be a sha256 hash");
+        let mut auction = self.internal_open_auction(&intent_id);
+        let now = env::block_timestamp();
+        assert!(now < auction.commit_ends_at, "Commit window has closed");
+        assert_ne!(agent_id, auction.client_id, "Clients cannot bid on their own intents");
+        if let Err(message) = self.internal_check_bidder(&agent_id, &auction.intent_type) {
+            env::panic_str(message);
+        }
+
+        let initial_storage = env::storage_usage();
+        auction.bids.retain(|bid| bid.agent_id != agent_id);
+        assert!(auction.bids.len() < MAX_BIDS_PER_AUCTION, "Auction has too many bids");
+        auction.bids.push(SealedBid {
+            agent_id: agent_id.clone(),
+            commitment,
+            committed_at: now,
+            price: None,
+            eta: None,
+            revealed_at: None,
+        });
+        self.intent_auctions.insert(&intent_id, &auction);
+        self.internal_charge_storage(&agent_id, initial_storage);
+
+        emit_event("bid_committed", json!({ "intent_id": intent_id, "agent_id": agent_id }));
+    }
+
+    // Reveal the calling agent's bid once the commit window has closed. The terms must hash to
+    // the commitment, the price must fit the budget and the ETA must leave the agent its
+    // response period.
+    pub fn reveal_bid(&mut self, intent_id: String, price: U128, eta: u64, salt: String) {
+        let agent_id = env::predecessor_account_id();
+        assert!(salt.len() <= MAX_BID_SALT_LENGTH, "Salt too long");
+        let mut auction = self.internal_open_auction(&intent_id);
+        let now = env::block_timestamp();
+        assert!(now >= auction.commit_ends_at, "Commit window is still open");
+        assert!(now < auction.reveal_ends_at, "Reveal window has closed");
+        assert!(price.0 > 0 && price.0 <= auction.budget.0, "Price must be within the budget");
+        assert!(eta > self.intent_response_period, "ETA must exceed the intent response period");
+
+        let commitment = bid_commitment(&intent_id, &agent_id, price.0, eta, &salt);
+        let bid = auction
+            .bids
+            .iter_mut()
+            .find(|bid| bid.agent_id == agent_id)
+            .expect("No bid committed");
+        assert!(bid.revealed_at.is_none(), "Bid already revealed");
+        assert!(bid.commitment.0 == commitment, "Bid does not match its commitment");
+        bid.price = Some(price);
+        bid.eta = Some(eta);
+        bid.revealed_at = Some(now);
+        self.intent_auctions.insert(&intent_id, &auction);
+
+        emit_event(
+            "bid_revealed",
+            json!({
+                "intent_id": intent_id,
+                "agent_id": agent_id,
+                "price": price,
+                "eta": eta,
+            }),
+        );
+    }
+
+    // Close an auction whose reveal window has passed (anyone can call). Among revealed bids
+    // from agents still eligible, the lowest price discounted by the bidder's score in the
+    // intent type wins; ties go to the higher score, then the earlier commitment. The intent
+    // is recorded with the winner, its price escrowed and the rest of the budget refunded. The
+    // intent's storage is paid from the client's storage deposit, topped up from the unspent
+    // budget if that falls short, so closing never fails on the client's storage balance.
+    pub fn close_intent_auction(&mut self, intent_id: String) {
+        let mut auction = self.internal_open_auction(&intent_id);
+        let now = env::block_timestamp();
+        assert!(now >= auction.reveal_ends_at, "Reveal window is still open");
+
+        let winner = auction
+            .bids
+            .iter()
+            .filter_map(|bid| Some((bid, bid.price?.0, bid.eta?)))
+            .filter(|(bid, _, _)| self.internal_check_bidder(&bid.agent_id, &auction.intent_type).is_ok())
+            .map(|(bid, price, eta)| {
+                let score_bps = self.internal_specialization_score_bps(&bid.agent_id, &auction.intent_type);
+                let discount_bps = mul_bps(score_bps, auction.reputation_weight_bps);
+                let effective_price = price * (10_000 - discount_bps) as u128 / 10_000;
+                (effective_price, std::cmp::Reverse(score_bps), bid.committed_at, bid.agent_id.clone(), price, eta)
+            })
+            .min();
+
+        self.open_auctions.remove(&intent_id);
+        let budget = auction.budget.0;
+        let refund = match winner {
+            Some((_, _, _, agent_id, price, eta)) => {
+                auction.status = AuctionStatus::Awarded;
+                auction.winner = Some(agent_id.clone());
+                auction.winning_price = Some(U128(price));
+                self.intent_auctions.insert(&intent_id, &auction);
+
+                // Lend the unspent budget to the client's storage deposit while the intent is
+                // written, then refund whatever the intent did not need
+                let unspent = budget - price;
+                self.internal_credit_storage(&auction.client_id, unspent);
+                let initial_storage = env::storage_usage();
+                self.internal_record_intent(
+                    intent_id.clone(),
+                    agent_id,
+                    auction.client_id.clone(),
+                    auction.intent_type.clone(),
+                    auction.parameters.clone(),
+                    Some(now + eta),
+                    price,
+                    EscrowAsset::Near,
+                    None,
+                    None,
+                );
+                self.internal_track_storage(&auction.client_id, initial_storage);
+                self.internal_take_available_storage(&auction.client_id, unspent)
+            },
+            None => {
+                auction.status = AuctionStatus::Unfilled;
+                self.intent_auctions.insert(&intent_id, &auction);
+                budget
+            },
+        };
+        if refund > 0 {
+            Promise::new(auction.client_id.clone()).transfer(refund);
+        }
+
+        emit_event(
+            "intent_auction_closed",
+            json!({
+                "intent_id": intent_id,
+                "status": auction.status,
+                "winner": auction.winner,
+                "price": auction.winning_price,
+                "refund": U128(refund),
+            }),
+        );
+    }
+
+    // Withdraw an auction before its reveal window begins (client only), refunding the budget
+    pub fn cancel_intent_auction(&mut self, intent_id: String) {
+        let mut auction = self.internal_open_auction(&intent_id);
+        assert_eq!(env::predecessor_account_id(), auction.client_id, "Only the auction's client can cancel it");
+        assert!(env::block_timestamp() < auction.commit_ends_at, "Bids are being revealed");
+
+        auction.status = AuctionStatus::Cancelled;
+        self.intent_auctions.insert(&intent_id, &auction);
+        self.open_auctions.remove(&intent_id);
+        Promise::new(auction.client_id.clone()).transfer(auction.budget.0);
+
+        emit_event("intent_auction_cancelled", json!({ "intent_id": intent_id }));
+    }
+
+    // Get an auction by its intent ID
+    pub fn get_intent_auction(&self, intent_id: String) -> Option<IntentAuction> {
+        self.intent_auctions.get(&intent_id)
+    }
+
+    // Get a page of the auctions not yet closed
+    pub fn get_open_auctions(&self, from_index: u64, limit: u32) -> Vec<IntentAuction> {
+        self.open_auctions
+            .iter()
+            .skip(from_index as usize)
+            .take(std::cmp::min(limit, MAX_AUCTIONS_PAGE) as usize)
+            .filter_map(|intent_id| self.intent_auctions.get(&intent_id))
+            .collect()
+    }
+
+    // Get the auction windows and winner selection weight
+    pub fn get_bidding_config(&self) -> BiddingConfig {
+        self.bidding_config.clone()
+    }
+
+    fn internal_open_auction(&self, intent_id: &String) -> IntentAuction {
+        let auction = self.intent_auctions.get(intent_id).expect("Auction not found");
+        assert!(auction.status == AuctionStatus::Open, "Auction is closed");
+        auction
+    }
+
+    // Whether an agent may bid on, and be awarded, intents of a type
+    fn internal_check_bidder(&self, agent_id: &AccountId, intent_type: &str) -> Result<(), &'static str> {
+        let agent_rep = self.agent_reputations.get(agent_id).ok_or("Agent not registered")?;
+        if !self.internal_agent_status(agent_id).in_good_standing() {
+            return Err("Agent is suspended or banned");
+        }
+        let metadata = self.intent_types.get(&intent_type.to_string()).ok_or("Unknown intent type")?;
+        self.internal_check_intent_type_standing(agent_id, &agent_rep, intent_type, &metadata)
+    }
+}
+
+// Commitment to a bid's terms
+#[cfg(feature = "escrow")]
+fn bid_commitment(intent_id: &str, agent_id: &AccountId, price: Balance, eta: u64, salt: &str) -> Vec<u8> {
+    let payload = (intent_id.to_string(), agent_id.clone(), price, eta, salt.to_string()).try_to_vec().expect("Failed to serialize bid");
+    env::sha256(&payload)
+}
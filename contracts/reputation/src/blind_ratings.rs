@@ -0,0 +1,177 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Longest the first rating of an intent can be held for the other side's (30 days)
+pub(crate) const MAX_BLIND_RATING_WINDOW: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+// A client's feedback for the agent of an intent, held until the intent's ratings are released
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PendingFeedback {
+    pub(crate) rating: u8,
+    pub(crate) category_ratings: CategoryRatings,
+    pub(crate) message: Option<String>,
+    pub(crate) anonymous: bool,
+    pub(crate) submitted_at: u64,
+}
+
+// The two ratings of a finished intent: the client's feedback for the agent and the agent's
+// rating of the client. Neither side's rating is applied or shown until both have rated or the
+// blind rating window since the first one closes, so neither can answer the other's.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct BlindRatings {
+    // Ratings submitted and not yet applied
+    pub(crate) feedback: Option<PendingFeedback>,
+    client_rating: Option<u8>,
+    // Whether each side has rated the intent
+    pub(crate) client_rated: bool,
+    agent_rated: bool,
+    // When the first rating was submitted (starts the window)
+    opened_at: u64,
+    released_at: Option<u64>,
+}
+
+impl BlindRatings {
+    fn new() -> Self {
+        Self {
+            feedback: None,
+            client_rating: None,
+            client_rated: false,
+            agent_rated: false,
+            opened_at: env::block_timestamp(),
+            released_at: None,
+        }
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Rate the client of a completed or failed intent the caller handled (0-5), once. The
+    // rating is held with the client's feedback until both are in or the window closes.
+    pub fn rate_client(&mut self, intent_id: String, rating: u8) {
+        self.assert_not_paused(PausableFeature::Feedback);
+        let agent_id = env::predecessor_account_id();
+        let initial_storage = env::storage_usage();
+
+        assert!(rating <= 5, "Rating must be between 0 and 5");
+        let intent = self.intents.get(&intent_id).expect("Intent not found");
+        assert_eq!(intent.agent_id, agent_id, "Only the intent's agent can rate its client");
+        assert!(
+            intent.status == IntentStatus::Completed || intent.status == IntentStatus::Failed,
+            "Rating requires a completed or failed intent"
+        );
+
+        let release = self.internal_hold_blind_rating(&intent_id, |ratings| {
+            assert!(!ratings.agent_rated, "Client already rated for this intent");
+            ratings.agent_rated = true;
+            ratings.client_rating = Some(rating);
+        });
+        self.internal_charge_storage(&agent_id, initial_storage);
+
+        if release {
+            self.internal_release_blind_ratings(&intent_id);
+        }
+    }
+
+    // Apply an intent's held rating once the blind rating window has closed without the other
+    // side rating. Callable by anyone.
+    pub fn release_blind_ratings(&mut self, intent_id: String) {
+        self.assert_not_paused(PausableFeature::Feedback);
+        let ratings = self.blind_ratings.get(&intent_id).expect("No ratings for this intent");
+        assert!(ratings.released_at.is_none(), "Ratings already released");
+        assert!(
+            env::block_timestamp() > ratings.opened_at + self.blind_rating_window,
+            "Blind rating window is still open"
+        );
+
+        self.internal_release_blind_ratings(&intent_id);
+    }
+
+    // Get which sides have rated an intent and when its ratings are released. The ratings
+    // themselves stay hidden until then.
+    pub fn get_blind_ratings(&self, intent_id: String) -> Option<BlindRatingsView> {
+        self.blind_ratings.get(&intent_id).map(|ratings| BlindRatingsView {
+            intent_id,
+            client_rated: ratings.client_rated,
+            agent_rated: ratings.agent_rated,
+            opened_at: ratings.opened_at,
+            closes_at: ratings.opened_at + self.blind_rating_window,
+            released_at: ratings.released_at,
+        })
+    }
+
+    // Add a rating to the intent's held ratings, opening them with the first one. Returns
+    // whether they are due for release: both sides have rated, or ratings are not held.
+    pub(crate) fn internal_hold_blind_rating<F: FnOnce(&mut BlindRatings)>(&mut self, intent_id: &str, submit: F) -> bool {
+        let intent_id = intent_id.to_string();
+        let mut ratings = self.blind_ratings.get(&intent_id).unwrap_or_else(BlindRatings::new);
+        assert!(
+            self.blind_rating_window == 0
+                || (ratings.released_at.is_none() && env::block_timestamp() <= ratings.opened_at + self.blind_rating_window),
+            "Blind rating window has closed"
+        );
+
+        submit(&mut ratings);
+        let release = self.blind_rating_window == 0 || (ratings.client_rated && ratings.agent_rated);
+        self.blind_ratings.insert(&intent_id, &ratings);
+
+        if !release {
+            emit_event(
+                "rating_held",
+                json!({
+                    "intent_id": intent_id,
+                    "client_rated": ratings.client_rated,
+                    "agent_rated": ratings.agent_rated,
+                    "closes_at": ratings.opened_at + self.blind_rating_window,
+                }),
+            );
+        }
+        release
+    }
+
+    // Apply both held ratings of an intent together
+    pub(crate) fn internal_release_blind_ratings(&mut self, intent_id: &str) {
+        let intent_id = intent_id.to_string();
+        let intent = self.intents.get(&intent_id).expect("Intent not found");
+        let mut ratings = self.blind_ratings.get(&intent_id).expect("No ratings for this intent");
+        let feedback = ratings.feedback.take();
+        let client_rating = ratings.client_rating.take();
+        ratings.released_at = Some(env::block_timestamp());
+
+        let initial_storage = env::storage_usage();
+        self.blind_ratings.insert(&intent_id, &ratings);
+        if let Some(feedback) = feedback {
+            self.internal_apply_feedback(&intent, feedback);
+        }
+        // The client pays for its applied feedback, less what holding it took. A shortfall is
+        // left on its storage account rather than holding back the agent's rating of it.
+        self.internal_track_storage(&intent.client_id, initial_storage);
+
+        if let Some(rating) = client_rating {
+            self.internal_record_client_rating(&intent.client_id, rating);
+        }
+
+        emit_event(
+            "ratings_released",
+            json!({
+                "intent_id": intent_id,
+                "agent_id": intent.agent_id,
+                "client_id": intent.client_id,
+                "client_rated": ratings.client_rated,
+                "agent_rated": ratings.agent_rated,
+            }),
+        );
+    }
+}
+
+// View struct for the state of an intent's blind ratings
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BlindRatingsView {
+    intent_id: String,
+    client_rated: bool,
+    agent_rated: bool,
+    opened_at: u64,
+    // When the held rating can be released without the other side's
+    closes_at: u64,
+    released_at: Option<u64>,
+}
@@ -0,0 +1,256 @@
+use crate::*;
+use crate::events::emit_event;
+use crate::violations::assert_valid_evidence;
+
+// How long an audited badge lasts after a passing audit is approved (180 days)
+const AUDIT_BADGE_DURATION: u64 = 180 * 24 * 60 * 60 * 1_000_000_000;
+
+// Attestations awaiting review on a single bounty
+const MAX_BOUNTY_ATTESTATIONS: usize = 10;
+
+// Maximum length of a bounty's audit scope
+const MAX_BOUNTY_SCOPE_LENGTH: usize = 1_000;
+
+// Upper bound on bounties returned per page
+const MAX_BOUNTIES_PAGE: u32 = 50;
+
+// Auditor's findings on a bounty, awaiting governance review
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuditAttestation {
+    auditor: AccountId,
+    // Whether the auditor found the agent's behavior and code sound
+    passed: bool,
+    // Audit report; only the content hash is authoritative
+    report: Evidence,
+    submitted_at: u64,
+}
+
+// ITLX posted by an agent for a third-party audit. Bounties are deleted once paid out or
+// cancelled; their outcome is kept in the event log.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct AuditBounty {
+    agent_id: AccountId,
+    amount: Balance,
+    // What the auditors are asked to review
+    scope: String,
+    created_at: u64,
+    attestations: Vec<AuditAttestation>,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Submit findings for an open bounty (anyone but the agent). Storage is charged to the auditor.
+    pub fn submit_audit_attestation(&mut self, bounty_id: u64, passed: bool, report: Evidence) {
+        let auditor = env::predecessor_account_id();
+        let mut bounty = self.audit_bounties.get(&bounty_id).expect("Audit bounty not found");
+        assert!(auditor != bounty.agent_id, "Agents cannot audit themselves");
+        assert!(
+            bounty.attestations.iter().all(|attestation| attestation.auditor != auditor),
+            "Auditor already submitted an attestation for this bounty"
+        );
+        assert!(bounty.attestations.len() < MAX_BOUNTY_ATTESTATIONS, "Too many attestations awaiting review");
+        assert_valid_evidence(std::slice::from_ref(&report));
+
+        let initial_storage = env::storage_usage();
+        bounty.attestations.push(AuditAttestation {
+            auditor: auditor.clone(),
+            passed,
+            report: report.clone(),
+            submitted_at: env::block_timestamp(),
+        });
+        self.audit_bounties.insert(&bounty_id, &bounty);
+        self.internal_charge_storage(&auditor, initial_storage);
+
+        emit_event(
+            "audit_attestation_submitted",
+            json!({
+                "bounty_id": bounty_id,
+                "agent_id": bounty.agent_id,
+                "auditor": auditor,
+                "passed": passed,
+                "report": report,
+            }),
+        );
+    }
+
+    // Approve or reject an auditor's attestation (owner or governance). Approval pays the bounty
+    // to the auditor and closes it, and a passing audit awards the agent the audited badge.
+    // Rejection drops the attestation and leaves the bounty open.
+    pub fn review_audit_attestation(&mut self, bounty_id: u64, auditor: AccountId, approved: bool) {
+        self.assert_owner_or_governance();
+        let mut bounty = self.audit_bounties.get(&bounty_id).expect("Audit bounty not found");
+        let position = bounty
+            .attestations
+            .iter()
+            .position(|attestation| attestation.auditor == auditor)
+            .expect("Attestation not found");
+
+        if !approved {
+            let initial_storage = env::storage_usage();
+            bounty.attestations.remove(position);
+            self.audit_bounties.insert(&bounty_id, &bounty);
+            self.internal_release_storage(&auditor, initial_storage.saturating_sub(env::storage_usage()));
+
+            emit_event(
+                "audit_attestation_rejected",
+                json!({
+                    "bounty_id": bounty_id,
+                    "agent_id": bounty.agent_id,
+                    "auditor": auditor,
+                }),
+            );
+            return;
+        }
+
+        let passed = bounty.attestations[position].passed;
+        let badge_until = if passed {
+            let badge_until = env::block_timestamp() + AUDIT_BADGE_DURATION;
+            let current = self.audit_badges.get(&bounty.agent_id).unwrap_or(0);
+            self.audit_badges.insert(&bounty.agent_id, &std::cmp::max(current, badge_until));
+            Some(badge_until)
+        } else {
+            None
+        };
+
+        self.internal_close_bounty(bounty_id, &bounty);
+        self.internal_transfer_bounty_itlx(&auditor, bounty.amount, &format!("Audit bounty {} payout", bounty_id));
+
+        emit_event(
+            "audit_bounty_awarded",
+            json!({
+                "bounty_id": bounty_id,
+                "agent_id": bounty.agent_id,
+                "auditor": auditor,
+                "amount": U128(bounty.amount),
+                "passed": passed,
+                "audited_until": badge_until,
+            }),
+        );
+    }
+
+    // Withdraw an open bounty and refund it (posting agent only). Not possible while
+    // attestations await review.
+    pub fn cancel_audit_bounty(&mut self, bounty_id: u64) {
+        let bounty = self.audit_bounties.get(&bounty_id).expect("Audit bounty not found");
+        assert_eq!(env::predecessor_account_id(), bounty.agent_id, "Only the posting agent can cancel a bounty");
+        assert!(bounty.attestations.is_empty(), "Bounty has attestations awaiting review");
+
+        self.internal_close_bounty(bounty_id, &bounty);
+        self.internal_transfer_bounty_itlx(&bounty.agent_id, bounty.amount, &format!("Audit bounty {} refund", bounty_id));
+
+        emit_event(
+            "audit_bounty_cancelled",
+            json!({
+                "bounty_id": bounty_id,
+                "agent_id": bounty.agent_id,
+                "amount": U128(bounty.amount),
+            }),
+        );
+    }
+
+    // Get an open bounty with its attestations
+    pub fn get_audit_bounty(&self, bounty_id: u64) -> Option<AuditBountyView> {
+        self.audit_bounties
+            .get(&bounty_id)
+            .map(|bounty| audit_bounty_view(bounty_id, bounty))
+    }
+
+    // Get a page of open bounties
+    pub fn get_audit_bounties(&self, from_index: u64, limit: u32) -> Vec<AuditBountyView> {
+        self.audit_bounties
+            .iter()
+            .skip(from_index as usize)
+            .take(std::cmp::min(limit, MAX_BOUNTIES_PAGE) as usize)
+            .map(|(bounty_id, bounty)| audit_bounty_view(bounty_id, bounty))
+            .collect()
+    }
+
+    // When the agent's audited badge expires, or None if it has never held one
+    pub fn get_audit_badge(&self, agent_id: AccountId) -> Option<u64> {
+        self.audit_badges.get(&agent_id)
+    }
+
+    // Whether the agent currently holds the audited badge
+    pub(crate) fn is_audited(&self, agent_id: &AccountId) -> bool {
+        self.audit_badges
+            .get(agent_id)
+            .is_some_and(|until| env::block_timestamp() < until)
+    }
+
+    // Open a bounty funded with ITLX received via ft_transfer_call (registered agents only)
+    pub(crate) fn internal_post_audit_bounty(&mut self, agent_id: &AccountId, amount: Balance, scope: String) {
+        assert!(self.agent_reputations.get(agent_id).is_some(), "Agent not registered");
+        assert!(amount > 0, "Bounty amount must be positive");
+        assert!(!scope.is_empty(), "Audit scope cannot be empty");
+        assert!(scope.len() <= MAX_BOUNTY_SCOPE_LENGTH, "Audit scope too long");
+
+        let bounty_id = self.next_bounty_id;
+        self.next_bounty_id += 1;
+
+        let initial_storage = env::storage_usage();
+        self.audit_bounties.insert(&bounty_id, &AuditBounty {
+            agent_id: agent_id.clone(),
+            amount,
+            scope: scope.clone(),
+            created_at: env::block_timestamp(),
+            attestations: Vec::new(),
+        });
+        self.internal_charge_storage(agent_id, initial_storage);
+        self.stats.itlx_in_bounties += amount;
+
+        emit_event(
+            "audit_bounty_posted",
+            json!({
+                "bounty_id": bounty_id,
+                "agent_id": agent_id,
+                "amount": U128(amount),
+                "scope": scope,
+            }),
+        );
+    }
+
+    // Delete a bounty, releasing each pending attestation's storage to its auditor and the
+    // rest to the posting agent
+    fn internal_close_bounty(&mut self, bounty_id: u64, bounty: &AuditBounty) {
+        let initial_storage = env::storage_usage();
+        self.audit_bounties.remove(&bounty_id);
+        let mut freed = initial_storage.saturating_sub(env::storage_usage());
+
+        for attestation in &bounty.attestations {
+            let attestation_bytes = attestation.try_to_vec().map_or(0, |bytes| bytes.len() as u64);
+            self.internal_release_storage(&attestation.auditor, attestation_bytes);
+            freed = freed.saturating_sub(attestation_bytes);
+        }
+        self.internal_release_storage(&bounty.agent_id, freed);
+        self.stats.itlx_in_bounties = self.stats.itlx_in_bounties.saturating_sub(bounty.amount);
+    }
+
+    // Pay out a closed bounty; a failed transfer is held for the receiver to claim (see payouts.rs)
+    fn internal_transfer_bounty_itlx(&self, receiver_id: &AccountId, amount: Balance, memo: &str) {
+        self.internal_transfer_payout(EscrowAsset::Itlx, receiver_id, amount, memo);
+    }
+}
+
+fn audit_bounty_view(bounty_id: u64, bounty: AuditBounty) -> AuditBountyView {
+    AuditBountyView {
+        bounty_id,
+        agent_id: bounty.agent_id,
+        amount: U128(bounty.amount),
+        scope: bounty.scope,
+        created_at: bounty.created_at,
+        attestations: bounty.attestations,
+    }
+}
+
+// View struct for an open audit bounty
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuditBountyView {
+    bounty_id: u64,
+    agent_id: AccountId,
+    amount: U128,
+    scope: String,
+    created_at: u64,
+    attestations: Vec<AuditAttestation>,
+}
@@ -0,0 +1,152 @@
+#[cfg(feature = "cross-chain")]
+use crate::*;
+#[cfg(feature = "cross-chain")]
+use crate::authorization::authorization_message;
+#[cfg(feature = "cross-chain")]
+use crate::events::emit_event;
+
+// Decimals of the amounts trust-level transaction limits are expressed in (those of ITLX)
+#[cfg(feature = "cross-chain")]
+const LIMIT_DECIMALS: u8 = 18;
+
+// Most tokens bridge accounts can check transfers of besides ITLX
+#[cfg(feature = "cross-chain")]
+const MAX_BRIDGE_TOKENS: u64 = 50;
+
+// Express an amount of a token with `decimals` in the unit of transaction limits, rounding up
+// so a transfer never passes a limit it exceeds
+#[cfg(feature = "cross-chain")]
+fn to_limit_unit(amount: Balance, decimals: u8) -> Balance {
+    if decimals >= LIMIT_DECIMALS {
+        let divisor = 10u128.pow((decimals - LIMIT_DECIMALS) as u32);
+        amount.div_ceil(divisor)
+    } else {
+        amount.saturating_mul(10u128.pow((LIMIT_DECIMALS - decimals) as u32))
+    }
+}
+
+#[cfg(feature = "cross-chain")]
+#[near_bindgen]
+impl AgentReputationContract {
+    // Check a bridged transfer initiated by an agent before the bridge releases it: the agent
+    // must be registered and in good standing, and the amount within its max_transaction_value
+    // once converted to the limit's decimals. Callable only by a bridge account, which should
+    // refuse the transfer unless `allowed`; tokens that are not registered are refused outright.
+    pub fn check_bridge_allowance(&self, agent_id: AccountId, token_id: AccountId, amount: U128) -> BridgeAllowanceView {
+        let bridge_id = env::predecessor_account_id();
+        assert!(self.bridge_accounts.contains(&bridge_id), "Only a bridge account can check bridge allowances");
+
+        let decimals = if token_id == self.token_contract_id {
+            LIMIT_DECIMALS
+        } else {
+            self.bridge_tokens.get(&token_id).expect("Token is not registered for bridge checks")
+        };
+        let limit_amount = to_limit_unit(amount.0, decimals);
+        let decision = self.evaluate_authorization(&agent_id, "transaction", Some(limit_amount));
+
+        emit_event(
+            "bridge_allowance_checked",
+            json!({
+                "bridge_id": bridge_id,
+                "agent_id": agent_id,
+                "token_id": token_id,
+                "amount": amount,
+                "limit_amount": U128(limit_amount),
+                "allowed": decision.allowed(),
+                "rule": decision.rule,
+            }),
+        );
+
+        BridgeAllowanceView {
+            agent_id,
+            token_id,
+            amount,
+            limit_amount: U128(limit_amount),
+            allowed: decision.allowed(),
+            rule: decision.rule,
+            message: authorization_message(decision.rule).to_string(),
+            trust_level: decision.trust_level,
+            limit: decision.limit.map(U128),
+        }
+    }
+
+    // Register the decimals of a token bridge accounts can check transfers of, or unregister it
+    // with None (owner or governance). ITLX is always registered.
+    pub fn set_bridge_token(&mut self, token_id: AccountId, decimals: Option<u8>) {
+        self.assert_owner_or_governance();
+        assert_ne!(token_id, self.token_contract_id, "ITLX is always registered for bridge checks");
+
+        let old_value = match decimals {
+            Some(decimals) => {
+                assert!(decimals <= 36, "Token decimals out of range");
+                assert!(
+                    self.bridge_tokens.get(&token_id).is_some() || self.bridge_tokens.len() < MAX_BRIDGE_TOKENS,
+                    "Too many bridge tokens"
+                );
+                self.bridge_tokens.insert(&token_id, &decimals)
+            },
+            None => self.bridge_tokens.remove(&token_id),
+        };
+
+        self.record_config_change(&format!("bridge_token.{}", token_id), json!(old_value), json!(decimals));
+    }
+
+    // Get the tokens bridge accounts can check transfers of, ITLX first
+    pub fn get_bridge_tokens(&self) -> Vec<BridgeTokenView> {
+        std::iter::once((self.token_contract_id.clone(), LIMIT_DECIMALS))
+            .chain(self.bridge_tokens.iter())
+            .map(|(token_id, decimals)| BridgeTokenView { token_id, decimals })
+            .collect()
+    }
+
+    // Allow a bridge account, such as the Omni Bridge, to check agents' bridged transfers
+    // (owner or governance)
+    pub fn add_bridge_account(&mut self, bridge_id: AccountId) {
+        self.assert_owner_or_governance();
+        assert!(self.bridge_accounts.insert(&bridge_id), "Account is already a bridge account");
+
+        self.record_config_change(&format!("bridge_account.{}", bridge_id), json!(false), json!(true));
+    }
+
+    // Remove a bridge account (owner or governance)
+    pub fn remove_bridge_account(&mut self, bridge_id: AccountId) {
+        self.assert_owner_or_governance();
+        assert!(self.bridge_accounts.remove(&bridge_id), "Account is not a bridge account");
+
+        self.record_config_change(&format!("bridge_account.{}", bridge_id), json!(true), json!(false));
+    }
+
+    // Get the accounts allowed to check bridged transfers
+    pub fn get_bridge_accounts(&self) -> Vec<AccountId> {
+        self.bridge_accounts.to_vec()
+    }
+}
+
+// View struct for a bridged transfer check
+#[cfg(feature = "cross-chain")]
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BridgeAllowanceView {
+    agent_id: AccountId,
+    token_id: AccountId,
+    // Amount in the token's decimals, and converted to those of `limit`
+    amount: U128,
+    limit_amount: U128,
+    allowed: bool,
+    // Rule that decided the check, and its explanation
+    rule: AuthorizationRule,
+    message: String,
+    // None if the agent is not registered
+    trust_level: Option<TrustLevel>,
+    // Agent's max_transaction_value; None if it is not registered or not in good standing
+    limit: Option<U128>,
+}
+
+// View struct for a token bridge accounts can check transfers of
+#[cfg(feature = "cross-chain")]
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BridgeTokenView {
+    token_id: AccountId,
+    decimals: u8,
+}
@@ -0,0 +1,446 @@
+use crate::*;
+#[cfg(feature = "cross-chain")]
+use crate::authorization::authorization_message;
+#[cfg(feature = "cross-chain")]
+use crate::events::emit_event;
+#[cfg(feature = "cross-chain")]
+use near_sdk::json_types::Base64VecU8;
+#[cfg(feature = "cross-chain")]
+use near_sdk::serde_json::Value;
+
+// Gas for the MPC signer's sign call
+#[cfg(feature = "cross-chain")]
+pub(crate) const GAS_FOR_CHAIN_SIGNATURE: Gas = Gas(250_000_000_000_000);
+
+// Gas reserved for on_evm_signature
+#[cfg(feature = "cross-chain")]
+pub(crate) const GAS_FOR_SIGNATURE_CALLBACK: Gas = Gas(10_000_000_000_000);
+
+// EIP-2718 type byte of EIP-1559 transactions, the only format accepted for signing
+#[cfg(feature = "cross-chain")]
+const EIP1559_TX_TYPE: u8 = 0x02;
+
+// ERC-20 transfer(address,uint256) and approve(address,uint256) selectors
+#[cfg(feature = "cross-chain")]
+const ERC20_TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+#[cfg(feature = "cross-chain")]
+const ERC20_APPROVE_SELECTOR: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+
+// Lowest trust level allowed to sign calldata the contract cannot check amounts in
+#[cfg(feature = "cross-chain")]
+const MIN_TRUST_LEVEL_FOR_ARBITRARY_CALLDATA: TrustLevel = TrustLevel::Trusted;
+
+// Spending allowance per agent on an EVM chain; chains without a budget cannot be signed for
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EvmChainBudget {
+    // Maximum native value (wei) an agent can move on the chain per period
+    pub period_limit: U128,
+    // Length of a budget period, in nanoseconds
+    pub period_length: u64,
+}
+
+// Per-agent, per-chain signing state
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct EvmDelegationAccount {
+    // Nonce the next signed transaction must use
+    next_nonce: u64,
+    // Start of the current budget period
+    period_start: u64,
+    // Native value signed for in the current budget period
+    spent_in_period: Balance,
+}
+
+// Fields of an unsigned EIP-1559 transaction the contract enforces policy on
+#[cfg(feature = "cross-chain")]
+struct EvmTransaction {
+    chain_id: u64,
+    nonce: u64,
+    // Recipient, or None for contract creation
+    to: Option<Vec<u8>>,
+    // Native value in wei
+    value: Balance,
+    call: EvmCall,
+}
+
+// What a transaction's calldata does, as far as the contract can tell
+#[cfg(feature = "cross-chain")]
+enum EvmCall {
+    // Plain value transfer
+    None,
+    // ERC-20 transfer or approve of this many token units
+    Erc20(Balance),
+    // Any other contract call or creation
+    Other,
+}
+
+#[cfg(feature = "cross-chain")]
+#[near_bindgen]
+impl AgentReputationContract {
+    // Ask the MPC signer to sign an unsigned EIP-1559 transaction from the calling agent's derived
+    // EVM address. The transaction's nonce must match the tracked nonce and its native value must
+    // fit both the agent's capability limit and the chain budget. The amount of an ERC-20
+    // transfer or approve must also fit the capability limit; any other calldata is only signed
+    // for agents at the Trusted level or above. Any deposit is forwarded to the signer as its
+    // fee. Resolves to the signer's response, or None if signing failed.
+    #[payable]
+    pub fn request_evm_signature(&mut self, chain_id: u64, payload: Base64VecU8) -> Promise {
+        let agent_id = env::predecessor_account_id();
+        let signer_id = self.chain_signer_id.clone().expect("Chain signatures are not configured");
+
+        let tx = decode_eip1559_transaction(&payload.0);
+        assert_eq!(tx.chain_id, chain_id, "Transaction chain ID does not match");
+
+        // Co-signing is itself a delegation of authority, on top of the transaction value check
+        for (action, value) in [("delegation", None), ("transaction", Some(tx.value))] {
+            let decision = self.evaluate_authorization(&agent_id, action, value);
+            assert!(decision.allowed(), "{}", authorization_message(decision.rule));
+        }
+        match tx.call {
+            EvmCall::None => {},
+            EvmCall::Erc20(amount) => {
+                let decision = self.evaluate_authorization(&agent_id, "transaction", Some(amount));
+                assert!(decision.allowed(), "{}", authorization_message(decision.rule));
+            },
+            EvmCall::Other => {
+                let agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+                assert!(
+                    self.get_trust_level(agent_rep.score) >= MIN_TRUST_LEVEL_FOR_ARBITRARY_CALLDATA,
+                    "Trust level too low to sign arbitrary calldata"
+                );
+            },
+        }
+
+        let budget = self.evm_chain_budgets.get(&chain_id).expect("No signing budget for this chain");
+        let key = evm_delegation_key(&agent_id, chain_id);
+        let mut account = self.evm_delegations.get(&key).unwrap_or_default();
+        assert_eq!(tx.nonce, account.next_nonce, "Unexpected transaction nonce");
+
+        let now = env::block_timestamp();
+        if now >= account.period_start + budget.period_length {
+            account.period_start = now;
+            account.spent_in_period = 0;
+        }
+        assert!(
+            account.spent_in_period + tx.value <= budget.period_limit.0,
+            "Transaction exceeds the chain budget for this period"
+        );
+
+        account.next_nonce += 1;
+        account.spent_in_period += tx.value;
+        self.evm_delegations.insert(&key, &account);
+
+        let payload_hash = env::keccak256(&payload.0);
+        let deposit = env::attached_deposit();
+
+        emit_event(
+            "evm_signature_requested",
+            json!({
+                "agent_id": agent_id,
+                "chain_id": chain_id,
+                "nonce": tx.nonce,
+                "to": tx.to.as_deref().map(to_hex),
+                "value": U128(tx.value),
+                "payload_hash": to_hex(&payload_hash),
+            }),
+        );
+
+        Promise::new(signer_id)
+            .function_call(
+                "sign".to_string(),
+                json!({
+                    "request": {
+                        "payload": payload_hash,
+                        // Each agent signs from its own derived address
+                        "path": agent_id,
+                        "key_version": 0,
+                    }
+                }).to_string().into_bytes(),
+                deposit,
+                GAS_FOR_CHAIN_SIGNATURE,
+            )
+            .then(
+                Promise::new(env::current_account_id()).function_call(
+                    "on_evm_signature".to_string(),
+                    json!({
+                        "agent_id": agent_id,
+                        "chain_id": chain_id,
+                        "nonce": tx.nonce,
+                        "value": U128(tx.value),
+                        "deposit": U128(deposit),
+                    }).to_string().into_bytes(),
+                    0,
+                    GAS_FOR_SIGNATURE_CALLBACK,
+                ),
+            )
+    }
+
+    // Callback returning the signature, or rolling back the nonce and budget if signing failed
+    #[private]
+    pub fn on_evm_signature(
+        &mut self,
+        agent_id: AccountId,
+        chain_id: u64,
+        nonce: u64,
+        value: U128,
+        deposit: U128,
+    ) -> Option<Value> {
+        if let PromiseResult::Successful(result) = env::promise_result(0) {
+            if let Ok(signature) = near_sdk::serde_json::from_slice::<Value>(&result) {
+                return Some(signature);
+            }
+        }
+
+        // Only roll back if no later request or resync has moved the nonce on
+        let key = evm_delegation_key(&agent_id, chain_id);
+        if let Some(mut account) = self.evm_delegations.get(&key) {
+            if account.next_nonce == nonce + 1 {
+                account.next_nonce = nonce;
+                account.spent_in_period = account.spent_in_period.saturating_sub(value.0);
+                self.evm_delegations.insert(&key, &account);
+            }
+        }
+        if deposit.0 > 0 {
+            Promise::new(agent_id.clone()).transfer(deposit.0);
+        }
+
+        emit_event(
+            "evm_signature_failed",
+            json!({
+                "agent_id": agent_id,
+                "chain_id": chain_id,
+                "nonce": nonce,
+            }),
+        );
+
+        None
+    }
+
+    // Set the tracked nonce to the derived address's on-chain nonce, e.g. after a signed
+    // transaction was never broadcast. Budget already spent in the period is not refunded.
+    pub fn resync_evm_nonce(&mut self, chain_id: u64, next_nonce: u64) {
+        let agent_id = env::predecessor_account_id();
+        assert!(self.agent_reputations.get(&agent_id).is_some(), "Agent not registered");
+
+        let key = evm_delegation_key(&agent_id, chain_id);
+        let mut account = self.evm_delegations.get(&key).unwrap_or_default();
+        account.next_nonce = next_nonce;
+        self.evm_delegations.insert(&key, &account);
+    }
+
+    // Set the MPC signer contract used for chain signatures, or None to disable signing
+    pub fn set_chain_signer(&mut self, signer_id: Option<AccountId>) {
+        self.assert_owner_or_governance();
+
+        let old_value = std::mem::replace(&mut self.chain_signer_id, signer_id.clone());
+
+        self.record_config_change("chain_signer_id", json!(old_value), json!(signer_id));
+    }
+
+    // Set or clear (None) an EVM chain's per-agent signing budget (owner or governance)
+    pub fn set_evm_chain_budget(&mut self, chain_id: u64, budget: Option<EvmChainBudget>) {
+        self.assert_owner_or_governance();
+
+        let old_value = match &budget {
+            Some(budget) => {
+                assert!(budget.period_length > 0, "Budget period must be positive");
+                self.evm_chain_budgets.insert(&chain_id, budget)
+            },
+            None => self.evm_chain_budgets.remove(&chain_id),
+        };
+
+        self.record_config_change(
+            &format!("evm_chain_budget.{}", chain_id),
+            json!(old_value),
+            json!(budget),
+        );
+    }
+
+    // Get the budgets of all EVM chains that can be signed for
+    pub fn get_evm_chain_budgets(&self) -> Vec<(u64, EvmChainBudget)> {
+        self.evm_chain_budgets.to_vec()
+    }
+
+    // Get an agent's signing state on an EVM chain
+    pub fn get_evm_delegation(&self, agent_id: AccountId, chain_id: u64) -> EvmDelegationView {
+        let account = self.evm_delegations.get(&evm_delegation_key(&agent_id, chain_id)).unwrap_or_default();
+        let budget = self.evm_chain_budgets.get(&chain_id);
+
+        // A lapsed period has nothing spent in it yet
+        let period_active = budget
+            .as_ref()
+            .is_some_and(|budget| env::block_timestamp() < account.period_start + budget.period_length);
+        let spent_in_period = if period_active { account.spent_in_period } else { 0 };
+
+        EvmDelegationView {
+            agent_id,
+            chain_id,
+            next_nonce: account.next_nonce,
+            spent_in_period: U128(spent_in_period),
+            period_limit: budget.as_ref().map(|budget| budget.period_limit),
+            period_resets_at: budget
+                .filter(|_| period_active)
+                .map(|budget| account.period_start + budget.period_length),
+        }
+    }
+}
+
+#[cfg(feature = "cross-chain")]
+fn evm_delegation_key(agent_id: &AccountId, chain_id: u64) -> String {
+    format!("{}:{}", agent_id, chain_id)
+}
+
+#[cfg(feature = "cross-chain")]
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    let digits: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("0x{}", digits)
+}
+
+// Decode hex, with or without a 0x prefix; None if malformed
+#[cfg(feature = "cross-chain")]
+pub(crate) fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// Decode an unsigned EIP-1559 transaction:
+// 0x02 || rlp([chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas_limit, to, value, data, access_list])
+#[cfg(feature = "cross-chain")]
+fn decode_eip1559_transaction(payload: &[u8]) -> EvmTransaction {
+    let (tx_type, encoded) = payload.split_first().expect("Empty EVM transaction");
+    assert_eq!(*tx_type, EIP1559_TX_TYPE, "Only EIP-1559 transactions can be signed");
+
+    let fields = match rlp_decode(encoded) {
+        Some((Rlp::List(body), [])) => rlp_list_items(body).expect("Malformed EVM transaction"),
+        _ => env::panic_str("Malformed EVM transaction"),
+    };
+    assert_eq!(fields.len(), 9, "Malformed EVM transaction");
+
+    let uint = |index: usize, name: &str| match fields[index] {
+        Rlp::Bytes(bytes) => rlp_uint(bytes).unwrap_or_else(|| env::panic_str(&format!("Invalid EVM transaction {}", name))),
+        Rlp::List(_) => env::panic_str(&format!("Invalid EVM transaction {}", name)),
+    };
+
+    let to = match fields[5] {
+        Rlp::Bytes([]) => None,
+        Rlp::Bytes(bytes) if bytes.len() == 20 => Some(bytes.to_vec()),
+        _ => env::panic_str("Invalid EVM transaction recipient"),
+    };
+
+    let call = match (&fields[7], &to) {
+        (Rlp::Bytes([]), _) => EvmCall::None,
+        (Rlp::Bytes(data), Some(_)) => match erc20_amount(data) {
+            Some(amount) => EvmCall::Erc20(amount),
+            None => EvmCall::Other,
+        },
+        (Rlp::Bytes(_), None) => EvmCall::Other,
+        (Rlp::List(_), _) => env::panic_str("Invalid EVM transaction data"),
+    };
+
+    EvmTransaction {
+        chain_id: u64::try_from(uint(0, "chain ID")).unwrap_or_else(|_| env::panic_str("Invalid EVM transaction chain ID")),
+        nonce: u64::try_from(uint(1, "nonce")).unwrap_or_else(|_| env::panic_str("Invalid EVM transaction nonce")),
+        to,
+        value: uint(6, "value"),
+        call,
+    }
+}
+
+// Amount argument of ERC-20 transfer or approve calldata; None for any other call. Amounts
+// beyond u128, such as unlimited approvals, are refused.
+#[cfg(feature = "cross-chain")]
+fn erc20_amount(data: &[u8]) -> Option<Balance> {
+    let (selector, args) = data.split_at_checked(4)?;
+    if (selector != ERC20_TRANSFER_SELECTOR && selector != ERC20_APPROVE_SELECTOR) || args.len() != 64 {
+        return None;
+    }
+    let (high, low) = args[32..].split_at(16);
+    assert!(high.iter().all(|byte| *byte == 0), "ERC-20 amount too large");
+    Some(low.iter().fold(0u128, |acc, byte| (acc << 8) | *byte as u128))
+}
+
+// A decoded RLP item; lists hold their still-encoded payload
+#[cfg(feature = "cross-chain")]
+enum Rlp<'a> {
+    Bytes(&'a [u8]),
+    List(&'a [u8]),
+}
+
+// Decode the first RLP item in `input`, returning it with the remaining input
+#[cfg(feature = "cross-chain")]
+fn rlp_decode(input: &[u8]) -> Option<(Rlp<'_>, &[u8])> {
+    let prefix = *input.first()?;
+    match prefix {
+        0x00..=0x7f => Some((Rlp::Bytes(&input[..1]), &input[1..])),
+        0x80..=0xb7 => rlp_split(input, 1, (prefix - 0x80) as usize).map(|(item, rest)| (Rlp::Bytes(item), rest)),
+        0xb8..=0xbf => {
+            let length_size = (prefix - 0xb7) as usize;
+            let length = rlp_length(input.get(1..1 + length_size)?)?;
+            rlp_split(input, 1 + length_size, length).map(|(item, rest)| (Rlp::Bytes(item), rest))
+        },
+        0xc0..=0xf7 => rlp_split(input, 1, (prefix - 0xc0) as usize).map(|(item, rest)| (Rlp::List(item), rest)),
+        0xf8..=0xff => {
+            let length_size = (prefix - 0xf7) as usize;
+            let length = rlp_length(input.get(1..1 + length_size)?)?;
+            rlp_split(input, 1 + length_size, length).map(|(item, rest)| (Rlp::List(item), rest))
+        },
+    }
+}
+
+#[cfg(feature = "cross-chain")]
+fn rlp_list_items(mut body: &[u8]) -> Option<Vec<Rlp<'_>>> {
+    let mut items = Vec::new();
+    while !body.is_empty() {
+        let (item, rest) = rlp_decode(body)?;
+        items.push(item);
+        body = rest;
+    }
+    Some(items)
+}
+
+#[cfg(feature = "cross-chain")]
+fn rlp_split(input: &[u8], offset: usize, length: usize) -> Option<(&[u8], &[u8])> {
+    let end = offset.checked_add(length)?;
+    if input.len() < end {
+        return None;
+    }
+    Some((&input[offset..end], &input[end..]))
+}
+
+#[cfg(feature = "cross-chain")]
+fn rlp_length(bytes: &[u8]) -> Option<usize> {
+    if bytes.len() > std::mem::size_of::<usize>() {
+        return None;
+    }
+    Some(bytes.iter().fold(0usize, |acc, byte| (acc << 8) | *byte as usize))
+}
+
+// Big-endian unsigned integer; values above u128 (possible for EVM uint256) are rejected
+#[cfg(feature = "cross-chain")]
+fn rlp_uint(bytes: &[u8]) -> Option<u128> {
+    if bytes.len() > 16 || bytes.first() == Some(&0) {
+        return None;
+    }
+    Some(bytes.iter().fold(0u128, |acc, byte| (acc << 8) | *byte as u128))
+}
+
+// View struct for an agent's EVM signing state
+#[cfg(feature = "cross-chain")]
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EvmDelegationView {
+    agent_id: AccountId,
+    chain_id: u64,
+    next_nonce: u64,
+    spent_in_period: U128,
+    // None if the chain has no budget
+    period_limit: Option<U128>,
+    // None until the agent signs in a new period
+    period_resets_at: Option<u64>,
+}
@@ -0,0 +1,136 @@
+use crate::*;
+use crate::events::emit_event;
+use near_sdk::json_types::Base64VecU8;
+
+// Maximum number of checkpoints stored per intent
+const MAX_CHECKPOINTS_PER_INTENT: usize = 100;
+
+// Maximum length of a checkpoint note
+const MAX_CHECKPOINT_NOTE_LENGTH: usize = 280;
+
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IntentCheckpoint {
+    // Reported progress (0-100)
+    pub(crate) percentage: u8,
+    // Short progress note
+    pub(crate) note: String,
+    // Optional hash of intermediate results
+    pub(crate) hash: Option<Base64VecU8>,
+    pub(crate) timestamp: u64,
+    // When the client approved this checkpoint, if at all
+    pub(crate) approved_at: Option<u64>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct LivenessStats {
+    // Checkpoints posted by the agent
+    checkpoints_posted: u64,
+    // Checkpoints posted after the intent had already stalled
+    late_checkpoints: u64,
+    // Intents cancelled by the client because progress stalled
+    stall_cancellations: u64,
+}
+
+impl LivenessStats {
+    // Share of on-time progress reports (0-100); agents with no history start at 100
+    pub fn liveness_score(&self) -> u32 {
+        let total = self.checkpoints_posted + self.stall_cancellations;
+        if total == 0 {
+            return 100;
+        }
+        let on_time = self.checkpoints_posted.saturating_sub(self.late_checkpoints);
+        (on_time * 100 / total) as u32
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Post a progress checkpoint for an in-progress intent (agent only)
+    pub fn post_checkpoint(&mut self, intent_id: String, percentage: u8, note: String, hash: Option<Base64VecU8>) {
+        let intent = self.intents.get(&intent_id).expect("Intent not found");
+
+        assert_eq!(env::predecessor_account_id(), intent.agent_id, "Only the assigned agent can post checkpoints");
+        assert!(intent.status == IntentStatus::InProgress, "Checkpoints can only be posted for in-progress intents");
+        assert!(percentage <= 100, "Percentage must be between 0 and 100");
+        assert!(note.len() <= MAX_CHECKPOINT_NOTE_LENGTH, "Checkpoint note too long");
+        self.internal_record_activity(&intent.agent_id);
+
+        let mut checkpoints = self.intent_checkpoints.get(&intent_id).unwrap_or_default();
+        assert!(checkpoints.len() < MAX_CHECKPOINTS_PER_INTENT, "Checkpoint limit reached for intent");
+        if let Some(last) = checkpoints.last() {
+            assert!(percentage >= last.percentage, "Checkpoint progress cannot decrease");
+        }
+
+        // Late checkpoints count against the agent's liveness, except during force majeure
+        let stalled = self.is_intent_stalled(&intent, &checkpoints);
+        let late = stalled && !self.is_force_majeure_active();
+
+        checkpoints.push(IntentCheckpoint {
+            percentage,
+            note,
+            hash,
+            timestamp: env::block_timestamp(),
+            approved_at: None,
+        });
+        self.intent_checkpoints.insert(&intent_id, &checkpoints);
+
+        let mut agent_rep = self.agent_reputations.get(&intent.agent_id).expect("Agent not registered");
+        agent_rep.liveness.checkpoints_posted += 1;
+        if late {
+            agent_rep.liveness.late_checkpoints += 1;
+        }
+        self.internal_save_agent(&intent.agent_id, &agent_rep);
+
+        emit_event(
+            "intent_checkpoint_posted",
+            json!({
+                "intent_id": intent_id,
+                "agent_id": intent.agent_id,
+                "percentage": percentage,
+                "stalled": stalled,
+            }),
+        );
+    }
+
+    // Cancel an in-progress intent whose progress has stalled (client only).
+    // Partial payment is based on the last checkpoint the client approved.
+    pub fn cancel_stalled_intent(&mut self, intent_id: String) {
+        self.internal_transition_intent(&intent_id, IntentStatus::Cancelled, None);
+    }
+
+    // Get all checkpoints posted for an intent
+    pub fn get_intent_checkpoints(&self, intent_id: String) -> Vec<IntentCheckpoint> {
+        self.intent_checkpoints.get(&intent_id).unwrap_or_default()
+    }
+
+    // Progress of an intent according to its latest client-approved checkpoint; progress the
+    // agent only reported does not count
+    #[cfg(feature = "escrow")]
+    pub(crate) fn intent_approved_progress(&self, intent_id: &str) -> u8 {
+        self.intent_checkpoints
+            .get(&intent_id.to_string())
+            .and_then(|checkpoints| checkpoints.iter().rev().find(|c| c.approved_at.is_some()).map(|c| c.percentage))
+            .unwrap_or(0)
+    }
+
+    // Whether an in-progress intent has gone longer than the stall period without progress
+    pub(crate) fn is_intent_stalled(&self, intent: &IntentData, checkpoints: &[IntentCheckpoint]) -> bool {
+        if intent.status != IntentStatus::InProgress {
+            return false;
+        }
+        let last_progress = checkpoints
+            .last()
+            .map(|c| c.timestamp)
+            .unwrap_or(intent.updated_at);
+        env::block_timestamp() - last_progress > self.checkpoint_stall_period + intent.deadline_extension
+    }
+
+    // Record that an agent let an intent stall until the client cancelled it
+    pub(crate) fn internal_record_stall(&mut self, agent_id: &AccountId) {
+        if let Some(mut agent_rep) = self.agent_reputations.get(agent_id) {
+            agent_rep.liveness.stall_cancellations += 1;
+            self.internal_save_agent(agent_id, &agent_rep);
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use crate::*;
+use crate::events::emit_event;
+use crate::fixed_point::{div_round, linear_share, rating_score_bps, to_points, SCORE_SCALE_BPS};
+
+// Finished intents' worth of neutral history a client's score starts from, so a couple of
+// intents cannot make or break it
+const CLIENT_PRIOR_INTENTS: u128 = 5;
+
+// Score a client starts at, and is pulled toward while its history is short (basis points)
+const CLIENT_PRIOR_SCORE_BPS: u32 = 5_000;
+
+// A client's record as seen by the agents it hires
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+pub struct ClientReputation {
+    intents_created: u64,
+    // Intents that finished completed, and those of them the client paid for through escrow
+    intents_completed: u64,
+    intents_paid: u64,
+    intents_failed: u64,
+    // Accepted or in-progress intents the client cancelled
+    intents_cancelled: u64,
+    disputes_filed: u64,
+    // Disputes ruled for the agent, and for the client
+    disputes_lost: u64,
+    disputes_won: u64,
+    first_intent_at: u64,
+    last_activity: u64,
+    // Ratings (0-5) the client received from its agents, and their sum
+    ratings_received: u64,
+    rating_total: u64,
+}
+
+impl ClientReputation {
+    fn new() -> Self {
+        let now = env::block_timestamp();
+        Self {
+            intents_created: 0,
+            intents_completed: 0,
+            intents_paid: 0,
+            intents_failed: 0,
+            intents_cancelled: 0,
+            disputes_filed: 0,
+            disputes_lost: 0,
+            disputes_won: 0,
+            first_intent_at: now,
+            last_activity: now,
+            ratings_received: 0,
+            rating_total: 0,
+        }
+    }
+
+    // Share of completed intents the client paid for through escrow (basis points), or None
+    // before any completed
+    fn payment_reliability_bps(&self) -> Option<u32> {
+        (self.intents_completed > 0)
+            .then(|| linear_share(SCORE_SCALE_BPS, self.intents_paid as u128, self.intents_completed as u128))
+    }
+
+    // Average rating from the client's agents on the score scale, or None before any rated it
+    fn average_rating_bps(&self) -> Option<u32> {
+        (self.ratings_received > 0).then(|| rating_score_bps(self.rating_total as u128, self.ratings_received as u128))
+    }
+
+    // Score (basis points): the mean of payment reliability, the share of finished intents not
+    // lost in dispute and, once rated, the agents' average rating, shrunk toward the neutral
+    // score while the history is short
+    fn score_bps(&self) -> u32 {
+        let finished = (self.intents_completed + self.intents_failed) as u128;
+        if finished == 0 {
+            return CLIENT_PRIOR_SCORE_BPS;
+        }
+        let payment = self.payment_reliability_bps().unwrap_or(SCORE_SCALE_BPS);
+        let disputes = SCORE_SCALE_BPS - linear_share(SCORE_SCALE_BPS, self.disputes_lost as u128, finished);
+        let raw_score = match self.average_rating_bps() {
+            Some(rating) => div_round(payment as u128 + disputes as u128 + rating as u128, 3),
+            None => div_round(payment as u128 + disputes as u128, 2),
+        };
+        div_round(
+            raw_score * finished + CLIENT_PRIOR_SCORE_BPS as u128 * CLIENT_PRIOR_INTENTS,
+            finished + CLIENT_PRIOR_INTENTS,
+        ) as u32
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Get a client's record with the agents it hired and its score, or None if it never
+    // recorded an intent
+    pub fn get_client_reputation(&self, client_id: AccountId) -> Option<ClientReputationView> {
+        let client_rep = self.client_reputations.get(&client_id)?;
+        let score_bps = client_rep.score_bps();
+
+        Some(ClientReputationView {
+            client_id,
+            score: to_points(score_bps),
+            score_bps,
+            intents_created: client_rep.intents_created,
+            intents_completed: client_rep.intents_completed,
+            intents_failed: client_rep.intents_failed,
+            intents_cancelled: client_rep.intents_cancelled,
+            payment_reliability_bps: client_rep.payment_reliability_bps(),
+            disputes_filed: client_rep.disputes_filed,
+            disputes_lost: client_rep.disputes_lost,
+            disputes_won: client_rep.disputes_won,
+            ratings_received: client_rep.ratings_received,
+            average_rating_bps: client_rep.average_rating_bps(),
+            first_intent_at: client_rep.first_intent_at,
+            last_activity: client_rep.last_activity,
+        })
+    }
+
+    // Set the client score from which the calling agent's new intents are accepted
+    // automatically, or clear it to respond to every intent by hand
+    pub fn set_client_auto_accept(&mut self, min_client_score: Option<u32>) {
+        let agent_id = env::predecessor_account_id();
+        assert!(self.agent_reputations.get(&agent_id).is_some(), "Agent not registered");
+
+        let initial_storage = env::storage_usage();
+        match min_client_score {
+            Some(score) => {
+                assert!(score <= 100, "Client score must be between 0 and 100");
+                self.client_auto_accept.insert(&agent_id, &score);
+            },
+            None => {
+                self.client_auto_accept.remove(&agent_id);
+            },
+        }
+        self.internal_charge_storage(&agent_id, initial_storage);
+
+        emit_event(
+            "client_auto_accept_updated",
+            json!({ "agent_id": agent_id, "min_client_score": min_client_score }),
+        );
+    }
+
+    // Get the client score from which an agent accepts intents automatically, if set
+    pub fn get_client_auto_accept(&self, agent_id: AccountId) -> Option<u32> {
+        self.client_auto_accept.get(&agent_id)
+    }
+
+    // Whether the agent accepts the client's intents automatically
+    pub(crate) fn internal_client_auto_accepted(&self, agent_id: &AccountId, client_id: &AccountId) -> bool {
+        let min_score = match self.client_auto_accept.get(agent_id) {
+            Some(min_score) => min_score,
+            None => return false,
+        };
+        let score_bps = self
+            .client_reputations
+            .get(client_id)
+            .map_or(CLIENT_PRIOR_SCORE_BPS, |client_rep| client_rep.score_bps());
+        to_points(score_bps) >= min_score
+    }
+
+    // Count a newly recorded intent toward its client's record
+    pub(crate) fn internal_record_client_intent(&mut self, client_id: &AccountId) {
+        self.internal_update_client(client_id, |client_rep| client_rep.intents_created += 1);
+    }
+
+    // Update the client's record for a dispute or cancellation transition of its intent
+    pub(crate) fn internal_record_client_transition(
+        &mut self,
+        intent: &IntentData,
+        previous_status: IntentStatus,
+        status: IntentStatus,
+    ) {
+        self.internal_update_client(&intent.client_id, |client_rep| {
+            match (previous_status, status) {
+                (_, IntentStatus::Disputed) => client_rep.disputes_filed += 1,
+                (IntentStatus::Disputed, IntentStatus::Completed) => client_rep.disputes_lost += 1,
+                (IntentStatus::Disputed, _) => client_rep.disputes_won += 1,
+                (IntentStatus::Accepted | IntentStatus::InProgress, IntentStatus::Cancelled) => {
+                    client_rep.intents_cancelled += 1
+                },
+                _ => {},
+            }
+        });
+    }
+
+    // Count a finalized intent toward its client's record
+    pub(crate) fn internal_record_client_outcome(&mut self, intent: &IntentData) {
+        self.internal_update_client(&intent.client_id, |client_rep| match intent.status {
+            IntentStatus::Completed => {
+                client_rep.intents_completed += 1;
+                if intent.escrow_amount > 0 {
+                    client_rep.intents_paid += 1;
+                }
+            },
+            IntentStatus::Failed => client_rep.intents_failed += 1,
+            _ => {},
+        });
+    }
+
+    // Count an agent's released rating (0-5) toward its client's record
+    pub(crate) fn internal_record_client_rating(&mut self, client_id: &AccountId, rating: u8) {
+        self.internal_update_client(client_id, |client_rep| {
+            client_rep.ratings_received += 1;
+            client_rep.rating_total += rating as u64;
+        });
+    }
+
+    fn internal_update_client<F: FnOnce(&mut ClientReputation)>(&mut self, client_id: &AccountId, update: F) {
+        let mut client_rep = self.client_reputations.get(client_id).unwrap_or_else(ClientReputation::new);
+        update(&mut client_rep);
+        client_rep.last_activity = env::block_timestamp();
+        self.client_reputations.insert(client_id, &client_rep);
+    }
+}
+
+// View struct for a client's reputation
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ClientReputationView {
+    client_id: AccountId,
+    score: u32,
+    score_bps: u32,
+    intents_created: u64,
+    intents_completed: u64,
+    intents_failed: u64,
+    intents_cancelled: u64,
+    // Share of completed intents paid through escrow; None before any completed
+    payment_reliability_bps: Option<u32>,
+    disputes_filed: u64,
+    disputes_lost: u64,
+    disputes_won: u64,
+    ratings_received: u64,
+    // Agents' average rating on the score scale; None before any rated the client
+    average_rating_bps: Option<u32>,
+    first_intent_at: u64,
+    last_activity: u64,
+}
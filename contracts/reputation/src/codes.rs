@@ -0,0 +1,233 @@
+use crate::*;
+
+// Upper bound on violations returned per page of numeric views
+const MAX_NUMERIC_VIOLATIONS_PAGE: u32 = 50;
+
+// Numeric codes are part of the contract's ABI: existing codes never change meaning and new
+// variants get the next unused code.
+
+impl TrustLevel {
+    pub const ALL: [TrustLevel; 5] = [
+        TrustLevel::Novice,
+        TrustLevel::Apprentice,
+        TrustLevel::Trusted,
+        TrustLevel::Expert,
+        TrustLevel::Master,
+    ];
+
+    // Stable numeric code for machine consumers
+    pub fn code(&self) -> u8 {
+        match self {
+            TrustLevel::Novice => 0,
+            TrustLevel::Apprentice => 1,
+            TrustLevel::Trusted => 2,
+            TrustLevel::Expert => 3,
+            TrustLevel::Master => 4,
+        }
+    }
+}
+
+impl IntentStatus {
+    pub const ALL: [IntentStatus; 9] = [
+        IntentStatus::Created,
+        IntentStatus::Accepted,
+        IntentStatus::Declined,
+        IntentStatus::InProgress,
+        IntentStatus::Completed,
+        IntentStatus::Failed,
+        IntentStatus::Disputed,
+        IntentStatus::Cancelled,
+        IntentStatus::Expired,
+    ];
+
+    // Stable numeric code for machine consumers
+    pub fn code(&self) -> u8 {
+        match self {
+            IntentStatus::Created => 0,
+            IntentStatus::Accepted => 1,
+            IntentStatus::Declined => 2,
+            IntentStatus::InProgress => 3,
+            IntentStatus::Completed => 4,
+            IntentStatus::Failed => 5,
+            IntentStatus::Disputed => 6,
+            IntentStatus::Cancelled => 7,
+            IntentStatus::Expired => 8,
+        }
+    }
+}
+
+// Numeric code of every governance-defined violation type
+const CUSTOM_VIOLATION_CODE: u8 = 5;
+
+impl ViolationType {
+    pub const ALL: [ViolationType; 5] = [
+        ViolationType::MinorInfraction,
+        ViolationType::MajorInfraction,
+        ViolationType::TermsViolation,
+        ViolationType::EthicalViolation,
+        ViolationType::SecurityBreach,
+    ];
+
+    // Stable numeric code for machine consumers
+    pub fn code(&self) -> u8 {
+        match self {
+            ViolationType::MinorInfraction => 0,
+            ViolationType::MajorInfraction => 1,
+            ViolationType::TermsViolation => 2,
+            ViolationType::EthicalViolation => 3,
+            ViolationType::SecurityBreach => 4,
+            // Governance-defined types share one code; the regular views carry the name
+            ViolationType::Custom(_) => CUSTOM_VIOLATION_CODE,
+        }
+    }
+}
+
+impl EscrowAsset {
+    pub const ALL: [EscrowAsset; 2] = [EscrowAsset::Near, EscrowAsset::Itlx];
+
+    // Stable numeric code for machine consumers
+    pub fn code(&self) -> u8 {
+        match self {
+            EscrowAsset::Near => 0,
+            EscrowAsset::Itlx => 1,
+        }
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Catalog mapping every numeric code used by the numeric views to the name the regular
+    // views use for it
+    pub fn get_code_map(&self) -> CodeMapView {
+        CodeMapView {
+            trust_levels: code_entries(&TrustLevel::ALL, TrustLevel::code),
+            intent_statuses: code_entries(&IntentStatus::ALL, IntentStatus::code),
+            violation_types: code_entries(&ViolationType::ALL, ViolationType::code)
+                .into_iter()
+                .chain([(CUSTOM_VIOLATION_CODE, "Custom".to_string())])
+                .collect(),
+            escrow_assets: code_entries(&EscrowAsset::ALL, EscrowAsset::code),
+            feedback_categories: code_entries(&FeedbackCategory::ALL, FeedbackCategory::code),
+        }
+    }
+
+    // Agent reputation with the trust level as a numeric code and category scores as an
+    // array indexed by feedback category code
+    pub fn get_agent_reputation_numeric(&self, agent_id: AccountId) -> Option<AgentReputationNumericView> {
+        self.agent_reputations.get(&agent_id).map(|rep| AgentReputationNumericView {
+            score: rep.score,
+            trust_level: self.get_trust_level(rep.score).code(),
+            total_interactions: rep.total_interactions,
+            successful_interactions: rep.successful_interactions,
+            category_scores: FeedbackCategory::ALL.map(|category| rep.category_scores.get(category)),
+            reliability_score: rep.reliability_score,
+            violation_count: rep.violation_history.len() as u32,
+            last_update: rep.last_update,
+        })
+    }
+
+    // Intent with its status and escrow asset as numeric codes
+    pub fn get_intent_numeric(&self, intent_id: String) -> Option<IntentNumericView> {
+        self.intents.get(&intent_id).map(|intent| IntentNumericView {
+            intent_id: intent.intent_id,
+            agent_id: intent.agent_id,
+            client_id: intent.client_id,
+            status: intent.status.code(),
+            timestamp: intent.timestamp,
+            updated_at: intent.updated_at,
+            deadline: intent.deadline,
+            escrow_amount: U128(intent.escrow_amount),
+            escrow_asset: intent.escrow_asset.code(),
+            finalized: intent.finalized,
+        })
+    }
+
+    // Page of an agent's violations with violation types as numeric codes
+    pub fn get_violations_numeric(&self, agent_id: AccountId, from_index: u32, limit: u32) -> Vec<ViolationNumericView> {
+        let agent_rep = match self.agent_reputations.get(&agent_id) {
+            Some(agent_rep) => agent_rep,
+            None => return Vec::new(),
+        };
+
+        agent_rep
+            .violation_history
+            .iter()
+            .enumerate()
+            .skip((from_index as u64).saturating_sub(agent_rep.violations_archived) as usize)
+            .take(std::cmp::min(limit, MAX_NUMERIC_VIOLATIONS_PAGE) as usize)
+            .map(|(position, violation)| ViolationNumericView {
+                index: agent_rep.violations_archived + position as u64,
+                violation_type: violation.violation_type.code(),
+                timestamp: violation.timestamp,
+                penalty_applied: violation.penalty_applied,
+                tokens_slashed: U128(violation.tokens_slashed),
+            })
+            .collect()
+    }
+}
+
+// (code, name) pairs for an enum, with names as the regular JSON views render them
+fn code_entries<T: near_sdk::serde::Serialize>(variants: &[T], code: fn(&T) -> u8) -> Vec<(u8, String)> {
+    variants
+        .iter()
+        .map(|variant| {
+            let name = match near_sdk::serde_json::to_value(variant) {
+                Ok(near_sdk::serde_json::Value::String(name)) => name,
+                _ => env::panic_str("Enum code variants must serialize as strings"),
+            };
+            (code(variant), name)
+        })
+        .collect()
+}
+
+// View struct for the numeric code catalog
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CodeMapView {
+    trust_levels: Vec<(u8, String)>,
+    intent_statuses: Vec<(u8, String)>,
+    violation_types: Vec<(u8, String)>,
+    escrow_assets: Vec<(u8, String)>,
+    feedback_categories: Vec<(u8, String)>,
+}
+
+// View struct for agent reputation with numeric codes
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentReputationNumericView {
+    score: u32,
+    trust_level: u8,
+    total_interactions: u64,
+    successful_interactions: u64,
+    category_scores: [u8; 5],
+    reliability_score: u32,
+    violation_count: u32,
+    last_update: u64,
+}
+
+// View struct for an intent with numeric codes
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IntentNumericView {
+    intent_id: String,
+    agent_id: AccountId,
+    client_id: AccountId,
+    status: u8,
+    timestamp: u64,
+    updated_at: u64,
+    deadline: u64,
+    escrow_amount: U128,
+    escrow_asset: u8,
+    finalized: bool,
+}
+
+// View struct for a violation with numeric codes
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ViolationNumericView {
+    index: u64,
+    violation_type: u8,
+    timestamp: u64,
+    penalty_applied: u32,
+    tokens_slashed: U128,
+}
@@ -0,0 +1,948 @@
+use crate::*;
+use crate::attestations::MAX_ATTESTATION_TTL;
+use crate::bidding::{MAX_BIDDING_PERIOD, MIN_BIDDING_PERIOD};
+use crate::blind_ratings::MAX_BLIND_RATING_WINDOW;
+use crate::events::emit_event;
+use crate::delegation::MAX_UNDELEGATION_COOLDOWN;
+use crate::endorsements::{MAX_ENDORSEMENT_BONUS, MAX_ENDORSEMENT_HALF_LIFE};
+use crate::epochs::{MAX_SCORE_EPOCH_LENGTH, MIN_SCORE_EPOCH_LENGTH};
+use crate::escheatment::{MIN_DORMANCY_PERIOD, MIN_ESCHEATMENT_NOTICE_PERIOD};
+use crate::escrow::MAX_PROTOCOL_FEE_BPS;
+use crate::fees::MAX_FEE_DISCOUNT_TIERS;
+use crate::insurance::MAX_INSURANCE_CLAIM_WINDOW;
+use crate::oracles::{MAX_PERFORMANCE_WINDOW, MIN_PERFORMANCE_WINDOW};
+use crate::feedback::MAX_CATEGORY_WEIGHT_BPS;
+use crate::feedback_limits::{MAX_DAMPENING_WINDOW, MAX_FEEDBACK_INTERVAL};
+use crate::fixed_point::{div_round, from_points, integer_sqrt};
+use crate::penalty_decay::{MAX_DECAY_EPOCH_LENGTH, MAX_DECAY_MIN_INTERACTIONS, MIN_DECAY_EPOCH_LENGTH};
+use crate::quotas::{MAX_QUOTA_WINDOW, MAX_USAGE_QUOTAS};
+use crate::rewards::MIN_REWARD_EPOCH_LENGTH;
+use crate::stake_health::MAX_STAKE_GRACE_PERIOD;
+use crate::task_delegation::MAX_TASK_DELEGATION_DEPTH;
+use crate::violation_policy::assert_valid_violation_policy;
+use near_sdk::serde_json::{json, Value};
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Propose a new minimum stake required to register an agent; applies after the timelock.
+    // Returns the queued action ID.
+    pub fn set_min_stake(&mut self, min_stake_amount: U128) -> u64 {
+        self.assert_owner_or_governance();
+        assert!(min_stake_amount.0 > 0, "Minimum stake must be greater than zero");
+
+        self.internal_queue_action(TimelockedAction::SetMinStake { min_stake_amount })
+    }
+
+    // Set the account (e.g. a price oracle) that can update staking token weights alongside the
+    // owner and governance, or clear it
+    pub fn set_staking_rate_oracle(&mut self, staking_rate_oracle: Option<AccountId>) {
+        self.assert_owner_or_governance();
+
+        let old_value = std::mem::replace(&mut self.staking_rate_oracle, staking_rate_oracle.clone());
+
+        self.record_config_change("staking_rate_oracle", json!(old_value), json!(staking_rate_oracle));
+    }
+
+    // Update the feedback expiration period (in nanoseconds)
+    pub fn set_feedback_expiry(&mut self, feedback_expiry_period: u64) {
+        self.assert_owner_or_governance();
+        assert!(feedback_expiry_period > 0, "Feedback expiry period must be greater than zero");
+
+        let old_periods = self.internal_category_expiry_periods();
+        let old_value = self.feedback_expiry_period;
+        self.feedback_expiry_period = feedback_expiry_period;
+        self.internal_bump_feedback_window_epoch(old_value, old_periods);
+
+        self.record_config_change("feedback_expiry_period", json!(old_value), json!(feedback_expiry_period));
+    }
+
+    // Replace the weights and expiry periods of feedback categories in the headline score.
+    // Categories left out have no weight and expire with the feedback. New expiry periods apply
+    // as each agent is next rescored; ratings a category has already dropped come back, a batch
+    // per rescore, if its period is lengthened and they have not been pruned.
+    pub fn set_category_scoring(&mut self, configs: Vec<CategoryScoringConfig>) {
+        self.assert_owner_or_governance();
+        for (i, config) in configs.iter().enumerate() {
+            assert!(
+                configs[..i].iter().all(|other| other.category != config.category),
+                "Duplicate feedback category"
+            );
+            assert!(config.weight_bps <= MAX_CATEGORY_WEIGHT_BPS, "Category weight exceeds maximum");
+            if let Some(expiry_period) = config.expiry_period {
+                assert!(
+                    expiry_period > 0 && expiry_period <= self.feedback_expiry_period,
+                    "Category expiry period must be between zero and the feedback expiry period"
+                );
+            }
+        }
+
+        let old_periods = self.internal_category_expiry_periods();
+        let old_value = std::mem::replace(&mut self.category_scoring, configs.clone());
+        self.internal_bump_feedback_window_epoch(self.feedback_expiry_period, old_periods);
+
+        self.record_config_change("category_scoring", json!(old_value), json!(configs));
+    }
+
+    // Update the prior feedback scores are shrunk toward while an agent has little feedback
+    pub fn set_bayesian_prior(&mut self, prior: BayesianPrior) {
+        self.assert_owner_or_governance();
+        assert!(prior.prior_weight <= MAX_PRIOR_WEIGHT, "Prior weight exceeds maximum");
+        assert!(prior.prior_mean <= 100, "Prior mean must be between 0 and 100");
+
+        let old_value = std::mem::replace(&mut self.bayesian_prior, prior.clone());
+
+        self.record_config_change("bayesian_prior", json!(old_value), json!(prior));
+    }
+
+    // Update how often one reviewer can rate an agent and how repeated ratings are dampened
+    pub fn set_feedback_limit_config(&mut self, config: FeedbackLimitConfig) {
+        self.assert_owner_or_governance();
+        assert!(config.min_feedback_interval <= MAX_FEEDBACK_INTERVAL, "Feedback interval exceeds maximum");
+        assert!(
+            config.dampening_window > 0 && config.dampening_window <= MAX_DAMPENING_WINDOW,
+            "Dampening window must be between zero and the maximum"
+        );
+        assert!(
+            config.repeat_weight_decay_bps <= 10_000 && config.min_repeat_weight_bps <= 10_000,
+            "Repeat weights cannot exceed 10000 basis points"
+        );
+        assert!(config.min_repeat_weight_bps > 0, "Repeated feedback must keep some weight");
+
+        let old_value = std::mem::replace(&mut self.feedback_limit_config, config.clone());
+
+        self.record_config_change("feedback_limit_config", json!(old_value), json!(config));
+    }
+
+    // Update how long reviewers can amend or revoke their feedback after submitting it
+    // (nanoseconds, 0 disables changes)
+    pub fn set_feedback_amendment_window(&mut self, feedback_amendment_window: u64) {
+        self.assert_owner_or_governance();
+        assert!(
+            feedback_amendment_window <= self.feedback_expiry_period,
+            "Amendment window cannot exceed the feedback expiry period"
+        );
+
+        let old_value = self.feedback_amendment_window;
+        self.feedback_amendment_window = feedback_amendment_window;
+
+        self.record_config_change("feedback_amendment_window", json!(old_value), json!(feedback_amendment_window));
+    }
+
+    // Update how long an intent's first rating is held for the other side's before it can be
+    // released alone (nanoseconds, 0 applies ratings as they come)
+    pub fn set_blind_rating_window(&mut self, blind_rating_window: u64) {
+        self.assert_owner_or_governance();
+        assert!(blind_rating_window <= MAX_BLIND_RATING_WINDOW, "Blind rating window too long");
+
+        let old_value = self.blind_rating_window;
+        self.blind_rating_window = blind_rating_window;
+
+        self.record_config_change("blind_rating_window", json!(old_value), json!(blind_rating_window));
+    }
+
+    // Update how heavily each failure cause counts against the success rate
+    pub fn set_failure_weights(&mut self, weights: FailureWeights) {
+        self.assert_owner_or_governance();
+        weights.assert_valid();
+
+        let old_value = std::mem::replace(&mut self.failure_weights, weights.clone());
+
+        self.record_config_change("failure_weights", json!(old_value), json!(weights));
+    }
+
+    // Update how oracle-reported performance is scored and how heavily it weighs in reputation
+    pub fn set_performance_config(&mut self, config: PerformanceConfig) {
+        self.assert_owner_or_governance();
+        assert!(config.weight_bps <= 10_000, "Performance weight cannot exceed 10000 basis points");
+        assert!(
+            config.window >= MIN_PERFORMANCE_WINDOW && config.window <= MAX_PERFORMANCE_WINDOW,
+            "Performance window out of range"
+        );
+        assert!(config.latency_max_ms > config.latency_target_ms, "Latency maximum must exceed the target");
+        assert!(config.throughput_target > 0, "Throughput target must be greater than zero");
+
+        let old_value = std::mem::replace(&mut self.performance_config, config.clone());
+
+        self.record_config_change("performance_config", json!(old_value), json!(config));
+    }
+
+    // Update the length of score epochs (nanoseconds); the epoch under way ends on the new length
+    pub fn set_score_epoch_length(&mut self, score_epoch_length: u64) {
+        self.assert_owner_or_governance();
+        assert!(
+            (MIN_SCORE_EPOCH_LENGTH..=MAX_SCORE_EPOCH_LENGTH).contains(&score_epoch_length),
+            "Score epoch length out of range"
+        );
+
+        let old_value = self.score_epoch_length;
+        self.score_epoch_length = score_epoch_length;
+
+        self.record_config_change("score_epoch_length", json!(old_value), json!(score_epoch_length));
+    }
+
+    // Update how long agents have to top their stake back up to the minimum (nanoseconds)
+    pub fn set_stake_grace_period(&mut self, stake_grace_period: u64) {
+        self.assert_owner_or_governance();
+        assert!(stake_grace_period <= MAX_STAKE_GRACE_PERIOD, "Stake grace period exceeds maximum");
+
+        let old_value = self.stake_grace_period;
+        self.stake_grace_period = stake_grace_period;
+
+        self.record_config_change("stake_grace_period", json!(old_value), json!(stake_grace_period));
+    }
+
+    // Update who may endorse agents, how many endorsements each may hold, how fast they decay
+    // and how much they can add to the score
+    pub fn set_endorsement_config(&mut self, config: EndorsementConfig) {
+        self.assert_owner_or_governance();
+        assert!(config.max_endorsements_per_endorser > 0, "Endorsers must be allowed at least one endorsement");
+        assert!(
+            config.half_life > 0 && config.half_life <= MAX_ENDORSEMENT_HALF_LIFE,
+            "Endorsement half-life must be positive and within the maximum"
+        );
+        assert!(config.max_endorsement_bonus <= MAX_ENDORSEMENT_BONUS, "Endorsement bonus exceeds maximum");
+
+        let old_value = std::mem::replace(&mut self.endorsement_config, config.clone());
+
+        self.record_config_change("endorsement_config", json!(old_value), json!(config));
+    }
+
+    // Update how many hops an intent can be delegated down from its agent
+    pub fn set_max_task_delegation_depth(&mut self, max_task_delegation_depth: u8) {
+        self.assert_owner_or_governance();
+        assert!(
+            max_task_delegation_depth > 0 && max_task_delegation_depth <= MAX_TASK_DELEGATION_DEPTH,
+            "Delegation depth must be between 1 and the maximum"
+        );
+
+        let old_value = self.max_task_delegation_depth;
+        self.max_task_delegation_depth = max_task_delegation_depth;
+
+        self.record_config_change("max_task_delegation_depth", json!(old_value), json!(max_task_delegation_depth));
+    }
+
+    // Update the insurance pool's share of slashes, the claim window and the per-incident cap
+    pub fn set_insurance_config(&mut self, config: InsuranceConfig) {
+        self.assert_owner_or_governance();
+        assert!(config.pool_share_bps <= 10_000, "Pool share cannot exceed 10000 basis points");
+        assert!(
+            config.claim_window > 0 && config.claim_window <= MAX_INSURANCE_CLAIM_WINDOW,
+            "Claim window must be positive and within the maximum"
+        );
+
+        let old_value = std::mem::replace(&mut self.insurance_config, config.clone());
+
+        self.record_config_change("insurance_config", json!(old_value), json!(config));
+    }
+
+    // Propose replacing the penalty table for violations; applies after the timelock. Every
+    // built-in type needs an entry; entries for `Custom` types define the governance-defined
+    // violation types that can be reported. Violations already recorded keep the penalties
+    // they were given. Returns the queued action ID.
+    pub fn set_violation_policy(&mut self, policy: Vec<ViolationPolicyEntry>) -> u64 {
+        self.assert_owner_or_governance();
+        assert_valid_violation_policy(&policy);
+
+        self.internal_queue_action(TimelockedAction::SetViolationPolicy { policy })
+    }
+
+    // Update how violation penalties are forgiven over clean epochs. Agents' epochs in progress
+    // are kept; the new length applies from the next roll-forward.
+    pub fn set_penalty_decay_config(&mut self, config: PenaltyDecayConfig) {
+        self.assert_owner_or_governance();
+        assert!(
+            config.epoch_length >= MIN_DECAY_EPOCH_LENGTH && config.epoch_length <= MAX_DECAY_EPOCH_LENGTH,
+            "Decay epoch length out of range"
+        );
+        assert!(config.forgiven_bps_per_epoch <= 10_000, "Forgiven share cannot exceed 10000 basis points");
+        assert!(config.min_interactions <= MAX_DECAY_MIN_INTERACTIONS, "Too many interactions required per epoch");
+
+        let old_value = std::mem::replace(&mut self.penalty_decay_config, config.clone());
+
+        self.record_config_change("penalty_decay_config", json!(old_value), json!(config));
+    }
+
+    // Set the account (e.g. an oracle) that can verify remediation proofs alongside the owner
+    // and governance, or clear it
+    pub fn set_remediation_verifier(&mut self, remediation_verifier: Option<AccountId>) {
+        self.assert_owner_or_governance();
+
+        let old_value = std::mem::replace(&mut self.remediation_verifier, remediation_verifier.clone());
+
+        self.record_config_change("remediation_verifier", json!(old_value), json!(remediation_verifier));
+    }
+
+    // Update the score thresholds for probation and the violation count that suspends agents
+    pub fn set_agent_status_config(&mut self, config: AgentStatusConfig) {
+        self.assert_owner_or_governance();
+        assert!(
+            config.probation_score_threshold <= config.probation_exit_score && config.probation_exit_score <= 100,
+            "Probation exit score must be between the probation threshold and 100"
+        );
+        assert!(config.suspension_violation_count > 0, "Suspension violation count must be greater than zero");
+        assert!(config.suspension_violation_window > 0, "Suspension violation window must be greater than zero");
+
+        let old_value = std::mem::replace(&mut self.agent_status_config, config.clone());
+
+        self.record_config_change("agent_status_config", json!(old_value), json!(config));
+    }
+
+    // Update the NEAR AI registry contract used to verify agents
+    pub fn set_registry(&mut self, near_ai_registry: AccountId) {
+        self.assert_owner_or_governance();
+
+        let old_value = self.near_ai_registry.replace(near_ai_registry.clone());
+
+        self.record_config_change("near_ai_registry", json!(old_value), json!(near_ai_registry));
+    }
+
+    // Update the NEAR Intents processor account, which can record intents for clients and relay
+    // agents' status updates
+    pub fn set_intents_processor(&mut self, intents_processor: AccountId) {
+        self.assert_owner_or_governance();
+
+        let old_value = self.intents_processor.replace(intents_processor.clone());
+
+        self.record_config_change("intents_processor", json!(old_value), json!(intents_processor));
+    }
+
+    // Update how long agents have to accept or decline an intent (in nanoseconds)
+    pub fn set_intent_response_period(&mut self, intent_response_period: u64) {
+        self.assert_owner_or_governance();
+        assert!(intent_response_period > 0, "Intent response period must be greater than zero");
+
+        let old_value = self.intent_response_period;
+        self.intent_response_period = intent_response_period;
+
+        self.record_config_change("intent_response_period", json!(old_value), json!(intent_response_period));
+    }
+
+    // Update the default time agents have to finish an intent (in nanoseconds)
+    pub fn set_intent_deadline_period(&mut self, intent_deadline_period: u64) {
+        self.assert_owner_or_governance();
+        assert!(
+            intent_deadline_period > self.intent_response_period,
+            "Intent deadline period must exceed the response period"
+        );
+
+        let old_value = self.intent_deadline_period;
+        self.intent_deadline_period = intent_deadline_period;
+
+        self.record_config_change("intent_deadline_period", json!(old_value), json!(intent_deadline_period));
+    }
+
+    // Update the intent auction windows and how far reputation discounts bids; auctions
+    // already open keep their terms
+    pub fn set_bidding_config(&mut self, config: BiddingConfig) {
+        self.assert_owner_or_governance();
+        assert!(
+            config.commit_period >= MIN_BIDDING_PERIOD && config.commit_period <= MAX_BIDDING_PERIOD,
+            "Commit period out of range"
+        );
+        assert!(
+            config.reveal_period >= MIN_BIDDING_PERIOD && config.reveal_period <= MAX_BIDDING_PERIOD,
+            "Reveal period out of range"
+        );
+        assert!(config.reputation_weight_bps <= 10_000, "Reputation weight cannot exceed 10000 basis points");
+
+        let old_value = std::mem::replace(&mut self.bidding_config, config.clone());
+
+        self.record_config_change("bidding_config", json!(old_value), json!(config));
+    }
+
+    // Update how long clients can dispute a completed intent (in nanoseconds; 0 finalizes immediately)
+    pub fn set_intent_dispute_window(&mut self, intent_dispute_window: u64) {
+        self.assert_owner_or_governance();
+
+        let old_value = self.intent_dispute_window;
+        self.intent_dispute_window = intent_dispute_window;
+
+        self.record_config_change("intent_dispute_window", json!(old_value), json!(intent_dispute_window));
+    }
+
+    // Update how long an in-progress intent may go without a checkpoint before it counts as stalled
+    pub fn set_checkpoint_stall_period(&mut self, checkpoint_stall_period: u64) {
+        self.assert_owner_or_governance();
+        assert!(checkpoint_stall_period > 0, "Checkpoint stall period must be greater than zero");
+
+        let old_value = self.checkpoint_stall_period;
+        self.checkpoint_stall_period = checkpoint_stall_period;
+
+        self.record_config_change("checkpoint_stall_period", json!(old_value), json!(checkpoint_stall_period));
+    }
+
+    // Propose a new account to receive slashed tokens; applies after the timelock.
+    // Returns the queued action ID.
+    pub fn set_treasury(&mut self, treasury_id: AccountId) -> u64 {
+        self.assert_owner_or_governance();
+
+        self.internal_queue_action(TimelockedAction::SetTreasury { treasury_id })
+    }
+
+    // Propose the share of slashed tokens that is burned instead of sent to the treasury (basis
+    // points); applies after the timelock. Returns the queued action ID.
+    pub fn set_slash_burn_bps(&mut self, slash_burn_bps: u16) -> u64 {
+        self.assert_owner_or_governance();
+        assert!(slash_burn_bps <= 10_000, "Burn share cannot exceed 10000 basis points");
+
+        self.internal_queue_action(TimelockedAction::SetSlashBurnBps { slash_burn_bps })
+    }
+
+    // Update the protocol fee taken from escrow released to agents (basis points)
+    pub fn set_protocol_fee_bps(&mut self, protocol_fee_bps: u16) {
+        self.assert_owner_or_governance();
+        assert!(protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS, "Protocol fee exceeds maximum");
+
+        let old_value = self.protocol_fee_bps;
+        self.protocol_fee_bps = protocol_fee_bps;
+
+        self.record_config_change("protocol_fee_bps", json!(old_value), json!(protocol_fee_bps));
+    }
+
+    // Replace the protocol fee discount tiers. Tiers are checked in order and the first one the
+    // agent qualifies for applies.
+    pub fn set_fee_discount_tiers(&mut self, tiers: Vec<FeeDiscountTier>) {
+        self.assert_owner_or_governance();
+        assert!(tiers.len() <= MAX_FEE_DISCOUNT_TIERS, "Too many fee discount tiers");
+        for tier in &tiers {
+            assert!(tier.discount_bps <= 10_000, "Fee discount cannot exceed 10000 basis points");
+        }
+
+        let old_value = std::mem::replace(&mut self.fee_discount_tiers, tiers.clone());
+
+        self.record_config_change("fee_discount_tiers", json!(old_value), json!(tiers));
+    }
+
+    // Update how the protocol fee is divided between the treasury, insurance pool and referrers
+    pub fn set_fee_split(&mut self, fee_split: FeeSplit) {
+        self.assert_owner_or_governance();
+        fee_split.assert_valid();
+
+        let old_value = std::mem::replace(&mut self.fee_split, fee_split.clone());
+
+        self.record_config_change("fee_split", json!(old_value), json!(fee_split));
+    }
+
+    // Update the rolling-window usage quotas debited by consume_capability
+    pub fn set_usage_quota_config(&mut self, config: UsageQuotaConfig) {
+        self.assert_owner_or_governance();
+        assert!(
+            config.window > 0 && config.window <= MAX_QUOTA_WINDOW,
+            "Quota window must be between zero and the maximum"
+        );
+        assert!(config.quotas.len() <= MAX_USAGE_QUOTAS, "Too many usage quotas");
+        for (i, quota) in config.quotas.iter().enumerate() {
+            assert!(
+                !config.quotas[..i]
+                    .iter()
+                    .any(|other| other.trust_level == quota.trust_level && other.action_type == quota.action_type),
+                "Duplicate quota for {} at trust level {:?}",
+                quota.action_type,
+                quota.trust_level
+            );
+        }
+
+        let old_value = std::mem::replace(&mut self.usage_quota_config, config.clone());
+
+        self.record_config_change("usage_quota_config", json!(old_value), json!(config));
+    }
+
+    // Update how long capability attestations stay valid after issue (nanoseconds)
+    pub fn set_attestation_ttl(&mut self, attestation_ttl: u64) {
+        self.assert_owner_or_governance();
+        assert!(
+            attestation_ttl > 0 && attestation_ttl <= MAX_ATTESTATION_TTL,
+            "Attestation TTL must be between zero and the maximum"
+        );
+
+        let old_value = self.attestation_ttl;
+        self.attestation_ttl = attestation_ttl;
+
+        self.record_config_change("attestation_ttl", json!(old_value), json!(attestation_ttl));
+    }
+
+    // Update the ITLX rebated to evaluated agents per authorize_action call (0 disables rebates)
+    pub fn set_query_rebate_amount(&mut self, query_rebate_amount: U128) {
+        self.assert_owner_or_governance();
+
+        let old_value = self.query_rebate_amount;
+        self.query_rebate_amount = query_rebate_amount.0;
+
+        self.record_config_change("query_rebate_amount", json!(U128(old_value)), json!(query_rebate_amount));
+    }
+
+    // Update the curve used to weight feedback by reviewer standing
+    pub fn set_reviewer_weight_config(&mut self, config: ReviewerWeightConfig) {
+        self.assert_owner_or_governance();
+        assert!(config.base_weight > 0, "Base reviewer weight must be greater than zero");
+        assert!(config.agent_min_score <= 100, "Agent minimum score must be between 0 and 100");
+        assert!(
+            config.staked_weight <= MAX_REVIEWER_WEIGHT && config.agent_max_weight <= MAX_REVIEWER_WEIGHT,
+            "Reviewer weight exceeds maximum"
+        );
+
+        let old_value = std::mem::replace(&mut self.reviewer_weight_config, config.clone());
+
+        self.record_config_change("reviewer_weight_config", json!(old_value), json!(config));
+    }
+
+    // Switch how reviewer weights are derived. Feedback already submitted keeps its weight.
+    pub fn set_scoring_mode(&mut self, scoring_mode: ScoringMode) {
+        self.assert_owner_or_governance();
+
+        let old_value = std::mem::replace(&mut self.scoring_mode, scoring_mode);
+
+        self.record_config_change("scoring_mode", json!(old_value), json!(scoring_mode));
+    }
+
+    // Update how much feedback and violation history is kept per agent
+    pub fn set_retention_config(&mut self, config: RetentionConfig) {
+        self.assert_owner_or_governance();
+        assert!(config.max_retained_feedback > 0, "Retained feedback count must be greater than zero");
+        assert!(config.violation_retention_period > 0, "Violation retention period must be greater than zero");
+
+        let old_value = std::mem::replace(&mut self.retention_config, config.clone());
+
+        self.record_config_change("retention_config", json!(old_value), json!(config));
+    }
+
+    // Update how long stakes must be inactive, and how long after notice, before they can be
+    // moved to the treasury
+    pub fn set_escheatment_config(&mut self, config: EscheatmentConfig) {
+        self.assert_owner_or_governance();
+        assert!(config.dormancy_period >= MIN_DORMANCY_PERIOD, "Dormancy period too short");
+        assert!(config.notice_period >= MIN_ESCHEATMENT_NOTICE_PERIOD, "Escheatment notice period too short");
+
+        let old_value = std::mem::replace(&mut self.escheatment_config, config.clone());
+
+        self.record_config_change("escheatment_config", json!(old_value), json!(config));
+    }
+
+    // Update how delegated stake counts toward the stake bonus and how long undelegation takes
+    pub fn set_delegation_config(&mut self, config: DelegationConfig) {
+        self.assert_owner_or_governance();
+        assert!(config.stake_bonus_discount_bps <= 10_000, "Stake bonus discount cannot exceed 10000 bps");
+        assert!(config.undelegation_cooldown <= MAX_UNDELEGATION_COOLDOWN, "Undelegation cooldown too long");
+
+        let old_value = std::mem::replace(&mut self.delegation_config, config.clone());
+
+        self.record_config_change("delegation_config", json!(old_value), json!(config));
+    }
+
+    // Update the staking reward epoch length and payout rate. Epochs finished so far are paid
+    // out under the old settings first.
+    pub fn set_rewards_config(&mut self, config: RewardsConfig) {
+        self.assert_owner_or_governance();
+        assert!(config.epoch_length >= MIN_REWARD_EPOCH_LENGTH, "Reward epoch too short");
+
+        self.internal_distribute_rewards();
+        let old_value = std::mem::replace(&mut self.rewards_config, config.clone());
+        self.internal_reset_reward_epoch();
+
+        self.record_config_change("rewards_config", json!(old_value), json!(config));
+    }
+
+    // Update when score changes are recorded in the reputation history
+    pub fn set_history_config(&mut self, config: HistoryConfig) {
+        self.assert_owner_or_governance();
+        assert!(config.epoch_length > 0, "History epoch length must be greater than zero");
+        assert!(config.min_score_delta <= 100, "History score delta must be between 0 and 100");
+
+        let old_value = std::mem::replace(&mut self.history_config, config.clone());
+
+        self.record_config_change("history_config", json!(old_value), json!(config));
+    }
+
+    // Get the full contract configuration
+    pub fn get_config(&self) -> ContractConfigView {
+        ContractConfigView {
+            owner_id: self.owner_id.clone(),
+            token_contract_id: self.token_contract_id.clone(),
+            min_stake_amount: U128(self.min_stake_amount),
+            feedback_expiry_period: self.feedback_expiry_period,
+            near_ai_registry: self.near_ai_registry.clone(),
+            intents_processor: self.intents_processor.clone(),
+            intent_response_period: self.intent_response_period,
+            intent_deadline_period: self.intent_deadline_period,
+            intent_dispute_window: self.intent_dispute_window,
+            reviewer_weight_config: self.reviewer_weight_config.clone(),
+            scoring_mode: self.scoring_mode,
+            staking_rate_oracle: self.staking_rate_oracle.clone(),
+            checkpoint_stall_period: self.checkpoint_stall_period,
+            treasury_id: self.treasury_id.clone(),
+            slash_burn_bps: self.slash_burn_bps,
+            protocol_fee_bps: self.protocol_fee_bps,
+            query_rebate_amount: U128(self.query_rebate_amount),
+            history_config: self.history_config.clone(),
+            retention_config: self.retention_config.clone(),
+            chain_signer_id: self.chain_signer_id.clone(),
+            aurora_mirror: self.aurora_mirror.clone(),
+            governance_dao: self.governance_dao.clone(),
+            escheatment_config: self.escheatment_config.clone(),
+            delegation_config: self.delegation_config.clone(),
+            fee_discount_tiers: self.fee_discount_tiers.clone(),
+            rewards_config: self.rewards_config.clone(),
+            category_scoring: self.category_scoring.clone(),
+            bayesian_prior: self.bayesian_prior.clone(),
+            feedback_limit_config: self.feedback_limit_config.clone(),
+            feedback_amendment_window: self.feedback_amendment_window,
+            blind_rating_window: self.blind_rating_window,
+            agent_status_config: self.agent_status_config.clone(),
+            usage_quota_config: self.usage_quota_config.clone(),
+            attestation_ttl: self.attestation_ttl,
+            failure_weights: self.failure_weights.clone(),
+            stake_grace_period: self.stake_grace_period,
+            endorsement_config: self.endorsement_config.clone(),
+            max_task_delegation_depth: self.max_task_delegation_depth,
+            insurance_config: self.insurance_config.clone(),
+            fee_split: self.fee_split.clone(),
+            violation_policy: self.violation_policy.clone(),
+            performance_config: self.performance_config.clone(),
+            score_epoch_length: self.score_epoch_length,
+            penalty_decay_config: self.penalty_decay_config.clone(),
+            remediation_verifier: self.remediation_verifier.clone(),
+            bidding_config: self.bidding_config.clone(),
+        }
+    }
+
+    // Get every governance-settable parameter in one versioned document
+    pub fn get_full_config(&self) -> Value {
+        self.full_config_json()
+    }
+
+    // SHA-256 (hex) of the full config document, for detecting parameter drift
+    pub fn config_hash(&self) -> String {
+        let hash = env::sha256(self.full_config_json().to_string().as_bytes());
+        hash.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // Panic unless the caller is the owner or a governance member
+    pub(crate) fn assert_owner_or_governance(&self) {
+        let caller = env::predecessor_account_id();
+        assert!(
+            caller == self.owner_id || self.is_governance_member(caller),
+            "Unauthorized: only owner or governance members can perform this action"
+        );
+    }
+}
+
+impl AgentReputationContract {
+    // Bump the config nonce and emit a config-change event
+    pub(crate) fn record_config_change(&mut self, parameter: &str, old_value: Value, new_value: Value) {
+        self.config_nonce += 1;
+
+        emit_event(
+            "config_updated",
+            json!({
+                "parameter": parameter,
+                "old_value": old_value,
+                "new_value": new_value,
+                "updated_by": env::predecessor_account_id(),
+                "config_nonce": self.config_nonce,
+            }),
+        );
+    }
+
+    // Every governance-settable parameter as one JSON document. Keys are emitted in sorted
+    // order and registries sorted by key, so the serialized form is stable and can be hashed.
+    pub(crate) fn full_config_json(&self) -> Value {
+        json!({
+            "schema_version": FULL_CONFIG_SCHEMA_VERSION,
+            "config_nonce": self.config_nonce,
+            "state_version": self.state_version,
+            "addresses": {
+                "owner_id": self.owner_id,
+                "token_contract_id": self.token_contract_id,
+                "near_ai_registry": self.near_ai_registry,
+                "intents_processor": self.intents_processor,
+                "treasury_id": self.treasury_id,
+                "guardian_id": self.guardian_id,
+                "governance_dao": self.governance_dao,
+                "chain_signer_id": self.chain_signer_id,
+                "arbiters": sorted_members(self.arbiters.to_vec()),
+                "quota_dispatchers": sorted_members(self.quota_dispatchers.to_vec()),
+                "remediation_verifier": self.remediation_verifier,
+                "realm_authorities": sorted(self.realm_authorities.to_vec()),
+                "performance_oracles": sorted(self.performance_oracles.to_vec()),
+            },
+            "staking": {
+                "min_stake_amount": U128(self.min_stake_amount),
+                "query_rebate_amount": U128(self.query_rebate_amount),
+                "escheatment_config": self.escheatment_config,
+                "delegation_config": self.delegation_config,
+                "rewards_config": self.rewards_config,
+                "stake_grace_period": self.stake_grace_period,
+                "staking_tokens": sorted(self.staking_tokens.to_vec()),
+                "staking_rate_oracle": self.staking_rate_oracle,
+            },
+            "scoring": {
+                "feedback_expiry_period": self.feedback_expiry_period,
+                "reviewer_weight_config": self.reviewer_weight_config,
+                "scoring_mode": self.scoring_mode,
+                "history_config": self.history_config,
+                "retention_config": self.retention_config,
+                "category_scoring": self.category_scoring,
+                "bayesian_prior": self.bayesian_prior,
+                "feedback_limit_config": self.feedback_limit_config,
+                "feedback_amendment_window": self.feedback_amendment_window,
+                "blind_rating_window": self.blind_rating_window,
+                "failure_weights": self.failure_weights,
+                "endorsement_config": self.endorsement_config,
+                "performance_config": self.performance_config,
+                "score_epoch_length": self.score_epoch_length,
+            },
+            "intents": {
+                "intent_response_period": self.intent_response_period,
+                "intent_deadline_period": self.intent_deadline_period,
+                "intent_dispute_window": self.intent_dispute_window,
+                "checkpoint_stall_period": self.checkpoint_stall_period,
+                "protocol_fee_bps": self.protocol_fee_bps,
+                "fee_discount_tiers": self.fee_discount_tiers,
+                "fee_split": self.fee_split,
+                "intent_types": sorted(self.intent_types.to_vec()),
+                "bidding_config": self.bidding_config,
+            },
+            "capabilities": {
+                "capability_policy": policy::capability_policy_json(&self.capability_policy),
+                "usage_quota_config": self.usage_quota_config,
+                "attestation_ttl": self.attestation_ttl,
+                "max_task_delegation_depth": self.max_task_delegation_depth,
+            },
+            "privacy": {
+                "epsilon_milli": self.privacy_config.epsilon_milli,
+            },
+            "penalties": {
+                "slash_burn_bps": self.slash_burn_bps,
+                "agent_status_config": self.agent_status_config,
+                "insurance_config": self.insurance_config,
+                "violation_policy": self.violation_policy,
+                "penalty_decay_config": self.penalty_decay_config,
+            },
+            "chain_signatures": {
+                "evm_chain_budgets": sorted(self.evm_chain_budgets.to_vec()),
+            },
+            "cross_chain": {
+                "chain_policies": sorted(self.chain_policies.to_vec()),
+                "aurora_mirror": self.aurora_mirror,
+                "trusted_attestors": sorted(self.trusted_attestors.to_vec()),
+                "bridge_accounts": sorted_members(self.bridge_accounts.to_vec()),
+                "bridge_tokens": sorted(self.bridge_tokens.to_vec()),
+            },
+            "timelock": {
+                "timelock_delay": self.timelock_delay,
+            },
+            "paused_features": self.get_paused_features(),
+        })
+    }
+}
+
+// Version of the get_full_config document layout
+pub(crate) const FULL_CONFIG_SCHEMA_VERSION: u32 = 2;
+
+// Entries of a registry sorted by key, independent of the order they were added and removed in
+fn sorted<K: Ord, V>(mut entries: Vec<(K, V)>) -> Vec<(K, V)> {
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries
+}
+
+// Members of a set registry in sorted order
+fn sorted_members<K: Ord>(mut members: Vec<K>) -> Vec<K> {
+    members.sort();
+    members
+}
+
+// View struct exposing the contract configuration
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractConfigView {
+    owner_id: AccountId,
+    token_contract_id: AccountId,
+    min_stake_amount: U128,
+    feedback_expiry_period: u64,
+    near_ai_registry: Option<AccountId>,
+    intents_processor: Option<AccountId>,
+    intent_response_period: u64,
+    intent_deadline_period: u64,
+    intent_dispute_window: u64,
+    reviewer_weight_config: ReviewerWeightConfig,
+    scoring_mode: ScoringMode,
+    staking_rate_oracle: Option<AccountId>,
+    checkpoint_stall_period: u64,
+    treasury_id: AccountId,
+    slash_burn_bps: u16,
+    protocol_fee_bps: u16,
+    query_rebate_amount: U128,
+    history_config: HistoryConfig,
+    retention_config: RetentionConfig,
+    chain_signer_id: Option<AccountId>,
+    aurora_mirror: Option<AuroraMirrorConfig>,
+    governance_dao: Option<AccountId>,
+    escheatment_config: EscheatmentConfig,
+    delegation_config: DelegationConfig,
+    fee_discount_tiers: Vec<FeeDiscountTier>,
+    rewards_config: RewardsConfig,
+    category_scoring: Vec<CategoryScoringConfig>,
+    bayesian_prior: BayesianPrior,
+    feedback_limit_config: FeedbackLimitConfig,
+    feedback_amendment_window: u64,
+    blind_rating_window: u64,
+    agent_status_config: AgentStatusConfig,
+    usage_quota_config: UsageQuotaConfig,
+    attestation_ttl: u64,
+    failure_weights: FailureWeights,
+    stake_grace_period: u64,
+    endorsement_config: EndorsementConfig,
+    max_task_delegation_depth: u8,
+    insurance_config: InsuranceConfig,
+    fee_split: FeeSplit,
+    violation_policy: Vec<ViolationPolicyEntry>,
+    performance_config: PerformanceConfig,
+    score_epoch_length: u64,
+    penalty_decay_config: PenaltyDecayConfig,
+    remediation_verifier: Option<AccountId>,
+    bidding_config: BiddingConfig,
+}
+
+// Upper bound for any reviewer weight (basis points, 5x)
+const MAX_REVIEWER_WEIGHT: u32 = 50_000;
+
+// Upper bound on the prior's pseudo-count (basis points of reviewer weight, 100 reviews at 1x)
+pub(crate) const MAX_PRIOR_WEIGHT: u32 = 1_000_000;
+
+// Prior the feedback score is shrunk toward until feedback accumulates. The score is the
+// average of the agent's feedback and `prior_weight` worth of pseudo-feedback at `prior_mean`.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BayesianPrior {
+    // Pseudo-count in reviewer weight (basis points, 10000 = one review at 1x weight); 0 disables the prior
+    pub prior_weight: u32,
+    // Score the prior pulls toward (0-100)
+    pub prior_mean: u32,
+}
+
+impl Default for BayesianPrior {
+    fn default() -> Self {
+        Self {
+            prior_weight: 50_000, // Five reviews at 1x weight
+            prior_mean: 50,       // The neutral score new agents start at
+        }
+    }
+}
+
+impl BayesianPrior {
+    // Average a score (basis points) backed by `evidence_weight` of feedback with the prior
+    pub(crate) fn apply(&self, score_bps: u32, evidence_weight: u128) -> u32 {
+        let total_weight = evidence_weight + self.prior_weight as u128;
+        if total_weight == 0 {
+            return score_bps;
+        }
+        let prior_bps = from_points(self.prior_mean) as u128;
+        div_round(score_bps as u128 * evidence_weight + prior_bps * self.prior_weight as u128, total_weight) as u32
+    }
+
+    // Approximate 95% credible interval around a score backed by `evidence_weight` of
+    // feedback, treating the score as a proportion with the evidence and the prior as its
+    // observation count
+    pub(crate) fn interval(&self, score: u32, evidence_weight: u128) -> (u32, u32) {
+        let observations = evidence_weight + self.prior_weight as u128 + 10_000;
+        // Variance in score points squared times 1.96^2; `observations` is in basis points, so
+        // 1.96^2 * 10000 = 38416
+        let score = std::cmp::min(score, 100);
+        let variance = score as u128 * (100 - score) as u128 * 38_416 / observations;
+        let half_width = integer_sqrt(variance) as u32;
+        (score.saturating_sub(half_width), std::cmp::min(score + half_width, 100))
+    }
+}
+
+// How reviewer weights are derived when feedback is submitted
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+#[derive(Default)]
+pub enum ScoringMode {
+    // Weight steps up to the staked weight at the minimum stake, and with a reviewing agent's score
+    #[default]
+    Standard,
+    // Weight grows with the square root of the reviewer's stake: the staked weight at the
+    // minimum stake, twice its increase over the base weight at four times the minimum, and so
+    // on up to the agent maximum weight, so large holders cannot buy proportional influence
+    Quadratic,
+}
+
+
+// Weighting curve for feedback based on the reviewer (all weights in basis points, 10000 = 1x)
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReviewerWeightConfig {
+    // Weight for accounts with no stake and no agent record
+    pub base_weight: u32,
+    // Weight for reviewers holding at least the minimum stake
+    pub staked_weight: u32,
+    // Weight for a registered agent reviewer with a perfect score
+    pub agent_max_weight: u32,
+    // Minimum score for a registered agent to receive agent weighting
+    pub agent_min_score: u32,
+}
+
+impl ReviewerWeightConfig {
+    // Weight under quadratic scoring: the base weight plus the staked weight's increase over
+    // it, scaled by the square root of the stake in minimum stakes, capped at the agent maximum
+    pub(crate) fn quadratic_weight(&self, stake: Balance, min_stake: Balance) -> u32 {
+        if stake == 0 || min_stake == 0 {
+            return self.base_weight;
+        }
+        // Square root of the stake as a multiple of the minimum, in basis points
+        let root_bps = integer_sqrt(stake.saturating_mul(100_000_000) / min_stake);
+        let increase = self.staked_weight.saturating_sub(self.base_weight) as u128 * root_bps / 10_000;
+        let cap = std::cmp::max(self.agent_max_weight, self.base_weight) as u128;
+        std::cmp::min(self.base_weight as u128 + increase, cap) as u32
+    }
+}
+
+impl Default for ReviewerWeightConfig {
+    fn default() -> Self {
+        Self {
+            base_weight: 5_000,
+            staked_weight: 10_000,
+            agent_max_weight: 20_000,
+            agent_min_score: 51,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::*;
+    use near_sdk::serde_json::json;
+
+    #[test]
+    fn full_config_holds_the_default_capability_policy() {
+        let contract = setup();
+        let config = contract.get_full_config();
+        let master = &config["capabilities"]["capability_policy"][0];
+        assert_eq!(master["name"], "master");
+        assert_eq!(master["limits"]["max_transaction_value"], u128::MAX.to_string());
+        assert_eq!(contract.config_hash().len(), 64);
+    }
+
+    #[test]
+    fn registries_are_listed_in_key_order() {
+        let mut contract = setup();
+        let nonce = contract.config_nonce;
+        for arbiter in ["c.test", "a.test", "b.test"] {
+            contract.add_arbiter(account(arbiter));
+        }
+        contract.remove_arbiter(account("a.test"));
+        contract.set_realm_authority("zeta".to_string(), Some(account("z.test")));
+        contract.set_realm_authority("alpha".to_string(), Some(account("a.test")));
+
+        let config = contract.get_full_config();
+        assert_eq!(config["schema_version"], 2);
+        // Every registry change counts as a config change
+        assert_eq!(config["config_nonce"], nonce + 6);
+        assert_eq!(config["addresses"]["arbiters"], json!(["b.test", "c.test"]));
+        assert_eq!(
+            config["addresses"]["realm_authorities"],
+            json!([["alpha", "a.test"], ["zeta", "z.test"]])
+        );
+    }
+}
@@ -1,94 +1,712 @@
-use near_sdk::{env, Promise, PromiseResult};
+use crate::*;
+#[cfg(feature = "cross-chain")]
+use crate::chain_signatures::{from_hex, to_hex, GAS_FOR_CHAIN_SIGNATURE, GAS_FOR_SIGNATURE_CALLBACK};
+#[cfg(feature = "cross-chain")]
+use crate::events::emit_event;
+#[cfg(feature = "cross-chain")]
+use crate::specializations::normalize_tag;
+use near_sdk::json_types::Base64VecU8;
+#[cfg(feature = "cross-chain")]
+use near_sdk::serde_json::Value;
 
+// Oldest attestation accepted for import (1 day, in nanoseconds)
+#[cfg(feature = "cross-chain")]
+const MAX_ATTESTATION_AGE: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+// Source chain named in exported attestations
+#[cfg(feature = "cross-chain")]
+const EXPORT_SOURCE_CHAIN: &str = "near";
+
+// Export signing key: the MPC signer derives it from this contract's account ID, this path and
+// this key version. Every export is signed with the same key, so a verifier pins the single
+// Ethereum address derived from it.
+#[cfg(feature = "cross-chain")]
+const EXPORT_SIGNING_PATH: &str = "reputation-export";
+#[cfg(feature = "cross-chain")]
+const EXPORT_SIGNING_KEY_VERSION: u32 = 0;
+
+// Import records kept per agent; older records are dropped first
+#[cfg(feature = "cross-chain")]
+const MAX_IMPORT_RECORDS: usize = 20;
+
+// Age at which a side's weight halves in a TimeDecayedBlend merge (30 days, in nanoseconds)
+#[cfg(feature = "cross-chain")]
+const MERGE_DECAY_HALF_LIFE: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+// Maximum number of trusted attestors per source chain
+#[cfg(feature = "cross-chain")]
+const MAX_ATTESTORS_PER_CHAIN: usize = 10;
+
+// Public key of an attestor trusted to sign reputation exported from a source chain
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum AttestorKey {
+    // 32-byte ed25519 public key
+    Ed25519(Base64VecU8),
+    // 64-byte uncompressed secp256k1 public key (without the 0x04 prefix)
+    Secp256k1(Base64VecU8),
+}
+
+// Governance settings for reputation imported from a source chain; chains without a policy
+// cannot be imported from
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ChainPolicy {
+    // Whether imports from the chain are currently accepted
+    pub enabled: bool,
+    // Share of the imported score that counts (basis points, 10000 = 100%)
+    pub trust_weight_bps: u16,
+    // Imported scores are capped at this value before weighting
+    pub max_importable_score: u32,
+    // How imported scores are combined with the agent's existing score
+    #[serde(default)]
+    pub merge_strategy: MergeStrategy,
+}
+
+// How an imported score is combined with the agent's existing score
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, PartialEq, Clone, Copy, Debug, Default)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum MergeStrategy {
+    // Average weighted by each side's interaction count
+    #[default]
+    WeightedByInteractions,
+    // Keep the higher of the two scores
+    MaxOf,
+    // Average weighted by recency, halving each side's weight every MERGE_DECAY_HALF_LIFE
+    TimeDecayedBlend,
+}
+
+// Audit entry recording one cross-chain import into an agent's record
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ImportRecord {
+    pub(crate) source_chain: String,
+    pub(crate) nonce: u64,
+    pub(crate) strategy: MergeStrategy,
+    // Score in the attestation, before the chain's cap and trust weight
+    pub(crate) attested_score: u32,
+    // Score after the chain's cap and trust weight, as merged
+    pub(crate) imported_score: u32,
+    // Local score the import was merged into, without any earlier import from the chain
+    pub(crate) score_before: u32,
+    pub(crate) score_after: u32,
+    pub(crate) total_interactions: u64,
+    pub(crate) successful_interactions: u64,
+    pub(crate) timestamp: u64,
+}
+
+// Signed reputation attestation submitted for import
+#[derive(near_sdk::serde::Serialize, near_sdk::serde::Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CrossChainProof {
+    // Borsh-encoded CrossChainReputation
+    pub payload: Base64VecU8,
+    // ed25519: 64-byte signature over the payload. secp256k1: 65-byte r || s || v signature
+    // over keccak256 of the payload.
+    pub signature: Base64VecU8,
+}
+
+// Reputation export attestation produced by export_reputation
+#[derive(near_sdk::serde::Serialize, near_sdk::serde::Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReputationExport {
+    // Export nonce, increasing across all exports
+    pub nonce: u64,
+    // Borsh-encoded CrossChainReputation
+    pub payload: Base64VecU8,
+    // keccak256 of the payload
+    pub payload_hash: Base64VecU8,
+}
+
+// Reputation export with the MPC signer's response, None if signing failed
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SignedReputationExport {
+    pub export: ReputationExport,
+    // The payload signed for EVM chains: ABI-encoded (bytes32 agentKey, uint32 score, uint64
+    // nonce, uint64 timestamp), see evm_export_payload
+    pub evm_payload: Base64VecU8,
+    pub signature: Option<near_sdk::serde_json::Value>,
+    // The signature as 0x-prefixed r || s || v, for ecrecover
+    pub evm_signature: Option<String>,
+}
+
+// Reputation export signed by the MPC signer, kept for get_signed_export
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SignedExport {
+    agent_id: AccountId,
+    // ABI-encoded (bytes32 agentKey, uint32 score, uint64 nonce, uint64 timestamp)
+    payload: Vec<u8>,
+    // 65-byte r || s || v signature over keccak256 of the payload, v being 27 or 28
+    signature: Vec<u8>,
+    signed_at: u64,
+}
+
+#[cfg(feature = "cross-chain")]
+#[near_bindgen]
 impl AgentReputationContract {
-    // Import reputation from another chain via Omni Bridge
-    pub fn import_cross_chain_reputation(&mut self, agent_id: AccountId, source_chain: String, proof_data: String) {
+    // Import reputation from another chain into a registered agent's record, signed by one of
+    // the chain's trusted attestors. A new import from a chain replaces the previous one from
+    // it rather than compounding on top of it.
+    pub fn import_cross_chain_reputation(&mut self, agent_id: AccountId, source_chain: String, proof: CrossChainProof) {
         // Only contract owner or the agent itself can import reputation
         assert!(
-            env::predecessor_account_id() == self.owner_id || 
+            env::predecessor_account_id() == self.owner_id ||
             env::predecessor_account_id() == agent_id,
             "Unauthorized"
         );
-        
-        // Here you would verify the proof_data from the Omni Bridge
-        // This is a simplified placeholder - you'd need to implement the actual verification
-        let verified_data = self.verify_cross_chain_data(source_chain, proof_data);
-        
-        if let Some(reputation_data) = verified_data {
-            // Update or create agent reputation
-            if self.agent_reputations.contains_key(&agent_id) {
-                let mut agent_rep = self.agent_reputations.get(&agent_id).unwrap();
-                
-                // Merge the imported reputation with existing data
-                // This is a simple approach - you might want a more sophisticated merging algorithm
-                agent_rep.score = (agent_rep.score + reputation_data.score) / 2;
-                
-                // Update other fields as needed
-                self.agent_reputations.insert(&agent_id, &agent_rep);
-            } else {
-                // Create new agent with imported reputation
-                let agent_reputation = AgentReputation {
-                    score: reputation_data.score,
-                    total_interactions: reputation_data.total_interactions,
-                    successful_interactions: reputation_data.successful_interactions,
-                    feedback_history: Vec::new(), // Don't import specific feedback entries
-                    last_update: env::block_timestamp(),
-                    specializations: reputation_data.specializations,
-                };
-                
-                self.agent_reputations.insert(&agent_id, &agent_reputation);
+        self.assert_reputation_portable(&agent_id);
+
+        let policy = self.chain_policies.get(&source_chain).expect("Source chain not supported");
+        assert!(policy.enabled, "Imports from this source chain are disabled");
+
+        let reputation_data = self.verify_cross_chain_data(&agent_id, &source_chain, &proof);
+        let imported_score = std::cmp::min(reputation_data.score, policy.max_importable_score)
+            * policy.trust_weight_bps as u32
+            / 10_000;
+
+        // Consume the nonce before merging so the same attestation cannot be replayed
+        self.cross_chain_nonces.insert(&cross_chain_nonce_key(&source_chain, &agent_id), &reputation_data.nonce);
+
+        // Merge into the existing record
+        let now = env::block_timestamp();
+        let mut agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+
+        // An earlier import from the same chain is superseded: its score change and interaction
+        // counts are taken back out before the new one is merged
+        let mut score_before = agent_rep.score;
+        if let Some(previous) = agent_rep.import_history.iter().rev().find(|r| r.source_chain == source_chain) {
+            let previous_change = previous.score_after as i64 - previous.score_before as i64;
+            score_before = (score_before as i64 - previous_change).clamp(0, 100) as u32;
+            agent_rep.total_interactions = agent_rep.total_interactions.saturating_sub(previous.total_interactions);
+            agent_rep.successful_interactions =
+                agent_rep.successful_interactions.saturating_sub(previous.successful_interactions);
+        }
+
+        let merged_score = if agent_rep.total_interactions == 0 && agent_rep.feedback_count == 0 {
+            // Nothing local to merge with
+            imported_score
+        } else {
+            merge_scores(
+                policy.merge_strategy,
+                (score_before, agent_rep.total_interactions, agent_rep.last_update),
+                (imported_score, reputation_data.total_interactions, reputation_data.timestamp),
+                now,
+            )
+        };
+        agent_rep.set_score(merged_score);
+        agent_rep.total_interactions += reputation_data.total_interactions;
+        agent_rep.successful_interactions += reputation_data.successful_interactions;
+
+        for tag in reputation_data.specializations {
+            let normalized = normalize_tag(&tag);
+            if !agent_rep.specializations.iter().any(|t| normalize_tag(t) == normalized) {
+                agent_rep.specializations.push(tag);
             }
         }
+
+        let record = ImportRecord {
+            source_chain: source_chain.clone(),
+            nonce: reputation_data.nonce,
+            strategy: policy.merge_strategy,
+            attested_score: reputation_data.score,
+            imported_score,
+            score_before,
+            score_after: agent_rep.score,
+            total_interactions: reputation_data.total_interactions,
+            successful_interactions: reputation_data.successful_interactions,
+            timestamp: now,
+        };
+        push_import_record(&mut agent_rep.import_history, record.clone());
+        agent_rep.last_update = now;
+        self.internal_save_agent(&agent_id, &agent_rep);
+
+        emit_event(
+            "cross_chain_reputation_imported",
+            json!({
+                "agent_id": agent_id,
+                "record": record,
+            }),
+        );
     }
-    
-    // Verify cross-chain data (placeholder)
-    fn verify_cross_chain_data(&self, source_chain: String, proof_data: String) -> Option<CrossChainReputation> {
-        // In a real implementation, you would:
-        // 1. Verify the proof using Omni Bridge verification mechanisms
-        // 2. Decode the proof data to extract reputation information
-        // 3. Return the verified reputation data or None if verification fails
-        
-        // This is a simplified placeholder
+
+    // Get the audit trail of cross-chain imports into an agent's record, oldest first
+    pub fn get_import_history(&self, agent_id: AccountId) -> Vec<ImportRecord> {
+        self.agent_reputations
+            .get(&agent_id)
+            .map(|rep| rep.import_history)
+            .unwrap_or_default()
+    }
+
+    // Verify that a proof carries a fresh attestation for this agent and chain, signed by one of
+    // the chain's trusted attestors, and decode it
+    fn verify_cross_chain_data(&self, agent_id: &AccountId, source_chain: &str, proof: &CrossChainProof) -> CrossChainReputation {
+        let attestors = self.trusted_attestors.get(&source_chain.to_string()).unwrap_or_default();
+        assert!(!attestors.is_empty(), "No trusted attestors for source chain");
+        assert!(
+            attestors.iter().any(|key| signature_matches(key, &proof.payload.0, &proof.signature.0)),
+            "Invalid attestation signature"
+        );
+
+        let data = CrossChainReputation::try_from_slice(&proof.payload.0)
+            .unwrap_or_else(|_| env::panic_str("Malformed attestation payload"));
+        assert_eq!(&data.agent_id, agent_id, "Attestation is for a different agent");
+        assert_eq!(data.source_chain, source_chain, "Attestation is for a different source chain");
+        assert!(data.score <= 100, "Attested score out of range");
+        assert!(
+            data.successful_interactions <= data.total_interactions,
+            "Attested interaction counts are inconsistent"
+        );
+
+        // Replay protection: nonces must increase per agent and chain, and attestations expire
+        let last_nonce = self.cross_chain_nonces.get(&cross_chain_nonce_key(source_chain, agent_id));
+        assert!(last_nonce.is_none_or(|last| data.nonce > last), "Attestation nonce already used");
+        let now = env::block_timestamp();
+        assert!(data.timestamp <= now, "Attestation timestamp is in the future");
+        assert!(now - data.timestamp <= MAX_ATTESTATION_AGE, "Attestation has expired");
+
+        data
+    }
+
+    // Set or remove (None) the import policy of a source chain (owner or governance)
+    pub fn set_chain_policy(&mut self, source_chain: String, policy: Option<ChainPolicy>) {
+        self.assert_owner_or_governance();
+
+        let old_value = match &policy {
+            Some(policy) => {
+                assert!(policy.trust_weight_bps <= 10_000, "Trust weight cannot exceed 10000 bps");
+                assert!(policy.max_importable_score <= 100, "Max importable score cannot exceed 100");
+                self.chain_policies.insert(&source_chain, policy)
+            },
+            None => self.chain_policies.remove(&source_chain),
+        };
+
+        self.record_config_change(
+            &format!("chain_policy.{}", source_chain),
+            json!(old_value),
+            json!(policy),
+        );
+    }
+
+    // Get every source chain with an import policy, enabled or not
+    pub fn list_supported_chains(&self) -> Vec<(String, ChainPolicy)> {
+        self.chain_policies.to_vec()
+    }
+
+    // Trust an attestor key for reputation imported from a source chain (owner only)
+    pub fn add_trusted_attestor(&mut self, source_chain: String, key: AttestorKey) {
+        assert_eq!(env::predecessor_account_id(), self.owner_id, "Only owner can manage attestors");
+        let (public_key, expected_length) = match &key {
+            AttestorKey::Ed25519(public_key) => (public_key, 32),
+            AttestorKey::Secp256k1(public_key) => (public_key, 64),
+        };
+        assert_eq!(public_key.0.len(), expected_length, "Invalid attestor public key length");
+
+        let mut attestors = self.trusted_attestors.get(&source_chain).unwrap_or_default();
+        assert!(!attestors.contains(&key), "Attestor already trusted for this chain");
+        assert!(attestors.len() < MAX_ATTESTORS_PER_CHAIN, "Too many attestors for this chain");
+        let old_value = json!(attestors);
+        attestors.push(key);
+        self.trusted_attestors.insert(&source_chain, &attestors);
+
+        self.record_config_change(&format!("trusted_attestors.{}", source_chain), old_value, json!(attestors));
+    }
+
+    // Stop trusting an attestor key for a source chain (owner only)
+    pub fn remove_trusted_attestor(&mut self, source_chain: String, key: AttestorKey) {
+        assert_eq!(env::predecessor_account_id(), self.owner_id, "Only owner can manage attestors");
+
+        let mut attestors = self.trusted_attestors.get(&source_chain).unwrap_or_default();
+        let index = attestors.iter().position(|k| k == &key).expect("Attestor not trusted for this chain");
+        let old_value = json!(attestors);
+        attestors.remove(index);
+        if attestors.is_empty() {
+            self.trusted_attestors.remove(&source_chain);
+        } else {
+            self.trusted_attestors.insert(&source_chain, &attestors);
+        }
+
+        self.record_config_change(&format!("trusted_attestors.{}", source_chain), old_value, json!(attestors));
+    }
+
+    // Get the attestor keys trusted for a source chain
+    pub fn get_trusted_attestors(&self, source_chain: String) -> Vec<AttestorKey> {
+        self.trusted_attestors.get(&source_chain).unwrap_or_default()
+    }
+
+    // Get the last attestation nonce imported for an agent from a source chain
+    pub fn get_cross_chain_nonce(&self, agent_id: AccountId, source_chain: String) -> Option<u64> {
+        self.cross_chain_nonces.get(&cross_chain_nonce_key(&source_chain, &agent_id))
+    }
+
+    // Export the calling agent's reputation as a Borsh-encoded CrossChainReputation attestation.
+    // The payload's keccak256 hash is recorded under a new export nonce and logged, so relayers
+    // and other contracts can check it with verify_export.
+    pub fn export_reputation(&mut self) -> ReputationExport {
+        let agent_id = env::predecessor_account_id();
+        let agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+        self.assert_reputation_portable(&agent_id);
+
+        let nonce = self.next_export_nonce;
+        self.next_export_nonce += 1;
+
+        // Export the score finalized at the last epoch, falling back to the live score for
+        // agents never finalized
+        let (epoch, score) = match self.internal_finalized_score(&agent_id) {
+            Some((epoch, score)) => (Some(epoch), score),
+            None => (None, agent_rep.score),
+        };
+        let payload = CrossChainReputation {
+            agent_id: agent_id.clone(),
+            score,
+            total_interactions: agent_rep.total_interactions,
+            successful_interactions: agent_rep.successful_interactions,
+            specializations: agent_rep.specializations,
+            source_chain: EXPORT_SOURCE_CHAIN.to_string(),
+            timestamp: env::block_timestamp(),
+            nonce,
+            epoch,
+        }
+        .try_to_vec()
+        .expect("Failed to serialize export payload");
+        let payload_hash = env::keccak256(&payload);
+        self.export_hashes.insert(&nonce, &payload_hash);
+
+        let export = ReputationExport {
+            nonce,
+            payload: payload.into(),
+            payload_hash: payload_hash.into(),
+        };
+
+        emit_event(
+            "reputation_exported",
+            json!({
+                "agent_id": agent_id,
+                "nonce": export.nonce,
+                "payload": export.payload,
+                "payload_hash": export.payload_hash,
+            }),
+        );
+
+        export
+    }
+
+    // Export the calling agent's reputation and have the MPC signer sign it with the contract's
+    // export key, for EVM chains. The signed payload is the export's ABI encoding rather than
+    // its Borsh encoding, so a Solidity verifier can decode it and ecrecover its keccak256 hash
+    // directly. Any deposit is forwarded to the signer as its fee.
+    #[payable]
+    pub fn export_reputation_signed(&mut self) -> Promise {
+        let signer_id = self.chain_signer_id.clone().expect("Chain signatures are not configured");
+        let export = self.export_reputation();
+        let attestation = CrossChainReputation::try_from_slice(&export.payload.0).expect("Invalid export payload");
+        let evm_payload = evm_export_payload(&attestation);
+        let deposit = env::attached_deposit();
+
+        Promise::new(signer_id)
+            .function_call(
+                "sign".to_string(),
+                json!({
+                    "request": {
+                        "payload": env::keccak256(&evm_payload),
+                        "path": EXPORT_SIGNING_PATH,
+                        "key_version": EXPORT_SIGNING_KEY_VERSION,
+                    }
+                }).to_string().into_bytes(),
+                deposit,
+                GAS_FOR_CHAIN_SIGNATURE,
+            )
+            .then(
+                Promise::new(env::current_account_id()).function_call(
+                    "on_export_signed".to_string(),
+                    json!({
+                        "agent_id": env::predecessor_account_id(),
+                        "export": export,
+                        "evm_payload": Base64VecU8::from(evm_payload),
+                        "deposit": U128(deposit),
+                    }).to_string().into_bytes(),
+                    0,
+                    GAS_FOR_SIGNATURE_CALLBACK,
+                ),
+            )
+    }
+
+    // Callback pairing the export with the MPC signature and storing it for get_signed_export.
+    // The unsigned export stays valid through verify_export if signing failed.
+    #[private]
+    pub fn on_export_signed(
+        &mut self,
+        agent_id: AccountId,
+        export: ReputationExport,
+        evm_payload: Base64VecU8,
+        deposit: U128,
+    ) -> SignedReputationExport {
+        let signature = match env::promise_result(0) {
+            PromiseResult::Successful(result) => near_sdk::serde_json::from_slice::<Value>(&result).ok(),
+            _ => None,
+        };
+        let evm_signature = signature.as_ref().and_then(evm_signature_from_mpc);
+
+        match &evm_signature {
+            Some(evm_signature) => {
+                // The agent pays for keeping its signed export; this runs in a callback and must
+                // not fail, so an overdrawn balance only blocks the agent's later writes
+                let initial_storage = env::storage_usage();
+                self.signed_exports.insert(
+                    &export.nonce,
+                    &SignedExport {
+                        agent_id: agent_id.clone(),
+                        payload: evm_payload.0.clone(),
+                        signature: evm_signature.to_vec(),
+                        signed_at: env::block_timestamp(),
+                    },
+                );
+                self.internal_track_storage(&agent_id, initial_storage);
+            },
+            None => {
+                if deposit.0 > 0 {
+                    Promise::new(agent_id.clone()).transfer(deposit.0);
+                }
+            },
+        }
+        let evm_signature = evm_signature.map(|evm_signature| to_hex(&evm_signature));
+
+        emit_event(
+            "reputation_export_signed",
+            json!({
+                "agent_id": agent_id,
+                "nonce": export.nonce,
+                "signature": signature,
+                "evm_signature": evm_signature,
+            }),
+        );
+
+        SignedReputationExport { export, evm_payload, signature, evm_signature }
+    }
+
+    // Get an agent's export signed by the MPC signer, ready for ecrecover on an EVM chain
+    pub fn get_signed_export(&self, agent_id: AccountId, nonce: u64) -> Option<SignedExportView> {
+        let signed = self.signed_exports.get(&nonce).filter(|signed| signed.agent_id == agent_id)?;
+        Some(SignedExportView {
+            agent_id,
+            nonce,
+            payload_hash: to_hex(&env::keccak256(&signed.payload)),
+            payload: signed.payload.into(),
+            r: to_hex(&signed.signature[..32]),
+            s: to_hex(&signed.signature[32..64]),
+            v: signed.signature[64],
+            signature: to_hex(&signed.signature),
+            signed_at: signed.signed_at,
+        })
+    }
+
+    // Get the MPC signer and derivation path exports are signed with; the signing key is
+    // derived from them and this contract's account ID
+    pub fn get_export_signer(&self) -> ExportSignerView {
+        ExportSignerView {
+            signer_id: self.chain_signer_id.clone(),
+            predecessor_id: env::current_account_id(),
+            path: EXPORT_SIGNING_PATH.to_string(),
+            key_version: EXPORT_SIGNING_KEY_VERSION,
+        }
+    }
+
+    // Whether `payload` is exactly the reputation export recorded under `nonce`
+    pub fn verify_export(&self, payload: Base64VecU8, nonce: u64) -> bool {
+        self.export_hashes
+            .get(&nonce)
+            .is_some_and(|hash| hash == env::keccak256(&payload.0))
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Why an agent's reputation cannot currently leave or be synced into this contract, or
+    // None if it is portable. Pending intent disputes and open manipulation audits lock it,
+    // so a clean score cannot be carried elsewhere just before a penalty lands.
+    pub fn get_portability_lock(&self, agent_id: AccountId) -> Option<String> {
+        self.internal_portability_lock(&agent_id)
+    }
+
+    // Panic if the agent's reputation is locked by an ongoing investigation
+    #[cfg(feature = "cross-chain")]
+    pub(crate) fn assert_reputation_portable(&self, agent_id: &AccountId) {
+        if let Some(reason) = self.internal_portability_lock(agent_id) {
+            env::panic_str(&format!("Reputation is locked: {}", reason));
+        }
+    }
+
+    pub(crate) fn internal_portability_lock(&self, agent_id: &AccountId) -> Option<String> {
+        if self.internal_open_audit_from_index(agent_id).is_some() {
+            return Some("open manipulation audit".to_string());
+        }
+        let pending_disputes = self
+            .agent_reputations
+            .get(agent_id)
+            .map_or(0, |agent_rep| agent_rep.dispute_stats.pending());
+        if pending_disputes > 0 {
+            return Some(format!("{} pending intent dispute(s)", pending_disputes));
+        }
         None
     }
-    
-    // Export reputation to another chain
-    pub fn export_reputation(&self, agent_id: AccountId) -> String {
-        // Check if agent exists
-        assert!(self.agent_reputations.contains_key(&agent_id), "Agent not registered");
-        
-        // Only the agent itself can export its reputation
-        assert_eq!(env::predecessor_account_id(), agent_id, "Only agent can export its reputation");
-        
-        let agent_rep = self.agent_reputations.get(&agent_id).unwrap();
-        
-        // Create exportable reputation data
-        let export_data = json!({
-            "agent_id": agent_id,
-            "score": agent_rep.score,
-            "total_interactions": agent_rep.total_interactions,
-            "successful_interactions": agent_rep.successful_interactions,
-            "specializations": agent_rep.specializations,
-            "timestamp": env::block_timestamp(),
-            "source_chain": "near",
-            "contract_id": env::current_account_id()
-        }).to_string();
-        
-        // In a real implementation, you would:
-        // 1. Sign this data with the contract's key
-        // 2. Format it for Omni Bridge compatibility
-        
-        export_data
-    }
-}
-
-// Structure for cross-chain reputation data
+}
+
+// Combine a local and an imported (score, interactions, timestamp) under a merge strategy
+#[cfg(feature = "cross-chain")]
+fn merge_scores(strategy: MergeStrategy, local: (u32, u64, u64), imported: (u32, u64, u64), now: u64) -> u32 {
+    let (local_score, local_interactions, local_updated) = local;
+    let (imported_score, imported_interactions, imported_at) = imported;
+
+    let (local_weight, imported_weight) = match strategy {
+        MergeStrategy::MaxOf => return std::cmp::max(local_score, imported_score),
+        MergeStrategy::WeightedByInteractions => (local_interactions as u128, imported_interactions as u128),
+        MergeStrategy::TimeDecayedBlend => (
+            decay_weight(now.saturating_sub(local_updated)),
+            decay_weight(now.saturating_sub(imported_at)),
+        ),
+    };
+
+    if local_weight + imported_weight == 0 {
+        return (local_score + imported_score) / 2;
+    }
+    ((local_score as u128 * local_weight + imported_score as u128 * imported_weight) / (local_weight + imported_weight)) as u32
+}
+
+// Weight (out of 10000) of a side `age` nanoseconds old, halving every half-life and
+// interpolated linearly between halvings
+#[cfg(feature = "cross-chain")]
+fn decay_weight(age: u64) -> u128 {
+    let halvings = age / MERGE_DECAY_HALF_LIFE;
+    if halvings >= 14 {
+        return 0;
+    }
+    let upper = 10_000u128 >> halvings;
+    let lower = upper >> 1;
+    upper - (upper - lower) * (age % MERGE_DECAY_HALF_LIFE) as u128 / MERGE_DECAY_HALF_LIFE as u128
+}
+
+// Append an import record, dropping the oldest superseded record (same chain imported again
+// later) once the history is full, or the oldest record if none is superseded
+#[cfg(feature = "cross-chain")]
+fn push_import_record(history: &mut Vec<ImportRecord>, record: ImportRecord) {
+    history.push(record);
+    if history.len() > MAX_IMPORT_RECORDS {
+        let superseded = (0..history.len()).find(|&i| {
+            history[i + 1..].iter().any(|later| later.source_chain == history[i].source_chain)
+        });
+        history.remove(superseded.unwrap_or(0));
+    }
+}
+
+#[cfg(feature = "cross-chain")]
+fn cross_chain_nonce_key(source_chain: &str, agent_id: &AccountId) -> String {
+    format!("{}:{}", source_chain, agent_id)
+}
+
+// Whether `signature` over `payload` was produced by the attestor key
+#[cfg(feature = "cross-chain")]
+fn signature_matches(key: &AttestorKey, payload: &[u8], signature: &[u8]) -> bool {
+    match key {
+        AttestorKey::Ed25519(public_key) => {
+            match (<&[u8; 64]>::try_from(signature), <&[u8; 32]>::try_from(public_key.0.as_slice())) {
+                (Ok(signature), Ok(public_key)) => crate::oracles::ed25519_verify(signature, payload, public_key),
+                _ => false,
+            }
+        },
+        AttestorKey::Secp256k1(public_key) => {
+            if signature.len() != 65 {
+                return false;
+            }
+            // Accept both raw (0/1) and Ethereum-style (27/28) recovery IDs
+            let v = signature[64];
+            let recovery_id = if v >= 27 { v - 27 } else { v };
+            let hash = env::keccak256(payload);
+            env::ecrecover(&hash, &signature[..64], recovery_id, true)
+                .is_some_and(|recovered| recovered.as_slice() == public_key.0.as_slice())
+        },
+    }
+}
+
+// Ethereum r || s || v signature from the MPC signer's response, {"big_r": {"affine_point":
+// compressed R in hex}, "s": {"scalar": hex}, "recovery_id": 0 or 1}
+#[cfg(feature = "cross-chain")]
+fn evm_signature_from_mpc(response: &Value) -> Option<[u8; 65]> {
+    let big_r = from_hex(response.get("big_r")?.get("affine_point")?.as_str()?)?;
+    let s = from_hex(response.get("s")?.get("scalar")?.as_str()?)?;
+    let recovery_id = response.get("recovery_id")?.as_u64()?;
+    if big_r.len() != 33 || s.len() != 32 || recovery_id > 1 {
+        return None;
+    }
+
+    // r is the x coordinate of R, after the compressed point's parity byte
+    let mut signature = [0u8; 65];
+    signature[..32].copy_from_slice(&big_r[1..]);
+    signature[32..64].copy_from_slice(&s);
+    signature[64] = 27 + recovery_id as u8;
+    Some(signature)
+}
+
+// Export payload signed for EVM chains: the ABI encoding of (bytes32 agentKey, uint32 score,
+// uint64 nonce, uint64 timestamp), each value left-padded to a 32-byte word. agentKey is
+// keccak256 of the agent's account ID and the timestamp is in seconds, as EVM contracts
+// compare it with block.timestamp.
+#[cfg(feature = "cross-chain")]
+fn evm_export_payload(attestation: &CrossChainReputation) -> Vec<u8> {
+    let mut payload = env::keccak256(attestation.agent_id.as_bytes());
+    for value in [attestation.score as u64, attestation.nonce, attestation.timestamp / 1_000_000_000] {
+        payload.extend_from_slice(&[0u8; 24]);
+        payload.extend_from_slice(&value.to_be_bytes());
+    }
+    payload
+}
+
+// Canonical attestation payload for cross-chain reputation; attestors sign its Borsh encoding
+#[cfg(feature = "cross-chain")]
 #[derive(BorshDeserialize, BorshSerialize)]
 struct CrossChainReputation {
+    agent_id: AccountId,
     score: u32,
     total_interactions: u64,
     successful_interactions: u64,
     specializations: Vec<String>,
     source_chain: String,
     timestamp: u64,
-} 
\ No newline at end of file
+    // Strictly increasing per agent and source chain
+    nonce: u64,
+    // Finalized epoch the score was taken from; None for a live score
+    epoch: Option<u64>,
+}
+
+// View struct for a signed reputation export. An EVM verifier decodes the payload with
+// abi.decode(payload, (bytes32, uint32, uint64, uint64)), recomputes payload_hash as
+// keccak256(payload) and checks ecrecover(payload_hash, v, r, s) against the export signer's
+// derived address.
+#[cfg(feature = "cross-chain")]
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SignedExportView {
+    agent_id: AccountId,
+    nonce: u64,
+    // ABI-encoded (bytes32 agentKey, uint32 score, uint64 nonce, uint64 timestamp)
+    payload: Base64VecU8,
+    payload_hash: String,
+    // 0x-prefixed r || s || v, and its parts
+    signature: String,
+    r: String,
+    s: String,
+    v: u8,
+    signed_at: u64,
+}
+
+// View struct for the MPC key exports are signed with
+#[cfg(feature = "cross-chain")]
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ExportSignerView {
+    // None while chain signatures are not configured
+    signer_id: Option<AccountId>,
+    // Account the key is derived for
+    predecessor_id: AccountId,
+    path: String,
+    key_version: u32,
+}
@@ -0,0 +1,126 @@
+use crate::*;
+use crate::violations::assert_valid_evidence;
+use near_sdk::json_types::{Base64VecU8, U64};
+use near_sdk::serde_json::Value;
+
+// Gas attached to the proposal actions; covers the call plus slashing and retention
+const GAS_FOR_DAO_VIOLATION_REPORT: Gas = Gas(100_000_000_000_000);
+const GAS_FOR_DAO_APPEAL_RULING: Gas = Gas(30_000_000_000_000);
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Set or clear the Sputnik DAO acting as governance (owner only). While set, the DAO is a
+    // governance member, and violation reports and appeal rulings are only accepted from it,
+    // which is to say from executed proposals.
+    pub fn set_governance_dao(&mut self, dao_id: Option<AccountId>) {
+        self.assert_owner();
+
+        let old_value = std::mem::replace(&mut self.governance_dao, dao_id.clone());
+
+        self.record_config_change("governance_dao", json!(old_value), json!(dao_id));
+    }
+
+    // Get the Sputnik DAO acting as governance
+    pub fn get_governance_dao(&self) -> Option<AccountId> {
+        self.governance_dao.clone()
+    }
+
+    // `add_proposal` arguments for a DAO proposal that reports a violation when executed. The
+    // DAO pays for the violation record's storage, so it needs a storage deposit here first.
+    pub fn get_dao_violation_proposal(
+        &self,
+        agent_id: AccountId,
+        violation_type: ViolationType,
+        description: String,
+        evidence: Vec<Evidence>,
+    ) -> DaoProposalArgs {
+        assert_valid_evidence(&evidence);
+        let summary = format!("Report {:?} by agent {}: {}", violation_type, agent_id, description);
+        let args = json!({
+            "agent_id": agent_id,
+            "violation_type": violation_type,
+            "description": description,
+            "evidence": evidence,
+        });
+
+        self.dao_function_call_proposal(summary, "report_violation", args, GAS_FOR_DAO_VIOLATION_REPORT)
+    }
+
+    // `add_proposal` arguments for a DAO proposal that rules on a violation appeal when executed
+    pub fn get_dao_appeal_proposal(
+        &self,
+        agent_id: AccountId,
+        violation_index: u64,
+        overturn: bool,
+        note: Option<String>,
+    ) -> DaoProposalArgs {
+        let ruling = if overturn { "Overturn" } else { "Uphold" };
+        let summary = format!("{} violation #{} of agent {} on appeal", ruling, violation_index, agent_id);
+        let args = json!({
+            "agent_id": agent_id,
+            "violation_index": violation_index,
+            "overturn": overturn,
+            "note": note,
+        });
+
+        self.dao_function_call_proposal(summary, "resolve_appeal", args, GAS_FOR_DAO_APPEAL_RULING)
+    }
+
+    // Panic unless the caller may report violations and rule on appeals: the governance DAO
+    // when one is configured, otherwise the owner
+    pub(crate) fn assert_violation_authority(&self) {
+        let caller = env::predecessor_account_id();
+        match &self.governance_dao {
+            Some(dao_id) => assert_eq!(&caller, dao_id, "Only the governance DAO can perform this action"),
+            None => assert_eq!(caller, self.owner_id, "Only owner can perform this action"),
+        }
+    }
+
+    fn dao_function_call_proposal(&self, description: String, method_name: &str, args: Value, gas: Gas) -> DaoProposalArgs {
+        DaoProposalArgs {
+            proposal: DaoProposalInput {
+                description,
+                kind: DaoProposalKind::FunctionCall {
+                    receiver_id: env::current_account_id(),
+                    actions: vec![DaoActionCall {
+                        method_name: method_name.to_string(),
+                        args: args.to_string().into_bytes().into(),
+                        deposit: U128(0),
+                        gas: U64(gas.0),
+                    }],
+                },
+            },
+        }
+    }
+}
+
+// Arguments for the Sputnik DAO v2 `add_proposal` method
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DaoProposalArgs {
+    proposal: DaoProposalInput,
+}
+
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DaoProposalInput {
+    description: String,
+    kind: DaoProposalKind,
+}
+
+// Subset of the Sputnik DAO v2 proposal kinds used by this contract
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum DaoProposalKind {
+    FunctionCall { receiver_id: AccountId, actions: Vec<DaoActionCall> },
+}
+
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DaoActionCall {
+    method_name: String,
+    // JSON call arguments
+    args: Base64VecU8,
+    deposit: U128,
+    gas: U64,
+}
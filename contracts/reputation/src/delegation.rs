@@ -0,0 +1,293 @@
+use crate::*;
+use crate::events::emit_event;
+use crate::fixed_point::{mul_div, mul_div_ceil};
+
+// Agents a single delegator can back at once
+const MAX_DELEGATIONS_PER_DELEGATOR: usize = 20;
+
+// Upper bound on the undelegation cooldown (90 days)
+pub(crate) const MAX_UNDELEGATION_COOLDOWN: u64 = 90 * 24 * 60 * 60 * 1_000_000_000;
+
+// How delegated stake counts toward an agent's stake bonus and how long it stays slashable
+// after undelegation
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DelegationConfig {
+    // Share of delegated stake counted toward the stake bonus (basis points)
+    pub stake_bonus_discount_bps: u16,
+    // Time undelegated stake stays slashable before it can be withdrawn (nanoseconds)
+    pub undelegation_cooldown: u64,
+}
+
+impl Default for DelegationConfig {
+    fn default() -> Self {
+        Self {
+            stake_bonus_discount_bps: 5_000,
+            undelegation_cooldown: 7 * 24 * 60 * 60 * 1_000_000_000, // 7 days in nanoseconds
+        }
+    }
+}
+
+// Stake delegated to one agent. Delegators hold shares of the pool, so slashing the pool's
+// amount slashes every delegator proportionally without touching their positions.
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct DelegationPool {
+    total_amount: Balance,
+    total_shares: u128,
+    // Shares undelegated but still cooling down; slashable, but no longer counted for the bonus
+    unbonding_shares: u128,
+}
+
+impl DelegationPool {
+    fn shares_value(&self, shares: u128) -> Balance {
+        if self.total_shares == 0 {
+            return 0;
+        }
+        mul_div(shares, self.total_amount, self.total_shares)
+    }
+
+    // Delegated amount counted toward the agent's stake bonus
+    pub(crate) fn active_amount(&self) -> Balance {
+        self.shares_value(self.total_shares - self.unbonding_shares)
+    }
+}
+
+// A delegator's backing of one agent
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct DelegationPosition {
+    agent_id: AccountId,
+    shares: u128,
+    // Of `shares`, those undelegated and cooling down
+    unbonding_shares: u128,
+    withdrawable_at: Option<u64>,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Start withdrawing delegated stake from an agent; all of it if no amount is given. The
+    // amount stays slashable for the undelegation cooldown, after which withdraw_delegation
+    // pays it out.
+    pub fn undelegate(&mut self, agent_id: AccountId, amount: Option<U128>) {
+        self.assert_not_paused(PausableFeature::Unstaking);
+        let delegator_id = env::predecessor_account_id();
+        let mut positions = self.delegation_positions.get(&delegator_id).unwrap_or_default();
+        let position = positions
+            .iter_mut()
+            .find(|position| position.agent_id == agent_id)
+            .expect("No delegation to this agent");
+        let mut pool = self.delegation_pools.get(&agent_id).unwrap_or_default();
+
+        let bonded_shares = position.shares - position.unbonding_shares;
+        let shares = match amount {
+            Some(amount) => {
+                assert!(amount.0 > 0 && amount.0 <= pool.shares_value(bonded_shares), "Invalid undelegation amount");
+                // Round up so the full requested amount leaves the bonded position
+                std::cmp::min(mul_div_ceil(amount.0, pool.total_shares, pool.total_amount), bonded_shares)
+            },
+            None => bonded_shares,
+        };
+        assert!(shares > 0, "Nothing to undelegate");
+
+        let withdrawable_at = env::block_timestamp() + self.delegation_config.undelegation_cooldown;
+        position.unbonding_shares += shares;
+        position.withdrawable_at = Some(withdrawable_at);
+        pool.unbonding_shares += shares;
+        let unbonding_amount = pool.shares_value(position.unbonding_shares);
+
+        self.delegation_positions.insert(&delegator_id, &positions);
+        self.delegation_pools.insert(&agent_id, &pool);
+        self.update_reputation_on_stake_change(agent_id.clone());
+
+        emit_event(
+            "stake_undelegated",
+            json!({
+                "delegator_id": delegator_id,
+                "agent_id": agent_id,
+                "unbonding_amount": U128(unbonding_amount),
+                "withdrawable_at": withdrawable_at,
+            }),
+        );
+    }
+
+    // Withdraw undelegated stake from an agent once its cooldown has passed. If the transfer
+    // fails, the amount is held for the delegator to claim with claim_unpaid_payouts.
+    pub fn withdraw_delegation(&mut self, agent_id: AccountId) -> Promise {
+        self.assert_not_paused(PausableFeature::Unstaking);
+        let delegator_id = env::predecessor_account_id();
+        let mut positions = self.delegation_positions.get(&delegator_id).unwrap_or_default();
+        let index = positions
+            .iter()
+            .position(|position| position.agent_id == agent_id)
+            .expect("No delegation to this agent");
+        let position = &mut positions[index];
+        assert!(position.unbonding_shares > 0, "Nothing to withdraw");
+        assert!(
+            env::block_timestamp() >= position.withdrawable_at.unwrap_or(u64::MAX),
+            "Undelegation cooldown has not passed"
+        );
+
+        let mut pool = self.delegation_pools.get(&agent_id).unwrap_or_default();
+        let shares = position.unbonding_shares;
+        let amount = pool.shares_value(shares);
+        pool.total_amount -= amount;
+        pool.total_shares -= shares;
+        pool.unbonding_shares -= shares;
+        position.shares -= shares;
+        position.unbonding_shares = 0;
+        position.withdrawable_at = None;
+
+        let initial_storage = env::storage_usage();
+        if position.shares == 0 {
+            positions.swap_remove(index);
+        }
+        if positions.is_empty() {
+            self.delegation_positions.remove(&delegator_id);
+        } else {
+            self.delegation_positions.insert(&delegator_id, &positions);
+        }
+        if pool.total_shares == 0 {
+            self.delegation_pools.remove(&agent_id);
+        } else {
+            self.delegation_pools.insert(&agent_id, &pool);
+        }
+        self.internal_release_storage(&delegator_id, initial_storage.saturating_sub(env::storage_usage()));
+        self.stats.itlx_delegated = self.stats.itlx_delegated.saturating_sub(amount);
+
+        emit_event(
+            "delegation_withdrawn",
+            json!({
+                "delegator_id": delegator_id,
+                "agent_id": agent_id,
+                "amount": U128(amount),
+            }),
+        );
+
+        self.internal_transfer_payout(EscrowAsset::Itlx, &delegator_id, amount, &format!("Delegation to {} withdrawn", agent_id))
+    }
+
+    // Get a delegator's positions, valued at the pools' current (post-slashing) amounts
+    pub fn get_delegations(&self, delegator_id: AccountId) -> Vec<DelegationView> {
+        self.delegation_positions
+            .get(&delegator_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|position| {
+                let pool = self.delegation_pools.get(&position.agent_id).unwrap_or_default();
+                DelegationView {
+                    amount: U128(pool.shares_value(position.shares)),
+                    unbonding_amount: U128(pool.shares_value(position.unbonding_shares)),
+                    withdrawable_at: position.withdrawable_at,
+                    agent_id: position.agent_id,
+                }
+            })
+            .collect()
+    }
+
+    // Get the stake delegated to an agent, in total and as counted toward its stake bonus
+    pub fn get_delegated_stake(&self, agent_id: AccountId) -> DelegatedStakeView {
+        let pool = self.delegation_pools.get(&agent_id).unwrap_or_default();
+        DelegatedStakeView {
+            total_amount: U128(pool.total_amount),
+            active_amount: U128(pool.active_amount()),
+        }
+    }
+
+    // Get how delegated stake is weighted and how long undelegation takes
+    pub fn get_delegation_config(&self) -> DelegationConfig {
+        self.delegation_config.clone()
+    }
+
+    // Add ITLX received via ft_transfer_call to the delegator's backing of an agent
+    pub(crate) fn internal_delegate_stake(&mut self, delegator_id: &AccountId, agent_id: &AccountId, amount: Balance) {
+        self.assert_not_paused(PausableFeature::Staking);
+        assert!(self.agent_reputations.get(agent_id).is_some(), "Agent not registered");
+        assert!(delegator_id != agent_id, "Agents cannot delegate to themselves");
+        assert!(amount > 0, "Delegation amount must be positive");
+
+        let mut pool = self.delegation_pools.get(agent_id).unwrap_or_default();
+        assert!(pool.total_amount > 0 || pool.total_shares == 0, "Delegation pool is depleted");
+        let shares = if pool.total_shares == 0 {
+            amount
+        } else {
+            mul_div(amount, pool.total_shares, pool.total_amount)
+        };
+        assert!(shares > 0, "Delegation amount too small");
+        pool.total_amount += amount;
+        pool.total_shares += shares;
+
+        let initial_storage = env::storage_usage();
+        let mut positions = self.delegation_positions.get(delegator_id).unwrap_or_default();
+        match positions.iter_mut().find(|position| &position.agent_id == agent_id) {
+            Some(position) => position.shares += shares,
+            None => {
+                assert!(positions.len() < MAX_DELEGATIONS_PER_DELEGATOR, "Too many delegations");
+                positions.push(DelegationPosition {
+                    agent_id: agent_id.clone(),
+                    shares,
+                    unbonding_shares: 0,
+                    withdrawable_at: None,
+                });
+            },
+        }
+        self.delegation_positions.insert(delegator_id, &positions);
+        self.delegation_pools.insert(agent_id, &pool);
+        self.internal_charge_storage(delegator_id, initial_storage);
+        self.stats.itlx_delegated += amount;
+        self.update_reputation_on_stake_change(agent_id.clone());
+
+        emit_event(
+            "stake_delegated",
+            json!({
+                "delegator_id": delegator_id,
+                "agent_id": agent_id,
+                "amount": U128(amount),
+            }),
+        );
+    }
+
+    // Slash the same percentage of an agent's delegated stake as of its own stake, including
+    // stake still cooling down after undelegation
+    pub(crate) fn internal_slash_delegations(&mut self, agent_id: &AccountId, slash_bps: u32) {
+        let mut pool = match self.delegation_pools.get(agent_id) {
+            Some(pool) => pool,
+            None => return,
+        };
+        let amount = pool.total_amount * slash_bps as u128 / 10_000;
+        if amount == 0 {
+            return;
+        }
+
+        pool.total_amount -= amount;
+        self.delegation_pools.insert(agent_id, &pool);
+        self.stats.itlx_delegated = self.stats.itlx_delegated.saturating_sub(amount);
+        self.route_slashed_tokens(agent_id, amount, true);
+    }
+
+    // Give back delegated stake whose slash could not be transferred to the treasury
+    pub(crate) fn internal_restore_delegations(&mut self, agent_id: &AccountId, amount: Balance) {
+        let mut pool = self.delegation_pools.get(agent_id).unwrap_or_default();
+        pool.total_amount += amount;
+        self.delegation_pools.insert(agent_id, &pool);
+        self.stats.itlx_delegated += amount;
+    }
+}
+
+// View struct for a delegator's position with one agent
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DelegationView {
+    agent_id: AccountId,
+    // Current value of the position, including any unbonding part
+    amount: U128,
+    unbonding_amount: U128,
+    withdrawable_at: Option<u64>,
+}
+
+// View struct for the stake delegated to an agent
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DelegatedStakeView {
+    total_amount: U128,
+    // Delegated stake not cooling down, before the stake bonus discount
+    active_amount: U128,
+}
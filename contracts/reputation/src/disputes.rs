@@ -0,0 +1,163 @@
+use crate::*;
+
+// Maximum length of a dispute reason
+const MAX_DISPUTE_REASON_LENGTH: usize = 1_000;
+
+// Per-agent dispute metrics
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct DisputeStats {
+    // Disputes opened by clients against the agent
+    opened: u64,
+    // Disputes resolved in the agent's favour
+    won: u64,
+    // Disputes resolved against the agent
+    lost: u64,
+    // Sum of resolution times of resolved disputes (nanoseconds)
+    total_resolution_time: u64,
+    // Intents the agent completed or failed, each counted once however a dispute ends
+    finished: u64,
+}
+
+impl DisputeStats {
+    pub(crate) fn record_finished(&mut self) {
+        self.finished += 1;
+    }
+
+    pub(crate) fn record_opened(&mut self) {
+        self.opened += 1;
+    }
+
+    pub(crate) fn record_resolved(&mut self, agent_won: bool, resolution_time: u64) {
+        if agent_won {
+            self.won += 1;
+        } else {
+            self.lost += 1;
+        }
+        self.total_resolution_time += resolution_time;
+    }
+
+    // Disputes opened and not yet resolved
+    pub(crate) fn pending(&self) -> u64 {
+        self.opened.saturating_sub(self.won + self.lost)
+    }
+
+    // Disputes opened per finished intent (basis points). Only finished intents can be
+    // disputed, so the rate never exceeds 100%.
+    pub(crate) fn dispute_rate_bps(&self) -> u64 {
+        (self.opened as u128 * 10_000)
+            .checked_div(std::cmp::max(self.finished, self.opened) as u128)
+            .unwrap_or(0) as u64
+    }
+
+    pub(crate) fn view(&self) -> DisputeStatsView {
+        let resolved = self.won + self.lost;
+
+        DisputeStatsView {
+            opened: self.opened,
+            won: self.won,
+            lost: self.lost,
+            pending: self.pending(),
+            finished_intents: self.finished,
+            average_resolution_time: self.total_resolution_time.checked_div(resolved).unwrap_or(0),
+            // Disputes per 100 finished intents, in basis points of a dispute (10000 = 1 dispute)
+            dispute_rate_per_100_bps: self.dispute_rate_bps() * 100,
+        }
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Challenge a completed intent within the dispute window (client only). Escrow release
+    // and reputation effects stay frozen until an arbiter rules.
+    pub fn dispute_intent(&mut self, intent_id: String, reason: String) {
+        assert!(reason.len() <= MAX_DISPUTE_REASON_LENGTH, "Dispute reason too long");
+
+        self.internal_transition_intent(&intent_id, IntentStatus::Disputed, None);
+
+        let mut intent = self.intents.get(&intent_id).expect("Intent not found");
+        intent.dispute_reason = Some(reason);
+        self.intents.insert(&intent_id, &intent);
+    }
+
+    // Rule on a disputed intent (governance or arbiter). Agent wins complete the intent and
+    // release the escrow; client wins fail it and refund the client.
+    pub fn resolve_intent_dispute(&mut self, intent_id: String, agent_wins: bool, ruling_note: Option<String>) {
+        let status = if agent_wins { IntentStatus::Completed } else { IntentStatus::Failed };
+        self.internal_transition_intent(&intent_id, status, ruling_note);
+    }
+
+    // Add an account allowed to rule on intent disputes (owner or governance)
+    pub fn add_arbiter(&mut self, arbiter_id: AccountId) {
+        self.assert_owner_or_governance();
+        assert!(self.arbiters.insert(&arbiter_id), "Account is already an arbiter");
+
+        self.record_config_change(&format!("arbiter.{}", arbiter_id), json!(false), json!(true));
+    }
+
+    // Remove an intent dispute arbiter (owner or governance)
+    pub fn remove_arbiter(&mut self, arbiter_id: AccountId) {
+        self.assert_owner_or_governance();
+        assert!(self.arbiters.remove(&arbiter_id), "Account is not an arbiter");
+
+        self.record_config_change(&format!("arbiter.{}", arbiter_id), json!(true), json!(false));
+    }
+
+    // Get the registered intent dispute arbiters
+    pub fn get_arbiters(&self) -> Vec<AccountId> {
+        self.arbiters.to_vec()
+    }
+
+    // Whether the account may rule on intent disputes
+    pub(crate) fn is_arbiter(&self, account_id: &AccountId) -> bool {
+        *account_id == self.owner_id
+            || self.is_governance_member(account_id.clone())
+            || self.arbiters.contains(account_id)
+    }
+
+    // Get an agent's dispute metrics
+    pub fn get_dispute_stats(&self, agent_id: AccountId) -> Option<DisputeStatsView> {
+        self.agent_reputations
+            .get(&agent_id)
+            .map(|rep| rep.dispute_stats.view())
+    }
+
+    // Update the agent's dispute metrics for an intent finishing, or for a dispute opening or
+    // being resolved
+    pub(crate) fn internal_record_dispute_transition(
+        &mut self,
+        intent: &mut IntentData,
+        previous_status: IntentStatus,
+        status: IntentStatus,
+    ) {
+        let now = env::block_timestamp();
+        let mut agent_rep = self.agent_reputations.get(&intent.agent_id).expect("Agent not registered");
+
+        if (status == IntentStatus::Completed || status == IntentStatus::Failed)
+            && previous_status != IntentStatus::Disputed
+        {
+            agent_rep.dispute_stats.record_finished();
+        }
+        if status == IntentStatus::Disputed {
+            intent.disputed_at = Some(now);
+            agent_rep.dispute_stats.record_opened();
+        } else if previous_status == IntentStatus::Disputed {
+            let resolution_time = now - intent.disputed_at.unwrap_or(now);
+            agent_rep.dispute_stats.record_resolved(status == IntentStatus::Completed, resolution_time);
+        }
+
+        self.internal_save_agent(&intent.agent_id, &agent_rep);
+    }
+}
+
+// View struct for dispute metrics
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DisputeStatsView {
+    opened: u64,
+    won: u64,
+    lost: u64,
+    pending: u64,
+    finished_intents: u64,
+    average_resolution_time: u64,
+    dispute_rate_per_100_bps: u64,
+}
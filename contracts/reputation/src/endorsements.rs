@@ -0,0 +1,238 @@
+use crate::*;
+use crate::events::emit_event;
+use crate::fixed_point::{from_points, linear_share};
+use crate::specializations::normalize_tag;
+
+// Upper bound on endorsements an agent can hold, keeping the score calculation cheap. An
+// agent filled up by unwanted endorsements can remove them.
+const MAX_ENDORSEMENTS_PER_AGENT: usize = 50;
+
+// Total endorsement weight (basis points) that earns the full endorsement bonus: three
+// endorsements from fully staked agents with perfect scores
+const FULL_ENDORSEMENT_WEIGHT_BPS: u64 = 30_000;
+
+// Upper bounds on the governance-set endorsement rules (1 year and 10 points)
+pub(crate) const MAX_ENDORSEMENT_HALF_LIFE: u64 = 365 * 24 * 60 * 60 * 1_000_000_000;
+pub(crate) const MAX_ENDORSEMENT_BONUS: u32 = 10;
+
+// Rules for agents vouching for each other's specializations
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EndorsementConfig {
+    // Lowest trust level an endorser must hold
+    pub min_endorser_trust_level: TrustLevel,
+    // Endorsements an agent can have outstanding at once
+    pub max_endorsements_per_endorser: u32,
+    // Age at which an endorsement's weight halves (nanoseconds)
+    pub half_life: u64,
+    // Maximum points endorsements can add to the score
+    pub max_endorsement_bonus: u32,
+}
+
+impl Default for EndorsementConfig {
+    fn default() -> Self {
+        Self {
+            min_endorser_trust_level: TrustLevel::Trusted,
+            max_endorsements_per_endorser: 10,
+            half_life: 90 * 24 * 60 * 60 * 1_000_000_000, // 90 days in nanoseconds
+            max_endorsement_bonus: 5,
+        }
+    }
+}
+
+// An agent vouching for another agent's specialization
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Endorsement {
+    endorser: AccountId,
+    // Normalized specialization tag
+    specialization: String,
+    // Weight from the endorser's score and stake when endorsing (basis points)
+    weight_bps: u32,
+    endorsed_at: u64,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Endorse another agent for one of its specializations. The endorsement is weighted by the
+    // caller's score and stake and decays with age. The endorser pays for its storage.
+    pub fn endorse_agent(&mut self, agent_id: AccountId, specialization: String) {
+        let endorser = env::predecessor_account_id();
+        let config = self.endorsement_config.clone();
+        assert!(endorser != agent_id, "Agents cannot endorse themselves");
+
+        let endorser_rep = self.agent_reputations.get(&endorser).expect("Endorser is not a registered agent");
+        self.assert_agent_in_good_standing(&endorser);
+        assert!(
+            self.get_trust_level(endorser_rep.score) >= config.min_endorser_trust_level,
+            "Endorser's trust level is too low"
+        );
+
+        let agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+        let specialization = normalize_tag(&specialization);
+        assert!(
+            agent_rep.specializations.iter().any(|tag| normalize_tag(tag) == specialization),
+            "Agent does not list this specialization"
+        );
+
+        let initial_storage = env::storage_usage();
+        let mut given = self.endorsements_given.get(&endorser).unwrap_or_default();
+        assert!(
+            (given.len() as u32) < config.max_endorsements_per_endorser,
+            "Endorser has reached its endorsement limit"
+        );
+        let mut received = self.endorsements.get(&agent_id).unwrap_or_default();
+        assert!(received.len() < MAX_ENDORSEMENTS_PER_AGENT, "Agent has reached the endorsement limit");
+        assert!(
+            !received.iter().any(|e| e.endorser == endorser && e.specialization == specialization),
+            "Already endorsed for this specialization"
+        );
+
+        let weight_bps = self.internal_endorser_weight_bps(&endorser, endorser_rep.score);
+        received.push(Endorsement {
+            endorser: endorser.clone(),
+            specialization: specialization.clone(),
+            weight_bps,
+            endorsed_at: env::block_timestamp(),
+        });
+        given.push((agent_id.clone(), specialization.clone()));
+        self.endorsements.insert(&agent_id, &received);
+        self.endorsements_given.insert(&endorser, &given);
+        self.internal_charge_storage(&endorser, initial_storage);
+
+        emit_event(
+            "agent_endorsed",
+            json!({
+                "agent_id": agent_id,
+                "endorser": endorser,
+                "specialization": specialization,
+                "weight_bps": weight_bps,
+            }),
+        );
+    }
+
+    // Withdraw the caller's endorsement of an agent's specialization, releasing its storage
+    pub fn revoke_endorsement(&mut self, agent_id: AccountId, specialization: String) {
+        let endorser = env::predecessor_account_id();
+        self.internal_remove_endorsement(&agent_id, &endorser, &normalize_tag(&specialization));
+    }
+
+    // Drop an endorsement the caller received, freeing a slot under the per-agent limit; the
+    // endorser gets its storage back
+    pub fn remove_endorsement(&mut self, endorser: AccountId, specialization: String) {
+        let agent_id = env::predecessor_account_id();
+        self.internal_remove_endorsement(&agent_id, &endorser, &normalize_tag(&specialization));
+    }
+
+    // Get the endorsements an agent has received, with their current decayed weights
+    pub fn get_endorsements(&self, agent_id: AccountId) -> Vec<EndorsementView> {
+        let now = env::block_timestamp();
+        self.endorsements
+            .get(&agent_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|endorsement| EndorsementView {
+                current_weight_bps: self.internal_effective_endorsement_weight(&endorsement, now),
+                endorsement,
+            })
+            .collect()
+    }
+
+    // Get the endorsement rules
+    pub fn get_endorsement_config(&self) -> EndorsementConfig {
+        self.endorsement_config.clone()
+    }
+
+    fn internal_remove_endorsement(&mut self, agent_id: &AccountId, endorser: &AccountId, specialization: &str) {
+        let initial_storage = env::storage_usage();
+        let mut received = self.endorsements.get(agent_id).unwrap_or_default();
+        let index = received
+            .iter()
+            .position(|e| &e.endorser == endorser && e.specialization == specialization)
+            .expect("Endorsement not found");
+        received.remove(index);
+        if received.is_empty() {
+            self.endorsements.remove(agent_id);
+        } else {
+            self.endorsements.insert(agent_id, &received);
+        }
+
+        let mut given = self.endorsements_given.get(endorser).unwrap_or_default();
+        given.retain(|(endorsed, tag)| !(endorsed == agent_id && tag == specialization));
+        if given.is_empty() {
+            self.endorsements_given.remove(endorser);
+        } else {
+            self.endorsements_given.insert(endorser, &given);
+        }
+        self.internal_track_storage(endorser, initial_storage);
+
+        emit_event(
+            "endorsement_revoked",
+            json!({
+                "agent_id": agent_id,
+                "endorser": endorser,
+                "specialization": specialization,
+                "revoked_by": env::predecessor_account_id(),
+            }),
+        );
+    }
+
+    // Score bonus (basis points) from the agent's endorsements: up to the configured maximum,
+    // reached at FULL_ENDORSEMENT_WEIGHT_BPS of decayed endorsement weight
+    pub(crate) fn internal_endorsement_bonus(&self, agent_id: &AccountId) -> u32 {
+        let endorsements = match self.endorsements.get(agent_id) {
+            Some(endorsements) => endorsements,
+            None => return 0,
+        };
+        let now = env::block_timestamp();
+        let total_weight: u64 = endorsements
+            .iter()
+            .map(|endorsement| self.internal_effective_endorsement_weight(endorsement, now) as u64)
+            .sum();
+
+        linear_share(
+            from_points(self.endorsement_config.max_endorsement_bonus),
+            total_weight as u128,
+            FULL_ENDORSEMENT_WEIGHT_BPS as u128,
+        )
+    }
+
+    // Endorser weight (basis points): its score as a share of 100, scaled by its stake up to
+    // the stake that earns the full stake bonus
+    fn internal_endorser_weight_bps(&self, endorser: &AccountId, score: u32) -> u32 {
+        let full_stake = self.min_stake_amount * STAKE_BONUS_FULL_MULTIPLE;
+        if full_stake == 0 {
+            return score * 100;
+        }
+        let stake = std::cmp::min(self.agent_stakes.get(endorser).unwrap_or(0), full_stake);
+
+        (score as u128 * 100 * stake / full_stake) as u32
+    }
+
+    // Weight of an endorsement after decay, halving every half-life with linear interpolation
+    // in between; endorsements from agents no longer in good standing count for nothing
+    fn internal_effective_endorsement_weight(&self, endorsement: &Endorsement, now: u64) -> u32 {
+        if !self.internal_agent_status(&endorsement.endorser).in_good_standing() {
+            return 0;
+        }
+        let half_life = self.endorsement_config.half_life;
+        let age = now.saturating_sub(endorsement.endorsed_at);
+        let halvings = age / half_life;
+        if halvings >= 32 {
+            return 0;
+        }
+
+        let upper = endorsement.weight_bps as u64 >> halvings;
+        let lower = upper >> 1;
+        (upper - (upper - lower) * (age % half_life) / half_life) as u32
+    }
+}
+
+// View struct for an endorsement with its decayed weight
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EndorsementView {
+    #[serde(flatten)]
+    endorsement: Endorsement,
+    current_weight_bps: u32,
+}
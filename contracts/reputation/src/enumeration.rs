@@ -0,0 +1,81 @@
+use crate::*;
+
+// Upper bound on agents returned by one enumeration call
+const MAX_ENUMERATION_LIMIT: u32 = 100;
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Number of registered agents
+    pub fn get_agent_count(&self) -> u64 {
+        self.agent_reputations.len()
+    }
+
+    // Page through every registered agent in registration order
+    pub fn get_agents(&self, from_index: Option<u64>, limit: Option<u32>) -> Vec<AgentSummaryView> {
+        let keys = self.agent_reputations.keys_as_vector();
+        let from_index = from_index.unwrap_or(0);
+        let limit = std::cmp::min(limit.unwrap_or(MAX_ENUMERATION_LIMIT), MAX_ENUMERATION_LIMIT) as u64;
+
+        (from_index..std::cmp::min(from_index + limit, keys.len()))
+            .filter_map(|index| keys.get(index))
+            .filter_map(|agent_id| self.agent_summary_view(agent_id))
+            .collect()
+    }
+
+    // Agents whose records changed at or after `since_timestamp`, oldest change first. Indexers
+    // resume from the last `updated_at` they saw; agents changed in that same block are
+    // returned again. Agents not saved since the index was introduced only appear in
+    // get_agents until their next change.
+    pub fn get_recently_updated_agents(&self, since_timestamp: u64, limit: Option<u32>) -> Vec<AgentSummaryView> {
+        let limit = std::cmp::min(limit.unwrap_or(MAX_ENUMERATION_LIMIT), MAX_ENUMERATION_LIMIT);
+
+        self.update_index
+            .iter_from((since_timestamp.saturating_sub(1), env::current_account_id()))
+            .skip_while(|((updated_at, _), _)| *updated_at < since_timestamp)
+            .take(limit as usize)
+            .filter_map(|((_, agent_id), _)| self.agent_summary_view(agent_id))
+            .collect()
+    }
+
+    // Move the agent's entry in the update index to the current block
+    pub(crate) fn internal_update_recency_index(&mut self, agent_id: &AccountId) {
+        let now = env::block_timestamp();
+        let previous = self.agent_updated_at.insert(agent_id, &now);
+        if previous == Some(now) {
+            return;
+        }
+        if let Some(previous) = previous {
+            self.update_index.remove(&(previous, agent_id.clone()));
+        }
+        self.update_index.insert(&(now, agent_id.clone()), &());
+    }
+
+    fn agent_summary_view(&self, agent_id: AccountId) -> Option<AgentSummaryView> {
+        let agent_rep = self.agent_reputations.get(&agent_id)?;
+        Some(AgentSummaryView {
+            score: agent_rep.score,
+            trust_level: self.get_trust_level(agent_rep.score),
+            status: self.internal_agent_status(&agent_id),
+            total_interactions: agent_rep.total_interactions,
+            successful_interactions: agent_rep.successful_interactions,
+            registered_at: agent_rep.registered_at,
+            updated_at: self.agent_updated_at.get(&agent_id),
+            agent_id,
+        })
+    }
+}
+
+// View struct for one agent in enumeration results
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentSummaryView {
+    agent_id: AccountId,
+    score: u32,
+    trust_level: TrustLevel,
+    status: AgentStatus,
+    total_interactions: u64,
+    successful_interactions: u64,
+    registered_at: u64,
+    // When the agent's record last changed, if it has since the update index was introduced
+    updated_at: Option<u64>,
+}
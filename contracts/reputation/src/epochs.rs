@@ -0,0 +1,169 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Shortest and longest score epoch
+pub(crate) const MIN_SCORE_EPOCH_LENGTH: u64 = 60 * 60 * 1_000_000_000; // 1 hour
+pub(crate) const MAX_SCORE_EPOCH_LENGTH: u64 = 30 * 24 * 60 * 60 * 1_000_000_000; // 30 days
+
+// Most agents checkpointed per finalize_epoch call
+const MAX_FINALIZE_BATCH: usize = 100;
+
+// Progress through score epochs
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ScoreEpochState {
+    // Epoch whose score changes are being collected
+    current: u64,
+    started_at: u64,
+    // Epoch whose changed agents are being checkpointed, if finalization is under way
+    finalizing: Option<u64>,
+    // Latest epoch with every changed agent checkpointed
+    last_finalized: Option<u64>,
+}
+
+impl ScoreEpochState {
+    pub(crate) fn new() -> Self {
+        Self { current: 0, started_at: env::block_timestamp(), finalizing: None, last_finalized: None }
+    }
+}
+
+// An agent's score as finalized at the end of an epoch. Checkpoints are only ever appended.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EpochCheckpoint {
+    pub epoch: u64,
+    pub score: u32,
+    pub score_bps: u32,
+    pub total_interactions: u64,
+    pub recorded_at: u64,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Finalize the epoch that has ended (anyone can call). Each agent whose score changed
+    // during it gets an immutable checkpoint of its score as finalization starts. Agents are
+    // checkpointed in batches; call again until it returns true.
+    pub fn finalize_epoch(&mut self) -> bool {
+        if self.score_epoch.finalizing.is_none() {
+            let now = env::block_timestamp();
+            let length = self.score_epoch_length;
+            assert!(now >= self.score_epoch.started_at + length, "Epoch has not ended");
+
+            let epoch = self.score_epoch.current;
+            self.score_epoch.finalizing = Some(epoch);
+            self.score_epoch.current += 1;
+            // Epoch boundaries stay on the original schedule however late finalization is
+            self.score_epoch.started_at += (now - self.score_epoch.started_at) / length * length;
+            // Changes from here on belong to the next epoch
+            std::mem::swap(&mut self.epoch_changed_agents, &mut self.epoch_finalizing_agents);
+
+            emit_event("epoch_finalization_started", json!({ "epoch": epoch }));
+        }
+        let epoch = self.score_epoch.finalizing.unwrap();
+
+        let batch: Vec<AccountId> = self.epoch_finalizing_agents.iter().take(MAX_FINALIZE_BATCH).collect();
+        for agent_id in batch {
+            if let Some(agent_rep) = self.agent_reputations.get(&agent_id) {
+                self.internal_write_epoch_checkpoint(&agent_id, epoch, &agent_rep);
+            }
+            self.epoch_finalizing_agents.remove(&agent_id);
+        }
+
+        if !self.epoch_finalizing_agents.is_empty() {
+            return false;
+        }
+        self.score_epoch.finalizing = None;
+        self.score_epoch.last_finalized = Some(epoch);
+
+        emit_event("epoch_finalized", json!({ "epoch": epoch }));
+        true
+    }
+
+    // Get the agent's finalized score as of an epoch: its checkpoint from the latest epoch up
+    // to that one in which its score changed. None for epochs not yet finalized.
+    pub fn get_score_at_epoch(&self, agent_id: AccountId, epoch: u64) -> Option<EpochCheckpoint> {
+        if self.score_epoch.last_finalized.is_none_or(|last| epoch > last) {
+            return None;
+        }
+        let checkpoints = self.epoch_checkpoints.get(&agent_id)?;
+
+        // Binary search for the last checkpoint at or before the epoch
+        let (mut low, mut high) = (0, checkpoints.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            if checkpoints.get(mid).unwrap().epoch <= epoch {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        if low == 0 {
+            return None;
+        }
+        checkpoints.get(low - 1)
+    }
+
+    // Get the epoch being collected, when it started and ends, and finalization progress
+    pub fn get_epoch_info(&self) -> EpochInfoView {
+        EpochInfoView {
+            current_epoch: self.score_epoch.current,
+            started_at: self.score_epoch.started_at,
+            ends_at: self.score_epoch.started_at + self.score_epoch_length,
+            finalizing_epoch: self.score_epoch.finalizing,
+            agents_pending: self.epoch_finalizing_agents.len(),
+            last_finalized_epoch: self.score_epoch.last_finalized,
+        }
+    }
+
+    // The agent's score as of the last finalized epoch, and that epoch; None before its score
+    // was first finalized
+    pub(crate) fn internal_finalized_score(&self, agent_id: &AccountId) -> Option<(u64, u32)> {
+        let epoch = self.score_epoch.last_finalized?;
+        self.get_score_at_epoch(agent_id.clone(), epoch).map(|checkpoint| (epoch, checkpoint.score))
+    }
+
+    // Note a saved agent's score change for the current epoch. An agent still awaiting its
+    // checkpoint for the epoch being finalized gets it first, from its score before the change.
+    pub(crate) fn internal_track_epoch_change(
+        &mut self,
+        agent_id: &AccountId,
+        previous: Option<&AgentReputation>,
+        current: &AgentReputation,
+    ) {
+        if previous.map(|p| p.score_bps) == Some(current.score_bps) {
+            return;
+        }
+        if let (Some(epoch), Some(previous)) = (self.score_epoch.finalizing, previous) {
+            if self.epoch_finalizing_agents.remove(agent_id) {
+                self.internal_write_epoch_checkpoint(agent_id, epoch, previous);
+            }
+        }
+        self.epoch_changed_agents.insert(agent_id);
+    }
+
+    fn internal_write_epoch_checkpoint(&mut self, agent_id: &AccountId, epoch: u64, agent_rep: &AgentReputation) {
+        let mut checkpoints = self.epoch_checkpoints.get(agent_id).unwrap_or_else(|| {
+            Vector::new([b"$".as_slice(), &env::sha256(agent_id.as_bytes())].concat())
+        });
+        checkpoints.push(&EpochCheckpoint {
+            epoch,
+            score: agent_rep.score,
+            score_bps: agent_rep.score_bps,
+            total_interactions: agent_rep.total_interactions,
+            recorded_at: env::block_timestamp(),
+        });
+        self.epoch_checkpoints.insert(agent_id, &checkpoints);
+    }
+}
+
+// View struct for score epoch progress
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EpochInfoView {
+    current_epoch: u64,
+    started_at: u64,
+    ends_at: u64,
+    finalizing_epoch: Option<u64>,
+    // Agents still to be checkpointed for the epoch being finalized
+    agents_pending: u64,
+    last_finalized_epoch: Option<u64>,
+}
@@ -0,0 +1,213 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Lower bounds keeping escheatment a last resort (180 and 30 days)
+pub(crate) const MIN_DORMANCY_PERIOD: u64 = 180 * 24 * 60 * 60 * 1_000_000_000;
+pub(crate) const MIN_ESCHEATMENT_NOTICE_PERIOD: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+// When an inactive staker's stake may be escheated
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EscheatmentConfig {
+    // Inactivity after which a dormancy notice can be issued (nanoseconds)
+    pub dormancy_period: u64,
+    // Time after the notice during which any activity keeps the stake (nanoseconds)
+    pub notice_period: u64,
+}
+
+impl Default for EscheatmentConfig {
+    fn default() -> Self {
+        Self {
+            dormancy_period: 2 * 365 * 24 * 60 * 60 * 1_000_000_000, // 2 years in nanoseconds
+            notice_period: 90 * 24 * 60 * 60 * 1_000_000_000,        // 90 days in nanoseconds
+        }
+    }
+}
+
+// A staker's last sign of life and any dormancy notice served on them
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct StakeActivity {
+    last_active: u64,
+    notice_issued_at: Option<u64>,
+}
+
+// Escheated stake the contract holds for its original account until reclaimed
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct EscheatedStake {
+    // Escheated and not yet reclaimed
+    owed: Balance,
+    last_escheated_at: u64,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Record a sign of life for the caller's stake without any other action
+    pub fn heartbeat(&mut self) {
+        let account_id = env::predecessor_account_id();
+        assert!(self.agent_stakes.get(&account_id).unwrap_or(0) > 0, "No stake to keep active");
+
+        self.internal_record_activity(&account_id);
+    }
+
+    // Serve notice on a staker inactive for the dormancy period (callable by anyone). Any
+    // activity before the notice period ends cancels the notice.
+    pub fn issue_dormancy_notice(&mut self, account_id: AccountId) {
+        let stake = self.agent_stakes.get(&account_id).unwrap_or(0);
+        assert!(stake > 0, "Account has no stake");
+
+        let now = env::block_timestamp();
+        let mut activity = self.internal_stake_activity(&account_id);
+        assert!(activity.notice_issued_at.is_none(), "Dormancy notice already issued");
+        assert!(
+            now >= activity.last_active + self.escheatment_config.dormancy_period,
+            "Stake is not dormant"
+        );
+
+        activity.notice_issued_at = Some(now);
+        self.stake_activity.insert(&account_id, &activity);
+
+        emit_event(
+            "stake_dormancy_notice",
+            json!({
+                "account_id": account_id,
+                "stake": U128(stake),
+                "last_active": activity.last_active,
+                "escheatable_at": now + self.escheatment_config.notice_period,
+            }),
+        );
+    }
+
+    // Escheat a dormant stake once its notice period has passed without activity (callable by
+    // anyone). The stake leaves the stake map but the tokens stay with the contract, held for
+    // the account, which can reclaim them at any time.
+    pub fn escheat_stake(&mut self, account_id: AccountId) {
+        let activity = self.internal_stake_activity(&account_id);
+        let notice_issued_at = activity.notice_issued_at.expect("No dormancy notice issued");
+        assert!(
+            env::block_timestamp() >= notice_issued_at + self.escheatment_config.notice_period,
+            "Dormancy notice period has not passed"
+        );
+        let amount = self.agent_stakes.get(&account_id).unwrap_or(0);
+        assert!(amount > 0, "Account has no stake");
+
+        self.internal_set_stake(&account_id, 0);
+        self.update_reputation_on_stake_change(account_id.clone());
+        self.stake_activity.remove(&account_id);
+
+        let mut escheated = self.escheated_stakes.get(&account_id).unwrap_or_default();
+        escheated.owed += amount;
+        escheated.last_escheated_at = env::block_timestamp();
+        self.escheated_stakes.insert(&account_id, &escheated);
+
+        emit_event(
+            "stake_escheated",
+            json!({
+                "account_id": account_id,
+                "amount": U128(amount),
+                "held": U128(escheated.owed),
+            }),
+        );
+    }
+
+    // Take escheated stake back as stake (original account only)
+    pub fn reclaim_escheated_stake(&mut self) -> U128 {
+        let account_id = env::predecessor_account_id();
+        let escheated = self.escheated_stakes.remove(&account_id).expect("No escheated stake");
+
+        let current_stake = self.agent_stakes.get(&account_id).unwrap_or(0);
+        self.internal_set_stake(&account_id, current_stake + escheated.owed);
+        self.update_reputation_on_stake_change(account_id.clone());
+        self.internal_record_activity(&account_id);
+
+        emit_event(
+            "escheated_stake_reclaimed",
+            json!({
+                "account_id": account_id,
+                "amount": U128(escheated.owed),
+            }),
+        );
+
+        U128(escheated.owed)
+    }
+
+    // Get an account's escheated stake held for it
+    pub fn get_escheated_stake(&self, account_id: AccountId) -> Option<EscheatedStakeView> {
+        self.escheated_stakes.get(&account_id).map(|escheated| EscheatedStakeView {
+            owed: U128(escheated.owed),
+            last_escheated_at: escheated.last_escheated_at,
+        })
+    }
+
+    // Get a staker's dormancy status
+    pub fn get_stake_dormancy(&self, account_id: AccountId) -> Option<StakeDormancyView> {
+        if self.agent_stakes.get(&account_id).unwrap_or(0) == 0 {
+            return None;
+        }
+
+        let activity = self.internal_stake_activity(&account_id);
+        Some(StakeDormancyView {
+            last_active: activity.last_active,
+            dormant_at: activity.last_active + self.escheatment_config.dormancy_period,
+            notice_issued_at: activity.notice_issued_at,
+            escheatable_at: activity
+                .notice_issued_at
+                .map(|issued_at| issued_at + self.escheatment_config.notice_period),
+        })
+    }
+
+    // Get the escheatment periods
+    pub fn get_escheatment_config(&self) -> EscheatmentConfig {
+        self.escheatment_config.clone()
+    }
+
+    // Record activity by a staker, cancelling any dormancy notice. Accounts without stake are
+    // not tracked.
+    pub(crate) fn internal_record_activity(&mut self, account_id: &AccountId) {
+        if self.agent_stakes.get(account_id).unwrap_or(0) == 0 {
+            return;
+        }
+
+        let initial_storage = env::storage_usage();
+        let previous = self.stake_activity.insert(account_id, &StakeActivity {
+            last_active: env::block_timestamp(),
+            notice_issued_at: None,
+        });
+        self.internal_track_storage(account_id, initial_storage);
+
+        if let Some(StakeActivity { notice_issued_at: Some(_), .. }) = previous {
+            emit_event("stake_dormancy_notice_cancelled", json!({ "account_id": account_id }));
+        }
+    }
+
+    // Activity record, falling back to registration time for agents staked before tracking
+    // began and to now for anyone else
+    fn internal_stake_activity(&self, account_id: &AccountId) -> StakeActivity {
+        self.stake_activity.get(account_id).unwrap_or_else(|| StakeActivity {
+            last_active: self
+                .agent_reputations
+                .get(account_id)
+                .map_or(env::block_timestamp(), |agent_rep| agent_rep.registered_at),
+            notice_issued_at: None,
+        })
+    }
+}
+
+// View struct for a staker's dormancy status
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StakeDormancyView {
+    last_active: u64,
+    // When a dormancy notice can be issued
+    dormant_at: u64,
+    notice_issued_at: Option<u64>,
+    // When the stake can be escheated, once notice has been issued
+    escheatable_at: Option<u64>,
+}
+
+// View struct for escheated stake owed back to an account
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EscheatedStakeView {
+    owed: U128,
+    last_escheated_at: u64,
+}
@@ -0,0 +1,294 @@
+use crate::*;
+#[cfg(feature = "escrow")]
+use crate::events::emit_event;
+
+// Upper bound for the protocol fee on released escrow (basis points, 10%)
+pub(crate) const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+
+// Asset an intent's escrow is denominated in
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum EscrowAsset {
+    // Attached NEAR deposit
+    Near,
+    // ITLX sent via ft_transfer_call
+    Itlx,
+}
+
+#[cfg(feature = "escrow")]
+impl IntentData {
+    // Escrow neither released to the agent nor refunded to the client yet
+    pub(crate) fn escrow_remaining(&self) -> Balance {
+        self.escrow_amount - self.escrow_released - self.escrow_refunded
+    }
+}
+
+#[cfg(not(feature = "escrow"))]
+impl AgentReputationContract {
+    // Intents never hold funds without escrow support, so there is nothing to settle
+    pub(crate) fn internal_settle_escrow(&mut self, _intent: &mut IntentData, _previous_status: IntentStatus) {}
+}
+
+#[cfg(feature = "escrow")]
+#[near_bindgen]
+impl AgentReputationContract {
+    // Approve an agent's checkpoint, releasing the matching share of the escrow early (client only)
+    pub fn approve_checkpoint(&mut self, intent_id: String, checkpoint_index: u32) {
+        let mut intent = self.intents.get(&intent_id).expect("Intent not found");
+
+        assert_eq!(env::predecessor_account_id(), intent.client_id, "Only the intent's client can approve checkpoints");
+        assert!(intent.status == IntentStatus::InProgress, "Checkpoints can only be approved for in-progress intents");
+
+        let mut checkpoints = self.intent_checkpoints.get(&intent_id).unwrap_or_default();
+        let checkpoint = checkpoints
+            .get_mut(checkpoint_index as usize)
+            .expect("Invalid checkpoint index");
+        assert!(checkpoint.approved_at.is_none(), "Checkpoint already approved");
+
+        checkpoint.approved_at = Some(env::block_timestamp());
+        let percentage = checkpoint.percentage;
+        self.intent_checkpoints.insert(&intent_id, &checkpoints);
+
+        // Release the tranche covering progress up to this checkpoint
+        let target_release = intent.escrow_amount * percentage as u128 / 100;
+        let tranche = target_release.saturating_sub(intent.escrow_released);
+        self.internal_release_escrow(&mut intent, tranche);
+        self.intents.insert(&intent_id, &intent);
+
+        // Each approval is a positive micro-interaction for the agent's reliability
+        let mut agent_rep = self.agent_reputations.get(&intent.agent_id).expect("Agent not registered");
+        agent_rep.reliability_score = std::cmp::min(
+            agent_rep.reliability_score + CHECKPOINT_APPROVAL_RELIABILITY_BONUS,
+            100,
+        );
+        self.internal_save_agent(&intent.agent_id, &agent_rep);
+
+        emit_event(
+            "checkpoint_approved",
+            json!({
+                "intent_id": intent_id,
+                "agent_id": intent.agent_id,
+                "checkpoint_index": checkpoint_index,
+                "percentage": percentage,
+                "released": U128(tranche),
+            }),
+        );
+    }
+
+    // Settle whatever escrow remains once an intent reaches a final outcome: completed work
+    // is paid out, stalled work is paid up to its last client-approved checkpoint, and
+    // everything else is refunded to the client
+    pub(crate) fn internal_settle_escrow(&mut self, intent: &mut IntentData, previous_status: IntentStatus) {
+        let remaining = intent.escrow_remaining();
+        if remaining == 0 {
+            return;
+        }
+
+        match intent.status {
+            IntentStatus::Completed => {
+                self.internal_release_escrow(intent, remaining);
+            },
+            IntentStatus::Cancelled if previous_status == IntentStatus::InProgress => {
+                let progress = self.intent_approved_progress(&intent.intent_id);
+                let earned = intent.escrow_amount * progress as u128 / 100;
+                let payout = std::cmp::min(earned.saturating_sub(intent.escrow_released), remaining);
+                self.internal_release_escrow(intent, payout);
+                self.internal_refund_escrow(intent);
+            },
+            IntentStatus::Failed | IntentStatus::Expired | IntentStatus::Declined | IntentStatus::Cancelled => {
+                self.internal_refund_escrow(intent);
+            },
+            _ => {},
+        }
+    }
+
+    // Pay `amount` of the escrow to the agent, less the protocol fee at the agent's current
+    // reputation-discounted rate, split between the treasury, insurance pool and referrer
+    fn internal_release_escrow(&mut self, intent: &mut IntentData, amount: Balance) {
+        if amount == 0 {
+            return;
+        }
+
+        let fee_bps = self.internal_fee_rate_bps(&intent.agent_id);
+        let fee = amount * fee_bps as u128 / 10_000;
+        intent.escrow_released += amount;
+        if intent.escrow_asset == EscrowAsset::Itlx {
+            self.stats.itlx_in_escrow = self.stats.itlx_in_escrow.saturating_sub(amount);
+        }
+
+        // Under a standing agreement, the agreed share of the payout goes back to the delegator
+        let split = intent
+            .agreement_id
+            .and_then(|agreement_id| self.agreements.get(&agreement_id))
+            .map(|agreement| {
+                let split = (amount - fee) * agreement.terms.revenue_split_bps as u128 / 10_000;
+                (agreement.delegator_id, split)
+            })
+            .filter(|(_, split)| *split > 0);
+        let payout = amount - fee - split.as_ref().map_or(0, |(_, split)| *split);
+
+        let memo = format!("Intent {} payment", intent.intent_id);
+        self.internal_transfer_asset(intent.escrow_asset, &intent.agent_id, payout, &memo);
+        if let Some((delegator_id, split)) = &split {
+            self.internal_transfer_asset(intent.escrow_asset, delegator_id, *split, &memo);
+        }
+        let (treasury_fee, insurance_fee, referrer_fee) =
+            self.internal_split_fee(fee, intent.escrow_asset, intent.referrer_id.is_some());
+        if treasury_fee > 0 {
+            let treasury_id = self.treasury_id.clone();
+            self.internal_transfer_asset(intent.escrow_asset, &treasury_id, treasury_fee, &memo);
+        }
+        if insurance_fee > 0 {
+            self.stats.itlx_insurance_pool += insurance_fee;
+        }
+        if let (Some(referrer_id), true) = (&intent.referrer_id, referrer_fee > 0) {
+            self.internal_transfer_asset(intent.escrow_asset, referrer_id, referrer_fee, &memo);
+        }
+
+        emit_event(
+            "escrow_released",
+            json!({
+                "intent_id": intent.intent_id,
+                "agent_id": intent.agent_id,
+                "asset": intent.escrow_asset,
+                "amount": U128(payout),
+                "protocol_fee": U128(fee),
+                "protocol_fee_bps": fee_bps,
+                "treasury_fee": U128(treasury_fee),
+                "insurance_fee": U128(insurance_fee),
+                "referrer_fee": U128(referrer_fee),
+                "revenue_split": U128(split.map_or(0, |(_, split)| split)),
+            }),
+        );
+    }
+
+    // Return the remaining escrow to the client
+    fn internal_refund_escrow(&mut self, intent: &mut IntentData) {
+        let remaining = intent.escrow_remaining();
+        if remaining == 0 {
+            return;
+        }
+
+        intent.escrow_refunded += remaining;
+        if intent.escrow_asset == EscrowAsset::Itlx {
+            self.stats.itlx_in_escrow = self.stats.itlx_in_escrow.saturating_sub(remaining);
+        }
+
+        let memo = format!("Intent {} refund", intent.intent_id);
+        self.internal_transfer_asset(intent.escrow_asset, &intent.client_id, remaining, &memo);
+
+        emit_event(
+            "escrow_refunded",
+            json!({
+                "intent_id": intent.intent_id,
+                "client_id": intent.client_id,
+                "asset": intent.escrow_asset,
+                "amount": U128(remaining),
+            }),
+        );
+    }
+
+    // Pay out escrow; a failed transfer is held for the receiver to claim (see payouts.rs)
+    fn internal_transfer_asset(&self, asset: EscrowAsset, receiver_id: &AccountId, amount: Balance, memo: &str) {
+        self.internal_transfer_payout(asset, receiver_id, amount, memo);
+    }
+}
+
+#[cfg(all(test, feature = "escrow"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    const ESCROW: Balance = 10_000;
+
+    // Contract with an agent working on an escrowed intent for a client
+    fn setup_intent() -> AgentReputationContract {
+        let mut contract = setup();
+        register_agent(&mut contract, "agent.test");
+        deposit_storage(&mut contract, "client.test");
+        start_intent(&mut contract, "intent-1", "agent.test", "client.test", ESCROW);
+        contract
+    }
+
+    fn post_checkpoint(contract: &mut AgentReputationContract, percentage: u8) {
+        set_context("agent.test", START, 0);
+        contract.post_checkpoint("intent-1".to_string(), percentage, String::new(), None);
+    }
+
+    fn cancel_stalled(contract: &mut AgentReputationContract) {
+        set_context("client.test", START + contract.checkpoint_stall_period + 1, 0);
+        contract.cancel_stalled_intent("intent-1".to_string());
+    }
+
+    #[test]
+    fn unapproved_checkpoint_earns_nothing_on_stalled_cancel() {
+        let mut contract = setup_intent();
+        post_checkpoint(&mut contract, 100);
+        cancel_stalled(&mut contract);
+
+        let intent = contract.intents.get(&"intent-1".to_string()).unwrap();
+        assert_eq!(intent.status, IntentStatus::Cancelled);
+        assert_eq!(intent.escrow_released, 0);
+        assert_eq!(intent.escrow_refunded, ESCROW);
+        assert_eq!(last_event("escrow_refunded")["client_id"], "client.test");
+    }
+
+    #[test]
+    fn stalled_cancel_pays_up_to_the_last_approved_checkpoint() {
+        let mut contract = setup_intent();
+        post_checkpoint(&mut contract, 40);
+        set_context("client.test", START, 0);
+        contract.approve_checkpoint("intent-1".to_string(), 0);
+        post_checkpoint(&mut contract, 100);
+        cancel_stalled(&mut contract);
+
+        let intent = contract.intents.get(&"intent-1".to_string()).unwrap();
+        assert_eq!(intent.escrow_released, 4_000);
+        assert_eq!(intent.escrow_refunded, 6_000);
+    }
+
+    #[test]
+    fn completed_intent_pays_the_agent_less_the_fee_split() {
+        let mut contract = setup();
+        contract.protocol_fee_bps = 500;
+        contract.intent_dispute_window = 0;
+        register_agent(&mut contract, "agent.test");
+        deposit_storage(&mut contract, "client.test");
+        set_context("client.test", START, ESCROW);
+        contract.record_intent(
+            "intent-1".to_string(),
+            account("agent.test"),
+            INTENT_TYPE.to_string(),
+            String::new(),
+            None,
+            Some(account("referrer.test")),
+        );
+        set_context("agent.test", START, 0);
+        contract.accept_intent("intent-1".to_string());
+        contract.update_intent_status("intent-1".to_string(), IntentStatus::InProgress, None, None);
+        contract.update_intent_status("intent-1".to_string(), IntentStatus::Completed, None, None);
+
+        let intent = contract.intents.get(&"intent-1".to_string()).unwrap();
+        assert!(intent.finalized);
+        assert_eq!(intent.escrow_released, ESCROW);
+        let released = last_event("escrow_released");
+        assert_eq!(released["amount"], "9500");
+        assert_eq!(released["protocol_fee"], "500");
+        // NEAR escrow funds no insurance share, so the treasury keeps it
+        assert_eq!(released["treasury_fee"], "450");
+        assert_eq!(released["insurance_fee"], "0");
+        assert_eq!(released["referrer_fee"], "50");
+    }
+
+    #[test]
+    fn failed_intent_refunds_the_client() {
+        let mut contract = setup_intent();
+        set_context("agent.test", START, 0);
+        contract.update_intent_status("intent-1".to_string(), IntentStatus::Failed, None, None);
+
+        let intent = contract.intents.get(&"intent-1".to_string()).unwrap();
+        assert!(intent.finalized);
+        assert_eq!(intent.escrow_released, 0);
+        assert_eq!(intent.escrow_refunded, ESCROW);
+    }
+}
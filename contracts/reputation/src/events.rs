@@ -0,0 +1,23 @@
+use near_sdk::env;
+use near_sdk::serde_json::{json, Value};
+
+// NEP-297 event standard name and version for this contract
+const EVENT_STANDARD: &str = "intellex_reputation";
+const EVENT_STANDARD_VERSION: &str = "1.0.0";
+
+// Emit a structured NEP-297 event so indexers can follow state changes
+pub(crate) fn emit_event(event: &str, data: Value) {
+    emit_standard_event(EVENT_STANDARD, EVENT_STANDARD_VERSION, event, data);
+}
+
+// Emit a NEP-297 event under another standard, e.g. NEP-171 for badge mints
+pub(crate) fn emit_standard_event(standard: &str, version: &str, event: &str, data: Value) {
+    let payload = json!({
+        "standard": standard,
+        "version": version,
+        "event": event,
+        "data": [data],
+    });
+
+    env::log_str(&format!("EVENT_JSON:{}", payload));
+}
@@ -0,0 +1,139 @@
+use crate::*;
+use crate::fixed_point::{div_round, to_points, SCORE_SCALE_BPS};
+
+// Cause of an intent failing. Intents failed without a cause count as wrong results.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum FailureCategory {
+    // Deadline passed before the agent delivered
+    Timeout,
+    WrongResult,
+    // Part of the work was delivered
+    PartialCompletion,
+    // Client walked away from stalled work; recorded automatically, never an interaction
+    ClientCancelled,
+}
+
+// How heavily each failure cause counts against the success rate (basis points of a failure)
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FailureWeights {
+    pub timeout_bps: u32,
+    pub wrong_result_bps: u32,
+    pub partial_completion_bps: u32,
+    pub client_cancelled_bps: u32,
+}
+
+impl Default for FailureWeights {
+    fn default() -> Self {
+        Self {
+            timeout_bps: 7_500,
+            wrong_result_bps: 10_000,
+            partial_completion_bps: 5_000,
+            client_cancelled_bps: 2_500,
+        }
+    }
+}
+
+impl FailureWeights {
+    pub(crate) fn assert_valid(&self) {
+        assert!(
+            self.timeout_bps <= 10_000
+                && self.wrong_result_bps <= 10_000
+                && self.partial_completion_bps <= 10_000
+                && self.client_cancelled_bps <= 10_000,
+            "Failure weights cannot exceed 10000 basis points"
+        );
+    }
+}
+
+// Agent's intent outcomes: completions and failures by cause
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Default, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FailureBreakdown {
+    completed: u64,
+    timeout: u64,
+    wrong_result: u64,
+    partial_completion: u64,
+    client_cancelled: u64,
+}
+
+impl FailureBreakdown {
+    pub(crate) fn record_completion(&mut self) {
+        self.completed += 1;
+    }
+
+    pub(crate) fn record(&mut self, category: FailureCategory) {
+        match category {
+            FailureCategory::Timeout => self.timeout += 1,
+            FailureCategory::WrongResult => self.wrong_result += 1,
+            FailureCategory::PartialCompletion => self.partial_completion += 1,
+            FailureCategory::ClientCancelled => self.client_cancelled += 1,
+        }
+    }
+
+    // Intent success rate (basis points, 0-10000) with each failure counted at its cause's
+    // weight. Completions and failures both come from finished intents, so with full weights
+    // this is the plain completed / finished rate; feedback does not enter it.
+    pub(crate) fn weighted_success_rate(&self, weights: &FailureWeights) -> u32 {
+        let weighted_failures = self.timeout as u128 * weights.timeout_bps as u128
+            + self.wrong_result as u128 * weights.wrong_result_bps as u128
+            + self.partial_completion as u128 * weights.partial_completion_bps as u128
+            + self.client_cancelled as u128 * weights.client_cancelled_bps as u128;
+        let denominator = self.completed as u128 * 10_000 + weighted_failures;
+        if denominator == 0 {
+            return SCORE_SCALE_BPS;
+        }
+
+        div_round(self.completed as u128 * 10_000 * SCORE_SCALE_BPS as u128, denominator) as u32
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Fail an intent with its cause (the agent for in-progress work, or an arbiter ruling on a
+    // dispute). Only arbiters choose the cause: agents cannot grade their own failures, so
+    // those count as wrong results, as do intents failed through update_intent_status.
+    pub fn fail_intent(&mut self, intent_id: String, category: FailureCategory, result: Option<String>) {
+        assert!(
+            category != FailureCategory::ClientCancelled,
+            "Client cancellations are recorded automatically"
+        );
+
+        let mut intent = self.internal_authorize_transition(&intent_id, IntentStatus::Failed, None);
+        // Disputed intents are failed by an arbiter, in-progress ones by their agent
+        intent.failure_category = Some(if intent.status == IntentStatus::Disputed {
+            category
+        } else {
+            FailureCategory::WrongResult
+        });
+        self.internal_apply_intent_transition(intent, IntentStatus::Failed, result);
+    }
+
+    // Get an agent's failures by cause, with its plain and weighted success rates
+    pub fn get_failure_breakdown(&self, agent_id: AccountId) -> Option<FailureBreakdownView> {
+        self.agent_reputations.get(&agent_id).map(|agent_rep| {
+            let success_rate = (agent_rep.successful_interactions * 100)
+                .checked_div(agent_rep.total_interactions)
+                .map_or(100, |rate| rate as u32);
+            let weighted_success_rate = to_points(agent_rep.failure_breakdown.weighted_success_rate(&self.failure_weights));
+
+            FailureBreakdownView {
+                breakdown: agent_rep.failure_breakdown,
+                success_rate,
+                weighted_success_rate,
+            }
+        })
+    }
+}
+
+// View struct for an agent's failure breakdown
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FailureBreakdownView {
+    breakdown: FailureBreakdown,
+    // Successful interactions per interaction (0-100)
+    success_rate: u32,
+    // Completed intents per finished intent with failures weighted by cause, as used in the score
+    weighted_success_rate: u32,
+}
@@ -0,0 +1,776 @@
+use crate::*;
+use crate::events::emit_event;
+use crate::fixed_point::{div_round, rating_score_bps};
+
+// Upper bound on feedback entries returned per page
+const MAX_FEEDBACK_PAGE: u32 = 50;
+
+// Upper bound on expired entries dropped from the aggregates per recalculation, keeping
+// feedback writes constant-cost however much history has expired
+const MAX_EXPIRED_PER_CALL: u64 = 50;
+
+// Upper bound on expired entries restored to the aggregates per recalculation after an
+// expiry period is lengthened
+const MAX_RESTORED_PER_CALL: u64 = 50;
+
+// Upper bound on feedback entries deleted per prune call
+const MAX_PRUNE_PER_CALL: u64 = 100;
+
+// Upper bound on amendments per feedback entry, bounding the revisions kept for audit
+const MAX_FEEDBACK_AMENDMENTS: usize = 3;
+
+// Upper bound on the length of an agent's reply to feedback
+const MAX_FEEDBACK_RESPONSE_LENGTH: usize = 1000;
+
+// Upper bound on a category's weight in the headline score (basis points, 5x the overall rating)
+pub(crate) const MAX_CATEGORY_WEIGHT_BPS: u32 = 50_000;
+
+// Weight of the overall rating in the headline score, against which category weights are set
+const OVERALL_RATING_WEIGHT_BPS: u32 = 10_000;
+
+// How a feedback category counts toward the headline score
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CategoryScoringConfig {
+    pub category: FeedbackCategory,
+    // Weight of the category average in the headline score, relative to the overall rating's
+    // 10000 (basis points, 20000 = twice the overall rating)
+    pub weight_bps: u32,
+    // Expiry of the category's ratings if shorter than the feedback expiry period (nanoseconds)
+    pub expiry_period: Option<u64>,
+}
+
+// Counts of 0-5 star ratings, overall and per category, for feedback that counts toward the score
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct RatingHistogram {
+    overall: [u32; 6],
+    // Indexed by FeedbackCategory code
+    categories: [[u32; 6]; 5],
+}
+
+impl RatingHistogram {
+    pub(crate) fn add(&mut self, feedback: &FeedbackEntry) {
+        self.overall[feedback.rating as usize] += 1;
+        for category in FeedbackCategory::ALL {
+            self.categories[category.code() as usize][feedback.category_ratings.get(category) as usize] += 1;
+        }
+    }
+
+    pub(crate) fn remove(&mut self, feedback: &FeedbackEntry) {
+        let overall = &mut self.overall[feedback.rating as usize];
+        *overall = overall.saturating_sub(1);
+        for category in FeedbackCategory::ALL {
+            let count = &mut self.categories[category.code() as usize][feedback.category_ratings.get(category) as usize];
+            *count = count.saturating_sub(1);
+        }
+    }
+}
+
+// A version of a feedback entry replaced by an amendment
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeedbackRevision {
+    rating: u8,
+    category_ratings: CategoryRatings,
+    message: Option<String>,
+    // When this version was replaced
+    replaced_at: u64,
+}
+
+// The rated agent's public reply to a feedback entry
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeedbackResponse {
+    text: String,
+    responded_at: u64,
+}
+
+// Rolling sums over the feedback inside the expiry window that counts toward the score, so
+// scores are recalculated without reading the feedback history
+#[derive(BorshDeserialize, BorshSerialize, Default, Clone)]
+pub struct FeedbackAggregates {
+    // Index of the oldest feedback entry inside the expiry window
+    window_start: u64,
+    // Sum of reviewer weights
+    weight_sum: u128,
+    // Sum of reviewer-weighted ratings
+    weighted_rating_sum: u128,
+    // The two sums above with each term also multiplied by the entry's index, from which
+    // recency weights relative to the window start are derived
+    indexed_weight_sum: u128,
+    indexed_rating_sum: u128,
+    // Sum of reviewer-weighted ratings per category, indexed by FeedbackCategory code
+    category_sums: [u128; 5],
+    // Sum of reviewer weights per category, indexed by FeedbackCategory code
+    category_weight_sums: [u128; 5],
+    // Index of the oldest entry inside each category's own expiry window, never behind
+    // window_start, indexed by FeedbackCategory code
+    category_window_starts: [u64; 5],
+}
+
+impl FeedbackAggregates {
+    fn add(&mut self, index: u64, feedback: &FeedbackEntry) {
+        let weight = feedback.reviewer_weight as u128;
+        self.weight_sum += weight;
+        self.weighted_rating_sum += weight * feedback.rating as u128;
+        self.indexed_weight_sum += index as u128 * weight;
+        self.indexed_rating_sum += index as u128 * weight * feedback.rating as u128;
+        for category in FeedbackCategory::ALL {
+            let code = category.code() as usize;
+            if index >= self.category_window_starts[code] {
+                self.category_sums[code] += weight * feedback.category_ratings.get(category) as u128;
+                self.category_weight_sums[code] += weight;
+            }
+        }
+    }
+
+    fn remove(&mut self, index: u64, feedback: &FeedbackEntry) {
+        let weight = feedback.reviewer_weight as u128;
+        self.weight_sum -= weight;
+        self.weighted_rating_sum -= weight * feedback.rating as u128;
+        self.indexed_weight_sum -= index as u128 * weight;
+        self.indexed_rating_sum -= index as u128 * weight * feedback.rating as u128;
+        for category in FeedbackCategory::ALL {
+            if index >= self.category_window_starts[category.code() as usize] {
+                self.remove_category(category, feedback);
+            }
+        }
+    }
+
+    fn add_category(&mut self, category: FeedbackCategory, feedback: &FeedbackEntry) {
+        let code = category.code() as usize;
+        let weight = feedback.reviewer_weight as u128;
+        self.category_sums[code] += weight * feedback.category_ratings.get(category) as u128;
+        self.category_weight_sums[code] += weight;
+    }
+
+    fn remove_category(&mut self, category: FeedbackCategory, feedback: &FeedbackEntry) {
+        let code = category.code() as usize;
+        let weight = feedback.reviewer_weight as u128;
+        self.category_sums[code] -= weight * feedback.category_ratings.get(category) as u128;
+        self.category_weight_sums[code] -= weight;
+    }
+
+    // Drop the entry at the front of the expiry window from the sums and move the window
+    // past it, along with any category window left behind
+    pub(crate) fn advance_window(&mut self, feedback: &FeedbackEntry) {
+        if feedback.counts_toward_score() {
+            self.remove(self.window_start, feedback);
+        }
+        self.window_start += 1;
+        for start in self.category_window_starts.iter_mut() {
+            *start = std::cmp::max(*start, self.window_start);
+        }
+    }
+
+    // Move the expiry window back over the entry just before it and count it again. Category
+    // windows stay where they are, so the entry's category ratings are not added.
+    fn rewind_window(&mut self, feedback: &FeedbackEntry) {
+        self.window_start -= 1;
+        if feedback.counts_toward_score() {
+            self.add(self.window_start, feedback);
+        }
+    }
+
+    // Weighted rating total and weight sum. With recency weighting, each entry's weight is
+    // further multiplied by its position in the history counted from the window start, so
+    // later feedback weighs more.
+    pub(crate) fn rating_totals(&self, recency_weighting: bool) -> (u128, u128) {
+        if !recency_weighting {
+            return (self.weighted_rating_sum, self.weight_sum);
+        }
+        // Every counted index is at least window_start, so neither subtraction underflows
+        let start = self.window_start as u128;
+        (
+            self.indexed_rating_sum - start * self.weighted_rating_sum + self.weighted_rating_sum,
+            self.indexed_weight_sum - start * self.weight_sum + self.weight_sum,
+        )
+    }
+
+    // Reviewer weight of the feedback counted toward the score, without recency weighting
+    pub(crate) fn evidence_weight(&self) -> u128 {
+        self.weight_sum
+    }
+
+    // Reviewer-weighted average rating of a category on the score scale (basis points), or None
+    // once none of its ratings are inside the category's expiry window
+    pub(crate) fn category_score(&self, category: FeedbackCategory) -> Option<u32> {
+        let code = category.code() as usize;
+        if self.category_weight_sums[code] == 0 {
+            return None;
+        }
+        Some(rating_score_bps(self.category_sums[code], self.category_weight_sums[code]))
+    }
+
+    // Reviewer-weighted average rating per category, or None without counted feedback. A
+    // category whose ratings have all expired averages 0.
+    pub(crate) fn category_averages(&self) -> Option<CategoryRatings> {
+        if self.weight_sum == 0 {
+            return None;
+        }
+        let average = |category: FeedbackCategory| {
+            let code = category.code() as usize;
+            self.category_sums[code].checked_div(self.category_weight_sums[code]).unwrap_or(0) as u8
+        };
+        Some(CategoryRatings {
+            accuracy: average(FeedbackCategory::Accuracy),
+            response_time: average(FeedbackCategory::ResponseTime),
+            communication: average(FeedbackCategory::Communication),
+            problem_solving: average(FeedbackCategory::ProblemSolving),
+            ethics: average(FeedbackCategory::Ethics),
+        })
+    }
+}
+
+impl AgentReputation {
+    // Append a feedback entry to the history and the aggregates
+    pub(crate) fn push_feedback(&mut self, feedback: &FeedbackEntry) {
+        let index = self.feedback_count;
+        self.rating_histogram.add(feedback);
+        self.feedback_aggregates.add(index, feedback);
+        self.feedback_history.insert(&index, feedback);
+        self.feedback_count += 1;
+    }
+
+    // Stop counting a feedback entry toward the score, e.g. after an upheld dispute
+    pub(crate) fn discount_feedback(&mut self, index: u64, feedback: &FeedbackEntry) {
+        self.rating_histogram.remove(feedback);
+        if index >= self.feedback_aggregates.window_start {
+            self.feedback_aggregates.remove(index, feedback);
+        }
+    }
+
+    // Count a feedback entry toward the score again, e.g. after a cleared audit
+    pub(crate) fn recount_feedback(&mut self, index: u64, feedback: &FeedbackEntry) {
+        self.rating_histogram.add(feedback);
+        if index >= self.feedback_aggregates.window_start {
+            self.feedback_aggregates.add(index, feedback);
+        }
+    }
+
+    // Move the expiry window past feedback older than `expiry_period`, dropping it from the
+    // aggregates, then move each category's window past ratings older than its own expiry
+    // period (indexed by FeedbackCategory code, none longer than `expiry_period`). Feedback is
+    // stored in submission order, so expired entries are always at the front of a window.
+    // Returns whether every window has moved past all expired entries.
+    pub(crate) fn expire_feedback(&mut self, expiry_period: u64, category_expiry_periods: [u64; 5], max_entries: u64) -> bool {
+        let now = env::block_timestamp();
+        let mut expired = 0;
+        loop {
+            let index = self.feedback_aggregates.window_start;
+            let feedback = match self.feedback_history.get(&index) {
+                Some(feedback) if now - feedback.timestamp > expiry_period => feedback,
+                _ => break,
+            };
+            if expired == max_entries {
+                return false;
+            }
+            self.feedback_aggregates.advance_window(&feedback);
+            expired += 1;
+        }
+
+        for category in FeedbackCategory::ALL {
+            let code = category.code() as usize;
+            let mut category_expired = 0;
+            loop {
+                let index = self.feedback_aggregates.category_window_starts[code];
+                let feedback = match self.feedback_history.get(&index) {
+                    Some(feedback) if now - feedback.timestamp > category_expiry_periods[code] => feedback,
+                    _ => break,
+                };
+                if category_expired == max_entries {
+                    return false;
+                }
+                if feedback.counts_toward_score() {
+                    self.feedback_aggregates.remove_category(category, &feedback);
+                }
+                self.feedback_aggregates.category_window_starts[code] += 1;
+                category_expired += 1;
+            }
+        }
+        true
+    }
+
+    // Whether the expiry window starts at the oldest stored entry with older ones pruned, so it
+    // may be missing feedback that a longer expiry period would count
+    pub(crate) fn window_reaches_pruned_feedback(&self) -> bool {
+        self.feedback_pruned > 0 && self.feedback_aggregates.window_start == self.feedback_pruned
+    }
+
+    // Move the expiry window, then each category's window, back over expired entries that are
+    // inside their lengthened expiry periods again, restoring them to the aggregates. Entries
+    // already pruned stay gone. Returns whether every window has reached its start, so that
+    // nothing is left to restore.
+    pub(crate) fn restore_feedback(&mut self, expiry_period: u64, category_expiry_periods: [u64; 5], max_entries: u64) -> bool {
+        let now = env::block_timestamp();
+        let mut restored = 0;
+        while self.feedback_aggregates.window_start > self.feedback_pruned {
+            let feedback = match self.feedback_history.get(&(self.feedback_aggregates.window_start - 1)) {
+                Some(feedback) if now - feedback.timestamp <= expiry_period => feedback,
+                _ => break,
+            };
+            if restored == max_entries {
+                return false;
+            }
+            self.feedback_aggregates.rewind_window(&feedback);
+            restored += 1;
+        }
+
+        for category in FeedbackCategory::ALL {
+            let code = category.code() as usize;
+            let mut category_restored = 0;
+            while self.feedback_aggregates.category_window_starts[code] > self.feedback_aggregates.window_start {
+                let index = self.feedback_aggregates.category_window_starts[code] - 1;
+                let feedback = match self.feedback_history.get(&index) {
+                    Some(feedback) if now - feedback.timestamp <= category_expiry_periods[code] => feedback,
+                    _ => break,
+                };
+                if category_restored == max_entries {
+                    return false;
+                }
+                if feedback.counts_toward_score() {
+                    self.feedback_aggregates.add_category(category, &feedback);
+                }
+                self.feedback_aggregates.category_window_starts[code] = index;
+                category_restored += 1;
+            }
+        }
+        true
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Get a page of an agent's feedback; anonymous reviewers are shown by pseudonym only, and
+    // without the intent, which would name them as its client
+    pub fn get_feedback(&self, agent_id: AccountId, from_index: u32, limit: u32) -> Vec<FeedbackView> {
+        let agent_rep = match self.agent_reputations.get(&agent_id) {
+            Some(agent_rep) => agent_rep,
+            None => return Vec::new(),
+        };
+
+        let from_index = from_index as u64;
+        let to_index = std::cmp::min(
+            from_index + std::cmp::min(limit, MAX_FEEDBACK_PAGE) as u64,
+            agent_rep.feedback_count,
+        );
+
+        (from_index..to_index)
+            .filter_map(|index| {
+                agent_rep.feedback_history.get(&index).map(|feedback| FeedbackView {
+                    index,
+                    anonymous: feedback.pseudonym.is_some(),
+                    intent_id: feedback.pseudonym.is_none().then_some(feedback.intent_id),
+                    reviewer: feedback.pseudonym.unwrap_or_else(|| feedback.user_id.to_string()),
+                    rating: feedback.rating,
+                    category_ratings: feedback.category_ratings,
+                    message: feedback.message,
+                    timestamp: feedback.timestamp,
+                    reviewer_weight: feedback.reviewer_weight,
+                    dispute_status: feedback.dispute_status,
+                    quarantined: feedback.quarantined,
+                    revisions: feedback.revisions,
+                    revoked_at: feedback.revoked_at,
+                    response: feedback.response,
+                })
+            })
+            .collect()
+    }
+
+    // Amend the caller's feedback within the amendment window. The replaced version is kept in
+    // the entry's revisions.
+    pub fn update_feedback(
+        &mut self,
+        agent_id: AccountId,
+        feedback_index: u64,
+        rating: u8,
+        category_ratings: CategoryRatings,
+        message: Option<String>,
+    ) {
+        self.assert_not_paused(PausableFeature::Feedback);
+        assert!(rating <= 5, "Rating must be between 0 and 5");
+        for category in FeedbackCategory::ALL {
+            assert!(category_ratings.get(category) <= 5, "Category ratings must be between 0 and 5");
+        }
+
+        let reviewer_id = env::predecessor_account_id();
+        let initial_storage = env::storage_usage();
+        let (mut agent_rep, mut feedback) = self.internal_amendable_feedback(&agent_id, feedback_index, &reviewer_id);
+        assert!(feedback.revisions.len() < MAX_FEEDBACK_AMENDMENTS, "Feedback has been amended too many times");
+
+        let counted = feedback.counts_toward_score();
+        if counted {
+            agent_rep.discount_feedback(feedback_index, &feedback);
+        }
+        if feedback.rating >= 3 && rating < 3 {
+            agent_rep.successful_interactions -= 1;
+        } else if feedback.rating < 3 && rating >= 3 {
+            agent_rep.successful_interactions += 1;
+        }
+
+        let previous_rating = feedback.rating;
+        feedback.revisions.push(FeedbackRevision {
+            rating: feedback.rating,
+            category_ratings: std::mem::replace(&mut feedback.category_ratings, category_ratings),
+            message: std::mem::replace(&mut feedback.message, message),
+            replaced_at: env::block_timestamp(),
+        });
+        feedback.rating = rating;
+        if counted {
+            agent_rep.recount_feedback(feedback_index, &feedback);
+        }
+        agent_rep.feedback_history.insert(&feedback_index, &feedback);
+
+        self.internal_rescore_after_feedback_change(&agent_id, &mut agent_rep);
+        // The reviewer pays for keeping the replaced version
+        self.internal_charge_storage(&reviewer_id, initial_storage);
+
+        emit_event(
+            "feedback_amended",
+            json!({
+                "agent_id": agent_id,
+                "index": feedback_index,
+                "previous_rating": previous_rating,
+                "rating": rating,
+                "revision": feedback.revisions.len(),
+            }),
+        );
+    }
+
+    // Withdraw the caller's feedback within the amendment window. The entry stays on record,
+    // marked revoked, but no longer counts toward the score or the interaction counts.
+    pub fn revoke_feedback(&mut self, agent_id: AccountId, feedback_index: u64) {
+        self.assert_not_paused(PausableFeature::Feedback);
+
+        let reviewer_id = env::predecessor_account_id();
+        let (mut agent_rep, mut feedback) = self.internal_amendable_feedback(&agent_id, feedback_index, &reviewer_id);
+
+        if feedback.counts_toward_score() {
+            agent_rep.discount_feedback(feedback_index, &feedback);
+        }
+        agent_rep.total_interactions -= 1;
+        if feedback.rating >= 3 {
+            agent_rep.successful_interactions -= 1;
+        }
+        feedback.revoked_at = Some(env::block_timestamp());
+        agent_rep.feedback_history.insert(&feedback_index, &feedback);
+
+        self.internal_rescore_after_feedback_change(&agent_id, &mut agent_rep);
+
+        emit_event(
+            "feedback_revoked",
+            json!({
+                "agent_id": agent_id,
+                "index": feedback_index,
+                "rating": feedback.rating,
+            }),
+        );
+    }
+
+    // Attach the caller's public reply to feedback it received (one reply per entry). The reply
+    // is shown with the feedback and does not affect the score.
+    pub fn respond_to_feedback(&mut self, feedback_index: u64, response: String) {
+        let agent_id = env::predecessor_account_id();
+        assert!(!response.is_empty(), "Response cannot be empty");
+        assert!(response.len() <= MAX_FEEDBACK_RESPONSE_LENGTH, "Response too long");
+
+        let initial_storage = env::storage_usage();
+        let mut agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+        let mut feedback = agent_rep.feedback_history.get(&feedback_index).expect("Feedback entry not found");
+        assert!(feedback.response.is_none(), "Feedback already has a response");
+
+        feedback.response = Some(FeedbackResponse {
+            text: response,
+            responded_at: env::block_timestamp(),
+        });
+        agent_rep.feedback_history.insert(&feedback_index, &feedback);
+
+        // The agent pays for the storage of its reply
+        self.internal_charge_storage(&agent_id, initial_storage);
+
+        emit_event(
+            "feedback_response_added",
+            json!({
+                "agent_id": agent_id,
+                "index": feedback_index,
+            }),
+        );
+    }
+
+    // Delete up to `max_entries` of an agent's expired feedback, oldest first (anyone may call),
+    // and apply the retention policy. Each reviewer's storage balance is released for the
+    // entry they paid for. After an expiry period is lengthened, the call first restores
+    // feedback back inside the window, and deletes nothing until that is done.
+    pub fn prune_expired_feedback(&mut self, agent_id: AccountId, max_entries: u64) -> u64 {
+        let max_entries = std::cmp::min(max_entries, MAX_PRUNE_PER_CALL);
+        let mut agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+
+        // Only entries already outside the expiry window are deleted
+        let prune_until = if self.internal_refresh_feedback_window(&mut agent_rep, max_entries) {
+            agent_rep.feedback_aggregates.window_start
+        } else {
+            agent_rep.feedback_pruned
+        };
+        let pruned = self.internal_remove_oldest_feedback(&agent_id, &mut agent_rep, prune_until, max_entries);
+
+        self.recalculate_reputation_with_categories(&agent_id, &mut agent_rep);
+        self.internal_save_agent(&agent_id, &agent_rep);
+        self.internal_update_shadow_score(&agent_id, &mut agent_rep);
+        self.internal_apply_retention(&agent_id, &mut agent_rep);
+
+        if pruned > 0 {
+            emit_event(
+                "feedback_pruned",
+                json!({
+                    "agent_id": agent_id,
+                    "count": pruned,
+                    "pruned_before": agent_rep.feedback_pruned,
+                }),
+            );
+        }
+
+        pruned
+    }
+
+    // Delete up to `max_entries` of the agent's oldest feedback below index `until`, emitting
+    // each as an archival event and releasing its reviewer's storage. Stops early at entries
+    // under a pending dispute or an open audit, which must stay readable. The caller saves
+    // the agent record.
+    pub(crate) fn internal_remove_oldest_feedback(
+        &mut self,
+        agent_id: &AccountId,
+        agent_rep: &mut AgentReputation,
+        until: u64,
+        max_entries: u64,
+    ) -> u64 {
+        let until = match self.internal_open_audit_from_index(agent_id) {
+            Some(audit_from) => std::cmp::min(until, audit_from),
+            None => until,
+        };
+
+        let mut removed = 0;
+        while removed < max_entries && agent_rep.feedback_pruned < until {
+            let index = agent_rep.feedback_pruned;
+            let feedback = agent_rep.feedback_history.get(&index).expect("Feedback entry missing");
+            if feedback.dispute_status == Some(FeedbackDisputeStatus::Pending) {
+                break;
+            }
+
+            // Entries still inside the expiry window leave the aggregates too
+            if index >= agent_rep.feedback_aggregates.window_start {
+                agent_rep.feedback_aggregates.advance_window(&feedback);
+            }
+
+            let initial_storage = env::storage_usage();
+            agent_rep.feedback_history.remove(&index);
+            self.internal_release_storage(&feedback.user_id, initial_storage - env::storage_usage());
+
+            emit_event(
+                "feedback_archived",
+                json!({
+                    "agent_id": agent_id,
+                    "index": index,
+                    "reviewer": feedback.pseudonym.clone().unwrap_or_else(|| feedback.user_id.to_string()),
+                    "anonymous": feedback.pseudonym.is_some(),
+                    "intent_id": feedback.pseudonym.is_none().then_some(&feedback.intent_id),
+                    "rating": feedback.rating,
+                    "category_ratings": feedback.category_ratings,
+                    "message": feedback.message,
+                    "timestamp": feedback.timestamp,
+                    "reviewer_weight": feedback.reviewer_weight,
+                    "dispute_status": feedback.dispute_status,
+                    "quarantined": feedback.quarantined,
+                }),
+            );
+
+            agent_rep.feedback_pruned += 1;
+            removed += 1;
+        }
+
+        removed
+    }
+
+    // Get how an agent's ratings are distributed across 0-5 stars, overall and per category.
+    // Feedback removed by an upheld dispute or quarantined by an audit is not counted.
+    pub fn get_rating_histogram(&self, agent_id: AccountId) -> Option<RatingHistogramView> {
+        self.agent_reputations.get(&agent_id).map(|rep| RatingHistogramView {
+            overall: rep.rating_histogram.overall,
+            categories: FeedbackCategory::ALL
+                .iter()
+                .map(|category| (*category, rep.rating_histogram.categories[category.code() as usize]))
+                .collect(),
+        })
+    }
+
+    // Reveal the account behind a feedback pseudonym during an abuse investigation.
+    // A change method so the lookup is access-controlled and leaves an audit trail.
+    pub fn reveal_pseudonym(&mut self, pseudonym: String, reason: String) -> AccountId {
+        self.assert_owner_or_governance();
+
+        let reviewer_id = self.pseudonyms.get(&pseudonym).expect("Unknown pseudonym");
+
+        emit_event(
+            "pseudonym_revealed",
+            json!({
+                "pseudonym": pseudonym,
+                "revealed_by": env::predecessor_account_id(),
+                "reason": reason,
+            }),
+        );
+
+        reviewer_id
+    }
+
+    // Drop expired feedback from an agent's aggregates, a bounded batch at a time
+    pub(crate) fn internal_expire_feedback(&self, agent_rep: &mut AgentReputation) {
+        self.internal_refresh_feedback_window(agent_rep, MAX_EXPIRED_PER_CALL);
+    }
+
+    // Bring an agent's aggregates up to the current expiry periods, moving each window at most
+    // `max_entries` in either direction. Feedback is only restored while the aggregates predate
+    // the last lengthened period, so the usual write reads no entries before the window.
+    // Returns whether the aggregates have been rebaselined for that change.
+    fn internal_refresh_feedback_window(&self, agent_rep: &mut AgentReputation, max_entries: u64) -> bool {
+        let category_expiry_periods = self.internal_category_expiry_periods();
+        if agent_rep.feedback_window_epoch < self.feedback_window_epoch {
+            let restore_limit = std::cmp::min(max_entries, MAX_RESTORED_PER_CALL);
+            if agent_rep.restore_feedback(self.feedback_expiry_period, category_expiry_periods, restore_limit) {
+                agent_rep.feedback_window_epoch = self.feedback_window_epoch;
+            }
+        }
+        agent_rep.expire_feedback(self.feedback_expiry_period, category_expiry_periods, max_entries);
+        agent_rep.feedback_window_epoch == self.feedback_window_epoch
+    }
+
+    // Expiry period of each category's ratings, indexed by FeedbackCategory code. Categories
+    // without their own period, or with one longer than the feedback expiry period, use the
+    // feedback expiry period.
+    pub(crate) fn internal_category_expiry_periods(&self) -> [u64; 5] {
+        self.internal_category_expiry_periods_within(self.feedback_expiry_period)
+    }
+
+    // Expiry period of each category's ratings under a feedback expiry period of
+    // `expiry_period`, indexed by FeedbackCategory code
+    pub(crate) fn internal_category_expiry_periods_within(&self, expiry_period: u64) -> [u64; 5] {
+        let mut periods = [expiry_period; 5];
+        for config in &self.category_scoring {
+            if let Some(expiry_period) = config.expiry_period {
+                let period = &mut periods[config.category.code() as usize];
+                *period = std::cmp::min(*period, expiry_period);
+            }
+        }
+        periods
+    }
+
+    // Start a new feedback window epoch if the feedback or any category expiry period is longer
+    // than before, so each agent's aggregates restore the feedback back inside its windows
+    pub(crate) fn internal_bump_feedback_window_epoch(&mut self, old_expiry_period: u64, old_periods: [u64; 5]) {
+        let new_periods = self.internal_category_expiry_periods();
+        if self.feedback_expiry_period > old_expiry_period
+            || new_periods.iter().zip(old_periods).any(|(new, old)| *new > old)
+        {
+            self.feedback_window_epoch += 1;
+        }
+    }
+
+    // Blend the overall rating score (basis points) with the agent's category scores by their
+    // configured weights. Categories without ratings in their window are left out.
+    pub(crate) fn internal_weighted_rating_score(&self, agent_rep: &AgentReputation, rating_score: u32) -> u32 {
+        let mut weighted_sum = rating_score as u64 * OVERALL_RATING_WEIGHT_BPS as u64;
+        let mut total_weight = OVERALL_RATING_WEIGHT_BPS as u64;
+        for config in &self.category_scoring {
+            if let Some(category_score) = agent_rep.feedback_aggregates.category_score(config.category) {
+                weighted_sum += category_score as u64 * config.weight_bps as u64;
+                total_weight += config.weight_bps as u64;
+            }
+        }
+        div_round(weighted_sum as u128, total_weight as u128) as u32
+    }
+
+    // Load feedback the reviewer may still amend or revoke
+    fn internal_amendable_feedback(
+        &self,
+        agent_id: &AccountId,
+        feedback_index: u64,
+        reviewer_id: &AccountId,
+    ) -> (AgentReputation, FeedbackEntry) {
+        let agent_rep = self.agent_reputations.get(agent_id).expect("Agent not registered");
+        let feedback = agent_rep.feedback_history.get(&feedback_index).expect("Feedback entry not found");
+        assert_eq!(&feedback.user_id, reviewer_id, "Only the reviewer can change their feedback");
+        assert!(feedback.revoked_at.is_none(), "Feedback has been revoked");
+        assert!(feedback.dispute_status.is_none(), "Disputed feedback cannot be changed");
+        assert!(
+            env::block_timestamp() <= feedback.timestamp + self.feedback_amendment_window,
+            "Feedback amendment window has passed"
+        );
+        (agent_rep, feedback)
+    }
+
+    fn internal_rescore_after_feedback_change(&mut self, agent_id: &AccountId, agent_rep: &mut AgentReputation) {
+        if agent_rep.total_interactions == 0 {
+            // No feedback left to score: back to the neutral starting score
+            agent_rep.set_score(INITIAL_SCORE);
+            agent_rep.category_scores = CategoryRatings::default();
+        } else {
+            self.recalculate_reputation_with_categories(agent_id, agent_rep);
+        }
+        agent_rep.last_update = env::block_timestamp();
+        self.internal_save_agent(agent_id, agent_rep);
+        self.internal_update_shadow_score(agent_id, agent_rep);
+    }
+
+    // Derive the reviewer's stable pseudonym for an agent and remember who it belongs to
+    pub(crate) fn internal_pseudonym_for(&mut self, reviewer_id: &AccountId, agent_id: &AccountId) -> String {
+        let preimage = [
+            reviewer_id.as_bytes(),
+            b":",
+            agent_id.as_bytes(),
+            b":",
+            self.pseudonym_salt.as_slice(),
+        ]
+        .concat();
+        let hash = env::sha256(&preimage);
+        let pseudonym: String = format!(
+            "anon-{}",
+            hash[..8].iter().map(|b| format!("{:02x}", b)).collect::<String>()
+        );
+
+        if !self.pseudonyms.contains_key(&pseudonym) {
+            self.pseudonyms.insert(&pseudonym, reviewer_id);
+        }
+
+        pseudonym
+    }
+}
+
+// View struct for an agent's rating distribution; arrays count ratings of 0 to 5 stars
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RatingHistogramView {
+    overall: [u32; 6],
+    categories: Vec<(FeedbackCategory, [u32; 6])>,
+}
+
+// View struct for a single feedback entry
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeedbackView {
+    index: u64,
+    // Reviewer account, or pseudonym for anonymous feedback
+    reviewer: String,
+    anonymous: bool,
+    // None for anonymous feedback
+    intent_id: Option<String>,
+    rating: u8,
+    category_ratings: CategoryRatings,
+    message: Option<String>,
+    timestamp: u64,
+    reviewer_weight: u32,
+    dispute_status: Option<FeedbackDisputeStatus>,
+    quarantined: bool,
+    // Earlier versions of an amended entry, oldest first
+    revisions: Vec<FeedbackRevision>,
+    revoked_at: Option<u64>,
+    // The agent's reply, if any
+    response: Option<FeedbackResponse>,
+}
@@ -0,0 +1,185 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Bond an agent must attach to dispute a feedback entry (0.1 NEAR)
+const FEEDBACK_DISPUTE_BOND: Balance = 100_000_000_000_000_000_000_000;
+
+// Feedback disputes an agent may open per quota period
+const FEEDBACK_DISPUTE_QUOTA: u32 = 3;
+
+// Length of the feedback dispute quota period (30 days)
+const FEEDBACK_DISPUTE_PERIOD: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+// Maximum length of a dispute reason or ruling note
+const MAX_FEEDBACK_DISPUTE_REASON_LENGTH: usize = 1_000;
+
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum FeedbackDisputeStatus {
+    // Awaiting a governance ruling
+    Pending,
+    // Feedback found malicious and removed from scoring; bond returned
+    Upheld,
+    // Feedback stands; bond forfeited to the treasury
+    Rejected,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeedbackDispute {
+    pub(crate) agent_id: AccountId,
+    // Index of the disputed entry in the agent's feedback history
+    pub(crate) feedback_index: u64,
+    pub(crate) reason: String,
+    pub(crate) bond: U128,
+    pub(crate) status: FeedbackDisputeStatus,
+    pub(crate) opened_at: u64,
+    pub(crate) resolved_at: Option<u64>,
+    pub(crate) resolved_by: Option<AccountId>,
+    pub(crate) ruling_note: Option<String>,
+}
+
+// Feedback disputes opened by an agent in the current quota period
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct FeedbackDisputeQuota {
+    period_start: u64,
+    disputes_in_period: u32,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Dispute a feedback entry left for the calling agent. Requires the dispute bond as deposit.
+    #[payable]
+    pub fn dispute_feedback(&mut self, feedback_index: u64, reason: String) -> u64 {
+        let agent_id = env::predecessor_account_id();
+        assert_eq!(env::attached_deposit(), FEEDBACK_DISPUTE_BOND, "Attach exactly the feedback dispute bond");
+        assert!(reason.len() <= MAX_FEEDBACK_DISPUTE_REASON_LENGTH, "Dispute reason too long");
+
+        let mut agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+        let mut feedback = agent_rep.feedback_history.get(&feedback_index).expect("Feedback entry not found");
+        assert!(feedback.dispute_status.is_none(), "Feedback entry has already been disputed");
+        feedback.dispute_status = Some(FeedbackDisputeStatus::Pending);
+        agent_rep.feedback_history.insert(&feedback_index, &feedback);
+
+        // Enforce the per-period dispute quota
+        let now = env::block_timestamp();
+        let quota = &mut agent_rep.feedback_dispute_quota;
+        if now - quota.period_start >= FEEDBACK_DISPUTE_PERIOD {
+            quota.period_start = now;
+            quota.disputes_in_period = 0;
+        }
+        assert!(quota.disputes_in_period < FEEDBACK_DISPUTE_QUOTA, "Feedback dispute quota exhausted for this period");
+        quota.disputes_in_period += 1;
+
+        self.agent_reputations.insert(&agent_id, &agent_rep);
+
+        let dispute_id = self.next_feedback_dispute_id;
+        self.next_feedback_dispute_id += 1;
+        self.feedback_disputes.insert(
+            &dispute_id,
+            &FeedbackDispute {
+                agent_id: agent_id.clone(),
+                feedback_index,
+                reason,
+                bond: U128(FEEDBACK_DISPUTE_BOND),
+                status: FeedbackDisputeStatus::Pending,
+                opened_at: now,
+                resolved_at: None,
+                resolved_by: None,
+                ruling_note: None,
+            },
+        );
+
+        emit_event(
+            "feedback_disputed",
+            json!({
+                "dispute_id": dispute_id,
+                "agent_id": agent_id,
+                "feedback_index": feedback_index,
+            }),
+        );
+
+        dispute_id
+    }
+
+    // Rule on a pending feedback dispute (owner or governance). Upheld disputes remove the
+    // entry from scoring and return the bond; rejected disputes forfeit it to the treasury.
+    pub fn resolve_feedback_dispute(&mut self, dispute_id: u64, uphold: bool, ruling_note: Option<String>) -> Promise {
+        self.assert_owner_or_governance();
+
+        let mut dispute = self.feedback_disputes.get(&dispute_id).expect("Feedback dispute not found");
+        assert!(dispute.status == FeedbackDisputeStatus::Pending, "Feedback dispute already resolved");
+        if let Some(note) = &ruling_note {
+            assert!(note.len() <= MAX_FEEDBACK_DISPUTE_REASON_LENGTH, "Ruling note too long");
+        }
+
+        let status = if uphold { FeedbackDisputeStatus::Upheld } else { FeedbackDisputeStatus::Rejected };
+
+        let mut agent_rep = self.agent_reputations.get(&dispute.agent_id).expect("Agent not registered");
+        let mut feedback = agent_rep.feedback_history.get(&dispute.feedback_index).expect("Feedback entry not found");
+        // Quarantined feedback is already out of the score
+        let counted = feedback.counts_toward_score();
+        feedback.dispute_status = Some(status);
+        agent_rep.feedback_history.insert(&dispute.feedback_index, &feedback);
+        if uphold && counted {
+            agent_rep.discount_feedback(dispute.feedback_index, &feedback);
+            self.recalculate_reputation_with_categories(&dispute.agent_id, &mut agent_rep);
+            agent_rep.last_update = env::block_timestamp();
+        }
+        self.internal_save_agent(&dispute.agent_id, &agent_rep);
+        if uphold {
+            self.internal_update_shadow_score(&dispute.agent_id, &mut agent_rep);
+        }
+
+        dispute.status = status;
+        dispute.resolved_at = Some(env::block_timestamp());
+        dispute.resolved_by = Some(env::predecessor_account_id());
+        dispute.ruling_note = ruling_note;
+        self.feedback_disputes.insert(&dispute_id, &dispute);
+
+        emit_event(
+            "feedback_dispute_resolved",
+            json!({
+                "dispute_id": dispute_id,
+                "agent_id": dispute.agent_id,
+                "feedback_index": dispute.feedback_index,
+                "status": status,
+            }),
+        );
+
+        let bond_recipient = if uphold { dispute.agent_id } else { self.treasury_id.clone() };
+        Promise::new(bond_recipient).transfer(dispute.bond.0)
+    }
+
+    // Get a feedback dispute by ID
+    pub fn get_feedback_dispute(&self, dispute_id: u64) -> Option<FeedbackDispute> {
+        self.feedback_disputes.get(&dispute_id)
+    }
+
+    // Get the feedback dispute bond and how many disputes the agent has left this period
+    pub fn get_feedback_dispute_quota(&self, agent_id: AccountId) -> Option<FeedbackDisputeQuotaView> {
+        self.agent_reputations.get(&agent_id).map(|rep| {
+            let now = env::block_timestamp();
+            let quota = &rep.feedback_dispute_quota;
+            let period_active = now - quota.period_start < FEEDBACK_DISPUTE_PERIOD;
+            let used = if period_active { quota.disputes_in_period } else { 0 };
+
+            FeedbackDisputeQuotaView {
+                bond: U128(FEEDBACK_DISPUTE_BOND),
+                quota: FEEDBACK_DISPUTE_QUOTA,
+                remaining: FEEDBACK_DISPUTE_QUOTA.saturating_sub(used),
+                period_resets_at: if period_active { quota.period_start + FEEDBACK_DISPUTE_PERIOD } else { now },
+            }
+        })
+    }
+}
+
+// View struct for an agent's feedback dispute allowance
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeedbackDisputeQuotaView {
+    bond: U128,
+    quota: u32,
+    remaining: u32,
+    period_resets_at: u64,
+}
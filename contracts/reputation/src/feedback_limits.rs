@@ -0,0 +1,100 @@
+use crate::*;
+
+// Upper bounds keeping the limits from locking reviewers out (30 days and 1 year)
+pub(crate) const MAX_FEEDBACK_INTERVAL: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+pub(crate) const MAX_DAMPENING_WINDOW: u64 = 365 * 24 * 60 * 60 * 1_000_000_000;
+
+// Limits on how often, and how heavily, one reviewer's feedback counts for one agent.
+// Feedback is already limited to one entry per finished intent; these limits stop a client
+// from dominating an agent's score through many small intents.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeedbackLimitConfig {
+    // Minimum time between two feedback entries from the same reviewer for the same agent
+    // (nanoseconds, 0 = no limit beyond one per intent)
+    pub min_feedback_interval: u64,
+    // Weight each further feedback from the same reviewer keeps relative to the one before
+    // (basis points, 10000 disables dampening)
+    pub repeat_weight_decay_bps: u32,
+    // Lowest share of its weight repeated feedback is dampened to (basis points)
+    pub min_repeat_weight_bps: u32,
+    // Repeated feedback is counted over this window, starting with the reviewer's first
+    // feedback in it (nanoseconds)
+    pub dampening_window: u64,
+}
+
+impl Default for FeedbackLimitConfig {
+    fn default() -> Self {
+        Self {
+            min_feedback_interval: 60 * 60 * 1_000_000_000, // 1 hour in nanoseconds
+            repeat_weight_decay_bps: 5_000,
+            min_repeat_weight_bps: 1_000,
+            dampening_window: 90 * 24 * 60 * 60 * 1_000_000_000, // 90 days in nanoseconds
+        }
+    }
+}
+
+impl FeedbackLimitConfig {
+    // Share of its weight (basis points) feedback keeps after `previous` feedback entries from
+    // the same reviewer in the window
+    fn repeat_weight_bps(&self, previous: u32) -> u32 {
+        let mut weight_bps = 10_000u32;
+        for _ in 0..previous {
+            if weight_bps <= self.min_repeat_weight_bps {
+                break;
+            }
+            weight_bps = weight_bps * self.repeat_weight_decay_bps / 10_000;
+        }
+        std::cmp::max(weight_bps, self.min_repeat_weight_bps)
+    }
+}
+
+// A reviewer's recent feedback for one agent
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ReviewerFeedbackHistory {
+    // Feedback entries since the window started
+    count: u32,
+    window_started_at: u64,
+    last_feedback_at: u64,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Get the feedback rate limit and repeat-reviewer dampening
+    pub fn get_feedback_limit_config(&self) -> FeedbackLimitConfig {
+        self.feedback_limit_config.clone()
+    }
+
+    // Enforce the reviewer's rate limit for the agent and record the new feedback. Returns the
+    // share of its weight (basis points) the feedback keeps after repeat dampening.
+    pub(crate) fn internal_record_reviewer_feedback(&mut self, reviewer_id: &AccountId, agent_id: &AccountId) -> u32 {
+        let config = &self.feedback_limit_config;
+        let now = env::block_timestamp();
+        let key = format!("{}:{}", reviewer_id, agent_id);
+
+        let mut history = match self.reviewer_feedback.get(&key) {
+            Some(history) => {
+                let next_allowed_at = history.last_feedback_at + config.min_feedback_interval;
+                assert!(
+                    now >= next_allowed_at,
+                    "Feedback for this agent is rate limited until {}",
+                    next_allowed_at
+                );
+                history
+            },
+            None => ReviewerFeedbackHistory { count: 0, window_started_at: now, last_feedback_at: now },
+        };
+
+        if now >= history.window_started_at + config.dampening_window {
+            history.count = 0;
+            history.window_started_at = now;
+        }
+        let weight_bps = config.repeat_weight_bps(history.count);
+
+        history.count += 1;
+        history.last_feedback_at = now;
+        self.reviewer_feedback.insert(&key, &history);
+
+        weight_bps
+    }
+}
@@ -0,0 +1,153 @@
+use crate::*;
+
+// Upper bound keeping fee evaluation cheap
+pub(crate) const MAX_FEE_DISCOUNT_TIERS: usize = 10;
+
+// Protocol fee discount for agents at or above a trust level with a dispute rate at or below
+// a threshold
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeDiscountTier {
+    pub min_trust_level: TrustLevel,
+    // Disputes opened per intent (basis points, 100 = 1%)
+    pub max_dispute_rate_bps: u32,
+    // Share of the protocol fee waived (basis points, 5000 = half)
+    pub discount_bps: u16,
+}
+
+// How the protocol fee is divided (basis points, summing to 10000). Without a referrer on the
+// intent, the referrer share goes to the treasury. The insurance pool holds ITLX only, so its
+// share of fees on NEAR escrow goes to the treasury too. Treasury and referrer shares are paid
+// like the agent's payout, so a share whose transfer fails is held for its receiver to claim.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeSplit {
+    pub treasury_bps: u16,
+    pub insurance_bps: u16,
+    pub referrer_bps: u16,
+}
+
+impl Default for FeeSplit {
+    fn default() -> Self {
+        Self {
+            treasury_bps: 8_000,
+            insurance_bps: 1_000,
+            referrer_bps: 1_000,
+        }
+    }
+}
+
+impl FeeSplit {
+    pub(crate) fn assert_valid(&self) {
+        assert_eq!(
+            self.treasury_bps as u32 + self.insurance_bps as u32 + self.referrer_bps as u32,
+            10_000,
+            "Fee split must add up to 10000 basis points"
+        );
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Get the protocol fee, its discount tiers and how it is split
+    pub fn get_fee_config(&self) -> FeeConfigView {
+        FeeConfigView {
+            protocol_fee_bps: self.protocol_fee_bps,
+            fee_discount_tiers: self.fee_discount_tiers.clone(),
+            fee_split: self.fee_split.clone(),
+        }
+    }
+
+    // Protocol fee that would apply to the agent's escrow payouts right now. The rate is
+    // re-evaluated whenever escrow is released, so it follows the agent's reputation.
+    pub fn get_fee_rate(&self, agent_id: AccountId) -> FeeRateView {
+        let discount_bps = self.internal_fee_discount_bps(&agent_id);
+        FeeRateView {
+            base_fee_bps: self.protocol_fee_bps,
+            discount_bps,
+            effective_fee_bps: apply_discount(self.protocol_fee_bps, discount_bps),
+        }
+    }
+
+    // Get the fee discount tiers in evaluation order
+    pub fn get_fee_discount_tiers(&self) -> Vec<FeeDiscountTier> {
+        self.fee_discount_tiers.clone()
+    }
+
+    // Protocol fee (basis points) for a payout to the agent
+    #[cfg(feature = "escrow")]
+    pub(crate) fn internal_fee_rate_bps(&self, agent_id: &AccountId) -> u16 {
+        apply_discount(self.protocol_fee_bps, self.internal_fee_discount_bps(agent_id))
+    }
+
+    // Divide a protocol fee into its (treasury, insurance pool, referrer) shares
+    #[cfg(feature = "escrow")]
+    pub(crate) fn internal_split_fee(&self, fee: Balance, asset: EscrowAsset, has_referrer: bool) -> (Balance, Balance, Balance) {
+        let split = &self.fee_split;
+        let insurance = if asset == EscrowAsset::Itlx {
+            fee * split.insurance_bps as u128 / 10_000
+        } else {
+            0
+        };
+        let referrer = if has_referrer {
+            fee * split.referrer_bps as u128 / 10_000
+        } else {
+            0
+        };
+
+        (fee - insurance - referrer, insurance, referrer)
+    }
+
+    // Discount of the first tier the agent qualifies for; unregistered agents get none
+    fn internal_fee_discount_bps(&self, agent_id: &AccountId) -> u16 {
+        let agent_rep = match self.agent_reputations.get(agent_id) {
+            Some(agent_rep) => agent_rep,
+            None => return 0,
+        };
+        let trust_level = self.get_trust_level(agent_rep.score);
+        let dispute_rate_bps = agent_rep.dispute_stats.dispute_rate_bps();
+
+        self.fee_discount_tiers
+            .iter()
+            .find(|tier| trust_level >= tier.min_trust_level && dispute_rate_bps <= tier.max_dispute_rate_bps as u64)
+            .map_or(0, |tier| tier.discount_bps)
+    }
+}
+
+fn apply_discount(fee_bps: u16, discount_bps: u16) -> u16 {
+    (fee_bps as u32 * (10_000 - discount_bps as u32) / 10_000) as u16
+}
+
+// Tiers rewarding top agents with few disputes
+pub(crate) fn default_fee_discount_tiers() -> Vec<FeeDiscountTier> {
+    vec![
+        FeeDiscountTier {
+            min_trust_level: TrustLevel::Master,
+            max_dispute_rate_bps: 99, // Under 1% of intents disputed
+            discount_bps: 5_000,
+        },
+        FeeDiscountTier {
+            min_trust_level: TrustLevel::Expert,
+            max_dispute_rate_bps: 200,
+            discount_bps: 2_500,
+        },
+    ]
+}
+
+// View struct for an agent's effective protocol fee
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeRateView {
+    base_fee_bps: u16,
+    discount_bps: u16,
+    effective_fee_bps: u16,
+}
+
+// View struct for the protocol fee configuration
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeConfigView {
+    protocol_fee_bps: u16,
+    fee_discount_tiers: Vec<FeeDiscountTier>,
+    fee_split: FeeSplit,
+}
@@ -0,0 +1,219 @@
+// Fixed-point arithmetic shared by the scoring paths. Scores are carried in basis points of the
+// 0-100 scale (0-10000) through every step of the calculation, and each division rounds half
+// up, so no step loses more than half a basis point. Scores are only reduced to whole 0-100
+// points for display, rounding down so a point is shown once it is fully earned.
+
+// Full score in basis points (100 points)
+pub(crate) const SCORE_SCALE_BPS: u32 = 10_000;
+
+// Basis points per score point
+const BPS_PER_POINT: u32 = SCORE_SCALE_BPS / 100;
+
+// Highest star rating
+const MAX_RATING: u128 = 5;
+
+// 256-bit intermediate for token-amount products that can exceed u128. The lints fire on
+// code generated by the macro.
+#[allow(clippy::all)]
+mod u256 {
+    uint::construct_uint! {
+        pub(crate) struct U256(4);
+    }
+}
+use u256::U256;
+
+// Divide, rounding half up; 0 when the denominator is 0
+pub(crate) fn div_round(numerator: u128, denominator: u128) -> u128 {
+    if denominator == 0 {
+        return 0;
+    }
+    (numerator + denominator / 2) / denominator
+}
+
+// `value * numerator / denominator`, rounded down, without overflowing the product
+pub(crate) fn mul_div(value: u128, numerator: u128, denominator: u128) -> u128 {
+    let result = U256::from(value) * U256::from(numerator) / U256::from(denominator);
+    u128::try_from(result).expect("Arithmetic overflow")
+}
+
+// `value * numerator / denominator`, rounded up, without overflowing the product
+pub(crate) fn mul_div_ceil(value: u128, numerator: u128, denominator: u128) -> u128 {
+    let (quotient, remainder) = (U256::from(value) * U256::from(numerator)).div_mod(U256::from(denominator));
+    let result = if remainder.is_zero() { quotient } else { quotient + 1 };
+    u128::try_from(result).expect("Arithmetic overflow")
+}
+
+// Cap a score at the top of the scale
+pub(crate) fn clamp_score(score_bps: u128) -> u32 {
+    std::cmp::min(score_bps, SCORE_SCALE_BPS as u128) as u32
+}
+
+// Score (basis points) of a weighted 0-5 star rating total over its weight sum
+pub(crate) fn rating_score_bps(total_rating: u128, weight_sum: u128) -> u32 {
+    clamp_score(div_round(total_rating * (SCORE_SCALE_BPS as u128 / MAX_RATING), weight_sum))
+}
+
+// Fraction `bps` (basis points) of `value`
+pub(crate) fn mul_bps(value: u32, bps: u32) -> u32 {
+    div_round(value as u128 * bps as u128, 10_000) as u32
+}
+
+// Weighted average of `base` and `other`, `other` taking `other_weight_bps` of the weight
+pub(crate) fn blend_bps(base: u32, other: u32, other_weight_bps: u32) -> u32 {
+    let other_weight_bps = std::cmp::min(other_weight_bps, 10_000);
+    div_round(
+        base as u128 * (10_000 - other_weight_bps) as u128 + other as u128 * other_weight_bps as u128,
+        10_000,
+    ) as u32
+}
+
+// `max` scaled by how far `value` is toward `full`, reaching all of it at `full`
+pub(crate) fn linear_share(max: u32, value: u128, full: u128) -> u32 {
+    if value >= full {
+        return max;
+    }
+    div_round(max as u128 * value, full) as u32
+}
+
+// Integer square root, rounded down
+pub(crate) fn integer_sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    // Newton's method from an estimate at or above the root
+    let mut x = value;
+    let mut y = x / 2 + x % 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+// Whole 0-100 points of a score, rounded down
+pub(crate) fn to_points(score_bps: u32) -> u32 {
+    std::cmp::min(score_bps, SCORE_SCALE_BPS) / BPS_PER_POINT
+}
+
+// Score in basis points of whole 0-100 points, capped at the top of the scale
+pub(crate) fn from_points(points: u32) -> u32 {
+    std::cmp::min(points.saturating_mul(BPS_PER_POINT), SCORE_SCALE_BPS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn div_round_is_within_half_of_exact() {
+        for denominator in 1..200u128 {
+            for numerator in 0..1_000u128 {
+                let rounded = div_round(numerator, denominator);
+                // |rounded - numerator / denominator| <= 1/2
+                assert!((2 * rounded * denominator).abs_diff(2 * numerator) <= denominator);
+            }
+        }
+        assert_eq!(div_round(7, 0), 0);
+    }
+
+    #[test]
+    fn rating_score_is_bounded_and_monotonic() {
+        for weight_sum in [1u128, 3, 5_000, 7_777, 10_000, 123_457] {
+            let mut previous = 0;
+            for total_rating in 0..=weight_sum.min(2_000) * MAX_RATING {
+                let score = rating_score_bps(total_rating, weight_sum);
+                assert!(score <= SCORE_SCALE_BPS);
+                assert!(score >= previous);
+                previous = score;
+            }
+            assert_eq!(rating_score_bps(weight_sum * MAX_RATING, weight_sum), SCORE_SCALE_BPS);
+            assert_eq!(rating_score_bps(0, weight_sum), 0);
+        }
+    }
+
+    #[test]
+    fn blend_is_between_its_inputs_and_monotonic() {
+        for base in (0..=SCORE_SCALE_BPS).step_by(97) {
+            for other in (0..=SCORE_SCALE_BPS).step_by(89) {
+                let mut previous = blend_bps(base, other, 0);
+                assert_eq!(previous, base);
+                for weight in (0..=10_000).step_by(125) {
+                    let blended = blend_bps(base, other, weight);
+                    assert!(blended >= base.min(other) && blended <= base.max(other));
+                    // Moving weight toward the larger input never lowers the blend
+                    if other >= base {
+                        assert!(blended >= previous);
+                    } else {
+                        assert!(blended <= previous);
+                    }
+                    previous = blended;
+                }
+                assert_eq!(previous, other);
+            }
+        }
+    }
+
+    #[test]
+    fn linear_share_is_bounded_and_monotonic() {
+        for max in [0u32, 1, 300, 1_500, SCORE_SCALE_BPS] {
+            let full = 5_000_000_000_000_000_000_000u128;
+            let mut previous = 0;
+            for step in 0..=120u128 {
+                let share = linear_share(max, full / 100 * step, full);
+                assert!(share <= max);
+                assert!(share >= previous);
+                previous = share;
+            }
+            assert_eq!(previous, max);
+        }
+    }
+
+    #[test]
+    fn mul_bps_is_bounded_and_monotonic() {
+        for value in (0..=SCORE_SCALE_BPS).step_by(101) {
+            let mut previous = 0;
+            for bps in (0..=10_000).step_by(50) {
+                let scaled = mul_bps(value, bps);
+                assert!(scaled <= value);
+                assert!(scaled >= previous);
+                previous = scaled;
+            }
+            assert_eq!(previous, value);
+        }
+    }
+
+    #[test]
+    fn mul_div_handles_products_beyond_u128() {
+        let large = u128::MAX / 3;
+        assert_eq!(mul_div(large, 6, 3), large * 2);
+        assert_eq!(mul_div(large, large, large), large);
+        assert_eq!(mul_div_ceil(large, large, large), large);
+        assert_eq!(mul_div(7, 3, 2), 10);
+        assert_eq!(mul_div_ceil(7, 3, 2), 11);
+        assert_eq!(mul_div_ceil(8, 3, 2), 12);
+    }
+
+    #[test]
+    fn integer_sqrt_is_the_floor_root() {
+        for value in (0..100_000u128).chain([u64::MAX as u128, u128::MAX / 3, u128::MAX]) {
+            let root = integer_sqrt(value);
+            assert!(root * root <= value);
+            assert!((root + 1).checked_mul(root + 1).is_none_or(|square| square > value));
+        }
+    }
+
+    #[test]
+    fn points_round_trip_and_round_down() {
+        for points in 0..=100 {
+            assert_eq!(to_points(from_points(points)), points);
+        }
+        assert_eq!(from_points(250), SCORE_SCALE_BPS);
+        let mut previous = 0;
+        for score_bps in 0..=SCORE_SCALE_BPS {
+            let points = to_points(score_bps);
+            assert!(from_points(points) <= score_bps);
+            assert!(points >= previous && points <= 100);
+            previous = points;
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Maximum length of a force-majeure reason
+const MAX_FORCE_MAJEURE_REASON_LENGTH: usize = 280;
+
+// Upper bound on windows returned per page
+const MAX_FORCE_MAJEURE_PAGE: u32 = 50;
+
+// Most force-majeure windows open at the same time
+const MAX_OPEN_FORCE_MAJEURE_WINDOWS: usize = 10;
+
+// Governance-declared period of systemic failure (chain halt, bridge outage, ...) during which
+// negative reputation effects are suspended network-wide
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ForceMajeureWindow {
+    pub(crate) start: u64,
+    // None while the window is still open
+    pub(crate) end: Option<u64>,
+    pub(crate) reason: String,
+    pub(crate) declared_by: AccountId,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Open a force-majeure window (owner or governance). `start` may backdate the window to
+    // when the outage began; it defaults to now.
+    pub fn declare_force_majeure(&mut self, reason: String, start: Option<u64>) -> u64 {
+        self.assert_owner_or_governance();
+        assert!(reason.len() <= MAX_FORCE_MAJEURE_REASON_LENGTH, "Force-majeure reason too long");
+
+        let now = env::block_timestamp();
+        let start = start.unwrap_or(now);
+        assert!(start <= now, "Force-majeure window cannot start in the future");
+        assert!(
+            self.open_force_majeure_windows.len() < MAX_OPEN_FORCE_MAJEURE_WINDOWS,
+            "Too many open force-majeure windows"
+        );
+
+        let window_id = self.force_majeure_windows.len();
+        self.force_majeure_windows.push(&ForceMajeureWindow {
+            start,
+            end: None,
+            reason: reason.clone(),
+            declared_by: env::predecessor_account_id(),
+        });
+        self.open_force_majeure_windows.push(window_id);
+
+        emit_event(
+            "force_majeure_declared",
+            json!({
+                "window_id": window_id,
+                "start": start,
+                "reason": reason,
+            }),
+        );
+
+        window_id
+    }
+
+    // Close an open force-majeure window (owner or governance)
+    pub fn end_force_majeure(&mut self, window_id: u64) {
+        self.assert_owner_or_governance();
+
+        let mut window = self.force_majeure_windows.get(window_id).expect("Force-majeure window not found");
+        assert!(window.end.is_none(), "Force-majeure window already ended");
+
+        let now = env::block_timestamp();
+        window.end = Some(now);
+        self.force_majeure_windows.replace(window_id, &window);
+        self.open_force_majeure_windows.retain(|open_id| *open_id != window_id);
+
+        emit_event(
+            "force_majeure_ended",
+            json!({
+                "window_id": window_id,
+                "end": now,
+            }),
+        );
+    }
+
+    // Push back the deadlines of an open intent by the time it overlapped a closed
+    // force-majeure window (client or agent of the intent, once per window)
+    pub fn extend_intent_for_force_majeure(&mut self, intent_id: String, window_id: u64) {
+        let caller = env::predecessor_account_id();
+        let mut intent = self.intents.get(&intent_id).expect("Intent not found");
+        assert!(
+            caller == intent.client_id || caller == intent.agent_id,
+            "Only the intent's client or agent can extend it"
+        );
+        assert!(!intent.status.is_terminal(), "Intent has already reached a final outcome");
+        assert!(
+            !intent.force_majeure_windows.contains(&window_id),
+            "Intent already extended for this force-majeure window"
+        );
+
+        let window = self.force_majeure_windows.get(window_id).expect("Force-majeure window not found");
+        let window_end = window.end.expect("Force-majeure window is still open");
+
+        // Only the part of the window during which the intent was open counts
+        let overlap = window_end.saturating_sub(std::cmp::max(window.start, intent.timestamp));
+        assert!(overlap > 0, "Intent was not affected by this force-majeure window");
+
+        let previous_expiry = intent.expires_at();
+        if intent.status == IntentStatus::Created {
+            intent.response_deadline += overlap;
+        }
+        intent.deadline += overlap;
+        intent.deadline_extension += overlap;
+        intent.force_majeure_windows.push(window_id);
+        self.intents.insert(&intent_id, &intent);
+        self.internal_update_open_intent_index(&intent, previous_expiry);
+
+        emit_event(
+            "intent_extended",
+            json!({
+                "intent_id": intent_id,
+                "window_id": window_id,
+                "extension": overlap,
+                "response_deadline": intent.response_deadline,
+                "deadline": intent.deadline,
+            }),
+        );
+    }
+
+    // Get a page of force-majeure windows, oldest first
+    pub fn get_force_majeure_windows(&self, from_index: u64, limit: u32) -> Vec<ForceMajeureWindow> {
+        self.force_majeure_windows
+            .iter()
+            .skip(from_index as usize)
+            .take(std::cmp::min(limit, MAX_FORCE_MAJEURE_PAGE) as usize)
+            .collect()
+    }
+
+    // Whether negative reputation effects are currently suspended. Windows never start in the
+    // future and closed ones end in the past, so exactly the open windows cover now.
+    pub fn is_force_majeure_active(&self) -> bool {
+        !self.open_force_majeure_windows.is_empty()
+    }
+
+    // Get the ids of the open force-majeure windows
+    pub fn get_open_force_majeure_windows(&self) -> Vec<u64> {
+        self.open_force_majeure_windows.clone()
+    }
+}
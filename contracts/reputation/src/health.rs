@@ -0,0 +1,248 @@
+use crate::*;
+
+// Gas for the ft_balance_of view on the token contract
+const GAS_FOR_BALANCE_QUERY: Gas = Gas(5_000_000_000_000);
+
+// Gas reserved for the balance refresh callback
+const GAS_FOR_BALANCE_CALLBACK: Gas = Gas(5_000_000_000_000);
+
+// Cheaply maintained aggregates used for invariant checks
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ContractStats {
+    // Sum of all agent stakes
+    total_staked: Balance,
+    // Registered agents per trust level (Novice..Master)
+    trust_level_counts: [u64; 5],
+    // Times an aggregate would have gone negative and was clamped at zero
+    aggregate_underflows: u64,
+    // Last known ITLX balance of this contract
+    cached_token_balance: Option<Balance>,
+    // When the cached token balance was refreshed
+    token_balance_cached_at: u64,
+    // ITLX held in escrow for open intents
+    pub(crate) itlx_in_escrow: Balance,
+    // ITLX held as consumer query credit and unclaimed agent rebates
+    pub(crate) itlx_query_balances: Balance,
+    // ITLX posted as open audit bounties
+    pub(crate) itlx_in_bounties: Balance,
+    // ITLX delegated to agents, including stake cooling down after undelegation
+    pub(crate) itlx_delegated: Balance,
+    // ITLX in the reward pool or accrued to agents and not yet claimed
+    pub(crate) itlx_rewards: Balance,
+    // ITLX in the insurance pool, less approved payouts
+    pub(crate) itlx_insurance_pool: Balance,
+    // ITLX escrow, bounty and delegation payouts whose transfer failed, held for their receivers
+    pub(crate) itlx_unpaid: Balance,
+}
+
+fn trust_level_index(level: TrustLevel) -> usize {
+    match level {
+        TrustLevel::Novice => 0,
+        TrustLevel::Apprentice => 1,
+        TrustLevel::Trusted => 2,
+        TrustLevel::Expert => 3,
+        TrustLevel::Master => 4,
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Verify internal invariants without iterating storage. Counts of agents are noised like
+    // get_network_stats, and checks report only how far their counts disagree.
+    pub fn health_check(&self) -> HealthStatusView {
+        let stats = &self.stats;
+        let agent_count = self.agent_reputations.len();
+        let mut checks = Vec::new();
+
+        checks.push(HealthCheckView {
+            name: "aggregates_non_negative".to_string(),
+            ok: stats.aggregate_underflows == 0,
+            detail: format!("{} clamped underflows", stats.aggregate_underflows),
+        });
+
+        let histogram_total: u64 = stats.trust_level_counts.iter().sum();
+        checks.push(HealthCheckView {
+            name: "trust_level_histogram".to_string(),
+            ok: histogram_total == agent_count,
+            detail: format!("histogram total off by {}", histogram_total.abs_diff(agent_count)),
+        });
+
+        let ranked_count = self.ranking_index.agent_count();
+        checks.push(HealthCheckView {
+            name: "ranking_index".to_string(),
+            ok: ranked_count == agent_count,
+            detail: format!("index count off by {}", ranked_count.abs_diff(agent_count)),
+        });
+
+        // Tokens held must cover all own and delegated stakes, intent escrow, query credit, audit
+        // bounties, staking rewards, the insurance pool, failed payouts held for their receivers
+        // and burned slashes locked in the contract
+        let required_balance = stats.total_staked
+            + stats.itlx_delegated
+            + stats.itlx_rewards
+            + stats.itlx_in_escrow
+            + stats.itlx_query_balances
+            + stats.itlx_in_bounties
+            + stats.itlx_insurance_pool
+            + stats.itlx_unpaid
+            + self.total_slashed_burned;
+        checks.push(match stats.cached_token_balance {
+            Some(balance) => HealthCheckView {
+                name: "token_accounting".to_string(),
+                ok: balance >= required_balance,
+                detail: format!(
+                    "cached balance {} vs {} required (cached at {})",
+                    balance, required_balance, stats.token_balance_cached_at
+                ),
+            },
+            None => HealthCheckView {
+                name: "token_accounting".to_string(),
+                ok: true,
+                detail: "token balance not cached yet; call refresh_token_balance".to_string(),
+            },
+        });
+
+        HealthStatusView {
+            healthy: checks.iter().all(|check| check.ok),
+            checks,
+            agent_count: self.internal_private_count("agent_count", agent_count),
+            total_staked: U128(stats.total_staked),
+            checked_at: env::block_timestamp(),
+        }
+    }
+
+    // Network-wide agent counts (noised when privacy noise is enabled)
+    pub fn get_network_stats(&self) -> NetworkStatsView {
+        let levels = [
+            TrustLevel::Novice,
+            TrustLevel::Apprentice,
+            TrustLevel::Trusted,
+            TrustLevel::Expert,
+            TrustLevel::Master,
+        ];
+
+        NetworkStatsView {
+            agent_count: self.internal_private_count("agent_count", self.agent_reputations.len()),
+            trust_level_counts: levels
+                .iter()
+                .map(|level| {
+                    let count = self.stats.trust_level_counts[trust_level_index(*level)];
+                    (*level, self.internal_private_count(&format!("trust_level_count:{:?}", level), count))
+                })
+                .collect(),
+            noised: self.is_privacy_noise_enabled(),
+        }
+    }
+
+    // Refresh the cached ITLX balance of this contract used by health_check
+    pub fn refresh_token_balance(&mut self) -> Promise {
+        Promise::new(self.token_contract_id.clone())
+            .function_call(
+                "ft_balance_of".to_string(),
+                json!({ "account_id": env::current_account_id() }).to_string().into_bytes(),
+                0,
+                GAS_FOR_BALANCE_QUERY,
+            )
+            .then(
+                Promise::new(env::current_account_id())
+                    .function_call(
+                        "on_token_balance_refreshed".to_string(),
+                        Vec::new(),
+                        0,
+                        GAS_FOR_BALANCE_CALLBACK,
+                    )
+            )
+    }
+
+    // Callback storing the refreshed token balance
+    #[private]
+    pub fn on_token_balance_refreshed(&mut self) {
+        match env::promise_result(0) {
+            PromiseResult::Successful(result) => match near_sdk::serde_json::from_slice::<U128>(&result) {
+                Ok(balance) => {
+                    self.stats.cached_token_balance = Some(balance.0);
+                    self.stats.token_balance_cached_at = env::block_timestamp();
+                },
+                // A callback must not panic; the previous cached balance stays in place
+                Err(_) => env::log_str("Invalid token balance response"),
+            },
+            _ => {
+                env::log_str("Token balance refresh failed");
+            }
+        }
+    }
+
+    // Seed the total staked aggregate after a migration (contract account only)
+    #[private]
+    pub fn reconcile_total_staked(&mut self, total_staked: U128) {
+        self.stats.total_staked = total_staked.0;
+        env::log_str(&format!("Total staked reconciled to {}", total_staked.0));
+    }
+
+    // Set an agent's stake, keeping the total staked aggregate in sync
+    pub(crate) fn internal_set_stake(&mut self, agent_id: &AccountId, amount: Balance) {
+        let previous = self.agent_stakes.insert(agent_id, &amount).unwrap_or(0);
+        self.internal_sync_reward_stake(agent_id, amount);
+
+        let stats = &mut self.stats;
+        if amount >= previous {
+            stats.total_staked += amount - previous;
+        } else if stats.total_staked >= previous - amount {
+            stats.total_staked -= previous - amount;
+        } else {
+            stats.total_staked = 0;
+            stats.aggregate_underflows += 1;
+        }
+
+        self.internal_update_stake_shortfall(agent_id);
+    }
+
+    // Move an agent between trust level buckets
+    pub(crate) fn internal_update_trust_histogram(&mut self, previous: Option<&AgentReputation>, current: &AgentReputation) {
+        let new_index = trust_level_index(self.get_trust_level(current.score));
+        let old_index = previous.map(|p| trust_level_index(self.get_trust_level(p.score)));
+        let counts = &mut self.stats.trust_level_counts;
+
+        if let Some(old_index) = old_index {
+            if old_index == new_index {
+                return;
+            }
+            if counts[old_index] > 0 {
+                counts[old_index] -= 1;
+            } else {
+                self.stats.aggregate_underflows += 1;
+            }
+        }
+        self.stats.trust_level_counts[new_index] += 1;
+    }
+}
+
+// Result of a single invariant check
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HealthCheckView {
+    name: String,
+    ok: bool,
+    detail: String,
+}
+
+// View struct for get_network_stats
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NetworkStatsView {
+    agent_count: u64,
+    trust_level_counts: Vec<(TrustLevel, u64)>,
+    // Whether the counts include differential privacy noise
+    noised: bool,
+}
+
+// View struct for health_check
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HealthStatusView {
+    healthy: bool,
+    checks: Vec<HealthCheckView>,
+    agent_count: u64,
+    total_staked: U128,
+    checked_at: u64,
+}
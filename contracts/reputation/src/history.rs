@@ -0,0 +1,107 @@
+use crate::*;
+
+// Maximum number of snapshots retained per agent; older snapshots are overwritten
+const MAX_HISTORY_LENGTH: usize = 100;
+
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReputationSnapshot {
+    score: u32,
+    trust_level: TrustLevel,
+    timestamp: u64,
+}
+
+// Bounded per-agent ring buffer of score snapshots
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ReputationHistory {
+    snapshots: Vec<ReputationSnapshot>,
+    // Slot the next snapshot overwrites once the buffer is full
+    head: u32,
+}
+
+impl ReputationHistory {
+    fn push(&mut self, snapshot: ReputationSnapshot) {
+        if self.snapshots.len() < MAX_HISTORY_LENGTH {
+            self.snapshots.push(snapshot);
+        } else {
+            self.snapshots[self.head as usize] = snapshot;
+            self.head = (self.head + 1) % MAX_HISTORY_LENGTH as u32;
+        }
+    }
+
+    fn latest(&self) -> Option<&ReputationSnapshot> {
+        if self.snapshots.len() < MAX_HISTORY_LENGTH {
+            self.snapshots.last()
+        } else {
+            let index = (self.head as usize + MAX_HISTORY_LENGTH - 1) % MAX_HISTORY_LENGTH;
+            self.snapshots.get(index)
+        }
+    }
+
+    // Snapshots oldest first
+    fn iter(&self) -> impl Iterator<Item = &ReputationSnapshot> {
+        let (newer, older) = self.snapshots.split_at(self.head as usize);
+        older.iter().chain(newer.iter())
+    }
+}
+
+// When score changes are recorded in the reputation history
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HistoryConfig {
+    // Score changes larger than this are always recorded
+    pub min_score_delta: u32,
+    // Smaller changes are recorded at most once per epoch (nanoseconds)
+    pub epoch_length: u64,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            min_score_delta: 5,
+            epoch_length: 24 * 60 * 60 * 1_000_000_000, // 1 day in nanoseconds
+        }
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Get an agent's recorded score snapshots within [from_ts, to_ts], oldest first
+    pub fn get_reputation_history(&self, agent_id: AccountId, from_ts: u64, to_ts: u64) -> Vec<ReputationSnapshot> {
+        self.reputation_history
+            .get(&agent_id)
+            .map(|history| {
+                history
+                    .iter()
+                    .filter(|s| s.timestamp >= from_ts && s.timestamp <= to_ts)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // Record a snapshot of the agent's score if it moved enough, or enough time has passed
+    pub(crate) fn internal_record_history(&mut self, agent_id: &AccountId, score: u32) {
+        let now = env::block_timestamp();
+        let mut history = self.reputation_history.get(agent_id).unwrap_or_default();
+
+        let should_record = match history.latest() {
+            None => true,
+            Some(latest) => {
+                let delta = (score as i64 - latest.score as i64).unsigned_abs() as u32;
+                delta > self.history_config.min_score_delta
+                    || (delta > 0 && now - latest.timestamp >= self.history_config.epoch_length)
+            }
+        };
+        if !should_record {
+            return;
+        }
+
+        history.push(ReputationSnapshot {
+            score,
+            trust_level: self.get_trust_level(score),
+            timestamp: now,
+        });
+        self.reputation_history.insert(agent_id, &history);
+    }
+}
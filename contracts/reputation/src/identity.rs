@@ -0,0 +1,391 @@
+use crate::*;
+use crate::events::emit_event;
+use crate::oracles::ed25519_verify;
+use near_sdk::json_types::Base64VecU8;
+
+// Upper bounds on what a single identity can link
+#[cfg(feature = "cross-chain")]
+const MAX_FOREIGN_ADDRESSES: usize = 10;
+const MAX_REALM_MEMBERSHIPS: usize = 10;
+
+// Maximum lengths of identifier parts
+const MAX_DID_LENGTH: usize = 256;
+const MAX_REALM_NAME_LENGTH: usize = 64;
+const MAX_REALM_MEMBER_ID_LENGTH: usize = 128;
+
+// Address on another chain, as a CAIP-10 account ID, e.g. "eip155:8453:0xab…"
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ForeignAddress {
+    account_id: String,
+    linked_at: u64,
+}
+
+// Membership of the agent in a realm, vouched for by the realm's authority
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RealmMembership {
+    realm: String,
+    // The agent's ID within the realm
+    member_id: String,
+    // Authority that recorded the membership and paid for its storage
+    recorded_by: AccountId,
+    recorded_at: u64,
+}
+
+// Canonical identity tying an agent's NEAR account to everything it is known by elsewhere.
+// Every linked identifier resolves to the same record.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct AgentIdentity {
+    near_account: AccountId,
+    foreign_addresses: Vec<ForeignAddress>,
+    realms: Vec<RealmMembership>,
+    did: Option<String>,
+    created_at: u64,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Find the identity any identifier belongs to: a NEAR account ("agent.near" or
+    // "near:agent.near"), a CAIP-10 foreign address ("eip155:8453:0x…"), a DID ("did:…") or a
+    // realm membership ("realm:<realm>:<member_id>")
+    pub fn resolve_identity(&self, identifier: String) -> Option<IdentityView> {
+        let identity_id = self.identity_index.get(&canonical_identifier(&identifier))?;
+        self.identities.get(&identity_id).map(|identity| IdentityView {
+            identity_id,
+            near_account: identity.near_account,
+            foreign_addresses: identity.foreign_addresses,
+            realms: identity.realms,
+            did: identity.did,
+            created_at: identity.created_at,
+        })
+    }
+
+    // Set or clear (None) the caller's DID (registered agents only). A DID can belong to only
+    // one identity, and setting one takes the DID controller's `signature` over the message
+    // returned by get_did_link_message: an Ed25519 signature for did:key, or a personal_sign
+    // (EIP-191) signature for did:pkh:eip155.
+    pub fn set_did(&mut self, did: Option<String>, signature: Option<Base64VecU8>) {
+        let agent_id = env::predecessor_account_id();
+        if let Some(did) = &did {
+            let message = self.get_did_link_message(agent_id.clone(), did.clone());
+            let signature = signature.expect("Setting a DID requires the DID's signature");
+            assert!(
+                did_signature_valid(did, &message, &signature.0),
+                "Invalid DID signature; only did:key (Ed25519) and did:pkh:eip155 DIDs can be verified"
+            );
+        }
+        let initial_storage = env::storage_usage();
+        let (identity_id, mut identity) = self.internal_agent_identity(&agent_id);
+
+        if let Some(previous) = identity.did.take() {
+            self.identity_index.remove(&previous);
+            emit_identity_event("identity_unlinked", identity_id, &agent_id, &previous);
+        }
+        if let Some(did) = &did {
+            assert!(did.starts_with("did:") && did.len() <= MAX_DID_LENGTH, "Invalid DID");
+            self.internal_index_identifier(did.clone(), identity_id);
+            emit_identity_event("identity_linked", identity_id, &agent_id, did);
+        }
+        identity.did = did;
+
+        self.identities.insert(&identity_id, &identity);
+        self.internal_track_identity_storage(&agent_id, initial_storage);
+    }
+
+    // Message the controller of a DID must sign for it to be set on the agent's identity
+    pub fn get_did_link_message(&self, agent_id: AccountId, did: String) -> String {
+        format!("Link {} to NEAR account {} in {}", did, agent_id, env::current_account_id())
+    }
+
+    // Remove a foreign address from the caller's identity
+    pub fn unlink_foreign_address(&mut self, account_id: String) {
+        let agent_id = env::predecessor_account_id();
+        let initial_storage = env::storage_usage();
+        let key = canonical_identifier(&account_id);
+        let (identity_id, mut identity) = self.internal_agent_identity(&agent_id);
+
+        let position = identity
+            .foreign_addresses
+            .iter()
+            .position(|address| address.account_id == key)
+            .expect("Address not linked");
+        identity.foreign_addresses.remove(position);
+        self.identity_index.remove(&key);
+        self.identities.insert(&identity_id, &identity);
+        self.internal_track_identity_storage(&agent_id, initial_storage);
+
+        emit_identity_event("identity_unlinked", identity_id, &agent_id, &key);
+    }
+
+    // Set or remove (None) the account allowed to vouch for memberships in a realm (owner or
+    // governance)
+    pub fn set_realm_authority(&mut self, realm: String, authority_id: Option<AccountId>) {
+        self.assert_owner_or_governance();
+        let realm = realm.to_lowercase();
+        assert_valid_realm_part(&realm, MAX_REALM_NAME_LENGTH);
+
+        let old_value = match &authority_id {
+            Some(authority_id) => self.realm_authorities.insert(&realm, authority_id),
+            None => self.realm_authorities.remove(&realm),
+        };
+
+        self.record_config_change(&format!("realm_authority.{}", realm), json!(old_value), json!(authority_id));
+    }
+
+    // Get the account vouching for memberships in a realm
+    pub fn get_realm_authority(&self, realm: String) -> Option<AccountId> {
+        self.realm_authorities.get(&realm.to_lowercase())
+    }
+
+    // Record that an agent is `member_id` in the caller's realm (realm authority only). The
+    // authority pays for the membership's storage.
+    pub fn record_realm_membership(&mut self, realm: String, member_id: String, agent_id: AccountId) {
+        let authority_id = env::predecessor_account_id();
+        let realm = realm.to_lowercase();
+        assert_eq!(
+            self.realm_authorities.get(&realm).as_ref(),
+            Some(&authority_id),
+            "Only the realm authority can record memberships"
+        );
+        assert_valid_realm_part(&member_id, MAX_REALM_MEMBER_ID_LENGTH);
+        assert!(self.agent_reputations.get(&agent_id).is_some(), "Agent not registered");
+
+        let initial_storage = env::storage_usage();
+        let (identity_id, mut identity) = self.internal_agent_identity(&agent_id);
+        assert!(identity.realms.len() < MAX_REALM_MEMBERSHIPS, "Too many realm memberships");
+
+        let key = realm_identifier(&realm, &member_id);
+        self.internal_index_identifier(key.clone(), identity_id);
+        identity.realms.push(RealmMembership {
+            realm,
+            member_id,
+            recorded_by: authority_id.clone(),
+            recorded_at: env::block_timestamp(),
+        });
+        self.identities.insert(&identity_id, &identity);
+        self.internal_charge_storage(&authority_id, initial_storage);
+
+        emit_identity_event("identity_linked", identity_id, &agent_id, &key);
+    }
+
+    // Remove a realm membership (the realm's authority or the agent itself)
+    pub fn remove_realm_membership(&mut self, realm: String, member_id: String) {
+        let caller = env::predecessor_account_id();
+        let realm = realm.to_lowercase();
+        let key = realm_identifier(&realm, &member_id);
+        let identity_id = self.identity_index.get(&key).expect("Realm membership not found");
+        let mut identity = self.identities.get(&identity_id).expect("Identity not found");
+        assert!(
+            caller == identity.near_account || self.realm_authorities.get(&realm).as_ref() == Some(&caller),
+            "Only the agent or the realm authority can remove a membership"
+        );
+
+        let position = identity
+            .realms
+            .iter()
+            .position(|membership| membership.realm == realm && membership.member_id == member_id)
+            .expect("Realm membership not found");
+        let membership = identity.realms.remove(position);
+
+        let initial_storage = env::storage_usage();
+        self.identity_index.remove(&key);
+        self.identities.insert(&identity_id, &identity);
+        self.internal_release_storage(&membership.recorded_by, initial_storage.saturating_sub(env::storage_usage()));
+
+        emit_identity_event("identity_unlinked", identity_id, &identity.near_account, &key);
+    }
+
+    // Give a newly registered agent its identity record
+    pub(crate) fn internal_create_identity(&mut self, agent_id: &AccountId) -> (u64, AgentIdentity) {
+        let identity_id = self.next_identity_id;
+        self.next_identity_id += 1;
+
+        let identity = AgentIdentity {
+            near_account: agent_id.clone(),
+            foreign_addresses: Vec::new(),
+            realms: Vec::new(),
+            did: None,
+            created_at: env::block_timestamp(),
+        };
+        self.internal_index_identifier(near_identifier(agent_id), identity_id);
+        self.identities.insert(&identity_id, &identity);
+
+        (identity_id, identity)
+    }
+
+    // The agent's identity record, created on first use for agents registered before identities
+    fn internal_agent_identity(&mut self, agent_id: &AccountId) -> (u64, AgentIdentity) {
+        assert!(self.agent_reputations.get(agent_id).is_some(), "Agent not registered");
+        match self.identity_index.get(&near_identifier(agent_id)) {
+            Some(identity_id) => (identity_id, self.identities.get(&identity_id).expect("Identity not found")),
+            None => self.internal_create_identity(agent_id),
+        }
+    }
+
+    fn internal_index_identifier(&mut self, key: String, identity_id: u64) {
+        assert!(self.identity_index.get(&key).is_none(), "Identifier already linked to an identity");
+        self.identity_index.insert(&key, &identity_id);
+    }
+
+    // Attribute identity storage changes to the agent, charging growth and releasing shrinkage
+    fn internal_track_identity_storage(&mut self, agent_id: &AccountId, initial_storage: u64) {
+        if env::storage_usage() >= initial_storage {
+            self.internal_charge_storage(agent_id, initial_storage);
+        } else {
+            self.internal_release_storage(agent_id, initial_storage - env::storage_usage());
+        }
+    }
+}
+
+#[cfg(feature = "cross-chain")]
+#[near_bindgen]
+impl AgentReputationContract {
+    // Link an EVM address to the caller's identity. `signature` is the address's 65-byte
+    // personal_sign (EIP-191) signature over the message returned by get_evm_link_message.
+    pub fn link_evm_address(&mut self, chain_id: u64, address: String, signature: Base64VecU8) {
+        let agent_id = env::predecessor_account_id();
+        let address = address.to_lowercase();
+        let address_bytes = parse_evm_address(&address);
+        assert!(
+            recover_evm_signer(&self.get_evm_link_message(agent_id.clone(), chain_id, address.clone()), &signature.0)
+                == Some(address_bytes),
+            "Signature does not match the address"
+        );
+
+        let initial_storage = env::storage_usage();
+        let (identity_id, mut identity) = self.internal_agent_identity(&agent_id);
+        assert!(identity.foreign_addresses.len() < MAX_FOREIGN_ADDRESSES, "Too many linked addresses");
+
+        let key = format!("eip155:{}:{}", chain_id, address);
+        self.internal_index_identifier(key.clone(), identity_id);
+        identity.foreign_addresses.push(ForeignAddress {
+            account_id: key.clone(),
+            linked_at: env::block_timestamp(),
+        });
+        self.identities.insert(&identity_id, &identity);
+        self.internal_track_identity_storage(&agent_id, initial_storage);
+
+        emit_identity_event("identity_linked", identity_id, &agent_id, &key);
+    }
+
+    // Message the EVM address must sign to be linked to the agent's identity
+    pub fn get_evm_link_message(&self, agent_id: AccountId, chain_id: u64, address: String) -> String {
+        format!(
+            "Link {} on eip155:{} to NEAR account {} in {}",
+            address.to_lowercase(),
+            chain_id,
+            agent_id,
+            env::current_account_id()
+        )
+    }
+}
+
+fn emit_identity_event(event: &str, identity_id: u64, agent_id: &AccountId, identifier: &str) {
+    emit_event(
+        event,
+        json!({
+            "identity_id": identity_id,
+            "agent_id": agent_id,
+            "identifier": identifier,
+        }),
+    );
+}
+
+fn near_identifier(account_id: &AccountId) -> String {
+    format!("near:{}", account_id)
+}
+
+fn realm_identifier(realm: &str, member_id: &str) -> String {
+    format!("realm:{}:{}", realm, member_id)
+}
+
+// Index key of an identifier: bare NEAR accounts get the "near:" prefix, and EVM addresses and
+// realm names are lowercased
+fn canonical_identifier(identifier: &str) -> String {
+    if let Some(rest) = identifier.strip_prefix("eip155:") {
+        format!("eip155:{}", rest.to_lowercase())
+    } else if let Some(rest) = identifier.strip_prefix("realm:") {
+        match rest.split_once(':') {
+            Some((realm, member_id)) => realm_identifier(&realm.to_lowercase(), member_id),
+            None => identifier.to_string(),
+        }
+    } else if identifier.starts_with("did:") || identifier.starts_with("near:") {
+        identifier.to_string()
+    } else {
+        format!("near:{}", identifier)
+    }
+}
+
+fn assert_valid_realm_part(part: &str, max_length: usize) {
+    assert!(!part.is_empty() && part.len() <= max_length, "Invalid realm name or member ID");
+    assert!(!part.contains(':'), "Realm names and member IDs cannot contain ':'");
+}
+
+// Decode a 0x-prefixed, 20-byte hex EVM address
+#[cfg(feature = "cross-chain")]
+pub(crate) fn parse_evm_address(address: &str) -> [u8; 20] {
+    let hex = address.strip_prefix("0x").expect("EVM address must start with 0x");
+    assert_eq!(hex.len(), 40, "EVM address must be 20 bytes");
+
+    let mut bytes = [0u8; 20];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).expect("Invalid hex in EVM address");
+    }
+    bytes
+}
+
+// Whether `signature` over `message` comes from the DID's controller. did:key DIDs carry an
+// Ed25519 key (multicodec 0xed01, base58btc); did:pkh:eip155 DIDs name an EVM address.
+fn did_signature_valid(did: &str, message: &str, signature: &[u8]) -> bool {
+    if let Some(key) = did.strip_prefix("did:key:z") {
+        let decoded = match near_sdk::bs58::decode(key).into_vec() {
+            Ok(decoded) => decoded,
+            Err(_) => return false,
+        };
+        return match (decoded.strip_prefix(&[0xed, 0x01]), <&[u8; 64]>::try_from(signature)) {
+            (Some(public_key), Ok(signature)) => match <&[u8; 32]>::try_from(public_key) {
+                Ok(public_key) => ed25519_verify(signature, message.as_bytes(), public_key),
+                Err(_) => false,
+            },
+            _ => false,
+        };
+    }
+    #[cfg(feature = "cross-chain")]
+    if let Some((_, address)) = did.strip_prefix("did:pkh:eip155:").and_then(|account| account.split_once(':')) {
+        return recover_evm_signer(message, signature) == Some(parse_evm_address(&address.to_lowercase()));
+    }
+    false
+}
+
+// Address that produced an EIP-191 personal_sign signature over `message`
+#[cfg(feature = "cross-chain")]
+fn recover_evm_signer(message: &str, signature: &[u8]) -> Option<[u8; 20]> {
+    if signature.len() != 65 {
+        return None;
+    }
+    let prefixed = format!("\x19Ethereum Signed Message:\n{}{}", message.len(), message);
+    let hash = env::keccak256(prefixed.as_bytes());
+    // Accept both raw (0/1) and Ethereum-style (27/28) recovery IDs
+    let v = signature[64];
+    let recovery_id = if v >= 27 { v - 27 } else { v };
+
+    let public_key = env::ecrecover(&hash, &signature[..64], recovery_id, true)?;
+    let public_key_hash = env::keccak256(&public_key);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&public_key_hash[12..]);
+    Some(address)
+}
+
+// View struct for a resolved identity
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IdentityView {
+    // Stable ID shared by every identifier of the identity
+    identity_id: u64,
+    near_account: AccountId,
+    foreign_addresses: Vec<ForeignAddress>,
+    realms: Vec<RealmMembership>,
+    did: Option<String>,
+    created_at: u64,
+}
@@ -0,0 +1,382 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Gas for the ft_transfer of an approved claim payout
+const GAS_FOR_INSURANCE_FT_TRANSFER: Gas = Gas(10_000_000_000_000);
+
+// Gas reserved for the payout callback
+const GAS_FOR_INSURANCE_CALLBACK: Gas = Gas(10_000_000_000_000);
+
+// Default per-incident payout cap, as a multiple of the minimum stake
+const DEFAULT_INCIDENT_CAP_MULTIPLE: Balance = 10;
+
+// Upper bound on the claim window (1 year)
+pub(crate) const MAX_INSURANCE_CLAIM_WINDOW: u64 = 365 * 24 * 60 * 60 * 1_000_000_000;
+
+// Longest description a claimant can give
+const MAX_CLAIM_DESCRIPTION_LENGTH: usize = 1000;
+
+// How slashed tokens fund the insurance pool and how clients claim from it
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InsuranceConfig {
+    // Share of every slash paid into the pool before the burn and treasury split (basis points)
+    pub pool_share_bps: u16,
+    // Time either side of a slash that a failed or disputed intent is covered, and after the
+    // slash that claims stay open (nanoseconds)
+    pub claim_window: u64,
+    // Most the pool pays out across all claims on one incident
+    pub max_incident_payout: U128,
+}
+
+impl InsuranceConfig {
+    pub(crate) fn new(min_stake_amount: Balance) -> Self {
+        Self {
+            pool_share_bps: 5_000,
+            claim_window: 30 * 24 * 60 * 60 * 1_000_000_000, // 30 days in nanoseconds
+            max_incident_payout: U128(min_stake_amount * DEFAULT_INCIDENT_CAP_MULTIPLE),
+        }
+    }
+}
+
+// Slashing of an agent that clients harmed by it can claim against. Own and delegated stake
+// slashed in the same block form one incident.
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+pub struct InsuranceIncident {
+    agent_id: AccountId,
+    slashed_at: u64,
+    // Slashed tokens paid into the pool
+    contributed: Balance,
+    // Approved payouts on this incident, including ones still being transferred
+    paid_out: Balance,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum ClaimStatus {
+    Pending,
+    // Approved and being transferred
+    Approved,
+    Paid,
+    Rejected,
+}
+
+// Client's claim for compensation over an intent the slashed agent failed
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+pub struct InsuranceClaim {
+    incident_id: u64,
+    intent_id: String,
+    claimant: AccountId,
+    requested: Balance,
+    description: String,
+    status: ClaimStatus,
+    payout: Balance,
+    filed_at: u64,
+    // Governance's reason when rejecting
+    resolution: Option<String>,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // File a claim against an insurance incident for an intent the slashed agent failed or that
+    // was disputed within the claim window of the slash. One claim per intent; the claimant
+    // pays for its storage. Returns the claim ID.
+    pub fn file_insurance_claim(
+        &mut self,
+        incident_id: u64,
+        intent_id: String,
+        amount: U128,
+        description: String,
+    ) -> u64 {
+        let claimant = env::predecessor_account_id();
+        let now = env::block_timestamp();
+        let window = self.insurance_config.claim_window;
+        assert!(amount.0 > 0, "Claim amount must be positive");
+        assert!(description.len() <= MAX_CLAIM_DESCRIPTION_LENGTH, "Claim description too long");
+
+        let incident = self.insurance_incidents.get(incident_id).expect("Incident not found");
+        assert!(now <= incident.slashed_at + window, "Claim window for this incident has closed");
+
+        let intent = self.intents.get(&intent_id).expect("Intent not found");
+        assert!(intent.client_id == claimant, "Only the intent's client can claim");
+        assert!(intent.agent_id == incident.agent_id, "Intent was not handled by the slashed agent");
+        assert!(
+            intent.status == IntentStatus::Failed || intent.disputed_at.is_some(),
+            "Only failed or disputed intents are covered"
+        );
+        assert!(
+            intent.updated_at.abs_diff(incident.slashed_at) <= window,
+            "Intent is outside the incident's claim window"
+        );
+        assert!(self.intent_insurance_claims.get(&intent_id).is_none(), "Intent already claimed");
+
+        let initial_storage = env::storage_usage();
+        let claim_id = self.insurance_claims.len();
+        self.insurance_claims.push(&InsuranceClaim {
+            incident_id,
+            intent_id: intent_id.clone(),
+            claimant: claimant.clone(),
+            requested: amount.0,
+            description,
+            status: ClaimStatus::Pending,
+            payout: 0,
+            filed_at: now,
+            resolution: None,
+        });
+        self.intent_insurance_claims.insert(&intent_id, &claim_id);
+        self.internal_charge_storage(&claimant, initial_storage);
+
+        emit_event(
+            "insurance_claim_filed",
+            json!({
+                "claim_id": claim_id,
+                "incident_id": incident_id,
+                "intent_id": intent_id,
+                "claimant": claimant,
+                "amount": amount,
+            }),
+        );
+
+        claim_id
+    }
+
+    // Approve a pending claim and pay it out of the pool, optionally for less than requested.
+    // Payouts on one incident are capped at the configured maximum and at what the incident
+    // contributed to the pool, so one incident cannot drain contributions from others.
+    pub fn approve_insurance_claim(&mut self, claim_id: u64, payout: Option<U128>) -> Promise {
+        self.assert_owner_or_governance();
+        let mut claim = self.insurance_claims.get(claim_id).expect("Claim not found");
+        assert!(claim.status == ClaimStatus::Pending, "Claim is not pending");
+        let payout = payout.map_or(claim.requested, |payout| payout.0);
+        assert!(payout > 0 && payout <= claim.requested, "Payout must be positive and at most the requested amount");
+
+        let mut incident = self.insurance_incidents.get(claim.incident_id).expect("Incident not found");
+        assert!(
+            incident.paid_out + payout <= incident_payout_cap(&incident, self.insurance_config.max_incident_payout.0),
+            "Payout exceeds the incident's cap"
+        );
+        assert!(payout <= self.stats.itlx_insurance_pool, "Insurance pool balance too low");
+
+        incident.paid_out += payout;
+        self.insurance_incidents.replace(claim.incident_id, &incident);
+        self.stats.itlx_insurance_pool -= payout;
+        claim.status = ClaimStatus::Approved;
+        claim.payout = payout;
+        self.insurance_claims.replace(claim_id, &claim);
+
+        emit_event(
+            "insurance_claim_approved",
+            json!({
+                "claim_id": claim_id,
+                "claimant": claim.claimant,
+                "payout": U128(payout),
+                "approved_by": env::predecessor_account_id(),
+            }),
+        );
+
+        Promise::new(self.token_contract_id.clone())
+            .function_call(
+                "ft_transfer".to_string(),
+                json!({
+                    "receiver_id": claim.claimant,
+                    "amount": U128(payout),
+                    "memo": format!("Insurance claim {}", claim_id),
+                }).to_string().into_bytes(),
+                1, // 1 yoctoNEAR
+                GAS_FOR_INSURANCE_FT_TRANSFER,
+            )
+            .then(
+                Promise::new(env::current_account_id())
+                    .function_call(
+                        "on_insurance_payout_complete".to_string(),
+                        json!({ "claim_id": claim_id }).to_string().into_bytes(),
+                        0,
+                        GAS_FOR_INSURANCE_CALLBACK,
+                    )
+            )
+    }
+
+    // Callback after transferring a claim payout
+    #[private]
+    pub fn on_insurance_payout_complete(&mut self, claim_id: u64) {
+        let mut claim = self.insurance_claims.get(claim_id).expect("Claim not found");
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => {
+                claim.status = ClaimStatus::Paid;
+                emit_event(
+                    "insurance_claim_paid",
+                    json!({
+                        "claim_id": claim_id,
+                        "claimant": claim.claimant,
+                        "payout": U128(claim.payout),
+                    }),
+                );
+            },
+            _ => {
+                // Transfer failed: the tokens never left, so the pool and incident cap get them
+                // back and the claim can be approved again
+                let mut incident = self.insurance_incidents.get(claim.incident_id).expect("Incident not found");
+                incident.paid_out -= claim.payout;
+                self.insurance_incidents.replace(claim.incident_id, &incident);
+                self.stats.itlx_insurance_pool += claim.payout;
+                claim.status = ClaimStatus::Pending;
+                claim.payout = 0;
+
+                emit_event(
+                    "insurance_payout_failed",
+                    json!({
+                        "claim_id": claim_id,
+                        "claimant": claim.claimant,
+                    }),
+                );
+            }
+        }
+        self.insurance_claims.replace(claim_id, &claim);
+    }
+
+    // Reject a pending claim
+    pub fn reject_insurance_claim(&mut self, claim_id: u64, reason: String) {
+        self.assert_owner_or_governance();
+        let mut claim = self.insurance_claims.get(claim_id).expect("Claim not found");
+        assert!(claim.status == ClaimStatus::Pending, "Claim is not pending");
+
+        claim.status = ClaimStatus::Rejected;
+        claim.resolution = Some(reason.clone());
+        self.insurance_claims.replace(claim_id, &claim);
+
+        emit_event(
+            "insurance_claim_rejected",
+            json!({
+                "claim_id": claim_id,
+                "claimant": claim.claimant,
+                "reason": reason,
+            }),
+        );
+    }
+
+    // Get the pool's balance and lifetime totals
+    pub fn get_insurance_pool(&self) -> InsurancePoolView {
+        InsurancePoolView {
+            balance: U128(self.stats.itlx_insurance_pool),
+            incident_count: self.insurance_incidents.len(),
+            claim_count: self.insurance_claims.len(),
+            config: self.insurance_config.clone(),
+        }
+    }
+
+    // Get an incident with how much of its cap is left
+    pub fn get_insurance_incident(&self, incident_id: u64) -> Option<InsuranceIncidentView> {
+        self.insurance_incidents.get(incident_id).map(|incident| InsuranceIncidentView {
+            incident_id,
+            claims_close_at: incident.slashed_at + self.insurance_config.claim_window,
+            remaining_cap: U128(
+                incident_payout_cap(&incident, self.insurance_config.max_incident_payout.0).saturating_sub(incident.paid_out),
+            ),
+            agent_id: incident.agent_id,
+            slashed_at: incident.slashed_at,
+            contributed: U128(incident.contributed),
+            paid_out: U128(incident.paid_out),
+        })
+    }
+
+    // Get a claim and its status
+    pub fn get_insurance_claim(&self, claim_id: u64) -> Option<InsuranceClaimView> {
+        self.insurance_claims.get(claim_id).map(|claim| InsuranceClaimView {
+            claim_id,
+            incident_id: claim.incident_id,
+            intent_id: claim.intent_id,
+            claimant: claim.claimant,
+            requested: U128(claim.requested),
+            description: claim.description,
+            status: claim.status,
+            payout: U128(claim.payout),
+            filed_at: claim.filed_at,
+            resolution: claim.resolution,
+        })
+    }
+
+    // Pay the pool's share of a slash into it, opening an incident clients can claim against.
+    // Returns what is left for the burn and treasury split.
+    pub(crate) fn internal_fund_insurance(&mut self, agent_id: &AccountId, amount: Balance) -> Balance {
+        let contribution = amount * self.insurance_config.pool_share_bps as u128 / 10_000;
+        let now = env::block_timestamp();
+
+        let last_index = self.insurance_incidents.len().checked_sub(1);
+        let same_incident = last_index
+            .and_then(|index| self.insurance_incidents.get(index).map(|incident| (index, incident)))
+            .filter(|(_, incident)| &incident.agent_id == agent_id && incident.slashed_at == now);
+        let incident_id = match same_incident {
+            Some((index, mut incident)) => {
+                incident.contributed += contribution;
+                self.insurance_incidents.replace(index, &incident);
+                index
+            },
+            None => {
+                self.insurance_incidents.push(&InsuranceIncident {
+                    agent_id: agent_id.clone(),
+                    slashed_at: now,
+                    contributed: contribution,
+                    paid_out: 0,
+                });
+                self.insurance_incidents.len() - 1
+            },
+        };
+        self.stats.itlx_insurance_pool += contribution;
+
+        emit_event(
+            "insurance_pool_funded",
+            json!({
+                "incident_id": incident_id,
+                "agent_id": agent_id,
+                "amount": U128(contribution),
+            }),
+        );
+
+        amount - contribution
+    }
+}
+
+// View struct for the insurance pool
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InsurancePoolView {
+    balance: U128,
+    incident_count: u64,
+    claim_count: u64,
+    config: InsuranceConfig,
+}
+
+// Most that can be paid out on an incident: its contribution to the pool, up to the configured cap
+fn incident_payout_cap(incident: &InsuranceIncident, max_incident_payout: Balance) -> Balance {
+    std::cmp::min(incident.contributed, max_incident_payout)
+}
+
+// View struct for an insurance incident
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InsuranceIncidentView {
+    incident_id: u64,
+    agent_id: AccountId,
+    slashed_at: u64,
+    claims_close_at: u64,
+    contributed: U128,
+    paid_out: U128,
+    // Payouts still available under the per-incident cap and the incident's contribution
+    remaining_cap: U128,
+}
+
+// View struct for an insurance claim
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InsuranceClaimView {
+    claim_id: u64,
+    incident_id: u64,
+    intent_id: String,
+    claimant: AccountId,
+    requested: U128,
+    description: String,
+    status: ClaimStatus,
+    payout: U128,
+    filed_at: u64,
+    resolution: Option<String>,
+}
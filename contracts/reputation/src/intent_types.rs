@@ -0,0 +1,121 @@
+use crate::*;
+use crate::fixed_point::to_points;
+use crate::specializations::normalize_tag;
+
+// Most intent types the taxonomy can hold
+const MAX_INTENT_TYPES: usize = 200;
+
+// Limits on an intent type's name and description
+const MAX_INTENT_TYPE_LENGTH: usize = 64;
+const MAX_INTENT_TYPE_DESCRIPTION_LENGTH: usize = 256;
+
+// Longest default deadline an intent type can set (1 year)
+const MAX_INTENT_TYPE_DEADLINE: u64 = 365 * 24 * 60 * 60 * 1_000_000_000;
+
+// Upper bound on intent types returned per page
+const MAX_INTENT_TYPES_PAGE: u32 = 100;
+
+// An intent type in the taxonomy. Intents must use a registered type; the agent handling one
+// must meet its trust level, and its capability limits must cover its complexity, both judged
+// by the agent's score in the type.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IntentTypeMetadata {
+    pub description: String,
+    // Complexity of the work (1-10), checked against the agent's max_complexity
+    pub complexity: u8,
+    // Lowest trust level of an agent handling the type
+    pub required_trust_level: TrustLevel,
+    // Deadline period for intents of the type recorded without one (nanoseconds); the
+    // contract-wide intent deadline period if unset
+    pub default_deadline_period: Option<u64>,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Add an intent type to the taxonomy or update its metadata (owner or governance). Names
+    // are normalized like specialization tags.
+    pub fn set_intent_type(&mut self, intent_type: String, metadata: IntentTypeMetadata) {
+        self.assert_owner_or_governance();
+        let intent_type = normalize_tag(&intent_type);
+        assert!(
+            !intent_type.is_empty() && intent_type.len() <= MAX_INTENT_TYPE_LENGTH,
+            "Invalid intent type"
+        );
+        assert!(metadata.description.len() <= MAX_INTENT_TYPE_DESCRIPTION_LENGTH, "Intent type description too long");
+        assert!((1..=10).contains(&metadata.complexity), "Complexity must be between 1 and 10");
+        if let Some(period) = metadata.default_deadline_period {
+            assert!(
+                period > 0 && period <= MAX_INTENT_TYPE_DEADLINE,
+                "Default deadline period out of range"
+            );
+        }
+        assert!(
+            self.intent_types.get(&intent_type).is_some() || self.intent_types.len() < MAX_INTENT_TYPES as u64,
+            "Too many intent types"
+        );
+
+        let old_value = self.intent_types.insert(&intent_type, &metadata);
+
+        self.record_config_change(&format!("intent_type.{}", intent_type), json!(old_value), json!(metadata));
+    }
+
+    // Remove an intent type from the taxonomy (owner or governance). Intents already recorded
+    // with it are unaffected; new ones can no longer use it.
+    pub fn remove_intent_type(&mut self, intent_type: String) {
+        self.assert_owner_or_governance();
+        let intent_type = normalize_tag(&intent_type);
+        let old_value = self.intent_types.remove(&intent_type).expect("Intent type not found");
+
+        self.record_config_change(&format!("intent_type.{}", intent_type), json!(old_value), json!(null));
+    }
+
+    // Get an intent type's metadata
+    pub fn get_intent_type(&self, intent_type: String) -> Option<IntentTypeMetadata> {
+        self.intent_types.get(&normalize_tag(&intent_type))
+    }
+
+    // Get a page of the taxonomy
+    pub fn get_intent_types(&self, from_index: u64, limit: u32) -> Vec<(String, IntentTypeMetadata)> {
+        self.intent_types
+            .iter()
+            .skip(from_index as usize)
+            .take(std::cmp::min(limit, MAX_INTENT_TYPES_PAGE) as usize)
+            .collect()
+    }
+
+    // Check a new intent's type against the taxonomy and the agent's standing in it. Returns
+    // the normalized type and its default deadline period.
+    pub(crate) fn internal_validate_intent_type(&self, agent_id: &AccountId, intent_type: &str) -> (String, u64) {
+        let intent_type = normalize_tag(intent_type);
+        let metadata = self.intent_types.get(&intent_type).expect("Unknown intent type");
+
+        let agent_rep = self.agent_reputations.get(agent_id).expect("Agent not registered");
+        if let Err(message) = self.internal_check_intent_type_standing(agent_id, &agent_rep, &intent_type, &metadata) {
+            env::panic_str(message);
+        }
+
+        let deadline_period = metadata.default_deadline_period.unwrap_or(self.intent_deadline_period);
+        (intent_type, deadline_period)
+    }
+
+    // Whether the agent's standing in a normalized intent type meets the type's trust level and
+    // its capability limits cover the type's complexity
+    pub(crate) fn internal_check_intent_type_standing(
+        &self,
+        agent_id: &AccountId,
+        agent_rep: &AgentReputation,
+        intent_type: &str,
+        metadata: &IntentTypeMetadata,
+    ) -> Result<(), &'static str> {
+        let score = to_points(self.internal_specialization_score_bps(agent_id, intent_type));
+        if self.get_trust_level(score) < metadata.required_trust_level {
+            return Err("Agent's trust level is too low for this intent type");
+        }
+        let limits = self.internal_capability_limits(agent_id, agent_rep, Some(intent_type));
+        if metadata.complexity > limits.max_complexity {
+            return Err("Intent type exceeds the agent's maximum complexity");
+        }
+        Ok(())
+    }
+}
@@ -1,86 +1,842 @@
-use near_sdk::{env, json_types::Base64VecU8};
+use crate::*;
+use crate::events::emit_event;
+
+// Upper bound on intent IDs returned by get_expired_intents
+const MAX_EXPIRED_INTENTS_PAGE: u32 = 100;
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct IntentData {
-    intent_id: String,
-    agent_id: AccountId,
-    client_id: AccountId,
-    intent_type: String,
-    parameters: String,
-    status: IntentStatus,
-    timestamp: u64,
+    pub(crate) intent_id: String,
+    pub(crate) agent_id: AccountId,
+    pub(crate) client_id: AccountId,
+    pub(crate) intent_type: String,
+    pub(crate) parameters: String,
+    pub(crate) status: IntentStatus,
+    // Result reported with the latest status update
+    pub(crate) result: Option<String>,
+    pub(crate) timestamp: u64,
+    // Timestamp of the latest status transition
+    pub(crate) updated_at: u64,
+    // Deadline for the agent to accept or decline the intent
+    pub(crate) response_deadline: u64,
+    // Deadline for the agent to finish the intent; afterwards anyone can fail it
+    pub(crate) deadline: u64,
+    // Timestamp the agent accepted the intent (starts the SLA clock)
+    pub(crate) accepted_at: Option<u64>,
+    // Whether the client has already left feedback for this intent
+    pub(crate) feedback_submitted: bool,
+    // Payment attached by the client and held by the contract
+    pub(crate) escrow_amount: Balance,
+    // Asset the escrow is held in
+    pub(crate) escrow_asset: EscrowAsset,
+    // Portion of the escrow already released to the agent
+    pub(crate) escrow_released: Balance,
+    // Portion of the escrow returned to the client
+    pub(crate) escrow_refunded: Balance,
+    // When the client disputed the outcome, if at all
+    pub(crate) disputed_at: Option<u64>,
+    // Client's reason for disputing the outcome
+    pub(crate) dispute_reason: Option<String>,
+    // Whether the outcome is final: escrow settled and reputation effects applied
+    pub(crate) finalized: bool,
+    // Total time the intent's deadlines were pushed back for force majeure
+    pub(crate) deadline_extension: u64,
+    // Force-majeure windows the intent has already been extended for
+    pub(crate) force_majeure_windows: Vec<u64>,
+    // Standing agreement the intent was delegated under, if any
+    pub(crate) agreement_id: Option<u64>,
+    // Cause of a failure, or of the client walking away from stalled work
+    pub(crate) failure_category: Option<FailureCategory>,
+    // Account that referred the client, paid its share of the protocol fee
+    pub(crate) referrer_id: Option<AccountId>,
+    // Status updates applied so far; an update quoting a nonce must match it, so a replayed
+    // update is rejected instead of applied twice
+    pub(crate) nonce: u64,
+}
+
+impl IntentData {
+    // Time after which the open intent can be expired: the response deadline until the agent
+    // accepts, then the deadline. None once the intent is no longer open.
+    pub(crate) fn expires_at(&self) -> Option<u64> {
+        match self.status {
+            IntentStatus::Created => Some(self.response_deadline),
+            IntentStatus::Accepted | IntentStatus::InProgress => Some(self.deadline),
+            _ => None,
+        }
+    }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, PartialEq)]
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
 pub enum IntentStatus {
     Created,
+    Accepted,
+    Declined,
     InProgress,
     Completed,
     Failed,
+    Disputed,
+    Cancelled,
+    Expired,
 }
 
+// Party that is allowed to drive a given status transition
+#[derive(PartialEq)]
+enum IntentActor {
+    Agent,
+    Client,
+    // Governance or a registered arbiter
+    Arbiter,
+    // Anyone, once the intent's response deadline has passed
+    Keeper,
+}
+
+impl IntentStatus {
+    // Returns the party authorized to move an intent from this status to `next`,
+    // or None if the transition is not part of the status machine
+    fn transition_actor(&self, next: IntentStatus) -> Option<IntentActor> {
+        use IntentStatus::*;
+
+        match (self, next) {
+            (Created, Accepted) | (Created, Declined) => Some(IntentActor::Agent),
+            (Accepted, InProgress) => Some(IntentActor::Agent),
+            (InProgress, Completed) | (InProgress, Failed) => Some(IntentActor::Agent),
+            (Completed, Disputed) => Some(IntentActor::Client),
+            (Created, Cancelled) | (Accepted, Cancelled) => Some(IntentActor::Client),
+            (InProgress, Cancelled) => Some(IntentActor::Client),
+            (Created, Expired) => Some(IntentActor::Keeper),
+            (Accepted, Expired) | (InProgress, Expired) => Some(IntentActor::Client),
+            (Disputed, Completed) | (Disputed, Failed) => Some(IntentActor::Arbiter),
+            _ => None,
+        }
+    }
+
+    // Whether the intent has reached a final outcome
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            IntentStatus::Completed
+                | IntentStatus::Failed
+                | IntentStatus::Declined
+                | IntentStatus::Cancelled
+                | IntentStatus::Expired
+        )
+    }
+}
+
+#[near_bindgen]
 impl AgentReputationContract {
-    // Record a new intent being handled by an agent
-    pub fn record_intent(&mut self, intent_id: String, agent_id: AccountId, intent_type: String, parameters: String) {
+    // Record a new intent being handled by an agent; any attached deposit is held in escrow.
+    // To escrow ITLX instead, send it with ft_transfer_call and a record_intent message.
+    // `deadline` defaults to the intent type's default deadline period, or the configured intent
+    // deadline period, from now. The type must be registered in the taxonomy. A `referrer_id`
+    // receives the referrer share of the protocol fee when the escrow is released.
+    #[payable]
+    pub fn record_intent(
+        &mut self,
+        intent_id: String,
+        agent_id: AccountId,
+        intent_type: String,
+        parameters: String,
+        deadline: Option<u64>,
+        referrer_id: Option<AccountId>,
+    ) {
         let client_id = env::predecessor_account_id();
-        
-        // Ensure agent exists
-        assert!(self.agent_reputations.contains_key(&agent_id), "Agent not registered");
-        
+        self.internal_record_intent(
+            intent_id,
+            agent_id,
+            client_id.clone(),
+            intent_type,
+            parameters,
+            deadline,
+            env::attached_deposit(),
+            EscrowAsset::Near,
+            referrer_id,
+            Some(&client_id),
+        );
+    }
+
+    // Record an intent on a client's behalf (intents processor only). Any attached deposit is
+    // held in escrow as the client's payment and refunds go to the client. The processor pays
+    // for the intent's storage, so it cannot draw on a client's storage balance.
+    #[payable]
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_intent_for_client(
+        &mut self,
+        intent_id: String,
+        agent_id: AccountId,
+        client_id: AccountId,
+        intent_type: String,
+        parameters: String,
+        deadline: Option<u64>,
+        referrer_id: Option<AccountId>,
+    ) {
+        let processor = env::predecessor_account_id();
+        assert!(
+            self.is_intents_processor(&processor),
+            "Only the intents processor can record intents for clients"
+        );
+        self.internal_record_intent(
+            intent_id,
+            agent_id,
+            client_id.clone(),
+            intent_type,
+            parameters,
+            deadline,
+            env::attached_deposit(),
+            EscrowAsset::Near,
+            referrer_id,
+            Some(&processor),
+        );
+    }
+
+    // Create an intent with its escrowed payment, charging its storage to `storage_payer`.
+    // With None the caller accounts for the storage itself.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn internal_record_intent(
+        &mut self,
+        intent_id: String,
+        agent_id: AccountId,
+        client_id: AccountId,
+        intent_type: String,
+        parameters: String,
+        deadline: Option<u64>,
+        escrow_amount: Balance,
+        escrow_asset: EscrowAsset,
+        referrer_id: Option<AccountId>,
+        storage_payer: Option<&AccountId>,
+    ) {
+        // Ensure agent exists and can take new work
+        assert!(self.agent_reputations.get(&agent_id).is_some(), "Agent not registered");
+        self.assert_agent_in_good_standing(&agent_id);
+        assert_ne!(agent_id, client_id, "Agents cannot record intents for themselves");
+
+        // Neither party can collect the referrer share of its own intent
+        if let Some(referrer_id) = &referrer_id {
+            assert!(
+                *referrer_id != agent_id && *referrer_id != client_id,
+                "Referrer cannot be the intent's agent or client"
+            );
+        }
+
+        // Ensure intent IDs are unique
+        assert!(self.intents.get(&intent_id).is_none(), "Intent already exists");
+        assert!(!self.open_auctions.contains(&intent_id), "Intent is out for bids");
+
+        // The type must be in the taxonomy and within the agent's standing in it
+        let (intent_type, deadline_period) = self.internal_validate_intent_type(&agent_id, &intent_type);
+
+        #[cfg(not(feature = "escrow"))]
+        assert_eq!(escrow_amount, 0, "Intent escrow is not enabled in this build");
+
+        // The agent must get the full response period to react before the intent can time out
+        let now = env::block_timestamp();
+        let response_deadline = now + self.intent_response_period;
+        let deadline = deadline.unwrap_or(now + deadline_period);
+        assert!(deadline > response_deadline, "Intent deadline must be after the response deadline");
+
+        let initial_storage = env::storage_usage();
+
         // Create and store intent data
         let intent_data = IntentData {
             intent_id: intent_id.clone(),
             agent_id: agent_id.clone(),
             client_id: client_id.clone(),
-            intent_type,
+            intent_type: intent_type.clone(),
             parameters,
             status: IntentStatus::Created,
-            timestamp: env::block_timestamp(),
+            result: None,
+            timestamp: now,
+            updated_at: now,
+            response_deadline,
+            deadline,
+            accepted_at: None,
+            feedback_submitted: false,
+            escrow_amount,
+            escrow_asset,
+            escrow_released: 0,
+            escrow_refunded: 0,
+            disputed_at: None,
+            dispute_reason: None,
+            finalized: false,
+            deadline_extension: 0,
+            force_majeure_windows: Vec::new(),
+            agreement_id: None,
+            failure_category: None,
+            referrer_id,
+            nonce: 0,
+        };
+
+        self.intents.insert(&intent_id, &intent_data);
+        self.internal_update_open_intent_index(&intent_data, None);
+        if escrow_asset == EscrowAsset::Itlx {
+            self.stats.itlx_in_escrow += escrow_amount;
+        }
+        self.internal_record_client_intent(&client_id);
+
+        if let Some(storage_payer) = storage_payer {
+            self.internal_charge_storage(storage_payer, initial_storage);
+        }
+
+        emit_event(
+            "intent_created",
+            json!({
+                "intent_id": intent_id,
+                "agent_id": agent_id,
+                "client_id": client_id,
+                "intent_type": intent_type,
+                "escrow_amount": U128(intent_data.escrow_amount),
+                "escrow_asset": escrow_asset,
+                "deadline": deadline,
+            }),
+        );
+
+        // Agents can accept intents from well-regarded clients automatically
+        if self.internal_client_auto_accepted(&agent_id, &client_id) {
+            self.internal_apply_intent_transition(intent_data, IntentStatus::Accepted, None);
+        }
+    }
+
+    // Update intent status and adjust reputation accordingly. A `nonce` must equal the intent's
+    // current nonce (see get_intent); the intents processor, relaying updates for the agent,
+    // must always quote one.
+    pub fn update_intent_status(&mut self, intent_id: String, status: IntentStatus, result: Option<String>, nonce: Option<u64>) {
+        let intent = self.internal_authorize_transition(&intent_id, status, nonce);
+        self.internal_apply_intent_transition(intent, status, result);
+    }
+
+    // Accept an assigned intent, starting the SLA clock
+    pub fn accept_intent(&mut self, intent_id: String) {
+        self.internal_transition_intent(&intent_id, IntentStatus::Accepted, None);
+    }
+
+    // Decline an assigned intent
+    pub fn decline_intent(&mut self, intent_id: String, reason: Option<String>) {
+        self.internal_transition_intent(&intent_id, IntentStatus::Declined, reason);
+    }
+
+    // Expire an intent the agent never responded to (callable by anyone after the response deadline)
+    pub fn expire_unaccepted_intent(&mut self, intent_id: String) {
+        self.internal_transition_intent(&intent_id, IntentStatus::Expired, None);
+    }
+
+    // Time out an intent whose deadline has passed (callable by anyone). Unaccepted intents
+    // expire without reputational effect; accepted or in-progress ones fail against the agent
+    // and the client is refunded.
+    pub fn expire_intent(&mut self, intent_id: String) {
+        let intent = self.intents.get(&intent_id).expect("Intent not found");
+
+        match intent.status {
+            IntentStatus::Created => {
+                self.internal_transition_intent(&intent_id, IntentStatus::Expired, None);
+            },
+            IntentStatus::Accepted | IntentStatus::InProgress => {
+                assert!(env::block_timestamp() > intent.deadline, "Intent deadline has not passed");
+                let mut intent = intent;
+                intent.failure_category = Some(FailureCategory::Timeout);
+                self.internal_apply_intent_transition(intent, IntentStatus::Failed, Some("Deadline exceeded".to_string()));
+            },
+            _ => panic!("Only open intents can be expired"),
+        }
+    }
+
+    // Get up to `limit` open intents that can be expired right now, for keepers
+    pub fn get_expired_intents(&self, limit: u32) -> Vec<String> {
+        let now = env::block_timestamp();
+
+        self.open_intent_deadlines
+            .iter()
+            .take_while(|((expires_at, _), _)| now > *expires_at)
+            .take(std::cmp::min(limit, MAX_EXPIRED_INTENTS_PAGE) as usize)
+            .map(|((_, intent_id), _)| intent_id)
+            .collect()
+    }
+
+    // Move the intent's entry in the open-intent index from `previous_expiry` to its current
+    // expiry, dropping it once the intent is no longer open
+    pub(crate) fn internal_update_open_intent_index(&mut self, intent: &IntentData, previous_expiry: Option<u64>) {
+        let expires_at = intent.expires_at();
+        if expires_at == previous_expiry {
+            return;
+        }
+        if let Some(previous_expiry) = previous_expiry {
+            self.open_intent_deadlines.remove(&(previous_expiry, intent.intent_id.clone()));
+        }
+        if let Some(expires_at) = expires_at {
+            self.open_intent_deadlines.insert(&(expires_at, intent.intent_id.clone()), &());
+        }
+    }
+
+    // Validate a status transition against the status machine and the caller, then apply it
+    pub(crate) fn internal_transition_intent(&mut self, intent_id: &str, status: IntentStatus, result: Option<String>) {
+        let intent = self.internal_authorize_transition(intent_id, status, None);
+        self.internal_apply_intent_transition(intent, status, result);
+    }
+
+    // Validate a status transition against the status machine, the caller and the quoted
+    // nonce, returning the intent for the transition to be applied to. The intents processor
+    // may make the agent's transitions, quoting the nonce.
+    pub(crate) fn internal_authorize_transition(&mut self, intent_id: &str, status: IntentStatus, nonce: Option<u64>) -> IntentData {
+        let caller = env::predecessor_account_id();
+        let now = env::block_timestamp();
+
+        let intent = self.intents.get(&intent_id.to_string()).expect("Intent not found");
+        let previous_status = intent.status;
+
+        // Ensure the transition is part of the status machine
+        let actor = previous_status
+            .transition_actor(status)
+            .unwrap_or_else(|| panic!("Invalid intent status transition: {:?} -> {:?}", previous_status, status));
+
+        // Ensure the caller is allowed to perform this transition
+        let relayed = actor == IntentActor::Agent && caller != intent.agent_id && self.is_intents_processor(&caller);
+        let authorized = match actor {
+            IntentActor::Agent => caller == intent.agent_id || relayed,
+            IntentActor::Client => caller == intent.client_id,
+            IntentActor::Arbiter => self.is_arbiter(&caller),
+            IntentActor::Keeper => now > intent.response_deadline,
+        };
+        assert!(authorized, "Unauthorized intent status transition");
+        assert!(nonce.is_some() || !relayed, "The intents processor must quote the intent nonce");
+        if let Some(nonce) = nonce {
+            assert_eq!(nonce, intent.nonce, "Intent nonce mismatch: update already applied or out of order");
+        }
+        if actor == IntentActor::Agent {
+            self.internal_record_activity(&intent.agent_id);
+        }
+
+        // Clients can only walk away from in-progress work once it has stalled
+        if previous_status == IntentStatus::InProgress && status == IntentStatus::Cancelled {
+            let checkpoints = self.intent_checkpoints.get(&intent.intent_id).unwrap_or_default();
+            assert!(self.is_intent_stalled(&intent, &checkpoints), "Intent has not stalled");
+            if !self.is_force_majeure_active() {
+                self.internal_record_stall(&intent.agent_id);
+            }
+        }
+
+        // Clients can only expire accepted or in-progress work once its deadline has passed
+        if status == IntentStatus::Expired && actor == IntentActor::Client {
+            assert!(now > intent.deadline, "Intent deadline has not passed");
+        }
+
+        // Agents must respond before the response deadline
+        if previous_status == IntentStatus::Created && actor == IntentActor::Agent {
+            assert!(now <= intent.response_deadline, "Intent response deadline has passed");
+        }
+
+        // Completed work can only be challenged before it is finalized
+        if status == IntentStatus::Disputed {
+            assert!(
+                !intent.finalized && now <= intent.updated_at + self.intent_dispute_window,
+                "Dispute window has passed"
+            );
+        }
+
+        intent
+    }
+
+    // Apply an authorized status transition, updating reputation for final outcomes
+    pub(crate) fn internal_apply_intent_transition(&mut self, mut intent: IntentData, status: IntentStatus, result: Option<String>) {
+        let caller = env::predecessor_account_id();
+        let now = env::block_timestamp();
+        let previous_status = intent.status;
+        let previous_expiry = intent.expires_at();
+
+        if status == IntentStatus::Accepted {
+            self.assert_agent_in_good_standing(&intent.agent_id);
+            intent.accepted_at = Some(now);
+        }
+
+        // Declines count against the agent's grace quota
+        if status == IntentStatus::Declined {
+            self.internal_record_decline(&intent.agent_id);
+        }
+
+        // Failures without a stated cause count as wrong results
+        if status == IntentStatus::Failed && intent.failure_category.is_none() {
+            intent.failure_category = Some(FailureCategory::WrongResult);
+        }
+        if status == IntentStatus::Cancelled && previous_status == IntentStatus::InProgress {
+            intent.failure_category = Some(FailureCategory::ClientCancelled);
+        }
+
+        // Track the intents the agent finished, and disputes opened against it and their outcomes
+        if matches!(status, IntentStatus::Completed | IntentStatus::Failed | IntentStatus::Disputed)
+            || previous_status == IntentStatus::Disputed
+        {
+            self.internal_record_dispute_transition(&mut intent, previous_status, status);
+        }
+        if status == IntentStatus::Disputed || previous_status == IntentStatus::Disputed || status == IntentStatus::Cancelled {
+            self.internal_record_client_transition(&intent, previous_status, status);
+        }
+
+        intent.status = status;
+        intent.updated_at = now;
+        intent.nonce += 1;
+        if result.is_some() {
+            intent.result = result;
+        }
+
+        // Completed work stays open to challenge by the client for the dispute window;
+        // every other final outcome takes effect immediately
+        let challengeable = status == IntentStatus::Completed
+            && previous_status == IntentStatus::InProgress
+            && self.intent_dispute_window > 0;
+        if status.is_terminal() && !challengeable {
+            self.internal_finalize_intent(&mut intent, previous_status);
+        }
+
+        self.intents.insert(&intent.intent_id, &intent);
+        self.internal_update_open_intent_index(&intent, previous_expiry);
+
+        emit_event(
+            "intent_status_updated",
+            json!({
+                "intent_id": intent.intent_id,
+                "agent_id": intent.agent_id,
+                "old_status": previous_status,
+                "new_status": status,
+                "updated_by": caller,
+                "nonce": intent.nonce,
+            }),
+        );
+    }
+
+    // Finalize a completed intent whose dispute window passed unchallenged (callable by anyone)
+    pub fn finalize_intent(&mut self, intent_id: String) {
+        let mut intent = self.intents.get(&intent_id).expect("Intent not found");
+        assert!(intent.status == IntentStatus::Completed, "Only completed intents await finalization");
+        assert!(!intent.finalized, "Intent already finalized");
+        assert!(
+            env::block_timestamp() > intent.updated_at + self.intent_dispute_window,
+            "Dispute window has not passed"
+        );
+
+        self.internal_finalize_intent(&mut intent, IntentStatus::Completed);
+        self.intents.insert(&intent_id, &intent);
+    }
+
+    // Settle the escrow and apply the reputation effect of a final outcome
+    pub(crate) fn internal_finalize_intent(&mut self, intent: &mut IntentData, previous_status: IntentStatus) {
+        intent.finalized = true;
+
+        // Pay out or refund the escrow
+        self.internal_settle_escrow(intent, previous_status);
+        self.internal_record_agreement_outcome(intent);
+
+        // Completed and failed intents count toward the agent's record. Declined, cancelled
+        // and expired intents carry no reputational effect, and failures during force majeure
+        // are not held against the agent.
+        let force_majeure = self.is_force_majeure_active();
+        let counted = match intent.status {
+            IntentStatus::Completed => true,
+            IntentStatus::Failed => !force_majeure,
+            _ => false,
         };
-        
-        // In a production system, you'd store this in a data structure
-        // For simplicity in this example, we'll just emit an event
-        env::log_str(&format!("Intent created: {}", intent_id));
-    }
-    
-    // Update intent status and adjust reputation accordingly
-    pub fn update_intent_status(&mut self, intent_id: String, status: String, result: Option<String>) {
-        let agent_id = env::predecessor_account_id();
-        
-        // Ensure agent exists
-        assert!(self.agent_reputations.contains_key(&agent_id), "Agent not registered");
-        
-        // Convert status string to enum
-        let status_enum = match status.as_str() {
-            "completed" => IntentStatus::Completed,
-            "failed" => IntentStatus::Failed,
-            "in_progress" => IntentStatus::InProgress,
-            _ => panic!("Invalid status"),
+
+        // Failures are broken down by cause. Stalled work the client walked away from is
+        // recorded too, though it is not an interaction.
+        let failure_category = match intent.status {
+            IntentStatus::Failed | IntentStatus::Cancelled if !force_majeure => intent.failure_category,
+            _ => None,
         };
-        
-        // In a real implementation, you would:
-        // 1. Retrieve the intent from storage
-        // 2. Verify the agent is authorized to update it
-        // 3. Update the intent status
-        
-        // If intent was completed or failed, update agent reputation
-        if status_enum == IntentStatus::Completed || status_enum == IntentStatus::Failed {
-            let mut agent_rep = self.agent_reputations.get(&agent_id).unwrap();
-            
+
+        if counted || failure_category.is_some() {
+            let mut agent_rep = self.agent_reputations.get(&intent.agent_id).expect("Agent not registered");
+
             // Update interaction counts
-            agent_rep.total_interactions += 1;
-            if status_enum == IntentStatus::Completed {
-                agent_rep.successful_interactions += 1;
+            if counted {
+                agent_rep.total_interactions += 1;
+                self.internal_record_decay_activity(&intent.agent_id, &mut agent_rep);
+                if intent.status == IntentStatus::Completed {
+                    agent_rep.successful_interactions += 1;
+                    agent_rep.failure_breakdown.record_completion();
+                }
+            }
+            if let Some(category) = failure_category {
+                agent_rep.failure_breakdown.record(category);
             }
-            
+
             // The client would still need to provide explicit feedback
             // for a more nuanced reputation update
-            
+
             // Update the agent reputation
-            self.agent_reputations.insert(&agent_id, &agent_rep);
+            self.internal_save_agent(&intent.agent_id, &agent_rep);
+        }
+
+        // Sub-agents share in counted outcomes of work delegated to them, and the outcome joins
+        // the client's history with the agent, the agent's record in the intent's type and the
+        // client's own record
+        if counted {
+            self.internal_record_client_outcome(intent);
+            self.internal_record_relationship_outcome(
+                &intent.client_id,
+                &intent.agent_id,
+                intent.status == IntentStatus::Completed,
+            );
+            self.internal_record_specialization_outcome(
+                &intent.agent_id,
+                &intent.intent_type,
+                intent.status == IntentStatus::Completed,
+            );
+            self.internal_attribute_delegated_outcome(intent);
+        }
+
+        emit_event(
+            "intent_finalized",
+            json!({
+                "intent_id": intent.intent_id,
+                "agent_id": intent.agent_id,
+                "status": intent.status,
+            }),
+        );
+    }
+
+    // Count a decline against the agent's quota, docking reliability once the quota is used up
+    fn internal_record_decline(&mut self, agent_id: &AccountId) {
+        let mut agent_rep = self.agent_reputations.get(agent_id).expect("Agent not registered");
+        let now = env::block_timestamp();
+
+        // Start a new quota period if the current one has elapsed
+        if now - agent_rep.decline_stats.period_start >= DECLINE_QUOTA_PERIOD {
+            agent_rep.decline_stats.period_start = now;
+            agent_rep.decline_stats.declines_in_period = 0;
+        }
+
+        agent_rep.decline_stats.declines_in_period += 1;
+
+        let quota = self.get_decline_quota(self.get_trust_level(agent_rep.score));
+        if agent_rep.decline_stats.declines_in_period > quota && !self.is_force_majeure_active() {
+            agent_rep.decline_stats.penalized_declines += 1;
+            agent_rep.reliability_score = agent_rep.reliability_score.saturating_sub(DECLINE_RELIABILITY_PENALTY);
+
+            env::log_str(&format!(
+                "Agent {} exceeded its decline quota ({} per period); reliability reduced to {}",
+                agent_id, quota, agent_rep.reliability_score
+            ));
         }
-        
-        // Log the update
-        env::log_str(&format!("Intent {} updated to {}", intent_id, status));
+
+        self.internal_save_agent(agent_id, &agent_rep);
     }
-} 
\ No newline at end of file
+
+    // Get an agent's decline usage for the current quota period
+    pub fn get_decline_quota_status(&self, agent_id: AccountId) -> Option<DeclineQuotaView> {
+        self.agent_reputations.get(&agent_id).map(|rep| {
+            let now = env::block_timestamp();
+            let period_active = now - rep.decline_stats.period_start < DECLINE_QUOTA_PERIOD;
+            let quota = self.get_decline_quota(self.get_trust_level(rep.score));
+            let used = if period_active { rep.decline_stats.declines_in_period } else { 0 };
+
+            DeclineQuotaView {
+                quota,
+                used,
+                remaining: quota.saturating_sub(used),
+                period_resets_at: if period_active { rep.decline_stats.period_start + DECLINE_QUOTA_PERIOD } else { now },
+                penalized_declines: rep.decline_stats.penalized_declines,
+            }
+        })
+    }
+
+    // Ensure `client_id` may leave feedback for `agent_id` on this intent and mark
+    // the intent so the same interaction cannot be rated twice
+    pub(crate) fn internal_use_feedback_slot(&mut self, intent_id: &str, client_id: &AccountId, agent_id: &AccountId) {
+        let mut intent = self.intents.get(&intent_id.to_string()).expect("Intent not found");
+
+        assert_eq!(&intent.client_id, client_id, "Only the intent's client can leave feedback");
+        assert_eq!(&intent.agent_id, agent_id, "Intent was not handled by this agent");
+        assert!(
+            intent.status == IntentStatus::Completed || intent.status == IntentStatus::Failed,
+            "Feedback requires a completed or failed intent"
+        );
+        assert!(!intent.feedback_submitted, "Feedback already submitted for this intent");
+
+        intent.feedback_submitted = true;
+        self.intents.insert(&intent.intent_id, &intent);
+    }
+
+    // Get an intent by ID
+    pub fn get_intent(&self, intent_id: String) -> Option<IntentView> {
+        self.intents.get(&intent_id).map(|intent| IntentView {
+            intent_id: intent.intent_id,
+            agent_id: intent.agent_id,
+            client_id: intent.client_id,
+            intent_type: intent.intent_type,
+            parameters: intent.parameters,
+            status: intent.status,
+            result: intent.result,
+            timestamp: intent.timestamp,
+            updated_at: intent.updated_at,
+            response_deadline: intent.response_deadline,
+            deadline: intent.deadline,
+            accepted_at: intent.accepted_at,
+            feedback_submitted: intent.feedback_submitted,
+            escrow_amount: U128(intent.escrow_amount),
+            escrow_asset: intent.escrow_asset,
+            escrow_released: U128(intent.escrow_released),
+            escrow_refunded: U128(intent.escrow_refunded),
+            disputed_at: intent.disputed_at,
+            dispute_reason: intent.dispute_reason,
+            finalized: intent.finalized,
+            deadline_extension: intent.deadline_extension,
+            agreement_id: intent.agreement_id,
+            failure_category: intent.failure_category,
+            referrer_id: intent.referrer_id,
+            nonce: intent.nonce,
+        })
+    }
+
+    // Whether the account is the configured NEAR Intents processor
+    pub(crate) fn is_intents_processor(&self, account_id: &AccountId) -> bool {
+        self.intents_processor.as_ref() == Some(account_id)
+    }
+}
+
+// View struct for intents
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IntentView {
+    intent_id: String,
+    agent_id: AccountId,
+    client_id: AccountId,
+    intent_type: String,
+    parameters: String,
+    status: IntentStatus,
+    result: Option<String>,
+    timestamp: u64,
+    updated_at: u64,
+    response_deadline: u64,
+    deadline: u64,
+    accepted_at: Option<u64>,
+    feedback_submitted: bool,
+    escrow_amount: U128,
+    escrow_asset: EscrowAsset,
+    escrow_released: U128,
+    escrow_refunded: U128,
+    disputed_at: Option<u64>,
+    dispute_reason: Option<String>,
+    finalized: bool,
+    deadline_extension: u64,
+    agreement_id: Option<u64>,
+    failure_category: Option<FailureCategory>,
+    referrer_id: Option<AccountId>,
+    // Nonce the next status update must quote
+    nonce: u64,
+}
+
+// View struct for an agent's decline quota
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DeclineQuotaView {
+    quota: u32,
+    used: u32,
+    remaining: u32,
+    period_resets_at: u64,
+    penalized_declines: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    fn setup_intent() -> AgentReputationContract {
+        let mut contract = setup();
+        register_agent(&mut contract, "agent.test");
+        deposit_storage(&mut contract, "client.test");
+        set_context("client.test", START, 0);
+        contract.record_intent("intent-1".to_string(), account("agent.test"), INTENT_TYPE.to_string(), String::new(), None, None);
+        contract
+    }
+
+    fn transition(contract: &mut AgentReputationContract, caller: &str, status: IntentStatus) {
+        set_context(caller, START, 0);
+        contract.update_intent_status("intent-1".to_string(), status, None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Agents cannot record intents for themselves")]
+    fn agent_cannot_be_its_own_client() {
+        let mut contract = setup();
+        register_agent(&mut contract, "agent.test");
+        set_context("agent.test", START, 0);
+        contract.record_intent("intent-1".to_string(), account("agent.test"), INTENT_TYPE.to_string(), String::new(), None, None);
+    }
+
+    #[test]
+    fn completed_intent_is_finalized_after_the_dispute_window() {
+        let mut contract = setup_intent();
+        transition(&mut contract, "agent.test", IntentStatus::Accepted);
+        transition(&mut contract, "agent.test", IntentStatus::InProgress);
+        transition(&mut contract, "agent.test", IntentStatus::Completed);
+        assert_eq!(last_event("intent_status_updated")["new_status"], "completed");
+
+        let intent = contract.intents.get(&"intent-1".to_string()).unwrap();
+        assert_eq!(intent.nonce, 3);
+        assert!(!intent.finalized);
+
+        set_context("anyone.test", START + contract.intent_dispute_window + 1, 0);
+        contract.finalize_intent("intent-1".to_string());
+        assert!(contract.intents.get(&"intent-1".to_string()).unwrap().finalized);
+        assert_eq!(contract.agent_reputations.get(&account("agent.test")).unwrap().successful_interactions, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid intent status transition: Created -> Completed")]
+    fn created_intent_cannot_complete() {
+        let mut contract = setup_intent();
+        transition(&mut contract, "agent.test", IntentStatus::Completed);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized intent status transition")]
+    fn client_cannot_accept_for_the_agent() {
+        let mut contract = setup_intent();
+        transition(&mut contract, "client.test", IntentStatus::Accepted);
+    }
+
+    #[test]
+    #[should_panic(expected = "Intent has not stalled")]
+    fn client_cannot_cancel_progressing_work() {
+        let mut contract = setup_intent();
+        transition(&mut contract, "agent.test", IntentStatus::Accepted);
+        transition(&mut contract, "agent.test", IntentStatus::InProgress);
+        transition(&mut contract, "client.test", IntentStatus::Cancelled);
+    }
+
+    #[test]
+    #[should_panic(expected = "Dispute window has passed")]
+    fn finalized_intent_cannot_be_disputed() {
+        let mut contract = setup_intent();
+        transition(&mut contract, "agent.test", IntentStatus::Accepted);
+        transition(&mut contract, "agent.test", IntentStatus::InProgress);
+        transition(&mut contract, "agent.test", IntentStatus::Completed);
+        set_context("client.test", START + contract.intent_dispute_window + 1, 0);
+        contract.update_intent_status("intent-1".to_string(), IntentStatus::Disputed, None, None);
+    }
+
+    #[test]
+    fn expired_intents_are_listed_by_expiry_from_the_index() {
+        let mut contract = setup_intent();
+        set_context("client.test", START + 1, 0);
+        contract.record_intent("intent-2".to_string(), account("agent.test"), INTENT_TYPE.to_string(), String::new(), None, None);
+        let response_deadline = START + contract.intent_response_period;
+
+        set_context("keeper.test", response_deadline, 0);
+        assert!(contract.get_expired_intents(10).is_empty());
+        set_context("keeper.test", response_deadline + 2, 0);
+        assert_eq!(contract.get_expired_intents(10), vec!["intent-1".to_string(), "intent-2".to_string()]);
+        assert_eq!(contract.get_expired_intents(1), vec!["intent-1".to_string()]);
+
+        // Accepting moves the intent to its deadline; expiring drops it from the index
+        transition(&mut contract, "agent.test", IntentStatus::Accepted);
+        set_context("keeper.test", response_deadline + 2, 0);
+        contract.expire_intent("intent-2".to_string());
+        assert!(contract.get_expired_intents(10).is_empty());
+
+        let deadline = contract.intents.get(&"intent-1".to_string()).unwrap().deadline;
+        set_context("keeper.test", deadline + 1, 0);
+        assert_eq!(contract.get_expired_intents(10), vec!["intent-1".to_string()]);
+        contract.expire_intent("intent-1".to_string());
+        assert!(contract.open_intent_deadlines.is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+use crate::*;
+use near_sdk::collections::TreeMap;
+
+// Upper bound on results returned by ranking views
+const MAX_RANKING_LIMIT: u32 = 100;
+
+// Feedback categories tracked in CategoryRatings
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum FeedbackCategory {
+    Accuracy,
+    ResponseTime,
+    Communication,
+    ProblemSolving,
+    Ethics,
+}
+
+impl FeedbackCategory {
+    pub const ALL: [FeedbackCategory; 5] = [
+        FeedbackCategory::Accuracy,
+        FeedbackCategory::ResponseTime,
+        FeedbackCategory::Communication,
+        FeedbackCategory::ProblemSolving,
+        FeedbackCategory::Ethics,
+    ];
+
+    // Stable code used in index keys
+    pub fn code(&self) -> u8 {
+        match self {
+            FeedbackCategory::Accuracy => 0,
+            FeedbackCategory::ResponseTime => 1,
+            FeedbackCategory::Communication => 2,
+            FeedbackCategory::ProblemSolving => 3,
+            FeedbackCategory::Ethics => 4,
+        }
+    }
+}
+
+impl CategoryRatings {
+    // Rating for a single category
+    pub fn get(&self, category: FeedbackCategory) -> u8 {
+        match category {
+            FeedbackCategory::Accuracy => self.accuracy,
+            FeedbackCategory::ResponseTime => self.response_time,
+            FeedbackCategory::Communication => self.communication,
+            FeedbackCategory::ProblemSolving => self.problem_solving,
+            FeedbackCategory::Ethics => self.ethics,
+        }
+    }
+}
+
+// Secondary indexes over agent scores so rankings don't scan the full registry
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct RankingIndex {
+    // (score, agent) for every registered agent
+    by_score: TreeMap<(u32, AccountId), ()>,
+    // (category code, category average, agent) for every registered agent
+    by_category: TreeMap<(u8, u8, AccountId), ()>,
+}
+
+impl Default for RankingIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RankingIndex {
+    pub fn new() -> Self {
+        Self {
+            by_score: TreeMap::new(b"r"),
+            by_category: TreeMap::new(b"c"),
+        }
+    }
+
+    // Number of agents in the score index
+    pub fn agent_count(&self) -> u64 {
+        self.by_score.len()
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Get agents ranked by score, highest first
+    pub fn get_top_agents(&self, limit: u32, offset: u32) -> Vec<RankedAgentView> {
+        self.ranking_index
+            .by_score
+            .iter_rev()
+            .skip(offset as usize)
+            .take(std::cmp::min(limit, MAX_RANKING_LIMIT) as usize)
+            .map(|((score, agent_id), _)| self.ranked_agent_view(agent_id, score))
+            .collect()
+    }
+
+    // Get agents ranked by their average in a feedback category, highest first
+    pub fn get_top_agents_by_category(&self, category: FeedbackCategory, limit: u32) -> Vec<RankedAgentView> {
+        let code = category.code();
+
+        // Category averages are at most 5, so this key sorts above every entry in the category
+        let upper_bound = (code, u8::MAX, env::current_account_id());
+
+        self.ranking_index
+            .by_category
+            .iter_rev_from(upper_bound)
+            .take_while(|((entry_code, _, _), _)| *entry_code == code)
+            .take(std::cmp::min(limit, MAX_RANKING_LIMIT) as usize)
+            .map(|((_, category_score, agent_id), _)| {
+                let score = self.agent_reputations.get(&agent_id).map(|rep| rep.score).unwrap_or(0);
+                let mut view = self.ranked_agent_view(agent_id, score);
+                view.category_score = Some(category_score);
+                view
+            })
+            .collect()
+    }
+
+    // Get all agents currently at a trust level, highest score first
+    pub fn get_agents_by_trust_level(&self, level: TrustLevel, from_index: u32, limit: u32) -> Vec<RankedAgentView> {
+        let (min_score, max_score) = trust_level_score_range(level);
+
+        self.ranking_index
+            .by_score
+            .iter_rev_from((max_score + 1, env::current_account_id()))
+            .take_while(|((score, _), _)| *score >= min_score)
+            .filter(|((score, _), _)| *score <= max_score)
+            .skip(from_index as usize)
+            .take(std::cmp::min(limit, MAX_RANKING_LIMIT) as usize)
+            .map(|((score, agent_id), _)| self.ranked_agent_view(agent_id, score))
+            .collect()
+    }
+
+    // Persist an agent record and keep the ranking and specialization indexes, the
+    // trust level histogram, the score history, the reward weight, probation, milestone
+    // badges, the update index and the epoch's changed agents in sync
+    pub(crate) fn internal_save_agent(&mut self, agent_id: &AccountId, agent_rep: &AgentReputation) {
+        let previous = self.agent_reputations.insert(agent_id, agent_rep);
+        self.internal_update_ranking_index(agent_id, previous.as_ref(), agent_rep);
+        self.internal_update_specialization_index(agent_id, previous.as_ref(), agent_rep);
+        self.internal_update_trust_histogram(previous.as_ref(), agent_rep);
+        let trust_level = self.get_trust_level(agent_rep.score);
+        if previous.as_ref().map(|p| self.get_trust_level(p.score)) != Some(trust_level) {
+            self.internal_sync_reward_trust_level(agent_id, trust_level);
+        }
+        self.internal_award_badges(agent_id, previous.as_ref(), agent_rep);
+        self.internal_update_recency_index(agent_id);
+        self.internal_track_epoch_change(agent_id, previous.as_ref(), agent_rep);
+        if previous.map(|p| p.score) != Some(agent_rep.score) {
+            self.internal_record_history(agent_id, agent_rep.score);
+            self.internal_update_probation(agent_id, agent_rep.score);
+            self.internal_queue_mirror_sync(agent_id, agent_rep.score);
+        }
+    }
+
+    // Move an agent's index entries from its previous scores to its current ones
+    pub(crate) fn internal_update_ranking_index(
+        &mut self,
+        agent_id: &AccountId,
+        previous: Option<&AgentReputation>,
+        current: &AgentReputation,
+    ) {
+        let index = &mut self.ranking_index;
+
+        if previous.map(|p| p.score) != Some(current.score) {
+            if let Some(previous) = previous {
+                index.by_score.remove(&(previous.score, agent_id.clone()));
+            }
+            index.by_score.insert(&(current.score, agent_id.clone()), &());
+        }
+
+        for category in FeedbackCategory::ALL {
+            let new_value = current.category_scores.get(category);
+            let old_value = previous.map(|p| p.category_scores.get(category));
+            if old_value != Some(new_value) {
+                if let Some(old_value) = old_value {
+                    index.by_category.remove(&(category.code(), old_value, agent_id.clone()));
+                }
+                index.by_category.insert(&(category.code(), new_value, agent_id.clone()), &());
+            }
+        }
+    }
+
+    pub(crate) fn ranked_agent_view(&self, agent_id: AccountId, score: u32) -> RankedAgentView {
+        let audited = self.is_audited(&agent_id);
+        RankedAgentView {
+            agent_id,
+            score,
+            trust_level: self.get_trust_level(score),
+            category_score: None,
+            audited,
+        }
+    }
+}
+
+// Inclusive score range covered by a trust level
+pub(crate) fn trust_level_score_range(level: TrustLevel) -> (u32, u32) {
+    match level {
+        TrustLevel::Novice => (0, 30),
+        TrustLevel::Apprentice => (31, 50),
+        TrustLevel::Trusted => (51, 75),
+        TrustLevel::Expert => (76, 90),
+        TrustLevel::Master => (91, 100),
+    }
+}
+
+// View struct for ranked agent listings
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RankedAgentView {
+    agent_id: AccountId,
+    score: u32,
+    trust_level: TrustLevel,
+    // Category average, for category rankings
+    category_score: Option<u8>,
+    // Whether the agent holds an unexpired audited badge
+    audited: bool,
+}
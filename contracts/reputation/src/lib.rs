@@ -1,11 +1,202 @@
+// Contract methods mirror the JSON ABI, so their argument counts follow it
+#![allow(clippy::too_many_arguments)]
+
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LookupMap, UnorderedMap};
-use near_sdk::{env, near_bindgen, AccountId, Balance, PanicOnDefault, Promise};
-use near_sdk::json_types::U128;
+use near_sdk::collections::{LookupMap, TreeMap, UnorderedMap, UnorderedSet, Vector};
+use near_sdk::{env, near_bindgen, AccountId, Balance, Gas, PanicOnDefault, Promise, PromiseResult};
+use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::serde_json::json;
+
+use crate::fixed_point::{blend_bps, clamp_score, from_points, linear_share, mul_bps, rating_score_bps, to_points, SCORE_SCALE_BPS};
+
+// Length of the period over which penalty-free intent declines are counted (30 days)
+const DECLINE_QUOTA_PERIOD: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+// Reliability points deducted for each decline beyond the quota
+const DECLINE_RELIABILITY_PENALTY: u32 = 2;
+
+// Reliability points granted for each client-approved checkpoint
+#[cfg(feature = "escrow")]
+const CHECKPOINT_APPROVAL_RELIABILITY_BONUS: u32 = 1;
+
+// Neutral score new agents start with
+const INITIAL_SCORE: u32 = 50;
+
+// Stake, as a multiple of the minimum stake, that earns the full stake bonus
+const STAKE_BONUS_FULL_MULTIPLE: Balance = 5;
+
+// Largest share of the feedback-based score the stake bonus can add (basis points)
+const MAX_STAKE_BONUS_SHARE_BPS: u32 = 2_500;
+
+// Upper bound on agents resolved by one bulk view call
+const MAX_BULK_AGENTS: usize = 100;
+
+// Gas for the NEAR AI registry's has_agent call
+const GAS_FOR_REGISTRY_CHECK: Gas = Gas(5_000_000_000_000);
+
+// Gas reserved for finalizing registration in on_agent_verified
+const GAS_FOR_REGISTRATION_CALLBACK: Gas = Gas(20_000_000_000_000);
+
+// Optional subsystems are gated behind Cargo features so deployments that only need core
+// reputation ship a smaller WASM; the full build enables all of them. Gated code adds
+// methods only, never state, so every build shares one storage layout and a deployment
+// can be upgraded from one build to another.
+//   cross-chain - importing and exporting reputation across chains, mirroring scores to
+//                 Aurora, chain-signature co-signing of agents' EVM transactions, and
+//                 checking agents' bridged transfers
+//   escrow      - holding and settling intent payments
+mod agreements;
+mod api;
+mod attestations;
+mod audits;
+mod aurora_mirror;
+mod authorization;
+mod badges;
+mod bidding;
+mod blind_ratings;
+mod bounties;
+mod bridge;
+mod chain_signatures;
+mod checkpoints;
+mod clients;
+mod codes;
+mod config;
+mod delegation;
+mod cross_chain;
+mod dao;
+mod disputes;
+mod endorsements;
+mod enumeration;
+mod escheatment;
+mod epochs;
+mod escrow;
+mod events;
+mod failures;
+mod fees;
+mod feedback;
+mod feedback_disputes;
+mod feedback_limits;
+mod fixed_point;
+mod force_majeure;
+mod health;
+mod history;
+mod identity;
+mod insurance;
+mod intent_types;
+mod intents;
+mod leaderboard;
+mod matching;
+mod migrate;
+mod oracles;
+mod ownership;
+mod pause;
+mod payouts;
+mod penalty_decay;
+mod policy;
+mod privacy;
+mod profile;
+mod query_rebates;
+mod quotas;
+mod relationships;
+mod remediation;
+#[cfg(test)]
+mod replay;
+mod retention;
+mod rewards;
+mod shadow;
+mod specializations;
+mod stake_health;
+mod staking_tokens;
+mod status;
+mod storage;
+mod task_delegation;
+#[cfg(test)]
+mod test_utils;
+mod timelock;
+mod token_integration;
+mod treasury;
+mod violation_policy;
+mod violations;
+mod whistleblower;
+
+pub use crate::agreements::{AgentAgreements, AgreementStats, AgreementStatus, AgreementTerms, AgreementView, ServiceAgreement};
+pub use crate::api::{ApiArgView, ApiMethod, ApiMethodView, ContractApiView, ContractSourceMetadata, MethodKind, Standard};
+pub use crate::attestations::{AttestationRecord, CapabilityAttestation, IssuedAttestation, SignedCapabilityAttestation};
+pub use crate::audits::{AuditStatus, ManipulationAudit, ManipulationAuditView, ManipulationReport};
+pub use crate::aurora_mirror::{AuroraMirrorConfig, MirrorSync, MirrorSyncStatus};
+#[cfg(feature = "cross-chain")]
+pub use crate::aurora_mirror::MirrorSyncView;
+pub use crate::authorization::{AuthorizationRule, AuthorizationSimulationView};
+pub use crate::badges::{Badge, BadgeContractMetadataView, BadgeKind, BadgeMetadataView, BadgeTokenView};
+pub use crate::bidding::{AuctionStatus, BiddingConfig, IntentAuction, SealedBid};
+pub use crate::blind_ratings::{BlindRatings, BlindRatingsView, PendingFeedback};
+pub use crate::bounties::{AuditAttestation, AuditBounty, AuditBountyView};
+#[cfg(feature = "cross-chain")]
+pub use crate::bridge::{BridgeAllowanceView, BridgeTokenView};
+pub use crate::chain_signatures::{EvmChainBudget, EvmDelegationAccount};
+#[cfg(feature = "cross-chain")]
+pub use crate::chain_signatures::EvmDelegationView;
+pub use crate::checkpoints::{IntentCheckpoint, LivenessStats};
+pub use crate::clients::{ClientReputation, ClientReputationView};
+pub use crate::codes::{AgentReputationNumericView, CodeMapView, IntentNumericView, ViolationNumericView};
+pub use crate::config::{BayesianPrior, ContractConfigView, ReviewerWeightConfig, ScoringMode};
+pub use crate::cross_chain::{AttestorKey, ChainPolicy, CrossChainProof, ImportRecord, MergeStrategy, ReputationExport, SignedExport, SignedReputationExport};
+#[cfg(feature = "cross-chain")]
+pub use crate::cross_chain::{ExportSignerView, SignedExportView};
+pub use crate::dao::{DaoActionCall, DaoProposalArgs, DaoProposalInput, DaoProposalKind};
+pub use crate::delegation::{DelegatedStakeView, DelegationConfig, DelegationPool, DelegationPosition, DelegationView};
+pub use crate::disputes::{DisputeStats, DisputeStatsView};
+pub use crate::endorsements::{Endorsement, EndorsementConfig, EndorsementView};
+pub use crate::enumeration::AgentSummaryView;
+pub use crate::escheatment::{EscheatedStake, EscheatedStakeView, EscheatmentConfig, StakeActivity, StakeDormancyView};
+pub use crate::epochs::{EpochCheckpoint, EpochInfoView, ScoreEpochState};
+pub use crate::escrow::EscrowAsset;
+pub use crate::failures::{FailureBreakdown, FailureBreakdownView, FailureCategory, FailureWeights};
+pub use crate::fees::{FeeConfigView, FeeDiscountTier, FeeRateView, FeeSplit};
+pub use crate::feedback::{CategoryScoringConfig, FeedbackAggregates, FeedbackResponse, FeedbackRevision, FeedbackView, RatingHistogram, RatingHistogramView};
+pub use crate::feedback_disputes::{FeedbackDispute, FeedbackDisputeQuota, FeedbackDisputeQuotaView, FeedbackDisputeStatus};
+pub use crate::feedback_limits::{FeedbackLimitConfig, ReviewerFeedbackHistory};
+pub use crate::force_majeure::ForceMajeureWindow;
+pub use crate::health::{ContractStats, HealthCheckView, HealthStatusView, NetworkStatsView};
+pub use crate::history::{HistoryConfig, ReputationHistory, ReputationSnapshot};
+pub use crate::identity::{AgentIdentity, ForeignAddress, IdentityView, RealmMembership};
+pub use crate::insurance::{ClaimStatus, InsuranceClaim, InsuranceClaimView, InsuranceConfig, InsuranceIncident, InsuranceIncidentView, InsurancePoolView};
+pub use crate::intent_types::IntentTypeMetadata;
+pub use crate::intents::{IntentData, IntentStatus, IntentView};
+pub use crate::leaderboard::{FeedbackCategory, RankedAgentView, RankingIndex};
+pub use crate::matching::AgentRecommendationView;
+pub use crate::migrate::{ContractVersionView, PendingMigration, StateVersion};
+pub use crate::oracles::{PerformanceConfig, PerformanceMetrics, PerformanceMetricsView, PerformanceReport, PerformanceSample};
+pub use crate::ownership::{OwnershipState, OwnershipView};
+pub use crate::pause::PausableFeature;
+pub use crate::penalty_decay::{PenaltyDecayConfig, PenaltyDecayState, ViolationDecayView, ViolationStatusView};
+pub use crate::policy::{CapabilityRule, PolicyCondition};
+pub use crate::privacy::PrivacyConfig;
+pub use crate::profile::AgentProfile;
+pub use crate::quotas::{RemainingQuotaView, UsageBucket, UsageQuota, UsageQuotaConfig, UsageWindow};
+pub use crate::relationships::{ClientRelationship, RelationshipScoreView};
+pub use crate::remediation::{RemediationAssignment, RemediationStatus, RemediationTask};
+pub use crate::retention::RetentionConfig;
+pub use crate::rewards::{RewardAccount, RewardPool, RewardPoolView, RewardsConfig};
+pub use crate::shadow::{ScoringParams, ShadowComparisonView, ShadowScoreView, ShadowScoring};
+pub use crate::specializations::{SpecializationRecord, SpecializationScoreView};
+pub use crate::stake_health::{StakeHealthView, StakeShortfall};
+pub use crate::staking_tokens::{AgentStakesView, StakingToken, StakingTokenView, TokenStakeView};
+pub use crate::status::{AgentStatus, AgentStatusConfig, AgentStatusRecord, AgentStatusView};
+pub use crate::storage::{StorageAccount, StorageBalance, StorageBalanceBounds};
+pub use crate::task_delegation::{TaskDelegation, TaskDelegationView};
+pub use crate::timelock::{PendingAction, PendingActionView, TimelockedAction};
+pub use crate::treasury::SlashingTotalsView;
+pub use crate::violation_policy::{ViolationPenalty, ViolationPolicyEntry};
+pub use crate::violations::{AppealStatus, Evidence, ViolationAppeal, ViolationView};
+pub use crate::whistleblower::{ReportStatus, ReportTermsView, WhistleblowerReport};
 
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct AgentReputationContract {
+    // Storage layout version
+    state_version: StateVersion,
+    
     // Contract owner
     owner_id: AccountId,
     
@@ -24,11 +215,440 @@ pub struct AgentReputationContract {
     // Feedback expiration period in nanoseconds (e.g., 30 days)
     feedback_expiry_period: u64,
     
-    // NEAR AI registry contract for verifying agents
-    near_ai_registry: AccountId,
+    // NEAR AI registry contract for verifying agents; None until configured
+    near_ai_registry: Option<AccountId>,
+    
+    // NEAR Intents processor for intent verification; None until configured
+    intents_processor: Option<AccountId>,
+    
+    // Map of intent ID to its data
+    intents: UnorderedMap<String, IntentData>,
+    
+    // Time an agent has to accept or decline an intent, in nanoseconds
+    intent_response_period: u64,
+    
+    // Default time an agent has to finish an intent, in nanoseconds
+    intent_deadline_period: u64,
+    
+    // Time a client has to dispute a completed intent before it is finalized, in nanoseconds
+    intent_dispute_window: u64,
+    
+    // Accounts allowed to rule on intent disputes alongside governance
+    arbiters: UnorderedSet<AccountId>,
+    
+    // ITLX rebated to an agent each time a consumer with prepaid credit authorizes against it
+    query_rebate_amount: Balance,
+    
+    // Map of consumer ID to its prepaid ITLX query credit
+    query_credits: LookupMap<AccountId, Balance>,
+    
+    // Map of agent ID to its accrued, unclaimed query rebates
+    query_rebates: LookupMap<AccountId, Balance>,
+    
+    // Curve used to weight feedback by the reviewer's standing
+    reviewer_weight_config: ReviewerWeightConfig,
+    
+    // Account allowed to pause features during an incident
+    guardian_id: Option<AccountId>,
+    
+    // Bitmask of paused features (see PausableFeature)
+    paused_features: u8,
+    
+    // Map of intent ID to its progress checkpoints
+    intent_checkpoints: LookupMap<String, Vec<IntentCheckpoint>>,
+    
+    // Time without a checkpoint after which an in-progress intent counts as stalled
+    checkpoint_stall_period: u64,
+    
+    // Account receiving slashed tokens
+    treasury_id: AccountId,
+    
+    // Share of slashed tokens burned instead of sent to the treasury (basis points)
+    slash_burn_bps: u16,
+    
+    // Protocol fee taken from escrow released to agents (basis points)
+    protocol_fee_bps: u16,
+    
+    // Lifetime slashed tokens transferred to the treasury
+    total_slashed_to_treasury: Balance,
+    
+    // Lifetime slashed tokens burned (locked in this contract)
+    total_slashed_burned: Balance,
+    
+    // Incremented on every configuration change
+    config_nonce: u64,
+    
+    // Score-ordered indexes backing the leaderboard views
+    ranking_index: RankingIndex,
+    
+    // Inverted index from normalized specialization tag to agents
+    specialization_index: LookupMap<String, UnorderedSet<AccountId>>,
+    
+    // Aggregates backing health_check
+    stats: ContractStats,
+    
+    // Candidate scoring configuration being trialled alongside the live score
+    shadow_scoring: Option<ShadowScoring>,
+    
+    // Map of agent ID to its latest shadow score and when it was computed
+    shadow_scores: LookupMap<AccountId, (u32, u64)>,
+    
+    // Salt mixed into reviewer pseudonyms. Contract state is publicly readable, so this only
+    // keeps raw accounts out of contract views; it is not a cryptographic secret.
+    pseudonym_salt: Vec<u8>,
+    
+    // Map of feedback pseudonym to the reviewer account, for governance de-anonymization
+    pseudonyms: LookupMap<String, AccountId>,
+    
+    // Map of feedback dispute ID to the dispute
+    feedback_disputes: UnorderedMap<u64, FeedbackDispute>,
+    
+    // ID assigned to the next feedback dispute
+    next_feedback_dispute_id: u64,
+    
+    // Governance-declared outage windows during which negative reputation effects are suspended
+    force_majeure_windows: Vector<ForceMajeureWindow>,
+    
+    // Map of agent ID to its bounded score history
+    reputation_history: LookupMap<AccountId, ReputationHistory>,
+    
+    // When score changes are recorded in the reputation history
+    history_config: HistoryConfig,
+    
+    // MPC signer contract co-signing agents' EVM transactions (None disables chain signatures)
+    chain_signer_id: Option<AccountId>,
+    
+    // Map of EVM chain ID to the per-agent signing budget on that chain
+    evm_chain_budgets: UnorderedMap<u64, EvmChainBudget>,
+    
+    // Map of "agent:chain_id" to the agent's nonce and budget usage on that chain
+    evm_delegations: LookupMap<String, EvmDelegationAccount>,
+    
+    // Map of source chain to the attestor keys trusted to sign reputation exported from it
+    trusted_attestors: UnorderedMap<String, Vec<AttestorKey>>,
+    
+    // Map of "source_chain:agent" to the last imported attestation nonce
+    cross_chain_nonces: LookupMap<String, u64>,
+    
+    // Map of source chain to its import policy; chains without one are not supported
+    chain_policies: UnorderedMap<String, ChainPolicy>,
+    
+    // Ordered rules mapping agent records to capability limits
+    capability_policy: Vec<CapabilityRule>,
+    
+    // Nonce assigned to the next reputation export
+    next_export_nonce: u64,
+    
+    // Map of export nonce to the keccak256 hash of the exported payload
+    export_hashes: LookupMap<u64, Vec<u8>>,
+    
+    // Differential privacy noise applied to aggregate views
+    privacy_config: PrivacyConfig,
+    
+    // Map of agreement ID to the standing agreement between two agents
+    agreements: UnorderedMap<u64, ServiceAgreement>,
+    
+    // ID assigned to the next agreement
+    next_agreement_id: u64,
+    
+    // Map of agent ID to the counts of the agreements it is party to
+    agent_agreements: LookupMap<AccountId, AgentAgreements>,
+
+    
+    // NEP-145 storage deposits and usage, keyed by paying account
+    storage_accounts: LookupMap<AccountId, StorageAccount>,
+    
+    // Latest manipulation audit of each audited agent
+    manipulation_audits: LookupMap<AccountId, ManipulationAudit>,
+    
+    // Pending ownership transfer and owner recovery arrangement
+    ownership: OwnershipState,
+    
+    // How much feedback and violation history is kept per agent
+    retention_config: RetentionConfig,
+    
+    // Delay before queued sensitive actions can execute (nanoseconds)
+    timelock_delay: u64,
+    
+    // Sensitive actions waiting out the timelock
+    pending_actions: UnorderedMap<u64, PendingAction>,
+    
+    // ID assigned to the next queued action
+    next_action_id: u64,
+    
+    // Open agent-funded audit bounties
+    audit_bounties: UnorderedMap<u64, AuditBounty>,
+    
+    // ID assigned to the next audit bounty
+    next_bounty_id: u64,
+    
+    // Map of agent ID to when its audited badge expires
+    audit_badges: LookupMap<AccountId, u64>,
+    
+    // Sputnik DAO acting as governance through executed proposals
+    governance_dao: Option<AccountId>,
+    
+    // When inactive stakes may be moved to the treasury
+    escheatment_config: EscheatmentConfig,
+    
+    // Map of staker to their last activity and any dormancy notice
+    stake_activity: LookupMap<AccountId, StakeActivity>,
+    
+    // Map of account to escheated stake the contract holds for it
+    escheated_stakes: LookupMap<AccountId, EscheatedStake>,
+    
+    // How delegated stake is weighted and released
+    delegation_config: DelegationConfig,
+    
+    // Map of agent ID to the stake delegated to it
+    delegation_pools: LookupMap<AccountId, DelegationPool>,
+    
+    // Map of delegator to its positions with agents
+    delegation_positions: LookupMap<AccountId, Vec<DelegationPosition>>,
+    
+    // Protocol fee discounts by trust level and dispute rate, first match applies
+    fee_discount_tiers: Vec<FeeDiscountTier>,
+    
+    // Staking reward epoch length and payout rate
+    rewards_config: RewardsConfig,
+    
+    // Staking reward pool and its distribution accumulator
+    reward_pool: RewardPool,
+    
+    // Map of agent ID to its reward weight and unclaimed rewards
+    reward_accounts: LookupMap<AccountId, RewardAccount>,
+    
+    // Map of identity ID to the canonical identity of an agent
+    identities: LookupMap<u64, AgentIdentity>,
+    
+    // Map of every linked identifier (NEAR account, foreign address, DID, realm member) to its identity ID
+    identity_index: LookupMap<String, u64>,
+    
+    // Next identity ID
+    next_identity_id: u64,
+    
+    // Map of realm name to the account that vouches for its memberships
+    realm_authorities: UnorderedMap<String, AccountId>,
+    
+    // Weights and expiry periods of feedback categories in the headline score
+    category_scoring: Vec<CategoryScoringConfig>,
+    
+    // Prior feedback scores are shrunk toward while an agent has little feedback
+    bayesian_prior: BayesianPrior,
+    
+    // Per-reviewer feedback rate limit and repeat dampening
+    feedback_limit_config: FeedbackLimitConfig,
+    
+    // Time after submission during which reviewers can amend or revoke feedback (nanoseconds)
+    feedback_amendment_window: u64,
+    
+    // Map of "reviewer:agent" to the reviewer's recent feedback for the agent
+    reviewer_feedback: LookupMap<String, ReviewerFeedbackHistory>,
+    
+    // Rules for automatic probation and suspension
+    agent_status_config: AgentStatusConfig,
+    
+    // Map of agent ID to its status, for agents no longer simply active since registration
+    agent_statuses: LookupMap<AccountId, AgentStatusRecord>,
     
-    // NEAR Intents processor for intent verification
-    intents_processor: AccountId,
+    // Map of agent ID to the profile it publishes
+    agent_profiles: LookupMap<AccountId, AgentProfile>,
+    
+    // Rolling-window limits on the actions agents can perform
+    usage_quota_config: UsageQuotaConfig,
+    
+    // Map of "agent:action" to the agent's recent usage of the action
+    usage_windows: LookupMap<String, UsageWindow>,
+    
+    // Accounts allowed to consume capabilities on behalf of agents
+    quota_dispatchers: UnorderedSet<AccountId>,
+    
+    // How long capability attestations stay valid (nanoseconds)
+    attestation_ttl: u64,
+    
+    // Next capability attestation nonce
+    next_attestation_nonce: u64,
+    
+    // Map of attestation nonce to the record it is verified against
+    capability_attestations: LookupMap<u64, AttestationRecord>,
+    
+    // Map of badge token ID to the soulbound milestone badge
+    badges: UnorderedMap<String, Badge>,
+    
+    // (last change, agent) for every agent saved since the index was introduced
+    update_index: TreeMap<(u64, AccountId), ()>,
+    
+    // Map of agent ID to when its record last changed
+    agent_updated_at: LookupMap<AccountId, u64>,
+    
+    // How heavily each failure cause counts against the success rate
+    failure_weights: FailureWeights,
+    
+    // Time agents have to top their stake back up to the minimum before being penalized
+    // (nanoseconds)
+    stake_grace_period: u64,
+    
+    // Map of agent ID to its stake shortfall, while its stake is below the minimum
+    stake_shortfalls: LookupMap<AccountId, StakeShortfall>,
+    
+    // Rules for agents endorsing each other's specializations
+    endorsement_config: EndorsementConfig,
+    
+    // Map of agent ID to the endorsements it has received
+    endorsements: LookupMap<AccountId, Vec<Endorsement>>,
+    
+    // Map of endorser ID to the (agent ID, specialization) pairs it has endorsed
+    endorsements_given: LookupMap<AccountId, Vec<(AccountId, String)>>,
+    
+    // Longest chain of agents an intent can be delegated down
+    max_task_delegation_depth: u8,
+    
+    // Map of intent ID to the agents it has been delegated to
+    task_delegations: LookupMap<String, Vec<TaskDelegation>>,
+    
+    // How slashed tokens fund the insurance pool and how clients claim from it
+    insurance_config: InsuranceConfig,
+    
+    // Slashing incidents in the order they happened, indexed by incident ID
+    insurance_incidents: Vector<InsuranceIncident>,
+    
+    // Insurance claims in filing order, indexed by claim ID
+    insurance_claims: Vector<InsuranceClaim>,
+    
+    // Map of intent ID to the insurance claim filed over it
+    intent_insurance_claims: LookupMap<String, u64>,
+    
+    // How the protocol fee is divided between the treasury, insurance pool and referrers
+    fee_split: FeeSplit,
+    
+    // Penalty for each violation type, built-in and governance-defined
+    violation_policy: Vec<ViolationPolicyEntry>,
+    
+    // Bumped whenever an expiry period is lengthened; agents whose aggregates predate it have
+    // expired feedback to restore
+    feedback_window_epoch: u64,
+    
+    // Schedule for forgiving violation penalties over clean epochs
+    penalty_decay_config: PenaltyDecayConfig,
+    
+    // Whistleblower reports by ID
+    whistleblower_reports: LookupMap<u64, WhistleblowerReport>,
+    
+    // IDs of reports awaiting triage
+    pending_reports: UnorderedSet<u64>,
+    
+    // ID assigned to the next whistleblower report
+    next_report_id: u64,
+    
+    // Remediation tasks by ID
+    remediation_tasks: UnorderedMap<String, RemediationTask>,
+    
+    // Remediation tasks assigned to each agent
+    remediation_assignments: LookupMap<AccountId, Vec<RemediationAssignment>>,
+    
+    // Submitted remediation proofs awaiting verification, by agent and task ID
+    remediation_reviews: UnorderedSet<(AccountId, String)>,
+    
+    // Account allowed to verify remediation alongside the owner and governance
+    remediation_verifier: Option<AccountId>,
+    
+    // Whitelisted performance oracles and the ed25519 keys they sign reports with
+    performance_oracles: UnorderedMap<AccountId, Base64VecU8>,
+    
+    // Oracle-reported performance samples per agent, oldest first
+    performance_samples: LookupMap<AccountId, Vec<PerformanceSample>>,
+    
+    // How oracle-reported performance is scored and blended into reputation
+    performance_config: PerformanceConfig,
+    
+    // Score epoch progress and length (nanoseconds)
+    score_epoch: ScoreEpochState,
+    score_epoch_length: u64,
+    
+    // Agents whose score changed in the current epoch
+    epoch_changed_agents: UnorderedSet<AccountId>,
+    
+    // Agents awaiting their checkpoint for the epoch being finalized
+    epoch_finalizing_agents: UnorderedSet<AccountId>,
+    
+    // Finalized score checkpoints per agent, in epoch order
+    epoch_checkpoints: LookupMap<AccountId, Vector<EpochCheckpoint>>,
+    
+    // How reviewer weights are derived from stake and reputation
+    scoring_mode: ScoringMode,
+    
+    // Tokens accepted for staking besides ITLX
+    staking_tokens: UnorderedMap<AccountId, StakingToken>,
+    
+    // Each agent's stakes in tokens other than ITLX
+    token_stakes: LookupMap<AccountId, Vec<(AccountId, Balance)>>,
+    
+    // Account allowed to update staking token weights alongside the owner and governance
+    staking_rate_oracle: Option<AccountId>,
+    
+    // Each client's history with each agent, keyed by "client:agent"
+    client_relationships: LookupMap<String, ClientRelationship>,
+    
+    // Each agent's record per specialization, taken from the types of its intents
+    specialization_records: LookupMap<AccountId, Vec<SpecializationRecord>>,
+    
+    // Taxonomy of intent types, keyed by normalized name
+    intent_types: UnorderedMap<String, IntentTypeMetadata>,
+    
+    // Intents put out for bids, keyed by intent ID, and those not yet closed
+    intent_auctions: LookupMap<String, IntentAuction>,
+    open_auctions: UnorderedSet<String>,
+    
+    // Auction windows and winner selection
+    bidding_config: BiddingConfig,
+    
+    // Each client's record with the agents it hires
+    client_reputations: LookupMap<AccountId, ClientReputation>,
+    
+    // Client score from which each agent accepts intents automatically
+    client_auto_accept: LookupMap<AccountId, u32>,
+    
+    // Each finished intent's client and agent ratings, held until both are in or the window closes
+    blind_ratings: LookupMap<String, BlindRatings>,
+    
+    // How long the first rating of an intent is held for the other side's (nanoseconds; 0
+    // applies ratings as they come)
+    blind_rating_window: u64,
+    
+    // Aurora contract agents' scores are mirrored to (None disables mirroring)
+    aurora_mirror: Option<AuroraMirrorConfig>,
+    
+    // Each agent's score as mirrored to Aurora, and the agents whose sync is pending
+    mirror_syncs: LookupMap<AccountId, MirrorSync>,
+    mirror_sync_queue: UnorderedSet<AccountId>,
+    
+    // Reputation exports signed by the MPC signer, by export nonce
+    signed_exports: LookupMap<u64, SignedExport>,
+    
+    // Bridge accounts allowed to check agents' bridged transfers
+    bridge_accounts: UnorderedSet<AccountId>,
+    
+    // Escrow, bounty and delegation payouts whose transfer failed, by receiver and asset, until
+    // claimed
+    unpaid_payouts: LookupMap<(AccountId, EscrowAsset), Balance>,
+    
+    // Agents whose mirror sync failed, until retried
+    failed_mirror_syncs: UnorderedSet<AccountId>,
+    
+    // Decimals of the tokens bridge accounts can check transfers of, besides ITLX
+    bridge_tokens: UnorderedMap<AccountId, u8>,
+    
+    // Ids of the force-majeure windows still open, so checks need not scan every window
+    open_force_majeure_windows: Vec<u64>,
+    
+    // Map of (agent ID, position in the agent's agreement index) to agreement ID
+    agent_agreement_ids: LookupMap<(AccountId, u64), u64>,
+    
+    // Records still to re-encode after an upgrade, while a migration is in progress
+    pending_migration: Option<PendingMigration>,
+    
+    // Open intents by (time they can be expired, intent ID), so keepers need not scan every intent
+    open_intent_deadlines: TreeMap<(u64, String), ()>,
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -42,8 +662,9 @@ pub struct AgentReputation {
     // Successful interactions
     successful_interactions: u64,
     
-    // Vector of feedback entries with timestamps
-    feedback_history: Vec<FeedbackEntry>,
+    // Feedback entries by index in submission order, stored under a per-agent prefix so the
+    // record stays small however much feedback accumulates
+    feedback_history: LookupMap<u64, FeedbackEntry>,
     
     // Timestamp of last reputation update
     last_update: u64,
@@ -56,6 +677,119 @@ pub struct AgentReputation {
     
     // Add this new field
     violation_history: Vec<ViolationRecord>,
+    
+    // Reliability sub-score (0-100) reflecting how predictably the agent takes on work
+    reliability_score: u32,
+    
+    // Intent declines within the current quota period
+    decline_stats: DeclineStats,
+    
+    // Timeliness of progress checkpoints on long-running intents
+    liveness: LivenessStats,
+    
+    // Disputes opened against the agent and their outcomes
+    dispute_stats: DisputeStats,
+    
+    // Feedback disputes opened in the current quota period
+    feedback_dispute_quota: FeedbackDisputeQuota,
+    
+    // Timestamp the agent registered (migration time for agents predating this field)
+    registered_at: u64,
+    
+    // Audit trail of cross-chain reputation imports
+    import_history: Vec<ImportRecord>,
+    
+    // Distribution of star ratings, maintained alongside feedback_history
+    rating_histogram: RatingHistogram,
+    
+    // Rolling sums over unexpired feedback that scores are computed from
+    feedback_aggregates: FeedbackAggregates,
+    
+    // Number of feedback entries ever submitted; the next entry's index
+    feedback_count: u64,
+    
+    // Entries below this index were pruned or archived
+    feedback_pruned: u64,
+    
+    // Number of violations archived under the retention policy
+    violations_archived: u64,
+    
+    // Failed intents by cause
+    failure_breakdown: FailureBreakdown,
+    
+    // Contract feedback window epoch the aggregates were last rebaselined for
+    feedback_window_epoch: u64,
+    
+    // Full-precision score in basis points (0-10000); `score` is its whole points
+    score_bps: u32,
+    
+    // Progress through the current violation penalty decay epoch
+    penalty_decay: PenaltyDecayState,
+}
+
+impl AgentReputation {
+    // Fresh reputation record for a newly registered agent
+    fn new(agent_id: &AccountId, specializations: Vec<String>) -> Self {
+        Self {
+            score: INITIAL_SCORE,
+            total_interactions: 0,
+            successful_interactions: 0,
+            feedback_history: LookupMap::new([b"v".as_slice(), &env::sha256(agent_id.as_bytes())].concat()),
+            last_update: env::block_timestamp(),
+            specializations,
+            category_scores: CategoryRatings::default(),
+            violation_history: Vec::new(),
+            reliability_score: 100,
+            decline_stats: DeclineStats::default(),
+            liveness: LivenessStats::default(),
+            dispute_stats: DisputeStats::default(),
+            feedback_dispute_quota: FeedbackDisputeQuota::default(),
+            registered_at: env::block_timestamp(),
+            import_history: Vec::new(),
+            rating_histogram: RatingHistogram::default(),
+            feedback_aggregates: FeedbackAggregates::default(),
+            feedback_count: 0,
+            feedback_pruned: 0,
+            violations_archived: 0,
+            failure_breakdown: FailureBreakdown::default(),
+            feedback_window_epoch: 0,
+            score_bps: from_points(INITIAL_SCORE),
+            penalty_decay: PenaltyDecayState::new(),
+        }
+    }
+    
+    // Set the score in basis points, capped at the top of the scale
+    pub(crate) fn set_score_bps(&mut self, score_bps: u32) {
+        self.score_bps = std::cmp::min(score_bps, SCORE_SCALE_BPS);
+        self.score = to_points(self.score_bps);
+    }
+    
+    // Set the score to whole points
+    pub(crate) fn set_score(&mut self, points: u32) {
+        self.set_score_bps(from_points(points));
+    }
+    
+    // Raise the score by whole points, capped at 100
+    pub(crate) fn add_score_points(&mut self, points: u32) {
+        self.set_score_bps(self.score_bps.saturating_add(from_points(points)));
+    }
+    
+    // Lower the score by whole points, stopping at 0
+    pub(crate) fn deduct_score_points(&mut self, points: u32) {
+        self.set_score_bps(self.score_bps.saturating_sub(from_points(points)));
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct DeclineStats {
+    // Start of the current quota period
+    period_start: u64,
+    
+    // Declines made in the current quota period
+    declines_in_period: u32,
+    
+    // Declines beyond the quota over the agent's lifetime
+    penalized_declines: u64,
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -63,6 +797,9 @@ pub struct FeedbackEntry {
     // User who provided feedback
     user_id: AccountId,
     
+    // Intent the feedback refers to
+    intent_id: String,
+    
     // Overall rating (0-5)
     rating: u8,
     
@@ -74,9 +811,38 @@ pub struct FeedbackEntry {
     
     // Timestamp when feedback was submitted
     timestamp: u64,
+    
+    // Weight applied to this feedback based on the reviewer (basis points, 10000 = 1x)
+    reviewer_weight: u32,
+    
+    // Pseudonym shown instead of the reviewer's account for anonymous feedback
+    pseudonym: Option<String>,
+    
+    // Outcome of an agent's dispute of this entry, if it was disputed
+    dispute_status: Option<FeedbackDisputeStatus>,
+    
+    // Held out of scoring by a manipulation audit
+    quarantined: bool,
+    
+    // Earlier versions of an amended entry, oldest first
+    revisions: Vec<FeedbackRevision>,
+    
+    // When the reviewer withdrew the feedback; revoked feedback stays on record but no longer counts
+    revoked_at: Option<u64>,
+    
+    // The rated agent's public reply
+    response: Option<FeedbackResponse>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default, near_sdk::serde::Serialize)]
+impl FeedbackEntry {
+    // Whether the entry still counts toward scoring (not removed by an upheld dispute or
+    // quarantined by a manipulation audit)
+    fn counts_toward_score(&self) -> bool {
+        self.dispute_status != Some(FeedbackDisputeStatus::Upheld) && !self.quarantined && self.revoked_at.is_none()
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, Clone, near_sdk::serde::Serialize, near_sdk::serde::Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct CategoryRatings {
     // Accuracy of the agent's responses/actions
@@ -96,14 +862,15 @@ pub struct CategoryRatings {
 }
 
 // Add these enums to define violation types
-#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize)]
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, PartialEq, Clone, Debug)]
 #[serde(crate = "near_sdk::serde")]
 pub enum ViolationType {
     MinorInfraction,    // Minor errors or issues
     MajorInfraction,    // Major errors or poor performance
     TermsViolation,     // Violation of terms of service
     EthicalViolation,   // Ethical violations
-    SecurityBreach      // Security breach or attack
+    SecurityBreach,     // Security breach or attack
+    Custom(String),     // Governance-defined type, named in the violation policy
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -111,10 +878,16 @@ pub struct ViolationRecord {
     violation_type: ViolationType,
     reporter: AccountId,
     description: String,
-    evidence: Option<String>,
+    // Content hashes of off-chain evidence
+    evidence: Vec<Evidence>,
     timestamp: u64,
     penalty_applied: u32, // Reputation points deducted
     tokens_slashed: Balance,
+    // The agent's appeal against this violation, if any
+    appeal: Option<ViolationAppeal>,
+    // Reputation (basis points) of the penalty since forgiven over clean epochs, or restored
+    // by an overturned appeal
+    penalty_forgiven_bps: u32,
 }
 
 #[near_bindgen]
@@ -122,55 +895,246 @@ impl AgentReputationContract {
     #[init]
     pub fn new(owner_id: AccountId, token_contract_id: AccountId, min_stake_amount: Balance) -> Self {
         Self {
+            state_version: migrate::CURRENT_STATE_VERSION,
+            treasury_id: owner_id.clone(),
             owner_id,
             token_contract_id,
             agent_reputations: UnorderedMap::new(b"a"),
             agent_stakes: LookupMap::new(b"s"),
             min_stake_amount,
             feedback_expiry_period: 30 * 24 * 60 * 60 * 1_000_000_000, // 30 days in nanoseconds
-            near_ai_registry: AccountId::new_unchecked("".to_string()),
-            intents_processor: AccountId::new_unchecked("".to_string()),
+            near_ai_registry: None,
+            intents_processor: None,
+            intents: UnorderedMap::new(b"i"),
+            intent_response_period: 24 * 60 * 60 * 1_000_000_000, // 1 day in nanoseconds
+            intent_deadline_period: 7 * 24 * 60 * 60 * 1_000_000_000, // 7 days in nanoseconds
+            intent_dispute_window: 3 * 24 * 60 * 60 * 1_000_000_000, // 3 days in nanoseconds
+            arbiters: UnorderedSet::new(b"b"),
+            query_rebate_amount: 0,
+            query_credits: LookupMap::new(b"q"),
+            query_rebates: LookupMap::new(b"e"),
+            reviewer_weight_config: ReviewerWeightConfig::default(),
+            guardian_id: None,
+            paused_features: 0,
+            intent_checkpoints: LookupMap::new(b"k"),
+            checkpoint_stall_period: 6 * 60 * 60 * 1_000_000_000, // 6 hours in nanoseconds
+            slash_burn_bps: 0,
+            protocol_fee_bps: 0,
+            total_slashed_to_treasury: 0,
+            total_slashed_burned: 0,
+            config_nonce: 0,
+            ranking_index: RankingIndex::new(),
+            specialization_index: LookupMap::new(b"p"),
+            stats: ContractStats::default(),
+            shadow_scoring: None,
+            shadow_scores: LookupMap::new(b"h"),
+            pseudonym_salt: env::random_seed(),
+            pseudonyms: LookupMap::new(b"n"),
+            feedback_disputes: UnorderedMap::new(b"f"),
+            next_feedback_dispute_id: 0,
+            force_majeure_windows: Vector::new(b"m"),
+            reputation_history: LookupMap::new(b"y"),
+            history_config: HistoryConfig::default(),
+            chain_signer_id: None,
+            evm_chain_budgets: UnorderedMap::new(b"g"),
+            evm_delegations: LookupMap::new(b"d"),
+            trusted_attestors: UnorderedMap::new(b"o"),
+            cross_chain_nonces: LookupMap::new(b"u"),
+            chain_policies: UnorderedMap::new(b"l"),
+            capability_policy: policy::default_capability_policy(),
+            next_export_nonce: 0,
+            export_hashes: LookupMap::new(b"x"),
+            privacy_config: PrivacyConfig::default(),
+            agreements: UnorderedMap::new(b"j"),
+            next_agreement_id: 0,
+            agent_agreements: LookupMap::new(b"}"),
+            storage_accounts: LookupMap::new(b"w"),
+            manipulation_audits: LookupMap::new(b"A"),
+            ownership: OwnershipState::default(),
+            retention_config: RetentionConfig::default(),
+            timelock_delay: 2 * 24 * 60 * 60 * 1_000_000_000, // 2 days in nanoseconds
+            pending_actions: UnorderedMap::new(b"B"),
+            next_action_id: 0,
+            audit_bounties: UnorderedMap::new(b"C"),
+            next_bounty_id: 0,
+            audit_badges: LookupMap::new(b"D"),
+            governance_dao: None,
+            escheatment_config: EscheatmentConfig::default(),
+            stake_activity: LookupMap::new(b"E"),
+            escheated_stakes: LookupMap::new(b"F"),
+            delegation_config: DelegationConfig::default(),
+            delegation_pools: LookupMap::new(b"G"),
+            delegation_positions: LookupMap::new(b"H"),
+            fee_discount_tiers: fees::default_fee_discount_tiers(),
+            rewards_config: RewardsConfig::default(),
+            reward_pool: RewardPool::default(),
+            reward_accounts: LookupMap::new(b"I"),
+            identities: LookupMap::new(b"J"),
+            identity_index: LookupMap::new(b"K"),
+            next_identity_id: 0,
+            realm_authorities: UnorderedMap::new(b"L"),
+            category_scoring: Vec::new(),
+            bayesian_prior: BayesianPrior::default(),
+            feedback_limit_config: FeedbackLimitConfig::default(),
+            feedback_amendment_window: 48 * 60 * 60 * 1_000_000_000, // 48 hours in nanoseconds
+            reviewer_feedback: LookupMap::new(b"M"),
+            agent_status_config: AgentStatusConfig::default(),
+            agent_statuses: LookupMap::new(b"N"),
+            agent_profiles: LookupMap::new(b"O"),
+            usage_quota_config: UsageQuotaConfig::default(),
+            usage_windows: LookupMap::new(b"P"),
+            quota_dispatchers: UnorderedSet::new(b"Q"),
+            attestation_ttl: 60 * 60 * 1_000_000_000, // 1 hour in nanoseconds
+            next_attestation_nonce: 0,
+            capability_attestations: LookupMap::new(b"R"),
+            badges: UnorderedMap::new(b"S"),
+            update_index: TreeMap::new(b"T"),
+            agent_updated_at: LookupMap::new(b"U"),
+            failure_weights: FailureWeights::default(),
+            stake_grace_period: 3 * 24 * 60 * 60 * 1_000_000_000, // 3 days in nanoseconds
+            stake_shortfalls: LookupMap::new(b"V"),
+            endorsement_config: EndorsementConfig::default(),
+            endorsements: LookupMap::new(b"W"),
+            endorsements_given: LookupMap::new(b"X"),
+            max_task_delegation_depth: 3,
+            task_delegations: LookupMap::new(b"Y"),
+            insurance_config: InsuranceConfig::new(min_stake_amount),
+            insurance_incidents: Vector::new(b"Z"),
+            insurance_claims: Vector::new(b"0"),
+            intent_insurance_claims: LookupMap::new(b"1"),
+            fee_split: FeeSplit::default(),
+            feedback_window_epoch: 0,
+            violation_policy: violation_policy::default_violation_policy(),
+            penalty_decay_config: PenaltyDecayConfig::default(),
+            whistleblower_reports: LookupMap::new(b"2"),
+            pending_reports: UnorderedSet::new(b"3"),
+            next_report_id: 0,
+            remediation_tasks: UnorderedMap::new(b"4"),
+            remediation_assignments: LookupMap::new(b"5"),
+            remediation_reviews: UnorderedSet::new(b"6"),
+            remediation_verifier: None,
+            performance_oracles: UnorderedMap::new(b"7"),
+            performance_samples: LookupMap::new(b"8"),
+            performance_config: PerformanceConfig::default(),
+            score_epoch: ScoreEpochState::new(),
+            score_epoch_length: 24 * 60 * 60 * 1_000_000_000, // 1 day
+            epoch_changed_agents: UnorderedSet::new(b"9"),
+            epoch_finalizing_agents: UnorderedSet::new(b"!"),
+            epoch_checkpoints: LookupMap::new(b"#"),
+            scoring_mode: ScoringMode::default(),
+            staking_tokens: UnorderedMap::new(b"%"),
+            token_stakes: LookupMap::new(b"^"),
+            staking_rate_oracle: None,
+            client_relationships: LookupMap::new(b"&"),
+            specialization_records: LookupMap::new(b"*"),
+            intent_types: UnorderedMap::new(b"("),
+            intent_auctions: LookupMap::new(b")"),
+            open_auctions: UnorderedSet::new(b"-"),
+            bidding_config: BiddingConfig::default(),
+            client_reputations: LookupMap::new(b"+"),
+            client_auto_accept: LookupMap::new(b"="),
+            blind_ratings: LookupMap::new(b"/"),
+            blind_rating_window: 7 * 24 * 60 * 60 * 1_000_000_000, // 7 days
+            aurora_mirror: None,
+            mirror_syncs: LookupMap::new(b"<"),
+            mirror_sync_queue: UnorderedSet::new(b">"),
+            signed_exports: LookupMap::new(b"?"),
+            bridge_accounts: UnorderedSet::new(b"@"),
+            unpaid_payouts: LookupMap::new(b"["),
+            failed_mirror_syncs: UnorderedSet::new(b"]"),
+            bridge_tokens: UnorderedMap::new(b"{"),
+            open_force_majeure_windows: Vec::new(),
+            agent_agreement_ids: LookupMap::new(b"|"),
+            pending_migration: None,
+            open_intent_deadlines: TreeMap::new(b"~"),
         }
     }
     
-    // Register a new AI agent with initial stake
-    pub fn register_agent(&mut self, agent_id: AccountId, specializations: Vec<String>) {
+    // Register a new AI agent with initial stake. Registration is finalized in
+    // on_agent_verified once the NEAR AI registry confirms the agent. The attached deposit
+    // is credited to the agent's storage balance, which must reach the NEP-145 minimum.
+    #[payable]
+    pub fn register_agent(&mut self, agent_id: AccountId, specializations: Vec<String>) -> Promise {
         // Check if caller is the agent owner or authorized entity
         assert_eq!(env::predecessor_account_id(), agent_id, "Only agent can register itself");
         
         // Ensure agent isn't already registered
-        assert!(!self.agent_reputations.contains_key(&agent_id), "Agent already registered");
+        assert!(self.agent_reputations.get(&agent_id).is_none(), "Agent already registered");
+        
+        // Ensure agent has staked the minimum amount. Agents without stake can register and
+        // stake atomically via ft_transfer_call with a "register" message instead.
+        assert!(
+            self.agent_stakes.get(&agent_id).unwrap_or(0) >= self.min_stake_amount,
+            "Stake below minimum; register via ft_transfer_call with a register message"
+        );
         
-        // Ensure agent has staked the minimum amount (would be handled via cross-contract call)
-        // For now, we're simplifying by assuming the stake transaction happens separately
+        self.assert_storage_registered(&agent_id, env::attached_deposit());
         
-        // Initialize agent reputation
-        let agent_reputation = AgentReputation {
-            score: 50, // Start with neutral reputation
-            total_interactions: 0,
-            successful_interactions: 0,
-            feedback_history: Vec::new(),
-            last_update: env::block_timestamp(),
-            specializations,
-            category_scores: CategoryRatings::default(),
-            violation_history: Vec::new(),
+        self.verify_agent_exists(agent_id, specializations, U128(env::attached_deposit()))
+    }
+    
+    // Callback finalizing registration once the registry has answered
+    #[private]
+    pub fn on_agent_verified(&mut self, agent_id: AccountId, specializations: Vec<String>, deposit: U128) -> bool {
+        let verified = match env::promise_result(0) {
+            PromiseResult::Successful(result) => near_sdk::serde_json::from_slice::<bool>(&result).unwrap_or(false),
+            _ => false,
         };
         
-        self.agent_reputations.insert(&agent_id, &agent_reputation);
+        // Another registration may have completed while the registry call was in flight
+        if !verified || self.agent_reputations.get(&agent_id).is_some() {
+            if deposit.0 > 0 {
+                Promise::new(agent_id.clone()).transfer(deposit.0);
+            }
+            env::log_str(&format!(
+                "Registration of agent {} aborted: {}",
+                agent_id,
+                if verified { "already registered" } else { "not found in NEAR AI registry" }
+            ));
+            return false;
+        }
+        
+        self.internal_credit_storage(&agent_id, deposit.0);
+        self.internal_register_agent(&agent_id, specializations);
+        true
+    }
+    
+    // Create the reputation record for a verified agent
+    pub(crate) fn internal_register_agent(&mut self, agent_id: &AccountId, specializations: Vec<String>) {
+        let initial_storage = env::storage_usage();
+        
+        // Initialize agent reputation
+        let agent_reputation = AgentReputation::new(agent_id, specializations);
+        
+        self.internal_save_agent(agent_id, &agent_reputation);
+        self.internal_create_identity(agent_id);
+        
+        // Storage balance was checked before the registry call; this runs in a callback and
+        // must not fail, so an overdrawn balance only blocks the agent's later writes
+        if !self.internal_track_storage(agent_id, initial_storage) {
+            env::log_str(&format!("Storage balance of agent {} is overdrawn", agent_id));
+        }
+        
+        events::emit_event("agent_registered", json!({ "agent_id": agent_id }));
     }
     
     // Add feedback for an agent after interaction
     pub fn add_feedback(
         &mut self, 
         agent_id: AccountId, 
+        intent_id: String,
         rating: u8, 
         category_ratings: CategoryRatings, 
-        message: Option<String>
+        message: Option<String>,
+        anonymous: Option<bool>
     ) {
+        self.assert_not_paused(PausableFeature::Feedback);
+        
         let user_id = env::predecessor_account_id();
+        let initial_storage = env::storage_usage();
         
         // Ensure agent exists
-        assert!(self.agent_reputations.contains_key(&agent_id), "Agent not registered");
+        assert!(self.agent_reputations.get(&agent_id).is_some(), "Agent not registered");
         
         // Ensure ratings are valid (0-5)
         assert!(rating <= 5, "Rating must be between 0 and 5");
@@ -180,116 +1144,218 @@ impl AgentReputationContract {
         assert!(category_ratings.problem_solving <= 5, "Problem solving rating must be between 0 and 5");
         assert!(category_ratings.ethics <= 5, "Ethics rating must be between 0 and 5");
         
+        // Suspended and banned agents can't review others
+        if self.agent_reputations.get(&user_id).is_some() {
+            self.assert_agent_in_good_standing(&user_id);
+        }
+        
+        // Only the client of a finished intent with this agent can leave feedback, once
+        self.internal_use_feedback_slot(&intent_id, &user_id, &agent_id);
+        
+        // Held until the agent has rated the client too, or the blind rating window closes
+        let release = self.internal_hold_blind_rating(&intent_id, |ratings| {
+            ratings.client_rated = true;
+            ratings.feedback = Some(PendingFeedback {
+                rating,
+                category_ratings,
+                message,
+                anonymous: anonymous.unwrap_or(false),
+                submitted_at: env::block_timestamp(),
+            });
+        });
+        
+        // The reviewer pays for the storage of their feedback
+        self.internal_charge_storage(&user_id, initial_storage);
+        
+        if release {
+            self.internal_release_blind_ratings(&intent_id);
+        }
+    }
+    
+    // Apply a client's released feedback to the agent of the intent
+    pub(crate) fn internal_apply_feedback(&mut self, intent: &IntentData, pending: PendingFeedback) {
+        let user_id = intent.client_id.clone();
+        let agent_id = intent.agent_id.clone();
+        
+        // Weight the feedback by the reviewer's own standing, dampened if the reviewer has
+        // rated this agent recently
+        let repeat_weight_bps = self.internal_record_reviewer_feedback(&user_id, &agent_id);
+        self.internal_record_relationship_rating(&user_id, &agent_id, pending.rating);
+        let reviewer_weight = self.calculate_reviewer_weight(&user_id) * repeat_weight_bps / 10_000;
+        self.internal_record_specialization_rating(&agent_id, &intent.intent_id, pending.rating, reviewer_weight);
+        
+        // Anonymous reviewers are shown under a per-agent pseudonym
+        let pseudonym = if pending.anonymous {
+            Some(self.internal_pseudonym_for(&user_id, &agent_id))
+        } else {
+            None
+        };
+        
         let mut agent_rep = self.agent_reputations.get(&agent_id).unwrap();
         
         // Add feedback entry
+        let rating = pending.rating;
         let feedback = FeedbackEntry {
             user_id,
+            intent_id: intent.intent_id.clone(),
             rating,
-            category_ratings,
-            message,
-            timestamp: env::block_timestamp(),
+            category_ratings: pending.category_ratings,
+            message: pending.message,
+            timestamp: pending.submitted_at,
+            reviewer_weight,
+            pseudonym,
+            dispute_status: None,
+            quarantined: false,
+            revisions: Vec::new(),
+            revoked_at: None,
+            response: None,
         };
         
-        agent_rep.feedback_history.push(feedback);
+        agent_rep.push_feedback(&feedback);
         
         // Update interaction counts
         agent_rep.total_interactions += 1;
+        self.internal_record_decay_activity(&agent_id, &mut agent_rep);
         if rating >= 3 {
             agent_rep.successful_interactions += 1;
         }
         
         // Recalculate reputation score and category averages
-        self.recalculate_reputation_with_categories(&mut agent_rep);
+        let previous_score_bps = agent_rep.score_bps;
+        self.recalculate_reputation_with_categories(&agent_id, &mut agent_rep);
+        
+        // Scores cannot drop while a force-majeure window is active
+        if self.is_force_majeure_active() {
+            agent_rep.set_score_bps(std::cmp::max(agent_rep.score_bps, previous_score_bps));
+        }
         
         // Update agent reputation
         agent_rep.last_update = env::block_timestamp();
-        self.agent_reputations.insert(&agent_id, &agent_rep);
+        self.internal_save_agent(&agent_id, &agent_rep);
+        self.internal_update_shadow_score(&agent_id, &mut agent_rep);
+        
+        self.internal_apply_retention(&agent_id, &mut agent_rep);
     }
     
     // Internal function to recalculate reputation score
-    fn recalculate_reputation(&self, agent_rep: &mut AgentReputation) {
+    fn recalculate_reputation(&self, agent_id: &AccountId, agent_rep: &mut AgentReputation) {
+        self.internal_expire_feedback(agent_rep);
+        if let Some(score_bps) = self.compute_score_bps(agent_id, agent_rep, &ScoringParams::live(self)) {
+            agent_rep.set_score_bps(score_bps);
+        }
+    }
+    
+    // Compute a reputation score (basis points) under the given scoring parameters, or None if
+    // there is no valid feedback to score. Ratings come from the agent's feedback aggregates,
+    // which cover the live expiry window.
+    pub(crate) fn compute_score_bps(&self, agent_id: &AccountId, agent_rep: &AgentReputation, params: &ScoringParams) -> Option<u32> {
         if agent_rep.total_interactions == 0 {
-            return;
+            return None;
         }
         
-        // Filter out expired feedback
-        let current_time = env::block_timestamp();
-        let valid_feedback: Vec<&FeedbackEntry> = agent_rep.feedback_history
-            .iter()
-            .filter(|f| current_time - f.timestamp <= self.feedback_expiry_period)
-            .collect();
-        
-        // Simple weighted calculation (can be enhanced with more complex algorithms)
-        let mut total_rating = 0;
-        let mut weight_sum = 0;
-        
-        for (i, feedback) in valid_feedback.iter().enumerate() {
-            // More recent feedback gets higher weight
-            let weight = i as u32 + 1;
-            total_rating += (feedback.rating as u32) * weight;
-            weight_sum += weight;
+        // More recent feedback gets higher weight, scaled by the reviewer's weight
+        let (total_rating, weight_sum) = agent_rep.feedback_aggregates.rating_totals(params.recency_weighting);
+        
+        if weight_sum == 0 {
+            return None;
         }
         
-        if weight_sum > 0 {
-            // Normalize to 0-100 scale
-            let raw_score = (total_rating * 20) / weight_sum; // Convert from 0-5 to 0-100
-            
-            // Apply success rate modifier
-            let success_rate = (agent_rep.successful_interactions * 100) / agent_rep.total_interactions;
-            
-            // Get stake-based bonus
-            let stake_bonus = self.calculate_stake_bonus(env::predecessor_account_id());
-            
-            // Final score with stake weight (capped at 100)
-            let combined_score = (raw_score + success_rate as u32) / 2;
-            agent_rep.score = std::cmp::min(combined_score + stake_bonus, 100);
+        // Normalize from 0-5 stars to the score scale
+        let rating_score = rating_score_bps(total_rating, weight_sum);
+        
+        // Blend in the category averages by their configured weights
+        let raw_score = self.internal_weighted_rating_score(agent_rep, rating_score);
+        
+        // Apply success rate modifier, with failures weighted by cause
+        let success_rate = agent_rep.failure_breakdown.weighted_success_rate(&params.failure_weights);
+        
+        let combined_score = blend_bps(raw_score, success_rate, params.success_rate_weight_bps);
+        
+        // Blend in oracle-measured performance while the agent has recent samples
+        let combined_score = match self.internal_performance_score_bps(agent_id) {
+            Some(performance_score) => blend_bps(combined_score, performance_score, params.performance_weight_bps),
+            None => combined_score,
+        };
+        
+        // Shrink toward the prior so a handful of reviews cannot outrank a long track record
+        let combined_score = params
+            .bayesian_prior
+            .apply(combined_score, agent_rep.feedback_aggregates.evidence_weight());
+        
+        // Stake-based bonus of the agent being scored, never more than a fraction of what its
+        // feedback earned so stake cannot outweigh feedback
+        let stake_bonus = std::cmp::min(
+            self.calculate_stake_bonus(agent_id, params.max_stake_bonus),
+            mul_bps(combined_score, MAX_STAKE_BONUS_SHARE_BPS),
+        );
+        
+        // Peer endorsements give agents a way to build trust without stake
+        let endorsement_bonus = self.internal_endorsement_bonus(agent_id);
+        
+        // Final score with stake and endorsement weight (capped at 100 points)
+        Some(clamp_score(combined_score as u128 + stake_bonus as u128 + endorsement_bonus as u128))
+    }
+    
+    // Calculate the weight of a reviewer's feedback (basis points) from their stake and
+    // reputation under the scoring mode
+    fn calculate_reviewer_weight(&self, reviewer_id: &AccountId) -> u32 {
+        let config = &self.reviewer_weight_config;
+        let mut weight = config.base_weight;
+        let stake = self.agent_stakes.get(reviewer_id).unwrap_or(0);
+        
+        if self.scoring_mode == ScoringMode::Quadratic {
+            return config.quadratic_weight(stake, self.min_stake_amount);
         }
+        
+        // Reviewers with skin in the game count fully
+        if stake >= self.min_stake_amount {
+            weight = std::cmp::max(weight, config.staked_weight);
+        }
+        
+        // Reputable agents scale up to the maximum weight with their score
+        if let Some(reviewer_rep) = self.agent_reputations.get(reviewer_id) {
+            if reviewer_rep.score >= config.agent_min_score {
+                let agent_weight = config.base_weight
+                    + config.agent_max_weight.saturating_sub(config.base_weight) * reviewer_rep.score / 100;
+                weight = std::cmp::max(weight, agent_weight);
+            }
+        }
+        
+        weight
     }
     
-    // Calculate reputation bonus based on staked amount
-    fn calculate_stake_bonus(&self, agent_id: AccountId) -> u32 {
-        let stake = self.agent_stakes.get(&agent_id).unwrap_or(0);
+    // Calculate reputation bonus (basis points) based on the agent's staked amount. The bonus
+    // grows linearly from a fifth of `max_bonus` points at the minimum stake to all of it at
+    // STAKE_BONUS_FULL_MULTIPLE times the minimum stake. Only ITLX stake can be slashed, so
+    // the agent's own ITLX stake must reach the minimum before anything else counts.
+    fn calculate_stake_bonus(&self, agent_id: &AccountId, max_bonus: u32) -> u32 {
+        let itlx_stake = self.agent_stakes.get(agent_id).unwrap_or(0);
         let min_stake = self.min_stake_amount;
         
-        if stake < min_stake {
+        if itlx_stake < min_stake || min_stake == 0 {
             return 0;
         }
         
-        // Calculate bonus (max 15 points)
-        // Scale based on multiples of minimum stake
-        let stake_multiple = stake / min_stake;
-        match stake_multiple {
-            0 => 0,
-            1 => 3,  // 3% bonus for minimum stake
-            2 => 6,  // 6% bonus for 2x minimum stake
-            3 => 9,  // 9% bonus for 3x minimum stake
-            4 => 12, // 12% bonus for 4x minimum stake
-            _ => 15, // 15% max bonus for 5x+ minimum stake
-        }
+        // On top of it, other staking tokens count at their ITLX-equivalent value and delegated
+        // stake at a discount
+        let delegated = self.delegation_pools.get(agent_id).map_or(0, |pool| pool.active_amount());
+        let stake = itlx_stake
+            + self.internal_weighted_token_stake(agent_id)
+            + delegated * self.delegation_config.stake_bonus_discount_bps as u128 / 10_000;
+        
+        linear_share(from_points(max_bonus), stake, min_stake * STAKE_BONUS_FULL_MULTIPLE)
     }
     
-    // Update token_integration.rs on_stake_complete function to recalculate reputation
-    // This is a new function to be added
+    // Recalculate an agent's reputation after its stake changes
     pub fn update_reputation_on_stake_change(&mut self, agent_id: AccountId) {
-        if self.agent_reputations.contains_key(&agent_id) {
+        if self.agent_reputations.get(&agent_id).is_some() {
             let mut agent_rep = self.agent_reputations.get(&agent_id).unwrap();
-            self.recalculate_reputation(&mut agent_rep);
-            self.agent_reputations.insert(&agent_id, &agent_rep);
+            self.recalculate_reputation(&agent_id, &mut agent_rep);
+            self.internal_save_agent(&agent_id, &agent_rep);
+            self.internal_update_shadow_score(&agent_id, &mut agent_rep);
         }
     }
     
-    // Stake ITLX tokens to boost reputation
-    pub fn stake_tokens(&mut self, amount: Balance) {
-        let agent_id = env::predecessor_account_id();
-        
-        // Would implement cross-contract call to token contract
-        // For now, simplified implementation
-        
-        let current_stake = self.agent_stakes.get(&agent_id).unwrap_or(0);
-        self.agent_stakes.insert(&agent_id, &(current_stake + amount));
-    }
-    
     // Get agent reputation
     pub fn get_agent_reputation(&self, agent_id: AccountId) -> Option<AgentReputationView> {
         self.agent_reputations.get(&agent_id).map(|rep| {
@@ -314,207 +1380,191 @@ impl AgentReputationContract {
         }
     }
     
-    // Get the capability limits based on trust level
-    pub fn get_capability_limits(&self, agent_id: AccountId) -> Option<CapabilityLimits> {
-        if let Some(agent_rep) = self.agent_reputations.get(&agent_id) {
-            let trust_level = self.get_trust_level(agent_rep.score);
-            
-            let limits = match trust_level {
-                TrustLevel::Novice => CapabilityLimits {
-                    max_complexity: 3,
-                    max_transaction_value: 100 * 10u128.pow(18), // 100 tokens
-                    can_access_critical_systems: false,
-                    can_operate_autonomously: false,
-                    can_delegate: false,
-                },
-                TrustLevel::Apprentice => CapabilityLimits {
-                    max_complexity: 5,
-                    max_transaction_value: 500 * 10u128.pow(18),
-                    can_access_critical_systems: false,
-                    can_operate_autonomously: true,
-                    can_delegate: false,
-                },
-                TrustLevel::Trusted => CapabilityLimits {
-                    max_complexity: 7,
-                    max_transaction_value: 2000 * 10u128.pow(18),
-                    can_access_critical_systems: false,
-                    can_operate_autonomously: true,
-                    can_delegate: true,
-                },
-                TrustLevel::Expert => CapabilityLimits {
-                    max_complexity: 9,
-                    max_transaction_value: 10000 * 10u128.pow(18),
-                    can_access_critical_systems: true,
-                    can_operate_autonomously: true,
-                    can_delegate: true,
-                },
-                TrustLevel::Master => CapabilityLimits {
-                    max_complexity: 10,
-                    max_transaction_value: u128::MAX, // Unlimited
-                    can_access_critical_systems: true,
-                    can_operate_autonomously: true,
-                    can_delegate: true,
-                },
-            };
-            
-            Some(limits)
-        } else {
-            None
+    // Number of penalty-free intent declines per quota period for a trust level
+    pub fn get_decline_quota(&self, trust_level: TrustLevel) -> u32 {
+        match trust_level {
+            TrustLevel::Novice => 1,
+            TrustLevel::Apprentice => 2,
+            TrustLevel::Trusted => 3,
+            TrustLevel::Expert => 4,
+            TrustLevel::Master => 5,
         }
     }
     
+    // Get the capability limits granted by the capability policy. With a specialization, score
+    // and trust level conditions are evaluated against the agent's score in it.
+    pub fn get_capability_limits(&self, agent_id: AccountId, specialization: Option<String>) -> Option<CapabilityLimits> {
+        self.agent_reputations
+            .get(&agent_id)
+            .map(|agent_rep| self.internal_capability_limits(&agent_id, &agent_rep, specialization.as_deref()))
+    }
+    
+    // Capability limits of many agents at once, in request order (None for unregistered agents)
+    pub fn get_capability_limits_bulk(&self, agent_ids: Vec<AccountId>) -> Vec<Option<CapabilityLimits>> {
+        assert!(agent_ids.len() <= MAX_BULK_AGENTS, "Too many agents requested");
+        agent_ids.into_iter().map(|agent_id| self.get_capability_limits(agent_id, None)).collect()
+    }
+    
     // Check if an agent can perform a specific action
     pub fn can_perform_action(&self, agent_id: AccountId, action_type: String, value: Option<Balance>) -> bool {
-        if let Some(limits) = self.get_capability_limits(agent_id) {
-            match action_type.as_str() {
-                "transaction" => {
-                    if let Some(tx_value) = value {
-                        return tx_value <= limits.max_transaction_value;
-                    }
-                    false
-                },
-                "critical_access" => limits.can_access_critical_systems,
-                "autonomous_operation" => limits.can_operate_autonomously,
-                "delegation" => limits.can_delegate,
-                _ => false,
-            }
-        } else {
-            false
-        }
+        self.evaluate_authorization(&agent_id, &action_type, value).allowed()
     }
     
     // New function to calculate category averages
-    fn recalculate_reputation_with_categories(&self, agent_rep: &mut AgentReputation) {
+    fn recalculate_reputation_with_categories(&self, agent_id: &AccountId, agent_rep: &mut AgentReputation) {
         if agent_rep.total_interactions == 0 {
             return;
         }
         
-        // Filter out expired feedback
-        let current_time = env::block_timestamp();
-        let valid_feedback: Vec<&FeedbackEntry> = agent_rep.feedback_history
-            .iter()
-            .filter(|f| current_time - f.timestamp <= self.feedback_expiry_period)
-            .collect();
-        
-        if valid_feedback.is_empty() {
-            return;
-        }
+        self.recalculate_reputation(agent_id, agent_rep);
         
-        // Calculate category averages
-        let mut accuracy_sum = 0;
-        let mut response_time_sum = 0;
-        let mut communication_sum = 0;
-        let mut problem_solving_sum = 0;
-        let mut ethics_sum = 0;
-        
-        for feedback in &valid_feedback {
-            accuracy_sum += feedback.category_ratings.accuracy as u32;
-            response_time_sum += feedback.category_ratings.response_time as u32;
-            communication_sum += feedback.category_ratings.communication as u32;
-            problem_solving_sum += feedback.category_ratings.problem_solving as u32;
-            ethics_sum += feedback.category_ratings.ethics as u32;
+        // Category averages, weighted by reviewer weight
+        if let Some(category_scores) = agent_rep.feedback_aggregates.category_averages() {
+            agent_rep.category_scores = category_scores;
         }
-        
-        let count = valid_feedback.len() as u32;
-        
-        agent_rep.category_scores = CategoryRatings {
-            accuracy: (accuracy_sum / count) as u8,
-            response_time: (response_time_sum / count) as u8,
-            communication: (communication_sum / count) as u8,
-            problem_solving: (problem_solving_sum / count) as u8,
-            ethics: (ethics_sum / count) as u8,
-        };
-        
-        // Continue with regular reputation calculation
-        self.recalculate_reputation(agent_rep);
     }
     
     // Extend the reputation view to include categories
     pub fn get_agent_reputation_detailed(&self, agent_id: AccountId) -> Option<AgentReputationDetailedView> {
         self.agent_reputations.get(&agent_id).map(|rep| {
+            let (score_lower_bound, score_upper_bound) =
+                self.bayesian_prior.interval(rep.score, rep.feedback_aggregates.evidence_weight());
             AgentReputationDetailedView {
                 score: rep.score,
+                score_bps: rep.score_bps,
+                score_lower_bound,
+                score_upper_bound,
                 total_interactions: rep.total_interactions,
                 successful_interactions: rep.successful_interactions,
                 specializations: rep.specializations,
                 last_update: rep.last_update,
                 trust_level: self.get_trust_level(rep.score),
+                status: self.internal_agent_status(&agent_id),
                 category_scores: rep.category_scores,
+                reliability_score: rep.reliability_score,
+                liveness_score: rep.liveness.liveness_score(),
+                dispute_stats: rep.dispute_stats.view(),
             }
         })
     }
     
-    // Report a violation (limited to authorized accounts)
+    // Detailed reputation of many agents at once, in request order (None for unregistered agents)
+    pub fn get_agents_bulk(&self, agent_ids: Vec<AccountId>) -> Vec<Option<AgentReputationDetailedView>> {
+        assert!(agent_ids.len() <= MAX_BULK_AGENTS, "Too many agents requested");
+        agent_ids.into_iter().map(|agent_id| self.get_agent_reputation_detailed(agent_id)).collect()
+    }
+    
+    // Report a violation (the governance DAO if configured, otherwise the owner)
     pub fn report_violation(
         &mut self,
         agent_id: AccountId,
         violation_type: ViolationType,
         description: String,
-        evidence: Option<String>
+        evidence: Vec<Evidence>
     ) {
+        self.assert_not_paused(PausableFeature::ViolationReporting);
+        
         let reporter = env::predecessor_account_id();
+        let initial_storage = env::storage_usage();
         
-        // Only allow authorized entities (contract owner or governance) to report violations
-        assert!(
-            reporter == self.owner_id || self.is_governance_member(reporter),
-            "Unauthorized: only owner or governance members can report violations"
-        );
+        // Only the governance DAO, or the owner while none is configured, can report violations
+        self.assert_violation_authority();
+        
+        // Evidence is stored as hashes and URIs only; the content stays off-chain
+        violations::assert_valid_evidence(&evidence);
+        
+        self.internal_record_violation(agent_id.clone(), violation_type, reporter, description, evidence, false);
+        
+        // The reporter pays for the storage of the violation record
+        self.internal_charge_storage(&env::predecessor_account_id(), initial_storage);
         
+        let mut agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+        self.internal_apply_retention(&agent_id, &mut agent_rep);
+    }
+    
+    // Apply a violation's penalty and slashing and record it against the agent
+    pub(crate) fn internal_record_violation(
+        &mut self,
+        agent_id: AccountId,
+        violation_type: ViolationType,
+        reporter: AccountId,
+        description: String,
+        evidence: Vec<Evidence>,
+        reward_reporter: bool
+    ) {
         // Ensure agent exists
-        assert!(self.agent_reputations.contains_key(&agent_id), "Agent not registered");
-        
-        // Calculate penalty based on violation type
-        let (reputation_penalty, token_slash_percentage) = match violation_type {
-            ViolationType::MinorInfraction => (5, 1),   // 5 points, 1% of stake
-            ViolationType::MajorInfraction => (15, 5),  // 15 points, 5% of stake
-            ViolationType::TermsViolation => (25, 10),  // 25 points, 10% of stake
-            ViolationType::EthicalViolation => (40, 25), // 40 points, 25% of stake
-            ViolationType::SecurityBreach => (60, 50),  // 60 points, 50% of stake
-        };
+        assert!(self.agent_reputations.get(&agent_id).is_some(), "Agent not registered");
+        
+        // Look up the penalty for the violation type in the governance-set policy
+        let penalty = self.internal_violation_penalty(&violation_type);
         
-        // Apply reputation penalty
+        // Apply reputation penalty. Decay is brought up to date first, so the epoch the
+        // violation falls in is not counted as clean.
         let mut agent_rep = self.agent_reputations.get(&agent_id).unwrap();
-        if agent_rep.score >= reputation_penalty {
-            agent_rep.score -= reputation_penalty;
-        } else {
-            agent_rep.score = 0;
-        }
+        self.internal_apply_penalty_decay(&agent_id, &mut agent_rep);
+        agent_rep.deduct_score_points(penalty.reputation_penalty);
         
         // Calculate token slashing
         let stake = self.agent_stakes.get(&agent_id).unwrap_or(0);
-        let tokens_to_slash = stake * token_slash_percentage as u128 / 100;
+        let tokens_to_slash = stake * penalty.slash_bps as u128 / 10_000;
+        
+        // A confirmed whistleblower report earns its reporter a share of the slash
+        let whistleblower = if reward_reporter { Some(reporter.clone()) } else { None };
         
         // Record the violation
         let violation = ViolationRecord {
-            violation_type,
+            violation_type: violation_type.clone(),
             reporter,
             description,
             evidence,
             timestamp: env::block_timestamp(),
-            penalty_applied: reputation_penalty,
+            penalty_applied: penalty.reputation_penalty,
             tokens_slashed: tokens_to_slash,
+            appeal: None,
+            penalty_forgiven_bps: 0,
         };
         
         agent_rep.violation_history.push(violation);
         
+        // Severe violation types suspend the agent outright; otherwise repeated violations do
+        if penalty.suspend && self.internal_agent_status(&agent_id).in_good_standing() {
+            let reason = format!("{:?} violation", violation_type);
+            self.internal_set_agent_status(&agent_id, AgentStatus::Suspended, Some(reason));
+        } else {
+            self.internal_check_violation_suspension(&agent_id, &agent_rep);
+        }
+        
         // Update the agent reputation
-        self.agent_reputations.insert(&agent_id, &agent_rep);
+        self.internal_save_agent(&agent_id, &agent_rep);
+        
+        // Delegators share the agent's fate
+        self.internal_slash_delegations(&agent_id, penalty.slash_bps);
         
         // If tokens to slash > 0, execute the slashing
         if tokens_to_slash > 0 {
-            self.execute_slashing(agent_id, tokens_to_slash);
+            self.execute_slashing(agent_id, tokens_to_slash, whistleblower);
         }
     }
     
-    // Execute token slashing (simplified - would be a cross-contract call in production)
-    fn execute_slashing(&mut self, agent_id: AccountId, amount: Balance) {
+    // Execute token slashing, paying any whistleblower their reward and routing the rest of
+    // the slashed tokens to the treasury
+    fn execute_slashing(&mut self, agent_id: AccountId, amount: Balance, whistleblower: Option<AccountId>) {
         let current_stake = self.agent_stakes.get(&agent_id).unwrap_or(0);
         if current_stake >= amount {
             // Update stake amount
-            self.agent_stakes.insert(&agent_id, &(current_stake - amount));
+            self.internal_set_stake(&agent_id, current_stake - amount);
+            
+            let mut routed = amount;
+            if let Some(reporter) = whistleblower {
+                let reward = amount * whistleblower::WHISTLEBLOWER_REWARD_BPS as u128 / 10_000;
+                if reward > 0 {
+                    self.internal_pay_whistleblower_reward(&agent_id, &reporter, reward);
+                    routed -= reward;
+                }
+            }
             
-            // In a real implementation, you would transfer the slashed tokens
-            // to a community fund or governance treasury
+            // Move the slashed tokens to the treasury (or burn them)
+            self.route_slashed_tokens(&agent_id, routed, false);
             
             // Log the slashing event
             env::log_str(&format!(
@@ -526,35 +1576,40 @@ impl AgentReputationContract {
     
     // Helper function to check if an account is a governance member
     fn is_governance_member(&self, account_id: AccountId) -> bool {
-        // In a real implementation, you would check against a list of governance members
-        // For now, just check if it's the owner
-        account_id == self.owner_id
+        // The governance DAO only calls this contract by executing approved proposals
+        self.governance_dao.as_ref() == Some(&account_id)
     }
     
-    // Allow an agent to appeal a violation
-    pub fn appeal_violation(&mut self, violation_index: usize, justification: String) {
+    // Allow an agent to appeal a violation, by its index in get_violations. The appeal is
+    // ruled on through resolve_appeal; the agent pays for its storage.
+    pub fn appeal_violation(&mut self, violation_index: u64, justification: String) {
         let agent_id = env::predecessor_account_id();
+        let initial_storage = env::storage_usage();
         
         // Ensure agent exists
-        assert!(self.agent_reputations.contains_key(&agent_id), "Agent not registered");
+        assert!(self.agent_reputations.get(&agent_id).is_some(), "Agent not registered");
+        assert!(justification.len() <= violations::MAX_APPEAL_TEXT_LENGTH, "Appeal justification too long");
         
         let mut agent_rep = self.agent_reputations.get(&agent_id).unwrap();
         
         // Ensure violation index is valid
-        assert!(
-            violation_index < agent_rep.violation_history.len(),
-            "Invalid violation index"
-        );
+        let position = agent_rep.violation_position(violation_index).expect("Invalid violation index");
         
-        // In a real system, this would create an appeal that governance would review
-        // For now, just log the appeal
-        env::log_str(&format!(
-            "Appeal received from agent {} for violation #{}: {}",
-            agent_id, violation_index, justification
-        ));
+        let violation = &mut agent_rep.violation_history[position];
+        assert!(violation.appeal.is_none(), "Violation already appealed");
+        violation.appeal = Some(ViolationAppeal::new(justification.clone()));
         
-        // Store the appeal with the violation (would need to modify ViolationRecord)
-        // For simplicity, not implemented here
+        self.internal_save_agent(&agent_id, &agent_rep);
+        self.internal_charge_storage(&agent_id, initial_storage);
+        
+        events::emit_event(
+            "violation_appealed",
+            json!({
+                "agent_id": agent_id,
+                "violation_index": violation_index,
+                "justification": justification,
+            }),
+        );
     }
     
     // Allow the owner or governance to restore reputation points
@@ -568,15 +1623,15 @@ impl AgentReputationContract {
         );
         
         // Ensure agent exists
-        assert!(self.agent_reputations.contains_key(&agent_id), "Agent not registered");
+        assert!(self.agent_reputations.get(&agent_id).is_some(), "Agent not registered");
         
         let mut agent_rep = self.agent_reputations.get(&agent_id).unwrap();
         
         // Add reputation points (cap at 100)
-        agent_rep.score = std::cmp::min(agent_rep.score + points, 100);
+        agent_rep.add_score_points(points);
         
         // Update the agent reputation
-        self.agent_reputations.insert(&agent_id, &agent_rep);
+        self.internal_save_agent(&agent_id, &agent_rep);
         
         // Log the restoration
         env::log_str(&format!(
@@ -585,93 +1640,42 @@ impl AgentReputationContract {
         ));
     }
     
-    // Allow agents to complete remediation tasks to recover reputation
-    pub fn complete_remediation_task(&mut self, task_id: String, proof: String) {
-        let agent_id = env::predecessor_account_id();
-        
-        // Ensure agent exists
-        assert!(self.agent_reputations.contains_key(&agent_id), "Agent not registered");
-        
-        // In a real system, you would:
-        // 1. Verify the task was assigned to this agent
-        // 2. Verify the proof of completion
-        // 3. Calculate the appropriate reputation recovery
-        
-        // For simplicity, we'll assume verification passed and grant a fixed recovery
-        let recovery_points = 5; // Fixed 5 points per remediation task
-        
-        let mut agent_rep = self.agent_reputations.get(&agent_id).unwrap();
-        
-        // Add reputation points (cap at 100)
-        agent_rep.score = std::cmp::min(agent_rep.score + recovery_points, 100);
-        
-        // Update the agent reputation
-        self.agent_reputations.insert(&agent_id, &agent_rep);
-        
-        // Log the recovery
-        env::log_str(&format!(
-            "Agent {} recovered {} reputation points by completing remediation task {}",
-            agent_id, recovery_points, task_id
-        ));
-    }
-    
-    // Path to reputation recovery through enhanced stake
-    pub fn boost_recovery_with_stake(&mut self, additional_stake: U128) -> Promise {
-        let agent_id = env::predecessor_account_id();
-        
+    // Path to reputation recovery through enhanced stake: credit a recovery-boost transfer to
+    // the agent's stake and grant recovery points on top of the usual stake bonus
+    fn internal_boost_recovery(&mut self, agent_id: &AccountId, amount: Balance) {
         // Ensure agent exists and has reputation below 50
-        assert!(self.agent_reputations.contains_key(&agent_id), "Agent not registered");
-        let agent_rep = self.agent_reputations.get(&agent_id).unwrap();
+        let agent_rep = self.agent_reputations.get(agent_id).expect("Agent not registered");
         assert!(agent_rep.score < 50, "Recovery boost only available for agents with reputation below 50");
-        
-        // Call stake_itlx (from token_integration.rs) with increased recovery factor
-        self.stake_itlx(additional_stake)
-    }
-    
-    // Special callback for recovery staking (would be added to token_integration.rs)
-    pub fn on_recovery_stake_complete(&mut self, agent_id: AccountId, amount: U128) {
-        // Verify callback is from previous cross-contract call
-        assert_eq!(env::predecessor_account_id(), env::current_account_id(), "Unauthorized");
-        
-        // Check if the transfer was successful
-        match env::promise_result(0) {
-            PromiseResult::Successful(_) => {
-                // Get current reputation
-                if self.agent_reputations.contains_key(&agent_id) {
-                    let mut agent_rep = self.agent_reputations.get(&agent_id).unwrap();
-                    
-                    // Calculate recovery boost (larger than normal stake bonus)
-                    // 1 point per 10% of minimum_stake, up to 20 points
-                    let recovery_points = std::cmp::min(
-                        (amount.0 * 10 / self.min_stake_amount) as u32,
-                        20
-                    );
-                    
-                    // Apply recovery points
-                    agent_rep.score = std::cmp::min(agent_rep.score + recovery_points, 100);
-                    self.agent_reputations.insert(&agent_id, &agent_rep);
-                    
-                    env::log_str(&format!(
-                        "Agent {} recovered {} reputation points through additional staking",
-                        agent_id, recovery_points
-                    ));
-                }
-            },
-            _ => {
-                // Handle failure case
-                env::log_str("Recovery staking failed");
-            }
-        }
+
+        let current_stake = self.agent_stakes.get(agent_id).unwrap_or(0);
+        self.internal_set_stake(agent_id, current_stake + amount);
+        self.internal_record_activity(agent_id);
+        self.update_reputation_on_stake_change(agent_id.clone());
+
+        // Calculate recovery boost (larger than normal stake bonus)
+        // 1 point per 10% of minimum_stake, up to 20 points
+        let recovery_points = std::cmp::min(amount * 10 / self.min_stake_amount, 20) as u32;
+
+        let mut agent_rep = self.agent_reputations.get(agent_id).unwrap();
+        agent_rep.add_score_points(recovery_points);
+        self.internal_save_agent(agent_id, &agent_rep);
+
+        env::log_str(&format!(
+            "Agent {} recovered {} reputation points through additional staking",
+            agent_id, recovery_points
+        ));
     }
 
-    // Function to verify an agent exists in NEAR AI Registry
-    pub fn verify_agent_exists(&self, agent_id: AccountId) -> Promise {
-        Promise::new(self.near_ai_registry.clone())
+    // Function to verify an agent exists in NEAR AI Registry, registering it on success
+    fn verify_agent_exists(&self, agent_id: AccountId, specializations: Vec<String>, deposit: U128) -> Promise {
+        let near_ai_registry = self.near_ai_registry.clone().expect("NEAR AI registry not configured");
+        
+        Promise::new(near_ai_registry)
             .function_call(
                 "has_agent".to_string(),
                 json!({ "agent_id": agent_id }).to_string().into_bytes(),
                 0,
-                env::prepaid_gas() / 3
+                GAS_FOR_REGISTRY_CHECK
             )
             .then(
                 Promise::new(env::current_account_id())
@@ -679,9 +1683,11 @@ impl AgentReputationContract {
                         "on_agent_verified".to_string(),
                         json!({
                             "agent_id": agent_id,
+                            "specializations": specializations,
+                            "deposit": deposit,
                         }).to_string().into_bytes(),
                         0,
-                        env::prepaid_gas() / 3
+                        GAS_FOR_REGISTRATION_CALLBACK
                     )
             )
     }
@@ -699,7 +1705,7 @@ pub struct AgentReputationView {
 }
 
 // Add these new structures after AgentReputationView
-#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize)]
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, PartialEq, PartialOrd, Clone, Copy, Debug)]
 #[serde(crate = "near_sdk::serde")]
 pub enum TrustLevel {
     Novice,     // 0-30 reputation score
@@ -709,7 +1715,8 @@ pub enum TrustLevel {
     Master      // 91-100 reputation score
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
 pub struct CapabilityLimits {
     // Maximum complexity of tasks this agent can handle (1-10)
     max_complexity: u8,
@@ -728,10 +1735,19 @@ pub struct CapabilityLimits {
 #[serde(crate = "near_sdk::serde")]
 pub struct AgentReputationDetailedView {
     score: u32,
+    // The score at full precision (basis points, 0-10000)
+    score_bps: u32,
+    // Approximate 95% credible interval of the score given how much feedback backs it
+    score_lower_bound: u32,
+    score_upper_bound: u32,
     total_interactions: u64,
     successful_interactions: u64,
     specializations: Vec<String>,
     last_update: u64,
     trust_level: TrustLevel,
+    status: AgentStatus,
     category_scores: CategoryRatings,
+    reliability_score: u32,
+    liveness_score: u32,
+    dispute_stats: DisputeStatsView,
 } 
\ No newline at end of file
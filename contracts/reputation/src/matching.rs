@@ -0,0 +1,78 @@
+use crate::*;
+use crate::fixed_point::to_points;
+use crate::specializations::normalize_tag;
+
+// Upper bound on agents recommended per call
+const MAX_RECOMMENDATIONS: u32 = 50;
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Recommend agents for an intent of a registered type, best first. Candidates list the type
+    // as a specialization, are in good standing, meet the type's trust level and complexity by
+    // their score in it, can transact `max_value`, and have transaction quota left for it.
+    // Ranked by score in the type, then overall score.
+    pub fn recommend_agents(&self, intent_type: String, max_value: Option<U128>, limit: Option<u32>) -> Vec<AgentRecommendationView> {
+        let intent_type = normalize_tag(&intent_type);
+        let metadata = self.intent_types.get(&intent_type).expect("Unknown intent type");
+        let agents = match self.specialization_index.get(&intent_type) {
+            Some(agents) => agents,
+            None => return Vec::new(),
+        };
+        let value = max_value.map_or(0, |v| v.0);
+
+        let mut candidates: Vec<AgentRecommendationView> = agents
+            .iter()
+            .filter_map(|agent_id| {
+                let agent_rep = self.agent_reputations.get(&agent_id)?;
+                if !self.internal_agent_status(&agent_id).in_good_standing() {
+                    return None;
+                }
+                self.internal_check_intent_type_standing(&agent_id, &agent_rep, &intent_type, &metadata).ok()?;
+
+                let limits = self.internal_capability_limits(&agent_id, &agent_rep, Some(&intent_type));
+                if value > limits.max_transaction_value {
+                    return None;
+                }
+                // Quotas are debited at the agent's overall trust level
+                if !self.internal_has_quota_headroom(&agent_id, self.get_trust_level(agent_rep.score), "transaction", value) {
+                    return None;
+                }
+
+                let specialization_score_bps = self.internal_specialization_score_bps(&agent_id, &intent_type);
+                Some(AgentRecommendationView {
+                    audited: self.is_audited(&agent_id),
+                    agent_id,
+                    score: agent_rep.score,
+                    specialization_score: to_points(specialization_score_bps),
+                    specialization_score_bps,
+                    trust_level: self.get_trust_level(to_points(specialization_score_bps)),
+                    max_transaction_value: U128(limits.max_transaction_value),
+                })
+            })
+            .collect();
+
+        candidates.sort_by(|a, b| {
+            b.specialization_score_bps
+                .cmp(&a.specialization_score_bps)
+                .then(b.score.cmp(&a.score))
+                .then(a.agent_id.cmp(&b.agent_id))
+        });
+        candidates.truncate(std::cmp::min(limit.unwrap_or(MAX_RECOMMENDATIONS), MAX_RECOMMENDATIONS) as usize);
+        candidates
+    }
+}
+
+// View struct for an agent recommended for an intent
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentRecommendationView {
+    agent_id: AccountId,
+    score: u32,
+    // Score and trust level in the intent type
+    specialization_score: u32,
+    specialization_score_bps: u32,
+    trust_level: TrustLevel,
+    // Largest transaction the agent can take on in the intent type
+    max_transaction_value: U128,
+    audited: bool,
+}
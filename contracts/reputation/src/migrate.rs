@@ -0,0 +1,614 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Storage layout versions of the contract state
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum StateVersion {
+    // Original layout without intents, versioning or per-feedback intent references
+    V1,
+    // Versioned layout with stored intents, escrow and runtime configuration
+    V2,
+    // Agent feedback in per-agent storage, agreements, governance, staking, cross-chain and
+    // the other registries added since V2
+    V3,
+}
+
+// State version written by this build of the contract. A release that changes the layout of
+// the state adds a variant, a shim for the layout it replaces and a migration from it.
+pub(crate) const CURRENT_STATE_VERSION: StateVersion = StateVersion::V3;
+
+// Maximum number of records migrate_records re-encodes per call
+const MAX_MIGRATION_BATCH: u64 = 100;
+
+// Progress of a migration whose records are re-encoded over several calls
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PendingMigration {
+    // Layout the records are read from
+    from_version: StateVersion,
+    // Agent records re-encoded so far, by position in the agent map
+    agents_migrated: u64,
+    // Agent records in the old layout: the map's length when the migration started. Records
+    // added after it are written in the current layout and need no migration.
+    agents_total: u64,
+    // Intents re-encoded so far, by position in the intent map
+    intents_migrated: u64,
+    // Intents in the old layout, as for agents_total
+    intents_total: u64,
+    // Paused features to restore once every record is migrated
+    paused_features: u8,
+}
+
+// V1 contract state, used to read deployments that predate versioning
+#[derive(BorshDeserialize)]
+struct ContractStateV1 {
+    owner_id: AccountId,
+    token_contract_id: AccountId,
+    agent_reputations: UnorderedMap<AccountId, AgentReputationV1>,
+    agent_stakes: LookupMap<AccountId, Balance>,
+    min_stake_amount: Balance,
+    feedback_expiry_period: u64,
+    // Empty until configured, which AccountId cannot hold
+    near_ai_registry: String,
+    intents_processor: String,
+}
+
+// V2 contract state, from the first versioned release
+#[derive(BorshDeserialize, BorshSerialize)]
+struct ContractStateV2 {
+    // Always V2; V1 state cannot decode here since it starts with the owner ID's length
+    state_version: StateVersion,
+    owner_id: AccountId,
+    token_contract_id: AccountId,
+    agent_reputations: UnorderedMap<AccountId, AgentReputationV2>,
+    agent_stakes: LookupMap<AccountId, Balance>,
+    min_stake_amount: Balance,
+    feedback_expiry_period: u64,
+    near_ai_registry: String,
+    intents_processor: String,
+    intents: UnorderedMap<String, IntentDataV2>,
+    intent_response_period: u64,
+    reviewer_weight_config: ReviewerWeightConfig,
+    guardian_id: Option<AccountId>,
+    paused_features: u8,
+    intent_checkpoints: LookupMap<String, Vec<IntentCheckpoint>>,
+    checkpoint_stall_period: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+struct AgentReputationV1 {
+    score: u32,
+    total_interactions: u64,
+    successful_interactions: u64,
+    feedback_history: Vec<FeedbackEntryV1>,
+    last_update: u64,
+    specializations: Vec<String>,
+    category_scores: CategoryRatings,
+    violation_history: Vec<ViolationRecordV1>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+struct AgentReputationV2 {
+    score: u32,
+    total_interactions: u64,
+    successful_interactions: u64,
+    feedback_history: Vec<FeedbackEntryV2>,
+    last_update: u64,
+    specializations: Vec<String>,
+    category_scores: CategoryRatings,
+    violation_history: Vec<ViolationRecordV1>,
+    reliability_score: u32,
+    decline_stats: DeclineStats,
+    liveness: LivenessStats,
+}
+
+// Violation records kept the V1 layout through V2
+#[derive(BorshDeserialize, BorshSerialize)]
+struct ViolationRecordV1 {
+    violation_type: ViolationType,
+    reporter: AccountId,
+    description: String,
+    evidence: Option<String>,
+    timestamp: u64,
+    penalty_applied: u32,
+    tokens_slashed: Balance,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+struct FeedbackEntryV1 {
+    user_id: AccountId,
+    rating: u8,
+    category_ratings: CategoryRatings,
+    message: Option<String>,
+    timestamp: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+struct FeedbackEntryV2 {
+    user_id: AccountId,
+    intent_id: String,
+    rating: u8,
+    category_ratings: CategoryRatings,
+    message: Option<String>,
+    timestamp: u64,
+    reviewer_weight: u32,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+struct IntentDataV2 {
+    intent_id: String,
+    agent_id: AccountId,
+    client_id: AccountId,
+    intent_type: String,
+    parameters: String,
+    status: IntentStatus,
+    result: Option<String>,
+    timestamp: u64,
+    updated_at: u64,
+    response_deadline: u64,
+    accepted_at: Option<u64>,
+    feedback_submitted: bool,
+    escrow_amount: Balance,
+    escrow_released: Balance,
+}
+
+impl AgentReputation {
+    fn from_v1(agent_id: &AccountId, old: AgentReputationV1) -> Self {
+        let mut agent_rep = AgentReputation::new(agent_id, old.specializations);
+        agent_rep.set_score(old.score);
+        agent_rep.total_interactions = old.total_interactions;
+        agent_rep.successful_interactions = old.successful_interactions;
+        agent_rep.last_update = old.last_update;
+        agent_rep.category_scores = old.category_scores;
+        agent_rep.violation_history = old
+            .violation_history
+            .into_iter()
+            .map(ViolationRecord::from)
+            .collect();
+        // Feedback moves into the agent's own storage vector, building the aggregates as it goes
+        for f in old.feedback_history {
+            agent_rep.push_feedback(&FeedbackEntry {
+                user_id: f.user_id,
+                // Legacy feedback predates intent tracking
+                intent_id: String::new(),
+                rating: f.rating,
+                category_ratings: f.category_ratings,
+                message: f.message,
+                timestamp: f.timestamp,
+                reviewer_weight: 10_000,
+                pseudonym: None,
+                dispute_status: None,
+                quarantined: false,
+                revisions: Vec::new(),
+                revoked_at: None,
+                response: None,
+            });
+        }
+        agent_rep
+    }
+
+    fn from_v2(agent_id: &AccountId, old: AgentReputationV2) -> Self {
+        let mut agent_rep = AgentReputation::new(agent_id, old.specializations);
+        agent_rep.set_score(old.score);
+        agent_rep.total_interactions = old.total_interactions;
+        agent_rep.successful_interactions = old.successful_interactions;
+        agent_rep.last_update = old.last_update;
+        agent_rep.category_scores = old.category_scores;
+        agent_rep.violation_history = old
+            .violation_history
+            .into_iter()
+            .map(ViolationRecord::from)
+            .collect();
+        agent_rep.reliability_score = old.reliability_score;
+        agent_rep.decline_stats = old.decline_stats;
+        agent_rep.liveness = old.liveness;
+        for f in old.feedback_history {
+            agent_rep.push_feedback(&FeedbackEntry {
+                user_id: f.user_id,
+                intent_id: f.intent_id,
+                rating: f.rating,
+                category_ratings: f.category_ratings,
+                message: f.message,
+                timestamp: f.timestamp,
+                reviewer_weight: f.reviewer_weight,
+                pseudonym: None,
+                dispute_status: None,
+                quarantined: false,
+                revisions: Vec::new(),
+                revoked_at: None,
+                response: None,
+            });
+        }
+        agent_rep
+    }
+}
+
+impl IntentData {
+    // V2 intents had no deadline, so open ones get a full deadline period from the migration
+    fn from_v2(old: IntentDataV2, deadline: u64) -> Self {
+        IntentData {
+            intent_id: old.intent_id,
+            agent_id: old.agent_id,
+            client_id: old.client_id,
+            intent_type: old.intent_type,
+            parameters: old.parameters,
+            status: old.status,
+            result: old.result,
+            timestamp: old.timestamp,
+            updated_at: old.updated_at,
+            response_deadline: old.response_deadline,
+            deadline,
+            accepted_at: old.accepted_at,
+            feedback_submitted: old.feedback_submitted,
+            escrow_amount: old.escrow_amount,
+            escrow_asset: EscrowAsset::Near,
+            escrow_released: old.escrow_released,
+            escrow_refunded: 0,
+            disputed_at: None,
+            dispute_reason: None,
+            // V2 applied reputation effects on reaching a final status but never settled the escrow
+            finalized: old.status.is_terminal(),
+            deadline_extension: 0,
+            force_majeure_windows: Vec::new(),
+            agreement_id: None,
+            failure_category: None,
+            referrer_id: None,
+            nonce: 0,
+        }
+    }
+}
+
+impl From<ViolationRecordV1> for ViolationRecord {
+    fn from(old: ViolationRecordV1) -> Self {
+        ViolationRecord {
+            violation_type: old.violation_type,
+            reporter: old.reporter,
+            description: old.description,
+            // Inline evidence is reduced to its hash so the blob leaves contract storage.
+            // Whoever holds the original text can still prove it against the hash.
+            evidence: old
+                .evidence
+                .map(|text| Evidence {
+                    hash: env::sha256(text.as_bytes()).into(),
+                    uri: String::new(),
+                    media_type: "text/plain".to_string(),
+                })
+                .into_iter()
+                .collect(),
+            timestamp: old.timestamp,
+            penalty_applied: old.penalty_applied,
+            tokens_slashed: old.tokens_slashed,
+            appeal: None,
+            penalty_forgiven_bps: 0,
+        }
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Upgrade V1 or V2 state after deploying new code. This converts the top-level state and
+    // pauses every feature; the agent records and intents are then re-encoded in batches with
+    // migrate_records, which restores the paused features once the last batch is done.
+    // State already in the current layout is left as it is.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let state = env::storage_read(b"STATE").expect("Failed to read contract state");
+        if let Ok(contract) = Self::try_from_slice(&state) {
+            env::log_str("Contract state is already at the current version");
+            return contract;
+        }
+        let (mut contract, from_version) = match ContractStateV2::try_from_slice(&state) {
+            Ok(old) => (Self::from_state_v2(old), StateVersion::V2),
+            Err(_) => {
+                let old = ContractStateV1::try_from_slice(&state).expect("Contract state is neither V1 nor V2");
+                (Self::from_state_v1(old), StateVersion::V1)
+            },
+        };
+
+        contract.pending_migration = Some(PendingMigration {
+            from_version,
+            agents_migrated: 0,
+            agents_total: contract.agent_reputations.len(),
+            intents_migrated: 0,
+            intents_total: contract.intents.len(),
+            paused_features: contract.paused_features,
+        });
+        contract.paused_features = PausableFeature::all_flags();
+
+        emit_event(
+            "state_migration_started",
+            json!({
+                "from_version": from_version,
+                "to_version": CURRENT_STATE_VERSION,
+                "agents": contract.agent_reputations.len(),
+                "intents": contract.intents.len(),
+            }),
+        );
+
+        contract
+    }
+
+    // Re-encode up to `limit` records of a migration in progress, agents first and then
+    // intents. Only records that existed when the migration started are re-encoded, so agents
+    // and intents added meanwhile do not hold it up. Returns whether the migration is complete.
+    #[private]
+    pub fn migrate_records(&mut self, limit: u64) -> bool {
+        let mut pending = self.pending_migration.take().expect("No migration in progress");
+        let mut remaining = std::cmp::min(limit, MAX_MIGRATION_BATCH);
+
+        while remaining > 0 && pending.agents_migrated < pending.agents_total {
+            self.internal_migrate_agent(pending.from_version, pending.agents_migrated);
+            pending.agents_migrated += 1;
+            remaining -= 1;
+        }
+        // V1 had no intents
+        while remaining > 0 && pending.intents_migrated < pending.intents_total {
+            self.internal_migrate_intent(pending.intents_migrated);
+            pending.intents_migrated += 1;
+            remaining -= 1;
+        }
+
+        let complete = pending.agents_migrated == pending.agents_total && pending.intents_migrated == pending.intents_total;
+        if !complete {
+            self.pending_migration = Some(pending);
+            return false;
+        }
+
+        self.paused_features = pending.paused_features;
+        emit_event(
+            "state_migrated",
+            json!({
+                "from_version": pending.from_version,
+                "to_version": CURRENT_STATE_VERSION,
+                "agents_migrated": pending.agents_migrated,
+                "intents_migrated": pending.intents_migrated,
+            }),
+        );
+        true
+    }
+
+    // Get the contract code and state versions
+    pub fn contract_version(&self) -> ContractVersionView {
+        ContractVersionView {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            state_version: self.state_version,
+            migrating_from: self.pending_migration.as_ref().map(|pending| pending.from_version),
+        }
+    }
+}
+
+impl AgentReputationContract {
+    fn from_state_v1(old: ContractStateV1) -> Self {
+        let mut contract = Self::new(old.owner_id, old.token_contract_id, old.min_stake_amount);
+        contract.feedback_expiry_period = old.feedback_expiry_period;
+        contract.near_ai_registry = old.near_ai_registry.parse().ok();
+        contract.intents_processor = old.intents_processor.parse().ok();
+        contract.agent_stakes = old.agent_stakes;
+
+        // Per-agent stakes are not enumerable, so the total staked aggregate starts from zero;
+        // seed it with reconcile_total_staked from an off-chain sum after migrating
+
+        // Reuse the existing map (same prefix and length) under the new value type; the records
+        // themselves are re-encoded by migrate_records
+        contract.agent_reputations = retype(&old.agent_reputations);
+        contract
+    }
+
+    fn from_state_v2(old: ContractStateV2) -> Self {
+        debug_assert!(old.state_version == StateVersion::V2);
+        let mut contract = Self::new(old.owner_id, old.token_contract_id, old.min_stake_amount);
+        contract.feedback_expiry_period = old.feedback_expiry_period;
+        contract.near_ai_registry = old.near_ai_registry.parse().ok();
+        contract.intents_processor = old.intents_processor.parse().ok();
+        contract.agent_stakes = old.agent_stakes;
+        contract.agent_reputations = retype(&old.agent_reputations);
+        contract.intents = retype(&old.intents);
+        contract.intent_response_period = old.intent_response_period;
+        contract.reviewer_weight_config = old.reviewer_weight_config;
+        contract.guardian_id = old.guardian_id;
+        contract.paused_features = old.paused_features;
+        contract.intent_checkpoints = old.intent_checkpoints;
+        contract.checkpoint_stall_period = old.checkpoint_stall_period;
+        contract
+    }
+
+    // Re-encode the agent record at `index` of the agent map and index it
+    fn internal_migrate_agent(&mut self, from_version: StateVersion, index: u64) {
+        let agent_id = self.agent_reputations.keys_as_vector().get(index).expect("Agent record missing");
+        let raw = self.agent_reputations.values_as_vector().get_raw(index).expect("Agent record missing");
+        let agent_rep = match from_version {
+            StateVersion::V1 => AgentReputation::from_v1(
+                &agent_id,
+                AgentReputationV1::try_from_slice(&raw).expect("Failed to decode V1 agent record"),
+            ),
+            _ => AgentReputation::from_v2(
+                &agent_id,
+                AgentReputationV2::try_from_slice(&raw).expect("Failed to decode V2 agent record"),
+            ),
+        };
+
+        // Raw insert: the typed insert would try to decode the replaced bytes
+        self.agent_reputations.insert_raw(
+            &agent_id.try_to_vec().expect("Failed to serialize agent ID"),
+            &agent_rep.try_to_vec().expect("Failed to serialize agent record"),
+        );
+        self.internal_update_ranking_index(&agent_id, None, &agent_rep);
+        self.internal_update_specialization_index(&agent_id, None, &agent_rep);
+        self.internal_update_trust_histogram(None, &agent_rep);
+        self.internal_update_recency_index(&agent_id);
+    }
+
+    // Re-encode the V2 intent at `index` of the intent map, settling the escrow V2 left behind
+    // on intents that already reached a final status and indexing the ones still open
+    fn internal_migrate_intent(&mut self, index: u64) {
+        let intent_id = self.intents.keys_as_vector().get(index).expect("Intent missing");
+        let raw = self.intents.values_as_vector().get_raw(index).expect("Intent missing");
+        let old = IntentDataV2::try_from_slice(&raw).expect("Failed to decode V2 intent");
+        let mut intent = IntentData::from_v2(old, env::block_timestamp() + self.intent_deadline_period);
+        if intent.finalized {
+            let status = intent.status;
+            self.internal_settle_escrow(&mut intent, status);
+        }
+
+        self.intents.insert_raw(
+            &intent_id.try_to_vec().expect("Failed to serialize intent ID"),
+            &intent.try_to_vec().expect("Failed to serialize intent"),
+        );
+        self.internal_update_open_intent_index(&intent, None);
+    }
+}
+
+// Read a map under a new value type; its prefix and length carry over unchanged
+fn retype<K, V, W>(map: &UnorderedMap<K, V>) -> UnorderedMap<K, W>
+where
+    K: BorshSerialize + BorshDeserialize,
+    V: BorshSerialize + BorshDeserialize,
+    W: BorshSerialize + BorshDeserialize,
+{
+    UnorderedMap::try_from_slice(&map.try_to_vec().expect("Failed to serialize map")).expect("Failed to retype map")
+}
+
+// View struct for contract versioning
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractVersionView {
+    version: String,
+    state_version: StateVersion,
+    // Layout the records are still being migrated from, if a migration is in progress
+    migrating_from: Option<StateVersion>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    // Write V2 state holding one agent with one feedback entry and one in-progress intent
+    fn write_v2_state() {
+        set_context(CONTRACT, START, 0);
+        near_sdk::mock::with_mocked_blockchain(|blockchain| drop(blockchain.take_storage()));
+
+        let mut agent_reputations = UnorderedMap::new(b"a");
+        agent_reputations.insert(
+            &account("agent.test"),
+            &AgentReputationV2 {
+                score: 72,
+                total_interactions: 3,
+                successful_interactions: 2,
+                feedback_history: vec![FeedbackEntryV2 {
+                    user_id: account("client.test"),
+                    intent_id: "intent-1".to_string(),
+                    rating: 4,
+                    category_ratings: CategoryRatings::default(),
+                    message: None,
+                    timestamp: 500,
+                    reviewer_weight: 10_000,
+                }],
+                last_update: 500,
+                specializations: vec![INTENT_TYPE.to_string()],
+                category_scores: CategoryRatings::default(),
+                violation_history: Vec::new(),
+                reliability_score: 90,
+                decline_stats: DeclineStats::default(),
+                liveness: LivenessStats::default(),
+            },
+        );
+        let mut intents = UnorderedMap::new(b"i");
+        intents.insert(
+            &"intent-1".to_string(),
+            &IntentDataV2 {
+                intent_id: "intent-1".to_string(),
+                agent_id: account("agent.test"),
+                client_id: account("client.test"),
+                intent_type: INTENT_TYPE.to_string(),
+                parameters: String::new(),
+                status: IntentStatus::InProgress,
+                result: None,
+                timestamp: 100,
+                updated_at: 200,
+                response_deadline: 300,
+                accepted_at: Some(200),
+                feedback_submitted: false,
+                escrow_amount: 0,
+                escrow_released: 0,
+            },
+        );
+        env::state_write(&ContractStateV2 {
+            state_version: StateVersion::V2,
+            owner_id: account(OWNER),
+            token_contract_id: account(TOKEN),
+            agent_reputations,
+            agent_stakes: LookupMap::new(b"s"),
+            min_stake_amount: MIN_STAKE,
+            feedback_expiry_period: 1_000_000,
+            near_ai_registry: String::new(),
+            intents_processor: "processor.test".to_string(),
+            intents,
+            intent_response_period: 2_000,
+            reviewer_weight_config: ReviewerWeightConfig::default(),
+            guardian_id: None,
+            paused_features: 1,
+            intent_checkpoints: LookupMap::new(b"k"),
+            checkpoint_stall_period: 3_000,
+        });
+    }
+
+    #[test]
+    fn migrates_v2_state_in_batches() {
+        write_v2_state();
+
+        let mut contract = AgentReputationContract::migrate();
+        assert_eq!(contract.contract_version().migrating_from, Some(StateVersion::V2));
+        assert_eq!(contract.paused_features, PausableFeature::all_flags());
+        assert!(!contract.migrate_records(1));
+        assert!(contract.migrate_records(10));
+        assert_eq!(contract.contract_version().state_version, StateVersion::V3);
+        assert_eq!(contract.contract_version().migrating_from, None);
+        assert_eq!(contract.paused_features, 1);
+        assert_eq!(contract.intents_processor, Some(account("processor.test")));
+        assert_eq!(contract.near_ai_registry, None);
+
+        let agent_rep = contract.agent_reputations.get(&account("agent.test")).unwrap();
+        assert_eq!(agent_rep.score, 72);
+        assert_eq!(agent_rep.reliability_score, 90);
+        assert_eq!(agent_rep.feedback_count, 1);
+        let intent = contract.intents.get(&"intent-1".to_string()).unwrap();
+        assert_eq!(intent.status, IntentStatus::InProgress);
+        assert!(!intent.finalized);
+        assert_eq!(intent.deadline, START + contract.intent_deadline_period);
+        assert!(contract.open_intent_deadlines.contains_key(&(intent.deadline, "intent-1".to_string())));
+    }
+
+    #[test]
+    fn records_written_during_migration_are_left_alone() {
+        write_v2_state();
+        let mut contract = AgentReputationContract::migrate();
+
+        // An agent registers and receives an intent before any old record is migrated
+        register_intent_type(&mut contract);
+        register_agent(&mut contract, "agent-2.test");
+        deposit_storage(&mut contract, "client-2.test");
+        start_intent(&mut contract, "intent-2", "agent-2.test", "client-2.test", 0);
+
+        assert!(contract.migrate_records(100));
+        assert_eq!(contract.paused_features, 1);
+        assert_eq!(contract.agent_reputations.get(&account("agent.test")).unwrap().score, 72);
+        assert!(contract.agent_reputations.get(&account("agent-2.test")).is_some());
+        assert_eq!(contract.intents.get(&"intent-1".to_string()).unwrap().agent_id, account("agent.test"));
+        assert_eq!(contract.intents.get(&"intent-2".to_string()).unwrap().status, IntentStatus::InProgress);
+    }
+
+    #[test]
+    fn migrating_current_state_is_a_no_op() {
+        write_v2_state();
+        let mut contract = AgentReputationContract::migrate();
+        assert!(contract.migrate_records(10));
+        env::state_write(&contract);
+
+        let contract = AgentReputationContract::migrate();
+        assert_eq!(contract.contract_version().migrating_from, None);
+        assert_eq!(contract.paused_features, 1);
+        assert_eq!(contract.agent_reputations.get(&account("agent.test")).unwrap().score, 72);
+    }
+}
@@ -0,0 +1,278 @@
+use crate::*;
+use crate::events::emit_event;
+use crate::fixed_point::div_round;
+use near_sdk::json_types::Base64VecU8;
+
+// Maximum number of whitelisted performance oracles
+const MAX_PERFORMANCE_ORACLES: usize = 20;
+
+// Most samples kept per agent; the oldest is dropped first
+const MAX_PERFORMANCE_SAMPLES: usize = 30;
+
+// Shortest and longest rolling window for performance samples
+pub(crate) const MIN_PERFORMANCE_WINDOW: u64 = 60 * 60 * 1_000_000_000; // 1 hour
+pub(crate) const MAX_PERFORMANCE_WINDOW: u64 = 90 * 24 * 60 * 60 * 1_000_000_000; // 90 days
+
+// Greatest clock skew tolerated on a report's observation time (5 minutes)
+const MAX_OBSERVATION_SKEW: u64 = 5 * 60 * 1_000_000_000;
+
+// Objective metrics an oracle measured for an agent
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PerformanceMetrics {
+    // Share of probes the agent answered (basis points)
+    pub uptime_bps: u32,
+    // Response latency percentiles (milliseconds)
+    pub latency_p50_ms: u32,
+    pub latency_p95_ms: u32,
+    pub latency_p99_ms: u32,
+    // Tasks completed over the oracle's measurement period
+    pub tasks_completed: u64,
+    // Result on the oracle's benchmark suite (basis points)
+    pub benchmark_score_bps: u32,
+}
+
+// A metrics report as signed by the oracle. The signature covers the Borsh encoding of
+// (this contract's account ID, report), so it cannot be replayed against another deployment.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PerformanceReport {
+    pub oracle_id: AccountId,
+    pub agent_id: AccountId,
+    pub metrics: PerformanceMetrics,
+    // When the metrics were measured (nanoseconds)
+    pub observed_at: u64,
+}
+
+// A report accepted into an agent's rolling window
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PerformanceSample {
+    oracle_id: AccountId,
+    metrics: PerformanceMetrics,
+    observed_at: u64,
+}
+
+// How oracle metrics are scored and blended into reputation
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PerformanceConfig {
+    // Weight of the performance score against the feedback score (basis points)
+    pub weight_bps: u32,
+    // Samples older than this are ignored (nanoseconds)
+    pub window: u64,
+    // p95 latency earning a full latency score, and the latency earning none (milliseconds)
+    pub latency_target_ms: u32,
+    pub latency_max_ms: u32,
+    // Tasks per sample earning a full throughput score
+    pub throughput_target: u64,
+}
+
+impl Default for PerformanceConfig {
+    fn default() -> Self {
+        Self {
+            weight_bps: 1_500,
+            window: 7 * 24 * 60 * 60 * 1_000_000_000, // 7 days
+            latency_target_ms: 500,
+            latency_max_ms: 10_000,
+            throughput_target: 100,
+        }
+    }
+}
+
+impl PerformanceConfig {
+    // Score (basis points) of one sample: the mean of its uptime, latency, throughput and
+    // benchmark scores
+    fn sample_score_bps(&self, metrics: &PerformanceMetrics) -> u32 {
+        let uptime = std::cmp::min(metrics.uptime_bps, SCORE_SCALE_BPS);
+        let latency = if metrics.latency_p95_ms <= self.latency_target_ms {
+            SCORE_SCALE_BPS
+        } else {
+            SCORE_SCALE_BPS - linear_share(
+                SCORE_SCALE_BPS,
+                (metrics.latency_p95_ms - self.latency_target_ms) as u128,
+                (self.latency_max_ms - self.latency_target_ms) as u128,
+            )
+        };
+        let throughput = linear_share(SCORE_SCALE_BPS, metrics.tasks_completed as u128, self.throughput_target as u128);
+        let benchmark = std::cmp::min(metrics.benchmark_score_bps, SCORE_SCALE_BPS);
+        div_round(uptime as u128 + latency as u128 + throughput as u128 + benchmark as u128, 4) as u32
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Whitelist an oracle account and the ed25519 key it signs reports with (owner or
+    // governance); re-adding an oracle rotates its key
+    pub fn add_performance_oracle(&mut self, oracle_id: AccountId, public_key: Base64VecU8) {
+        self.assert_owner_or_governance();
+        assert_eq!(public_key.0.len(), 32, "Invalid oracle public key length");
+        assert!(
+            self.performance_oracles.get(&oracle_id).is_some() || self.performance_oracles.len() < MAX_PERFORMANCE_ORACLES as u64,
+            "Too many performance oracles"
+        );
+
+        let old_value = self.performance_oracles.insert(&oracle_id, &public_key);
+
+        self.record_config_change(&format!("performance_oracle.{}", oracle_id), json!(old_value), json!(public_key));
+    }
+
+    // Remove an oracle from the whitelist (owner or governance); its samples stay until they
+    // leave the window
+    pub fn remove_performance_oracle(&mut self, oracle_id: AccountId) {
+        self.assert_owner_or_governance();
+        let old_value = self.performance_oracles.remove(&oracle_id).expect("Not a performance oracle");
+
+        self.record_config_change(&format!("performance_oracle.{}", oracle_id), json!(old_value), json!(null));
+    }
+
+    // Get every whitelisted oracle with its signing key
+    pub fn get_performance_oracles(&self) -> Vec<(AccountId, Base64VecU8)> {
+        self.performance_oracles.to_vec()
+    }
+
+    // Push a signed metrics report (the reporting oracle only). The sample joins the agent's
+    // rolling window and the agent is rescored; the oracle pays for the sample's storage.
+    pub fn submit_performance_metrics(&mut self, report: PerformanceReport, signature: Base64VecU8) {
+        let oracle_id = env::predecessor_account_id();
+        assert_eq!(oracle_id, report.oracle_id, "Reports can only be submitted by their oracle");
+        let public_key = self.performance_oracles.get(&oracle_id).expect("Not a performance oracle");
+
+        let payload = (env::current_account_id(), report.clone()).try_to_vec().expect("Failed to serialize report");
+        let verified = match (<&[u8; 64]>::try_from(signature.0.as_slice()), <&[u8; 32]>::try_from(public_key.0.as_slice())) {
+            (Ok(signature), Ok(public_key)) => ed25519_verify(signature, &payload, public_key),
+            _ => false,
+        };
+        assert!(verified, "Invalid report signature");
+
+        let now = env::block_timestamp();
+        let window = self.performance_config.window;
+        assert!(report.observed_at <= now + MAX_OBSERVATION_SKEW, "Report observed in the future");
+        assert!(report.observed_at + window > now, "Report is older than the performance window");
+        assert!(report.metrics.uptime_bps <= 10_000, "Uptime cannot exceed 10000 basis points");
+        assert!(report.metrics.benchmark_score_bps <= 10_000, "Benchmark score cannot exceed 10000 basis points");
+        assert!(
+            report.metrics.latency_p50_ms <= report.metrics.latency_p95_ms
+                && report.metrics.latency_p95_ms <= report.metrics.latency_p99_ms,
+            "Latency percentiles must be non-decreasing"
+        );
+
+        let initial_storage = env::storage_usage();
+        let agent_id = report.agent_id.clone();
+        let mut agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+
+        // Each oracle's reports for an agent must move forward in time
+        let mut samples = self.performance_samples.get(&agent_id).unwrap_or_default();
+        assert!(
+            samples
+                .iter()
+                .filter(|sample| sample.oracle_id == oracle_id)
+                .all(|sample| sample.observed_at < report.observed_at),
+            "Report is not newer than the oracle's last report"
+        );
+        samples.retain(|sample| sample.observed_at + window > now);
+        if samples.len() >= MAX_PERFORMANCE_SAMPLES {
+            samples.remove(0);
+        }
+        samples.push(PerformanceSample {
+            oracle_id: oracle_id.clone(),
+            metrics: report.metrics,
+            observed_at: report.observed_at,
+        });
+        self.performance_samples.insert(&agent_id, &samples);
+
+        self.recalculate_reputation_with_categories(&agent_id, &mut agent_rep);
+        agent_rep.last_update = now;
+        self.internal_save_agent(&agent_id, &agent_rep);
+        self.internal_update_shadow_score(&agent_id, &mut agent_rep);
+        self.internal_charge_storage(&oracle_id, initial_storage);
+
+        emit_event(
+            "performance_metrics_submitted",
+            json!({
+                "oracle_id": oracle_id,
+                "agent_id": agent_id,
+                "observed_at": report.observed_at,
+            }),
+        );
+    }
+
+    // Get an agent's performance samples within the window, with their averages and the score
+    // blended into its reputation
+    pub fn get_performance_metrics(&self, agent_id: AccountId) -> Option<PerformanceMetricsView> {
+        self.agent_reputations.get(&agent_id)?;
+        let samples = self.internal_live_performance_samples(&agent_id);
+        let count = samples.len() as u128;
+        let average = |metric: fn(&PerformanceMetrics) -> u128| -> u64 {
+            div_round(samples.iter().map(|sample| metric(&sample.metrics)).sum(), count) as u64
+        };
+
+        Some(PerformanceMetricsView {
+            agent_id: agent_id.clone(),
+            average_uptime_bps: average(|metrics| metrics.uptime_bps as u128) as u32,
+            average_latency_p50_ms: average(|metrics| metrics.latency_p50_ms as u128) as u32,
+            average_latency_p95_ms: average(|metrics| metrics.latency_p95_ms as u128) as u32,
+            average_latency_p99_ms: average(|metrics| metrics.latency_p99_ms as u128) as u32,
+            average_tasks_completed: average(|metrics| metrics.tasks_completed as u128),
+            average_benchmark_score_bps: average(|metrics| metrics.benchmark_score_bps as u128) as u32,
+            performance_score_bps: self.internal_performance_score_bps(&agent_id),
+            weight_bps: self.performance_config.weight_bps,
+            samples,
+        })
+    }
+
+    // Samples for the agent observed within the window
+    fn internal_live_performance_samples(&self, agent_id: &AccountId) -> Vec<PerformanceSample> {
+        let now = env::block_timestamp();
+        let window = self.performance_config.window;
+        self.performance_samples
+            .get(agent_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|sample| sample.observed_at + window > now)
+            .collect()
+    }
+
+    // Mean score (basis points) of the agent's samples within the window, or None without any
+    pub(crate) fn internal_performance_score_bps(&self, agent_id: &AccountId) -> Option<u32> {
+        let samples = self.internal_live_performance_samples(agent_id);
+        if samples.is_empty() {
+            return None;
+        }
+        let config = &self.performance_config;
+        let total: u128 = samples.iter().map(|sample| config.sample_score_bps(&sample.metrics) as u128).sum();
+        Some(div_round(total, samples.len() as u128) as u32)
+    }
+}
+
+// Verify an ed25519 signature over a message. near-sdk 4.1 does not wrap the host function,
+// so this calls it directly.
+pub(crate) fn ed25519_verify(signature: &[u8; 64], message: &[u8], public_key: &[u8; 32]) -> bool {
+    unsafe {
+        near_sys::ed25519_verify(
+            signature.len() as _,
+            signature.as_ptr() as _,
+            message.len() as _,
+            message.as_ptr() as _,
+            public_key.len() as _,
+            public_key.as_ptr() as _,
+        ) == 1
+    }
+}
+
+// View struct for an agent's oracle-reported performance
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PerformanceMetricsView {
+    agent_id: AccountId,
+    average_uptime_bps: u32,
+    average_latency_p50_ms: u32,
+    average_latency_p95_ms: u32,
+    average_latency_p99_ms: u32,
+    average_tasks_completed: u64,
+    average_benchmark_score_bps: u32,
+    // Score blended into reputation; None without samples in the window
+    performance_score_bps: Option<u32>,
+    weight_bps: u32,
+    samples: Vec<PerformanceSample>,
+}
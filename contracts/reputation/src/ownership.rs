@@ -0,0 +1,157 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Time the owner has to cancel a recovery claim before the recovery account can take over (7 days)
+const RECOVERY_TIMELOCK: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
+// Pending ownership handover and the owner's recovery arrangement
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct OwnershipState {
+    // Account proposed as the next owner, which must accept to take over
+    pending_owner: Option<AccountId>,
+    // Backup account that can reclaim ownership if the owner key is lost
+    recovery_account: Option<AccountId>,
+    // When the recovery account started its claim
+    recovery_started_at: Option<u64>,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Current contract owner
+    pub fn owner(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+
+    // Get the pending ownership transfer and recovery arrangement
+    pub fn get_ownership(&self) -> OwnershipView {
+        OwnershipView {
+            owner_id: self.owner_id.clone(),
+            pending_owner: self.ownership.pending_owner.clone(),
+            recovery_account: self.ownership.recovery_account.clone(),
+            recovery_unlocks_at: self.ownership.recovery_started_at.map(|started| started + RECOVERY_TIMELOCK),
+        }
+    }
+
+    // Propose a new owner, or withdraw the proposal with None (owner only). Ownership only
+    // moves once the proposed account calls accept_ownership, so a typo cannot lock the owner out.
+    pub fn propose_owner(&mut self, new_owner: Option<AccountId>) {
+        self.assert_owner();
+
+        self.ownership.pending_owner = new_owner.clone();
+        self.config_nonce += 1;
+
+        emit_event(
+            "ownership_proposed",
+            json!({
+                "owner_id": self.owner_id,
+                "pending_owner": new_owner,
+            }),
+        );
+    }
+
+    // Take over ownership (proposed owner only)
+    pub fn accept_ownership(&mut self) {
+        let caller = env::predecessor_account_id();
+        assert_eq!(
+            self.ownership.pending_owner.as_ref(),
+            Some(&caller),
+            "Only the proposed owner can accept ownership"
+        );
+
+        self.internal_transfer_ownership(caller, "proposal");
+    }
+
+    // Set or clear the recovery account (owner only). Clearing it also cancels a running claim.
+    pub fn set_recovery_account(&mut self, recovery_account: Option<AccountId>) {
+        self.assert_owner();
+
+        self.ownership.recovery_account = recovery_account.clone();
+        self.ownership.recovery_started_at = None;
+        self.config_nonce += 1;
+
+        emit_event("recovery_account_updated", json!({ "recovery_account": recovery_account }));
+    }
+
+    // Start reclaiming ownership (recovery account only). The claim can be completed once the
+    // timelock has passed unless the owner cancels it first.
+    pub fn initiate_recovery(&mut self) {
+        self.assert_recovery_account();
+        assert!(self.ownership.recovery_started_at.is_none(), "Recovery already initiated");
+
+        let now = env::block_timestamp();
+        self.ownership.recovery_started_at = Some(now);
+
+        emit_event(
+            "ownership_recovery_initiated",
+            json!({
+                "recovery_account": self.ownership.recovery_account,
+                "unlocks_at": now + RECOVERY_TIMELOCK,
+            }),
+        );
+    }
+
+    // Cancel a recovery claim (owner only); proves the owner key is still in use
+    pub fn cancel_recovery(&mut self) {
+        self.assert_owner();
+        assert!(self.ownership.recovery_started_at.is_some(), "No recovery in progress");
+
+        self.ownership.recovery_started_at = None;
+
+        emit_event("ownership_recovery_cancelled", json!({ "owner_id": self.owner_id }));
+    }
+
+    // Take over ownership after an uncancelled claim's timelock (recovery account only)
+    pub fn complete_recovery(&mut self) {
+        self.assert_recovery_account();
+        let started_at = self.ownership.recovery_started_at.expect("Recovery not initiated");
+        assert!(
+            env::block_timestamp() >= started_at + RECOVERY_TIMELOCK,
+            "Recovery timelock has not passed"
+        );
+
+        self.internal_transfer_ownership(env::predecessor_account_id(), "recovery");
+    }
+
+    // Panic unless the caller is the owner
+    pub(crate) fn assert_owner(&self) {
+        assert_eq!(env::predecessor_account_id(), self.owner_id, "Only owner can perform this action");
+    }
+
+    fn assert_recovery_account(&self) {
+        assert_eq!(
+            self.ownership.recovery_account.as_ref(),
+            Some(&env::predecessor_account_id()),
+            "Only the recovery account can perform this action"
+        );
+    }
+
+    // Hand ownership over. The recovery account was chosen by the previous owner, so it is
+    // cleared for the new owner to set their own.
+    fn internal_transfer_ownership(&mut self, new_owner: AccountId, method: &str) {
+        let previous_owner = std::mem::replace(&mut self.owner_id, new_owner.clone());
+        self.ownership.pending_owner = None;
+        self.ownership.recovery_account = None;
+        self.ownership.recovery_started_at = None;
+        self.config_nonce += 1;
+
+        emit_event(
+            "ownership_transferred",
+            json!({
+                "previous_owner": previous_owner,
+                "new_owner": new_owner,
+                "method": method,
+            }),
+        );
+    }
+}
+
+// View struct for ownership state
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnershipView {
+    owner_id: AccountId,
+    pending_owner: Option<AccountId>,
+    recovery_account: Option<AccountId>,
+    // When a running recovery claim can be completed
+    recovery_unlocks_at: Option<u64>,
+}
@@ -0,0 +1,99 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Features that can be halted independently during an incident
+#[derive(near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum PausableFeature {
+    Staking,
+    Unstaking,
+    Feedback,
+    ViolationReporting,
+}
+
+impl PausableFeature {
+    const ALL: [PausableFeature; 4] = [
+        PausableFeature::Staking,
+        PausableFeature::Unstaking,
+        PausableFeature::Feedback,
+        PausableFeature::ViolationReporting,
+    ];
+
+    // Mask with every feature paused
+    pub(crate) fn all_flags() -> u8 {
+        Self::ALL.iter().fold(0, |mask, feature| mask | feature.flag())
+    }
+
+    // Bit for this feature in the `paused_features` mask
+    fn flag(&self) -> u8 {
+        match self {
+            PausableFeature::Staking => 1 << 0,
+            PausableFeature::Unstaking => 1 << 1,
+            PausableFeature::Feedback => 1 << 2,
+            PausableFeature::ViolationReporting => 1 << 3,
+        }
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Propose setting or clearing the guardian account allowed to pause features (owner only);
+    // applies after the timelock. Returns the queued action ID.
+    pub fn set_guardian(&mut self, guardian_id: Option<AccountId>) -> u64 {
+        assert_eq!(env::predecessor_account_id(), self.owner_id, "Only owner can set the guardian");
+
+        self.internal_queue_action(TimelockedAction::SetGuardian { guardian_id })
+    }
+
+    // Halt the given features (owner or guardian)
+    pub fn pause_features(&mut self, features: Vec<PausableFeature>) {
+        let caller = env::predecessor_account_id();
+        assert!(
+            caller == self.owner_id || self.guardian_id.as_ref() == Some(&caller),
+            "Unauthorized: only owner or guardian can pause features"
+        );
+
+        for feature in &features {
+            self.paused_features |= feature.flag();
+        }
+        self.config_nonce += 1;
+
+        emit_event("features_paused", json!({ "features": features, "paused_by": caller }));
+    }
+
+    // Resume the given features (owner only)
+    pub fn unpause_features(&mut self, features: Vec<PausableFeature>) {
+        let caller = env::predecessor_account_id();
+        assert_eq!(caller, self.owner_id, "Only owner can unpause features");
+
+        for feature in &features {
+            self.paused_features &= !feature.flag();
+        }
+        self.config_nonce += 1;
+
+        emit_event("features_unpaused", json!({ "features": features, "unpaused_by": caller }));
+    }
+
+    // List the currently paused features
+    pub fn get_paused_features(&self) -> Vec<PausableFeature> {
+        PausableFeature::ALL
+            .iter()
+            .filter(|feature| self.is_paused(**feature))
+            .copied()
+            .collect()
+    }
+
+    // Get the guardian account, if any
+    pub fn get_guardian(&self) -> Option<AccountId> {
+        self.guardian_id.clone()
+    }
+
+    pub(crate) fn is_paused(&self, feature: PausableFeature) -> bool {
+        self.paused_features & feature.flag() != 0
+    }
+
+    // Panic if the given feature is currently paused
+    pub(crate) fn assert_not_paused(&self, feature: PausableFeature) {
+        assert!(!self.is_paused(feature), "Feature is paused: {:?}", feature);
+    }
+}
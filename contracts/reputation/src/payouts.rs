@@ -0,0 +1,97 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Gas for the ft_transfer of an ITLX payout
+const GAS_FOR_PAYOUT_FT_TRANSFER: Gas = Gas(10_000_000_000_000);
+
+// Gas reserved for the payout callback
+const GAS_FOR_PAYOUT_CALLBACK: Gas = Gas(5_000_000_000_000);
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Callback after an escrow, bounty or delegation payout. A failed transfer leaves the funds
+    // with this contract, so they are held for the receiver to claim again instead of being lost.
+    #[private]
+    pub fn on_payout_complete(&mut self, asset: EscrowAsset, receiver_id: AccountId, amount: U128) {
+        if let PromiseResult::Successful(_) = env::promise_result(0) {
+            return;
+        }
+
+        let key = (receiver_id.clone(), asset);
+        let unpaid = self.unpaid_payouts.get(&key).unwrap_or(0);
+        self.unpaid_payouts.insert(&key, &(unpaid + amount.0));
+        if asset == EscrowAsset::Itlx {
+            self.stats.itlx_unpaid += amount.0;
+        }
+
+        emit_event(
+            "payout_failed",
+            json!({
+                "receiver_id": receiver_id,
+                "asset": asset,
+                "amount": amount,
+            }),
+        );
+    }
+
+    // Claim payouts in `asset` whose transfer to the caller failed, for example because it was
+    // not registered with the token contract at the time
+    pub fn claim_unpaid_payouts(&mut self, asset: EscrowAsset) -> Promise {
+        let receiver_id = env::predecessor_account_id();
+        let amount = self
+            .unpaid_payouts
+            .remove(&(receiver_id.clone(), asset))
+            .expect("No unpaid payouts to claim");
+        if asset == EscrowAsset::Itlx {
+            self.stats.itlx_unpaid -= amount;
+        }
+
+        emit_event(
+            "unpaid_payouts_claimed",
+            json!({
+                "receiver_id": receiver_id,
+                "asset": asset,
+                "amount": U128(amount),
+            }),
+        );
+
+        self.internal_transfer_payout(asset, &receiver_id, amount, "Unpaid payout claim")
+    }
+
+    // Get the payouts in `asset` held for an account after their transfer failed
+    pub fn get_unpaid_payouts(&self, account_id: AccountId, asset: EscrowAsset) -> U128 {
+        U128(self.unpaid_payouts.get(&(account_id, asset)).unwrap_or(0))
+    }
+
+    // Transfer a payout the contract has already accounted for, holding it for the receiver
+    // if the transfer fails
+    pub(crate) fn internal_transfer_payout(&self, asset: EscrowAsset, receiver_id: &AccountId, amount: Balance, memo: &str) -> Promise {
+        let transfer = match asset {
+            EscrowAsset::Near => Promise::new(receiver_id.clone()).transfer(amount),
+            EscrowAsset::Itlx => Promise::new(self.token_contract_id.clone()).function_call(
+                "ft_transfer".to_string(),
+                json!({
+                    "receiver_id": receiver_id,
+                    "amount": U128(amount),
+                    "memo": memo,
+                }).to_string().into_bytes(),
+                1, // 1 yoctoNEAR
+                GAS_FOR_PAYOUT_FT_TRANSFER,
+            ),
+        };
+
+        transfer.then(
+            Promise::new(env::current_account_id())
+                .function_call(
+                    "on_payout_complete".to_string(),
+                    json!({
+                        "asset": asset,
+                        "receiver_id": receiver_id,
+                        "amount": U128(amount),
+                    }).to_string().into_bytes(),
+                    0,
+                    GAS_FOR_PAYOUT_CALLBACK,
+                )
+        )
+    }
+}
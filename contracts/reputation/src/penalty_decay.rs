@@ -0,0 +1,220 @@
+use crate::*;
+use crate::events::emit_event;
+use crate::fixed_point::{from_points, mul_bps};
+
+// Shortest decay epoch (1 day)
+pub(crate) const MIN_DECAY_EPOCH_LENGTH: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+// Longest decay epoch (1 year)
+pub(crate) const MAX_DECAY_EPOCH_LENGTH: u64 = 365 * 24 * 60 * 60 * 1_000_000_000;
+
+// Upper bound on the interactions an epoch can require to count as clean
+pub(crate) const MAX_DECAY_MIN_INTERACTIONS: u32 = 1_000;
+
+// Upper bound on violations listed by get_violation_status
+const MAX_VIOLATION_STATUS_ENTRIES: usize = 50;
+
+// How violation penalties are forgiven over epochs of clean behavior
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PenaltyDecayConfig {
+    // Length of a decay epoch (nanoseconds)
+    pub epoch_length: u64,
+    // Share of each violation's reputation penalty forgiven per clean epoch (basis points);
+    // 0 disables decay
+    pub forgiven_bps_per_epoch: u32,
+    // Counted interactions an agent needs within an epoch for it to be clean
+    pub min_interactions: u32,
+}
+
+impl Default for PenaltyDecayConfig {
+    fn default() -> Self {
+        Self {
+            epoch_length: 30 * 24 * 60 * 60 * 1_000_000_000, // 30 days
+            forgiven_bps_per_epoch: 1_000,                   // 10% per clean epoch
+            min_interactions: 1,
+        }
+    }
+}
+
+// An agent's progress through the current decay epoch
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct PenaltyDecayState {
+    // Start of the epoch not yet rolled forward
+    epoch_start: u64,
+    // Counted interactions within that epoch
+    interactions: u32,
+}
+
+impl PenaltyDecayState {
+    pub(crate) fn new() -> Self {
+        Self { epoch_start: env::block_timestamp(), interactions: 0 }
+    }
+
+    // Clean epochs completed since the state was last rolled forward, and the start of the
+    // epoch now in progress. Violations and interactions roll the state forward before they are
+    // recorded, so both can only fall in the first completed epoch; any epochs after it passed
+    // without interactions, and are clean only when none are required.
+    fn completed_epochs(&self, config: &PenaltyDecayConfig, last_violation_at: Option<u64>, now: u64) -> (u64, u64) {
+        let elapsed = now.saturating_sub(self.epoch_start) / config.epoch_length;
+        if elapsed == 0 {
+            return (0, self.epoch_start);
+        }
+
+        let first_clean = self.interactions >= config.min_interactions
+            && last_violation_at.is_none_or(|timestamp| timestamp < self.epoch_start);
+        let idle_clean = if config.min_interactions == 0 { elapsed - 1 } else { 0 };
+        (first_clean as u64 + idle_clean, self.epoch_start + elapsed * config.epoch_length)
+    }
+}
+
+impl ViolationRecord {
+    // Reputation (basis points) of this violation's penalty not yet forgiven
+    pub(crate) fn outstanding_penalty_bps(&self) -> u32 {
+        from_points(self.penalty_applied).saturating_sub(self.penalty_forgiven_bps)
+    }
+
+    // Reputation (basis points) forgiven over the given number of clean epochs
+    fn decay_bps(&self, config: &PenaltyDecayConfig, clean_epochs: u64) -> u32 {
+        let per_epoch = mul_bps(from_points(self.penalty_applied), config.forgiven_bps_per_epoch) as u64;
+        std::cmp::min(per_epoch.saturating_mul(clean_epochs), self.outstanding_penalty_bps() as u64) as u32
+    }
+}
+
+impl AgentReputation {
+    fn last_violation_at(&self) -> Option<u64> {
+        self.violation_history.last().map(|violation| violation.timestamp)
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Get an agent's outstanding violation penalties and when they will be forgiven, with
+    // any clean epochs completed since its last update already applied
+    pub fn get_violation_status(&self, agent_id: AccountId) -> Option<ViolationStatusView> {
+        let agent_rep = self.agent_reputations.get(&agent_id)?;
+        let config = &self.penalty_decay_config;
+        let (clean_epochs, epoch_start) =
+            agent_rep
+                .penalty_decay
+                .completed_epochs(config, agent_rep.last_violation_at(), env::block_timestamp());
+        let rolled = epoch_start != agent_rep.penalty_decay.epoch_start;
+        let epoch_interactions = if rolled { 0 } else { agent_rep.penalty_decay.interactions };
+        let epoch_has_violation = !rolled
+            && agent_rep.last_violation_at().is_some_and(|timestamp| timestamp >= epoch_start);
+
+        let mut outstanding = Vec::new();
+        let mut outstanding_penalty_bps = 0u32;
+        for (position, violation) in agent_rep.violation_history.iter().enumerate() {
+            let remaining = violation.outstanding_penalty_bps() - violation.decay_bps(config, clean_epochs);
+            if remaining == 0 {
+                continue;
+            }
+            outstanding_penalty_bps = outstanding_penalty_bps.saturating_add(remaining);
+            if outstanding.len() < MAX_VIOLATION_STATUS_ENTRIES {
+                let per_epoch = mul_bps(from_points(violation.penalty_applied), config.forgiven_bps_per_epoch);
+                outstanding.push(ViolationDecayView {
+                    index: agent_rep.violations_archived + position as u64,
+                    penalty_applied: violation.penalty_applied,
+                    outstanding_penalty_bps: remaining,
+                    forgiven_per_clean_epoch_bps: per_epoch,
+                    clean_epochs_remaining: if per_epoch == 0 {
+                        None
+                    } else {
+                        Some(remaining.div_ceil(per_epoch) as u64)
+                    },
+                });
+            }
+        }
+
+        Some(ViolationStatusView {
+            agent_id,
+            epoch_start,
+            epoch_end: epoch_start + config.epoch_length,
+            epoch_interactions,
+            interactions_required: config.min_interactions,
+            epoch_clean_so_far: !epoch_has_violation && epoch_interactions >= config.min_interactions,
+            forgiven_bps_per_epoch: config.forgiven_bps_per_epoch,
+            outstanding_penalty_bps,
+            outstanding,
+        })
+    }
+
+    // Roll an agent's decay state forward to the current epoch, restoring the penalties
+    // forgiven over the clean epochs completed since. Called before recording anything that
+    // decides whether an epoch is clean, so it applies lazily as the agent is written.
+    pub(crate) fn internal_apply_penalty_decay(&self, agent_id: &AccountId, agent_rep: &mut AgentReputation) {
+        let config = &self.penalty_decay_config;
+        let (clean_epochs, epoch_start) =
+            agent_rep
+                .penalty_decay
+                .completed_epochs(config, agent_rep.last_violation_at(), env::block_timestamp());
+        if epoch_start == agent_rep.penalty_decay.epoch_start {
+            return;
+        }
+        agent_rep.penalty_decay = PenaltyDecayState { epoch_start, interactions: 0 };
+
+        if clean_epochs == 0 || config.forgiven_bps_per_epoch == 0 {
+            return;
+        }
+        let mut restored_bps = 0u32;
+        for violation in agent_rep.violation_history.iter_mut() {
+            let forgiven = violation.decay_bps(config, clean_epochs);
+            violation.penalty_forgiven_bps += forgiven;
+            restored_bps = restored_bps.saturating_add(forgiven);
+        }
+        if restored_bps == 0 {
+            return;
+        }
+        agent_rep.set_score_bps(agent_rep.score_bps.saturating_add(restored_bps));
+
+        emit_event(
+            "violation_penalties_forgiven",
+            json!({
+                "agent_id": agent_id,
+                "clean_epochs": clean_epochs,
+                "restored_bps": restored_bps,
+            }),
+        );
+    }
+
+    // Count a completed interaction toward the agent's current decay epoch
+    pub(crate) fn internal_record_decay_activity(&self, agent_id: &AccountId, agent_rep: &mut AgentReputation) {
+        self.internal_apply_penalty_decay(agent_id, agent_rep);
+        agent_rep.penalty_decay.interactions = agent_rep.penalty_decay.interactions.saturating_add(1);
+    }
+}
+
+// A violation whose penalty is still being forgiven
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ViolationDecayView {
+    index: u64,
+    // Reputation points originally deducted
+    penalty_applied: u32,
+    // Reputation (basis points) still to be forgiven
+    outstanding_penalty_bps: u32,
+    forgiven_per_clean_epoch_bps: u32,
+    // Clean epochs until the penalty is fully forgiven; None while decay is disabled
+    clean_epochs_remaining: Option<u64>,
+}
+
+// An agent's violation penalty decay schedule
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ViolationStatusView {
+    agent_id: AccountId,
+    // Current decay epoch
+    epoch_start: u64,
+    epoch_end: u64,
+    // Counted interactions so far this epoch, and the number needed for it to be clean
+    epoch_interactions: u32,
+    interactions_required: u32,
+    // Whether the epoch would be clean if it ended now
+    epoch_clean_so_far: bool,
+    forgiven_bps_per_epoch: u32,
+    // Reputation (basis points) still to be forgiven across all violations
+    outstanding_penalty_bps: u32,
+    // Violations with penalty left to forgive, oldest first
+    outstanding: Vec<ViolationDecayView>,
+}
@@ -0,0 +1,200 @@
+use crate::*;
+use crate::specializations::normalize_tag;
+use near_sdk::serde_json::Value;
+
+// Upper bounds keeping policy evaluation cheap
+const MAX_POLICY_RULES: usize = 20;
+const MAX_RULE_CONDITIONS: usize = 8;
+const MAX_RULE_NAME_LENGTH: usize = 64;
+
+// Condition over an agent's record; a rule applies when all of its conditions hold
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde", tag = "condition", rename_all = "snake_case")]
+pub enum PolicyCondition {
+    // Overall score at least `score`
+    MinScore { score: u32 },
+    // Trust level at least `level`
+    MinTrustLevel { level: TrustLevel },
+    // Average rating in a feedback category at least `score`
+    MinCategoryScore { category: FeedbackCategory, score: u8 },
+    // Staked ITLX at least `amount`
+    MinStake { amount: U128 },
+    // At most `count` recorded violations, not counting those overturned on appeal
+    MaxViolations { count: u32 },
+    // Registered for at least `duration` nanoseconds
+    MinTenure { duration: u64 },
+    // Holds an unexpired audited badge
+    Audited,
+    // Lists the specialization `tag` and has verified it through completed intents of that
+    // type or an audit. Rules with this condition placed ahead of the trust level tiers
+    // override them for agents in that specialization.
+    HasSpecialization { tag: String },
+}
+
+// Capabilities granted to agents matching every condition
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CapabilityRule {
+    pub name: String,
+    pub conditions: Vec<PolicyCondition>,
+    pub limits: CapabilityLimits,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Replace the capability policy. Rules are checked in order and the first rule whose
+    // conditions all hold decides an agent's capabilities; agents matching no rule get none.
+    pub fn set_capability_policy(&mut self, rules: Vec<CapabilityRule>) {
+        self.assert_owner_or_governance();
+        assert!(rules.len() <= MAX_POLICY_RULES, "Too many policy rules");
+        for rule in &rules {
+            assert!(rule.name.len() <= MAX_RULE_NAME_LENGTH, "Policy rule name too long");
+            assert!(rule.conditions.len() <= MAX_RULE_CONDITIONS, "Too many conditions in policy rule");
+            assert!(rule.limits.max_complexity <= 10, "Max complexity must be between 0 and 10");
+            for condition in &rule.conditions {
+                if let PolicyCondition::HasSpecialization { tag } = condition {
+                    assert!(!normalize_tag(tag).is_empty(), "Specialization tag cannot be empty");
+                }
+            }
+        }
+
+        let old_value = std::mem::replace(&mut self.capability_policy, rules.clone());
+
+        self.record_config_change("capability_policy", json!(old_value), json!(rules));
+    }
+
+    // Get the capability policy rules in evaluation order
+    pub fn get_capability_policy(&self) -> Vec<CapabilityRule> {
+        self.capability_policy.clone()
+    }
+
+    // Name of the policy rule deciding an agent's capabilities, or None if no rule matches
+    pub fn get_matching_capability_rule(&self, agent_id: AccountId) -> Option<String> {
+        let agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+        self.internal_matching_rule(&agent_id, &agent_rep, agent_rep.score).map(|rule| rule.name.clone())
+    }
+
+    // Capabilities the policy grants an agent, judging score conditions by its score in the
+    // specialization if one is given
+    pub(crate) fn internal_capability_limits(
+        &self,
+        agent_id: &AccountId,
+        agent_rep: &AgentReputation,
+        specialization: Option<&str>,
+    ) -> CapabilityLimits {
+        let score = match specialization {
+            Some(tag) => to_points(self.internal_specialization_score_bps(agent_id, tag)),
+            None => agent_rep.score,
+        };
+        self.internal_matching_rule(agent_id, agent_rep, score)
+            .map(|rule| rule.limits.clone())
+            .unwrap_or_else(CapabilityLimits::none)
+    }
+
+    fn internal_matching_rule(&self, agent_id: &AccountId, agent_rep: &AgentReputation, score: u32) -> Option<&CapabilityRule> {
+        self.capability_policy.iter().find(|rule| {
+            rule.conditions
+                .iter()
+                .all(|condition| self.condition_holds(condition, agent_id, agent_rep, score))
+        })
+    }
+
+    fn condition_holds(&self, condition: &PolicyCondition, agent_id: &AccountId, agent_rep: &AgentReputation, score: u32) -> bool {
+        match condition {
+            PolicyCondition::MinScore { score: min_score } => score >= *min_score,
+            PolicyCondition::MinTrustLevel { level } => self.get_trust_level(score) >= *level,
+            PolicyCondition::MinCategoryScore { category, score } => agent_rep.category_scores.get(*category) >= *score,
+            PolicyCondition::MinStake { amount } => self.agent_stakes.get(agent_id).unwrap_or(0) >= amount.0,
+            PolicyCondition::MaxViolations { count } => {
+                agent_rep.violation_history.iter().filter(|violation| !violation.overturned()).count() as u64
+                    <= *count as u64
+            },
+            PolicyCondition::MinTenure { duration } => {
+                env::block_timestamp().saturating_sub(agent_rep.registered_at) >= *duration
+            },
+            PolicyCondition::Audited => self.is_audited(agent_id),
+            PolicyCondition::HasSpecialization { tag } => self.internal_has_verified_specialization(agent_id, agent_rep, tag),
+        }
+    }
+}
+
+impl CapabilityLimits {
+    // No capabilities at all
+    fn none() -> Self {
+        Self {
+            max_complexity: 0,
+            max_transaction_value: 0,
+            can_access_critical_systems: false,
+            can_operate_autonomously: false,
+            can_delegate: false,
+        }
+    }
+}
+
+// Policy equivalent to the original fixed trust-level table
+pub(crate) fn default_capability_policy() -> Vec<CapabilityRule> {
+    let tier = |name: &str, level: TrustLevel, limits: CapabilityLimits| CapabilityRule {
+        name: name.to_string(),
+        conditions: vec![PolicyCondition::MinTrustLevel { level }],
+        limits,
+    };
+
+    vec![
+        tier("master", TrustLevel::Master, CapabilityLimits {
+            max_complexity: 10,
+            max_transaction_value: u128::MAX, // Unlimited
+            can_access_critical_systems: true,
+            can_operate_autonomously: true,
+            can_delegate: true,
+        }),
+        tier("expert", TrustLevel::Expert, CapabilityLimits {
+            max_complexity: 9,
+            max_transaction_value: 10000 * 10u128.pow(18),
+            can_access_critical_systems: true,
+            can_operate_autonomously: true,
+            can_delegate: true,
+        }),
+        tier("trusted", TrustLevel::Trusted, CapabilityLimits {
+            max_complexity: 7,
+            max_transaction_value: 2000 * 10u128.pow(18),
+            can_access_critical_systems: false,
+            can_operate_autonomously: true,
+            can_delegate: true,
+        }),
+        tier("apprentice", TrustLevel::Apprentice, CapabilityLimits {
+            max_complexity: 5,
+            max_transaction_value: 500 * 10u128.pow(18),
+            can_access_critical_systems: false,
+            can_operate_autonomously: true,
+            can_delegate: false,
+        }),
+        tier("novice", TrustLevel::Novice, CapabilityLimits {
+            max_complexity: 3,
+            max_transaction_value: 100 * 10u128.pow(18), // 100 tokens
+            can_access_critical_systems: false,
+            can_operate_autonomously: false,
+            can_delegate: false,
+        }),
+    ]
+}
+
+// Capability policy for the full config document. Transaction limits are written as strings,
+// since a JSON value cannot hold a number above u64::MAX.
+pub(crate) fn capability_policy_json(rules: &[CapabilityRule]) -> Value {
+    rules
+        .iter()
+        .map(|rule| {
+            json!({
+                "name": rule.name,
+                "conditions": rule.conditions,
+                "limits": {
+                    "max_complexity": rule.limits.max_complexity,
+                    "max_transaction_value": U128(rule.limits.max_transaction_value),
+                    "can_access_critical_systems": rule.limits.can_access_critical_systems,
+                    "can_operate_autonomously": rule.limits.can_operate_autonomously,
+                    "can_delegate": rule.limits.can_delegate,
+                },
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,86 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Smallest accepted epsilon (thousandths); lower values drown every count in noise
+const MIN_EPSILON_MILLI: u32 = 10;
+
+// How long a noise seed is used before it can be rotated (1 day)
+const NOISE_SEED_EPOCH: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+// Differential privacy settings for aggregate views. Per-agent views are always exact.
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct PrivacyConfig {
+    // Privacy budget per released count, in thousandths (0 disables noise)
+    pub(crate) epsilon_milli: u32,
+    // Seed of the current noise epoch, mixed from the previous seed and the block's random
+    // seed when it is drawn, so no one (governance included) knows or picks it beforehand.
+    // Contract state is publicly readable, so a seed is only hidden until it is drawn;
+    // rotating it every epoch limits how long one recovered seed strips the noise.
+    noise_seed: Vec<u8>,
+    // When the current seed was drawn
+    seed_drawn_at: u64,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Enable Laplace noise on aggregate views with the given epsilon (thousandths), or disable it
+    // with 0. A fresh noise seed is drawn either way.
+    pub fn set_privacy_config(&mut self, epsilon_milli: u32) {
+        self.assert_owner_or_governance();
+        assert!(
+            epsilon_milli == 0 || epsilon_milli >= MIN_EPSILON_MILLI,
+            "Epsilon too small"
+        );
+
+        let old_value = std::mem::replace(&mut self.privacy_config.epsilon_milli, epsilon_milli);
+        self.internal_draw_noise_seed();
+
+        self.record_config_change("privacy_epsilon_milli", json!(old_value), json!(epsilon_milli));
+    }
+
+    // Draw a new noise seed once the current one has been in use for a full epoch. Callable
+    // by anyone.
+    pub fn rotate_privacy_seed(&mut self) {
+        assert!(
+            env::block_timestamp() >= self.privacy_config.seed_drawn_at + NOISE_SEED_EPOCH,
+            "Noise seed epoch has not ended"
+        );
+
+        self.internal_draw_noise_seed();
+
+        emit_event("privacy_seed_rotated", json!({ "drawn_at": self.privacy_config.seed_drawn_at }));
+    }
+
+    // Whether aggregate views are released with noise
+    pub fn is_privacy_noise_enabled(&self) -> bool {
+        self.privacy_config.epsilon_milli > 0
+    }
+
+    // Release a count under the privacy config. `query` identifies the statistic, so within a
+    // seed epoch the same question always gets the same noise and repeating it cannot average
+    // the noise away.
+    pub(crate) fn internal_private_count(&self, query: &str, count: u64) -> u64 {
+        let epsilon_milli = self.privacy_config.epsilon_milli;
+        if epsilon_milli == 0 {
+            return count;
+        }
+
+        // Uniform sample in (-0.5, 0.5) from the seeded hash of the query
+        let hash = env::sha256(&[self.privacy_config.noise_seed.as_slice(), query.as_bytes()].concat());
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&hash[..8]);
+        let uniform = (u64::from_le_bytes(bytes) >> 11) as f64 / (1u64 << 53) as f64 - 0.5;
+
+        // Laplace(0, 1/epsilon) noise by inverse transform; counts have sensitivity 1
+        let scale = 1000.0 / epsilon_milli as f64;
+        let noise = -scale * uniform.signum() * (1.0 - 2.0 * uniform.abs()).max(f64::MIN_POSITIVE).ln();
+
+        (count as f64 + noise).round().max(0.0) as u64
+    }
+
+    fn internal_draw_noise_seed(&mut self) {
+        self.privacy_config.noise_seed =
+            env::sha256(&[self.privacy_config.noise_seed.as_slice(), &env::random_seed()].concat());
+        self.privacy_config.seed_drawn_at = env::block_timestamp();
+    }
+}
@@ -0,0 +1,83 @@
+use crate::*;
+use crate::events::emit_event;
+use near_sdk::json_types::Base64VecU8;
+
+// Size limits keeping profiles small enough to render agent cards from a single view call
+const MAX_DISPLAY_NAME_LENGTH: usize = 64;
+const MAX_DESCRIPTION_LENGTH: usize = 1000;
+const MAX_ENDPOINT_URL_LENGTH: usize = 256;
+const MAX_PROFILE_TAGS: usize = 10;
+const MAX_PROFILE_TAG_LENGTH: usize = 32;
+const ICON_HASH_LENGTH: usize = 32;
+
+// Descriptive metadata an agent publishes about itself. Nothing here affects scoring.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentProfile {
+    pub display_name: String,
+    pub description: String,
+    // HTTPS endpoint clients reach the agent at
+    pub endpoint_url: Option<String>,
+    // Model and framework tags, e.g. "llama-3" or "langchain"
+    pub tags: Vec<String>,
+    // SHA-256 of the agent's icon image
+    pub icon_hash: Option<Base64VecU8>,
+}
+
+impl AgentProfile {
+    fn assert_valid(&self) {
+        assert!(!self.display_name.trim().is_empty(), "Display name cannot be empty");
+        assert!(self.display_name.len() <= MAX_DISPLAY_NAME_LENGTH, "Display name too long");
+        assert!(self.description.len() <= MAX_DESCRIPTION_LENGTH, "Description too long");
+        if let Some(endpoint_url) = &self.endpoint_url {
+            assert!(endpoint_url.starts_with("https://"), "Endpoint URL must use https");
+            assert!(endpoint_url.len() <= MAX_ENDPOINT_URL_LENGTH, "Endpoint URL too long");
+        }
+        assert!(self.tags.len() <= MAX_PROFILE_TAGS, "Too many profile tags");
+        for tag in &self.tags {
+            assert!(!tag.trim().is_empty(), "Profile tags cannot be empty");
+            assert!(tag.len() <= MAX_PROFILE_TAG_LENGTH, "Profile tag too long");
+        }
+        if let Some(icon_hash) = &self.icon_hash {
+            assert_eq!(icon_hash.0.len(), ICON_HASH_LENGTH, "Icon hash must be a 32-byte SHA-256 digest");
+        }
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Set or replace the calling agent's profile. The agent pays for its storage.
+    pub fn set_agent_profile(&mut self, profile: AgentProfile) {
+        let agent_id = env::predecessor_account_id();
+        assert!(self.agent_reputations.get(&agent_id).is_some(), "Agent not registered");
+        profile.assert_valid();
+
+        let initial_storage = env::storage_usage();
+        self.agent_profiles.insert(&agent_id, &profile);
+        self.internal_charge_storage(&agent_id, initial_storage);
+
+        emit_event(
+            "agent_profile_updated",
+            json!({
+                "agent_id": agent_id,
+                "display_name": profile.display_name,
+            }),
+        );
+    }
+
+    // Remove the calling agent's profile, releasing its storage
+    pub fn remove_agent_profile(&mut self) {
+        let agent_id = env::predecessor_account_id();
+
+        let initial_storage = env::storage_usage();
+        assert!(self.agent_profiles.remove(&agent_id).is_some(), "Agent has no profile");
+        self.internal_track_storage(&agent_id, initial_storage);
+
+        emit_event("agent_profile_removed", json!({ "agent_id": agent_id }));
+    }
+
+    // Get an agent's profile, if it has published one
+    pub fn get_agent_profile(&self, agent_id: AccountId) -> Option<AgentProfile> {
+        self.agent_profiles.get(&agent_id)
+    }
+}
@@ -0,0 +1,147 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Gas for ft_transfer of withdrawn credit and claimed rebates
+const GAS_FOR_REBATE_FT_TRANSFER: Gas = Gas(10_000_000_000_000);
+
+// Gas reserved for the transfer callback
+const GAS_FOR_REBATE_CALLBACK: Gas = Gas(5_000_000_000_000);
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Authorize an action on behalf of a consumer contract. If the consumer holds prepaid query
+    // credit, the configured rebate is moved from that credit to the evaluated agent.
+    pub fn authorize_action(&mut self, agent_id: AccountId, action: String, value: Option<U128>) -> bool {
+        let consumer_id = env::predecessor_account_id();
+        let decision = self.evaluate_authorization(&agent_id, &action, value.map(|v| v.0));
+
+        // Only listed agents earn rebates, whether or not the action is allowed
+        let rebate = self.query_rebate_amount;
+        if rebate > 0 && self.agent_reputations.get(&agent_id).is_some() {
+            let credit = self.query_credits.get(&consumer_id).unwrap_or(0);
+            if credit >= rebate {
+                self.query_credits.insert(&consumer_id, &(credit - rebate));
+                let accrued = self.query_rebates.get(&agent_id).unwrap_or(0);
+                self.query_rebates.insert(&agent_id, &(accrued + rebate));
+            }
+        }
+
+        decision.allowed()
+    }
+
+    // Withdraw unused query credit back to the consumer
+    pub fn withdraw_query_credit(&mut self, amount: U128) -> Promise {
+        let consumer_id = env::predecessor_account_id();
+        let credit = self.query_credits.get(&consumer_id).unwrap_or(0);
+        assert!(amount.0 > 0 && amount.0 <= credit, "Invalid withdrawal amount");
+
+        self.query_credits.insert(&consumer_id, &(credit - amount.0));
+        self.stats.itlx_query_balances -= amount.0;
+
+        emit_event(
+            "query_credit_withdrawn",
+            json!({
+                "consumer_id": consumer_id,
+                "amount": amount,
+            }),
+        );
+
+        self.internal_transfer_query_itlx(&consumer_id, amount.0, false, "Query credit withdrawal")
+    }
+
+    // Claim the query rebates accrued by the calling agent
+    pub fn claim_query_rebates(&mut self) -> Promise {
+        let agent_id = env::predecessor_account_id();
+        let accrued = self.query_rebates.get(&agent_id).unwrap_or(0);
+        assert!(accrued > 0, "No query rebates to claim");
+
+        self.query_rebates.remove(&agent_id);
+        self.stats.itlx_query_balances -= accrued;
+
+        emit_event(
+            "query_rebates_claimed",
+            json!({
+                "agent_id": agent_id,
+                "amount": U128(accrued),
+            }),
+        );
+
+        self.internal_transfer_query_itlx(&agent_id, accrued, true, "Query rebate claim")
+    }
+
+    // Callback after transferring withdrawn credit or claimed rebates
+    #[private]
+    pub fn on_query_transfer_complete(&mut self, account_id: AccountId, amount: U128, rebate: bool) {
+        if let PromiseResult::Successful(_) = env::promise_result(0) {
+            return;
+        }
+
+        // Transfer failed: the tokens never left, so they go back to the balance they came from
+        let balances = if rebate { &mut self.query_rebates } else { &mut self.query_credits };
+        let balance = balances.get(&account_id).unwrap_or(0);
+        balances.insert(&account_id, &(balance + amount.0));
+        self.stats.itlx_query_balances += amount.0;
+
+        emit_event(
+            "query_transfer_rolled_back",
+            json!({
+                "account_id": account_id,
+                "amount": amount,
+                "rebate": rebate,
+            }),
+        );
+    }
+
+    // Get a consumer's remaining prepaid query credit
+    pub fn get_query_credit(&self, consumer_id: AccountId) -> U128 {
+        U128(self.query_credits.get(&consumer_id).unwrap_or(0))
+    }
+
+    // Get the query rebates an agent has accrued but not claimed
+    pub fn get_query_rebates(&self, agent_id: AccountId) -> U128 {
+        U128(self.query_rebates.get(&agent_id).unwrap_or(0))
+    }
+
+    // Credit ITLX received via ft_transfer_call to a consumer's query credit
+    pub(crate) fn internal_deposit_query_credit(&mut self, consumer_id: &AccountId, amount: Balance) {
+        let credit = self.query_credits.get(consumer_id).unwrap_or(0);
+        self.query_credits.insert(consumer_id, &(credit + amount));
+        self.stats.itlx_query_balances += amount;
+
+        emit_event(
+            "query_credit_deposited",
+            json!({
+                "consumer_id": consumer_id,
+                "amount": U128(amount),
+            }),
+        );
+    }
+
+    // Transfer query credit (`rebate` false) or rebates back out, restoring them on failure
+    fn internal_transfer_query_itlx(&self, receiver_id: &AccountId, amount: Balance, rebate: bool, memo: &str) -> Promise {
+        Promise::new(self.token_contract_id.clone())
+            .function_call(
+                "ft_transfer".to_string(),
+                json!({
+                    "receiver_id": receiver_id,
+                    "amount": U128(amount),
+                    "memo": memo,
+                }).to_string().into_bytes(),
+                1, // 1 yoctoNEAR
+                GAS_FOR_REBATE_FT_TRANSFER,
+            )
+            .then(
+                Promise::new(env::current_account_id())
+                    .function_call(
+                        "on_query_transfer_complete".to_string(),
+                        json!({
+                            "account_id": receiver_id,
+                            "amount": U128(amount),
+                            "rebate": rebate,
+                        }).to_string().into_bytes(),
+                        0,
+                        GAS_FOR_REBATE_CALLBACK,
+                    )
+            )
+    }
+}
@@ -0,0 +1,250 @@
+use crate::*;
+use crate::authorization::authorization_message;
+use crate::events::emit_event;
+
+// Upper bounds keeping quota evaluation cheap (30 days)
+pub(crate) const MAX_USAGE_QUOTAS: usize = 25;
+pub(crate) const MAX_QUOTA_WINDOW: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+// Number of buckets the rolling window is tracked in; usage leaves the window one bucket
+// (1/24th of the window) at a time
+const QUOTA_BUCKETS: u64 = 24;
+
+// Limit on how often, and for how much value in total, agents at a trust level can perform an
+// action within the rolling window
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UsageQuota {
+    pub trust_level: TrustLevel,
+    pub action_type: String,
+    pub max_actions: u32,
+    // None leaves the value unlimited
+    pub max_value: Option<U128>,
+}
+
+// Rolling-window quotas debited by consume_capability. Actions without a quota for the
+// agent's trust level are only subject to the capability policy.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UsageQuotaConfig {
+    // Length of the rolling window (nanoseconds)
+    pub window: u64,
+    pub quotas: Vec<UsageQuota>,
+}
+
+impl Default for UsageQuotaConfig {
+    fn default() -> Self {
+        let transactions = |trust_level: TrustLevel, max_actions: u32, max_value: Option<Balance>| UsageQuota {
+            trust_level,
+            action_type: "transaction".to_string(),
+            max_actions,
+            max_value: max_value.map(U128),
+        };
+
+        Self {
+            window: 24 * 60 * 60 * 1_000_000_000, // 1 day in nanoseconds
+            quotas: vec![
+                transactions(TrustLevel::Novice, 10, Some(500 * 10u128.pow(18))),
+                transactions(TrustLevel::Apprentice, 25, Some(2_500 * 10u128.pow(18))),
+                transactions(TrustLevel::Trusted, 50, Some(10_000 * 10u128.pow(18))),
+                transactions(TrustLevel::Expert, 100, Some(50_000 * 10u128.pow(18))),
+                transactions(TrustLevel::Master, 250, None),
+            ],
+        }
+    }
+}
+
+impl UsageQuotaConfig {
+    fn quota_for(&self, trust_level: TrustLevel, action_type: &str) -> Option<&UsageQuota> {
+        self.quotas
+            .iter()
+            .find(|quota| quota.trust_level == trust_level && quota.action_type == action_type)
+    }
+
+    fn bucket_length(&self) -> u64 {
+        std::cmp::max(self.window / QUOTA_BUCKETS, 1)
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct UsageBucket {
+    started_at: u64,
+    actions: u32,
+    value: Balance,
+}
+
+// An agent's recent usage of one action, oldest bucket first
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct UsageWindow {
+    buckets: Vec<UsageBucket>,
+}
+
+impl UsageWindow {
+    // Drop buckets that have left the rolling window
+    fn prune(&mut self, now: u64, config: &UsageQuotaConfig) {
+        let window_start = now.saturating_sub(config.window);
+        self.buckets
+            .retain(|bucket| bucket.started_at + config.bucket_length() > window_start);
+    }
+
+    fn totals(&self) -> (u32, Balance) {
+        self.buckets
+            .iter()
+            .fold((0, 0), |(actions, value), bucket| (actions + bucket.actions, value + bucket.value))
+    }
+
+    fn record(&mut self, now: u64, config: &UsageQuotaConfig, value: Balance) {
+        let started_at = now - now % config.bucket_length();
+        match self.buckets.last_mut() {
+            Some(bucket) if bucket.started_at == started_at => {
+                bucket.actions += 1;
+                bucket.value += value;
+            },
+            _ => self.buckets.push(UsageBucket { started_at, actions: 1, value }),
+        }
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Check an action against the agent's capabilities and debit it from the agent's rolling
+    // quota, panicking if either rejects it. Callable by the agent itself, or by a quota
+    // dispatcher acting for `agent_id`. The agent pays for the usage record's storage.
+    pub fn consume_capability(&mut self, action_type: String, value: Option<U128>, agent_id: Option<AccountId>) {
+        let caller = env::predecessor_account_id();
+        let agent_id = agent_id.unwrap_or_else(|| caller.clone());
+        assert!(
+            caller == agent_id || self.quota_dispatchers.contains(&caller),
+            "Only the agent or a quota dispatcher can consume its capabilities"
+        );
+
+        let decision = self.evaluate_authorization(&agent_id, &action_type, value.map(|v| v.0));
+        assert!(decision.allowed(), "{}", authorization_message(decision.rule));
+
+        let trust_level = decision.trust_level.expect("Agent not registered");
+        let quota = match self.usage_quota_config.quota_for(trust_level, &action_type) {
+            Some(quota) => quota.clone(),
+            None => return,
+        };
+
+        let initial_storage = env::storage_usage();
+        let now = env::block_timestamp();
+        let value = value.map_or(0, |v| v.0);
+        let key = format!("{}:{}", agent_id, action_type);
+        let mut usage = self.usage_windows.get(&key).unwrap_or_default();
+        usage.prune(now, &self.usage_quota_config);
+
+        let (used_actions, used_value) = usage.totals();
+        assert!(used_actions < quota.max_actions, "Action quota exhausted for this window");
+        if let Some(max_value) = quota.max_value {
+            assert!(used_value + value <= max_value.0, "Value quota exhausted for this window");
+        }
+
+        usage.record(now, &self.usage_quota_config, value);
+        self.usage_windows.insert(&key, &usage);
+        self.internal_charge_storage(&agent_id, initial_storage);
+
+        emit_event(
+            "capability_consumed",
+            json!({
+                "agent_id": agent_id,
+                "action_type": action_type,
+                "value": U128(value),
+                "consumed_by": caller,
+            }),
+        );
+    }
+
+    // Remaining quota of each action limited at the agent's current trust level
+    pub fn get_remaining_quota(&self, agent_id: AccountId) -> Vec<RemainingQuotaView> {
+        let agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+        let trust_level = self.get_trust_level(agent_rep.score);
+        let config = &self.usage_quota_config;
+        let now = env::block_timestamp();
+
+        config
+            .quotas
+            .iter()
+            .filter(|quota| quota.trust_level == trust_level)
+            .map(|quota| {
+                let mut usage = self
+                    .usage_windows
+                    .get(&format!("{}:{}", agent_id, quota.action_type))
+                    .unwrap_or_default();
+                usage.prune(now, config);
+                let (used_actions, used_value) = usage.totals();
+
+                RemainingQuotaView {
+                    action_type: quota.action_type.clone(),
+                    remaining_actions: quota.max_actions.saturating_sub(used_actions),
+                    remaining_value: quota.max_value.map(|max_value| U128(max_value.0.saturating_sub(used_value))),
+                    used_actions,
+                    used_value: U128(used_value),
+                }
+            })
+            .collect()
+    }
+
+    // Whether the agent's quota for an action at the trust level has room for one more action
+    // of the value; actions without a quota always do
+    pub(crate) fn internal_has_quota_headroom(
+        &self,
+        agent_id: &AccountId,
+        trust_level: TrustLevel,
+        action_type: &str,
+        value: Balance,
+    ) -> bool {
+        let config = &self.usage_quota_config;
+        let quota = match config.quota_for(trust_level, action_type) {
+            Some(quota) => quota,
+            None => return true,
+        };
+        let mut usage = self
+            .usage_windows
+            .get(&format!("{}:{}", agent_id, action_type))
+            .unwrap_or_default();
+        usage.prune(env::block_timestamp(), config);
+        let (used_actions, used_value) = usage.totals();
+
+        used_actions < quota.max_actions
+            && quota.max_value.is_none_or(|max_value| used_value + value <= max_value.0)
+    }
+
+    // Get the rolling-window usage quotas
+    pub fn get_usage_quota_config(&self) -> UsageQuotaConfig {
+        self.usage_quota_config.clone()
+    }
+
+    // Allow an account to consume capabilities on behalf of agents (owner or governance)
+    pub fn add_quota_dispatcher(&mut self, dispatcher_id: AccountId) {
+        self.assert_owner_or_governance();
+        assert!(self.quota_dispatchers.insert(&dispatcher_id), "Account is already a quota dispatcher");
+
+        self.record_config_change(&format!("quota_dispatcher.{}", dispatcher_id), json!(false), json!(true));
+    }
+
+    // Remove a quota dispatcher (owner or governance)
+    pub fn remove_quota_dispatcher(&mut self, dispatcher_id: AccountId) {
+        self.assert_owner_or_governance();
+        assert!(self.quota_dispatchers.remove(&dispatcher_id), "Account is not a quota dispatcher");
+
+        self.record_config_change(&format!("quota_dispatcher.{}", dispatcher_id), json!(true), json!(false));
+    }
+
+    // Get the accounts allowed to consume capabilities on behalf of agents
+    pub fn get_quota_dispatchers(&self) -> Vec<AccountId> {
+        self.quota_dispatchers.to_vec()
+    }
+}
+
+// View struct for an agent's remaining quota of one action
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RemainingQuotaView {
+    action_type: String,
+    used_actions: u32,
+    remaining_actions: u32,
+    used_value: U128,
+    // None when the value is unlimited
+    remaining_value: Option<U128>,
+}
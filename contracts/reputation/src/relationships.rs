@@ -0,0 +1,154 @@
+use crate::*;
+use crate::fixed_point::{blend_bps, linear_share, rating_score_bps, to_points, SCORE_SCALE_BPS};
+
+// Largest weight a client's own history with an agent gets against the agent's global score
+// (basis points)
+const MAX_RELATIONSHIP_WEIGHT_BPS: u32 = 7_000;
+
+// Interactions and ratings between a client and an agent earning their history the full weight
+const RELATIONSHIP_FULL_WEIGHT_HISTORY: u128 = 50;
+
+// One client's history with one agent
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+pub struct ClientRelationship {
+    // Finalized intents the agent completed or failed for the client
+    interactions: u64,
+    successful_interactions: u64,
+    // Feedback the client left for the agent, and the sum of its 0-5 star ratings
+    feedback_count: u64,
+    rating_total: u64,
+    first_interaction_at: u64,
+    last_interaction_at: u64,
+}
+
+impl ClientRelationship {
+    fn new() -> Self {
+        let now = env::block_timestamp();
+        Self {
+            interactions: 0,
+            successful_interactions: 0,
+            feedback_count: 0,
+            rating_total: 0,
+            first_interaction_at: now,
+            last_interaction_at: now,
+        }
+    }
+
+    // Score (basis points) of the pairwise history alone: the client's average rating blended
+    // with the agent's success rate on the client's intents, or whichever of the two exists
+    fn score_bps(&self, success_rate_weight_bps: u32) -> Option<u32> {
+        let rating = (self.feedback_count > 0)
+            .then(|| rating_score_bps(self.rating_total as u128, self.feedback_count as u128));
+        let success_rate = (self.interactions > 0)
+            .then(|| linear_share(SCORE_SCALE_BPS, self.successful_interactions as u128, self.interactions as u128));
+        match (rating, success_rate) {
+            (Some(rating), Some(success_rate)) => Some(blend_bps(rating, success_rate, success_rate_weight_bps)),
+            (rating, success_rate) => rating.or(success_rate),
+        }
+    }
+
+    // Weight (basis points) of the pairwise history against the global score, growing with
+    // the length of the history
+    fn weight_bps(&self) -> u32 {
+        let history = std::cmp::max(self.interactions, self.feedback_count);
+        linear_share(MAX_RELATIONSHIP_WEIGHT_BPS, history as u128, RELATIONSHIP_FULL_WEIGHT_HISTORY)
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Get a client's personalized score for an agent: the agent's global score blended with
+    // the client's own history with it, weighted by how long that history is. None for
+    // unregistered agents.
+    pub fn get_relationship_score(&self, client_id: AccountId, agent_id: AccountId) -> Option<RelationshipScoreView> {
+        let agent_rep = self.agent_reputations.get(&agent_id)?;
+        let relationship = self.client_relationships.get(&relationship_key(&client_id, &agent_id));
+
+        let success_rate_weight_bps = ScoringParams::live(self).success_rate_weight_bps;
+        let pairwise_score_bps = relationship.as_ref().and_then(|r| r.score_bps(success_rate_weight_bps));
+        let personal_weight_bps = match (&relationship, pairwise_score_bps) {
+            (Some(relationship), Some(_)) => relationship.weight_bps(),
+            _ => 0,
+        };
+        let relationship_score_bps = blend_bps(agent_rep.score_bps, pairwise_score_bps.unwrap_or(0), personal_weight_bps);
+        let relationship = relationship.unwrap_or_else(ClientRelationship::new);
+
+        Some(RelationshipScoreView {
+            client_id,
+            agent_id,
+            global_score: agent_rep.score,
+            global_score_bps: agent_rep.score_bps,
+            interactions: relationship.interactions,
+            successful_interactions: relationship.successful_interactions,
+            feedback_count: relationship.feedback_count,
+            average_rating_bps: (relationship.feedback_count > 0)
+                .then(|| rating_score_bps(relationship.rating_total as u128, relationship.feedback_count as u128)),
+            first_interaction_at: (relationship.feedback_count + relationship.interactions > 0)
+                .then_some(relationship.first_interaction_at),
+            last_interaction_at: (relationship.feedback_count + relationship.interactions > 0)
+                .then_some(relationship.last_interaction_at),
+            pairwise_score_bps,
+            personal_weight_bps,
+            relationship_score: to_points(relationship_score_bps),
+            relationship_score_bps,
+        })
+    }
+
+    // Count a finalized intent toward its client's history with the agent
+    pub(crate) fn internal_record_relationship_outcome(&mut self, client_id: &AccountId, agent_id: &AccountId, successful: bool) {
+        self.internal_update_relationship(client_id, agent_id, |relationship| {
+            relationship.interactions += 1;
+            if successful {
+                relationship.successful_interactions += 1;
+            }
+        });
+    }
+
+    // Count a client's rating toward its history with the agent
+    pub(crate) fn internal_record_relationship_rating(&mut self, client_id: &AccountId, agent_id: &AccountId, rating: u8) {
+        self.internal_update_relationship(client_id, agent_id, |relationship| {
+            relationship.feedback_count += 1;
+            relationship.rating_total += rating as u64;
+        });
+    }
+
+    fn internal_update_relationship<F: FnOnce(&mut ClientRelationship)>(
+        &mut self,
+        client_id: &AccountId,
+        agent_id: &AccountId,
+        update: F,
+    ) {
+        let key = relationship_key(client_id, agent_id);
+        let mut relationship = self.client_relationships.get(&key).unwrap_or_else(ClientRelationship::new);
+        update(&mut relationship);
+        relationship.last_interaction_at = env::block_timestamp();
+        self.client_relationships.insert(&key, &relationship);
+    }
+}
+
+fn relationship_key(client_id: &AccountId, agent_id: &AccountId) -> String {
+    format!("{}:{}", client_id, agent_id)
+}
+
+// View struct for a client's personalized score for an agent
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RelationshipScoreView {
+    client_id: AccountId,
+    agent_id: AccountId,
+    global_score: u32,
+    global_score_bps: u32,
+    // The client's own history with the agent
+    interactions: u64,
+    successful_interactions: u64,
+    feedback_count: u64,
+    average_rating_bps: Option<u32>,
+    first_interaction_at: Option<u64>,
+    last_interaction_at: Option<u64>,
+    // Score of the history alone; None without any
+    pairwise_score_bps: Option<u32>,
+    // Weight of the history in the relationship score
+    personal_weight_bps: u32,
+    relationship_score: u32,
+    relationship_score_bps: u32,
+}
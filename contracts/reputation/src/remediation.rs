@@ -0,0 +1,271 @@
+use crate::*;
+use crate::events::emit_event;
+use crate::fixed_point::from_points;
+
+// Most reputation points a single remediation task can restore
+const MAX_REMEDIATION_POINTS: u32 = 20;
+
+// Maximum length of a task ID
+const MAX_TASK_ID_LENGTH: usize = 64;
+
+// Maximum length of a task description, its requirements, a proof or a review note
+const MAX_REMEDIATION_TEXT_LENGTH: usize = 1_000;
+
+// Upper bound on the tasks an agent can hold that are not yet completed
+const MAX_OPEN_ASSIGNMENTS: usize = 10;
+
+// Upper bound on tasks or reviews returned per page
+const MAX_REMEDIATION_PAGE: u32 = 50;
+
+// A remediation task governance can assign to penalized agents
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RemediationTask {
+    pub(crate) task_id: String,
+    pub(crate) description: String,
+    // What the proof of completion must show
+    pub(crate) requirements: String,
+    // Reputation points restored on verified completion
+    pub(crate) points: u32,
+    pub(crate) created_at: u64,
+    // Closed tasks take no new assignments
+    pub(crate) open: bool,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum RemediationStatus {
+    // Awaiting the agent's proof
+    Assigned,
+    // Proof submitted; awaiting verification
+    Submitted,
+    // Verified; points granted
+    Completed,
+}
+
+// A task assigned to an agent, with its progress
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RemediationAssignment {
+    pub(crate) task_id: String,
+    pub(crate) agent_id: AccountId,
+    pub(crate) status: RemediationStatus,
+    pub(crate) assigned_at: u64,
+    pub(crate) proof: Option<String>,
+    pub(crate) submitted_at: Option<u64>,
+    // Note from the last review, including why a proof was rejected
+    pub(crate) review_note: Option<String>,
+    pub(crate) verified_by: Option<AccountId>,
+    pub(crate) completed_at: Option<u64>,
+    // Reputation (basis points) restored on completion
+    pub(crate) restored_bps: u32,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Create a remediation task (owner or governance), paying for its storage
+    pub fn create_remediation_task(&mut self, task_id: String, description: String, requirements: String, points: u32) {
+        self.assert_owner_or_governance();
+        assert!(!task_id.is_empty() && task_id.len() <= MAX_TASK_ID_LENGTH, "Invalid task ID");
+        assert!(description.len() <= MAX_REMEDIATION_TEXT_LENGTH, "Task description too long");
+        assert!(requirements.len() <= MAX_REMEDIATION_TEXT_LENGTH, "Task requirements too long");
+        assert!(points > 0 && points <= MAX_REMEDIATION_POINTS, "Task points out of range");
+        assert!(self.remediation_tasks.get(&task_id).is_none(), "Task already exists");
+
+        let initial_storage = env::storage_usage();
+        self.remediation_tasks.insert(
+            &task_id,
+            &RemediationTask {
+                task_id: task_id.clone(),
+                description,
+                requirements,
+                points,
+                created_at: env::block_timestamp(),
+                open: true,
+            },
+        );
+        self.internal_charge_storage(&env::predecessor_account_id(), initial_storage);
+
+        emit_event("remediation_task_created", json!({ "task_id": task_id, "points": points }));
+    }
+
+    // Stop a task taking new assignments (owner or governance); assignments already made can
+    // still be completed
+    pub fn close_remediation_task(&mut self, task_id: String) {
+        self.assert_owner_or_governance();
+        let mut task = self.remediation_tasks.get(&task_id).expect("Task not found");
+        assert!(task.open, "Task already closed");
+        task.open = false;
+        self.remediation_tasks.insert(&task_id, &task);
+
+        emit_event("remediation_task_closed", json!({ "task_id": task_id }));
+    }
+
+    // Assign an open task to a penalized agent (owner or governance): one with violation
+    // penalties still outstanding, or not in good standing
+    pub fn assign_remediation_task(&mut self, task_id: String, agent_id: AccountId) {
+        self.assert_owner_or_governance();
+        let task = self.remediation_tasks.get(&task_id).expect("Task not found");
+        assert!(task.open, "Task is closed");
+
+        let agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+        let penalized = agent_rep.violation_history.iter().any(|violation| violation.outstanding_penalty_bps() > 0)
+            || !self.internal_agent_status(&agent_id).in_good_standing();
+        assert!(penalized, "Agent has no penalty to remediate");
+
+        let initial_storage = env::storage_usage();
+        let mut assignments = self.remediation_assignments.get(&agent_id).unwrap_or_default();
+        assert!(
+            assignments.iter().all(|assignment| assignment.task_id != task_id),
+            "Task already assigned to agent"
+        );
+        assert!(
+            assignments.iter().filter(|assignment| assignment.status != RemediationStatus::Completed).count()
+                < MAX_OPEN_ASSIGNMENTS,
+            "Agent has too many open remediation tasks"
+        );
+        assignments.push(RemediationAssignment {
+            task_id: task_id.clone(),
+            agent_id: agent_id.clone(),
+            status: RemediationStatus::Assigned,
+            assigned_at: env::block_timestamp(),
+            proof: None,
+            submitted_at: None,
+            review_note: None,
+            verified_by: None,
+            completed_at: None,
+            restored_bps: 0,
+        });
+        self.remediation_assignments.insert(&agent_id, &assignments);
+        self.internal_charge_storage(&env::predecessor_account_id(), initial_storage);
+
+        emit_event("remediation_task_assigned", json!({ "task_id": task_id, "agent_id": agent_id }));
+    }
+
+    // Submit proof of completing a remediation task assigned to the calling agent. Points are
+    // granted once a verifier approves it; the agent pays for the proof's storage.
+    pub fn complete_remediation_task(&mut self, task_id: String, proof: String) {
+        let agent_id = env::predecessor_account_id();
+        assert!(!proof.is_empty() && proof.len() <= MAX_REMEDIATION_TEXT_LENGTH, "Invalid proof");
+
+        let initial_storage = env::storage_usage();
+        let mut assignments = self.remediation_assignments.get(&agent_id).unwrap_or_default();
+        let assignment = assignments
+            .iter_mut()
+            .find(|assignment| assignment.task_id == task_id)
+            .expect("Task not assigned to agent");
+        assert!(assignment.status == RemediationStatus::Assigned, "Task already submitted or completed");
+        assignment.status = RemediationStatus::Submitted;
+        assignment.proof = Some(proof);
+        assignment.submitted_at = Some(env::block_timestamp());
+        self.remediation_assignments.insert(&agent_id, &assignments);
+        self.remediation_reviews.insert(&(agent_id.clone(), task_id.clone()));
+        self.internal_charge_storage(&agent_id, initial_storage);
+
+        emit_event("remediation_submitted", json!({ "task_id": task_id, "agent_id": agent_id }));
+    }
+
+    // Review a submitted proof (the remediation verifier, owner or governance). Approving
+    // restores the task's points, credited against the agent's outstanding violation penalties
+    // oldest first and capped at what is outstanding, so decay does not restore them again.
+    // Rejecting returns the task to the agent to submit again.
+    pub fn verify_remediation(&mut self, agent_id: AccountId, task_id: String, approve: bool, note: Option<String>) {
+        self.assert_remediation_verifier();
+        if let Some(note) = &note {
+            assert!(note.len() <= MAX_REMEDIATION_TEXT_LENGTH, "Review note too long");
+        }
+
+        let mut assignments = self.remediation_assignments.get(&agent_id).expect("No remediation tasks for agent");
+        let assignment = assignments
+            .iter_mut()
+            .find(|assignment| assignment.task_id == task_id)
+            .expect("Task not assigned to agent");
+        assert!(assignment.status == RemediationStatus::Submitted, "No submission awaiting verification");
+        assignment.review_note = note;
+
+        let mut restored_bps = 0;
+        if approve {
+            let task = self.remediation_tasks.get(&task_id).expect("Task not found");
+            let mut agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+            self.internal_apply_penalty_decay(&agent_id, &mut agent_rep);
+            let mut credit = from_points(task.points);
+            for violation in agent_rep.violation_history.iter_mut() {
+                let forgiven = std::cmp::min(credit, violation.outstanding_penalty_bps());
+                violation.penalty_forgiven_bps += forgiven;
+                credit -= forgiven;
+                restored_bps += forgiven;
+            }
+            agent_rep.set_score_bps(agent_rep.score_bps.saturating_add(restored_bps));
+            self.internal_save_agent(&agent_id, &agent_rep);
+
+            assignment.status = RemediationStatus::Completed;
+            assignment.verified_by = Some(env::predecessor_account_id());
+            assignment.completed_at = Some(env::block_timestamp());
+            assignment.restored_bps = restored_bps;
+        } else {
+            assignment.status = RemediationStatus::Assigned;
+        }
+        self.remediation_assignments.insert(&agent_id, &assignments);
+        self.remediation_reviews.remove(&(agent_id.clone(), task_id.clone()));
+
+        emit_event(
+            "remediation_verified",
+            json!({
+                "task_id": task_id,
+                "agent_id": agent_id,
+                "approved": approve,
+                "restored_bps": restored_bps,
+            }),
+        );
+    }
+
+    // Get a remediation task by ID
+    pub fn get_remediation_task(&self, task_id: String) -> Option<RemediationTask> {
+        self.remediation_tasks.get(&task_id)
+    }
+
+    // Get a page of the tasks still taking assignments
+    pub fn get_open_remediation_tasks(&self, from_index: u64, limit: u32) -> Vec<RemediationTask> {
+        self.remediation_tasks
+            .values()
+            .filter(|task| task.open)
+            .skip(from_index as usize)
+            .take(std::cmp::min(limit, MAX_REMEDIATION_PAGE) as usize)
+            .collect()
+    }
+
+    // Get the tasks assigned to an agent, optionally only those in one status
+    pub fn get_remediation_assignments(&self, agent_id: AccountId, status: Option<RemediationStatus>) -> Vec<RemediationAssignment> {
+        self.remediation_assignments
+            .get(&agent_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|assignment| status.is_none_or(|status| assignment.status == status))
+            .collect()
+    }
+
+    // Get a page of the submissions awaiting verification
+    pub fn get_pending_remediation_reviews(&self, from_index: u64, limit: u32) -> Vec<RemediationAssignment> {
+        self.remediation_reviews
+            .iter()
+            .skip(from_index as usize)
+            .take(std::cmp::min(limit, MAX_REMEDIATION_PAGE) as usize)
+            .filter_map(|(agent_id, task_id)| {
+                self.remediation_assignments
+                    .get(&agent_id)
+                    .and_then(|assignments| assignments.into_iter().find(|assignment| assignment.task_id == task_id))
+            })
+            .collect()
+    }
+
+    // Panic unless the caller is the remediation verifier, the owner or governance
+    fn assert_remediation_verifier(&self) {
+        let caller = env::predecessor_account_id();
+        assert!(
+            self.remediation_verifier.as_ref() == Some(&caller)
+                || caller == self.owner_id
+                || self.is_governance_member(caller),
+            "Unauthorized: only the remediation verifier, owner or governance can verify remediation"
+        );
+    }
+}
@@ -0,0 +1,379 @@
+// Scenario replay: sequences of contract calls are replayed against the current code, and the
+// outcome of every step is compared with the outcome recorded in the fixture. A scoring or
+// slashing refactor that changes historical behavior fails here instead of silently rescoring
+// agents.
+//
+// No mainnet traffic has been recorded yet. The fixtures are hand-written scenarios, plus
+// example.json, recorded from the synthetic trace in tests/traces to exercise the recorder
+// (replay/recorder.rs), which anonymizes real traces into fixtures once there are some.
+//
+// Fixtures live in tests/scenarios/*.json:
+//
+//   {
+//     "description": "...",
+//     "init": { "owner_id": "...", "token_contract_id": "...", "min_stake_amount": "..." },
+//     "observe": ["agent-1.test"],
+//     "steps": [
+//       {
+//         "at": 1000000000,                  // block timestamp (nanoseconds)
+//         "caller": "client-1.test",
+//         "deposit": "0",                    // attached yoctoNEAR, optional
+//         "method": "add_feedback",
+//         "args": { ... },                   // the call's JSON arguments
+//         "promise_results": [true],         // JSON results the callback sees, optional
+//         "expect": { "agents": { "agent-1.test": { "score": 100 } } }
+//       }
+//     ]
+//   }
+//
+// `expect` is matched as a subset of the step's outcome, so a hand-written fixture can pin just
+// the values it cares about. A step that panics ends the scenario; expect `{"panic": "..."}` to
+// assert the failure.
+//
+// When a change is meant to alter outcomes, rerun with REPLAY_BLESS=1 to rewrite every
+// fixture's `expect` with the full outcomes of the new code, and review the fixture diff like
+// any other change.
+
+use crate::*;
+use near_sdk::serde::Deserialize;
+use near_sdk::serde_json::{self, Value};
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{testing_env, RuntimeFeesConfig, VMConfig};
+use std::collections::BTreeMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+mod recorder;
+
+// Account the contract is deployed to during replay; callbacks are called from it
+const CONTRACT_ACCOUNT: &str = "reputation.test";
+
+#[derive(Deserialize, near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct Scenario {
+    description: String,
+    init: ScenarioInit,
+    // Accounts whose state is captured after every step
+    observe: Vec<AccountId>,
+    steps: Vec<ScenarioStep>,
+}
+
+#[derive(Deserialize, near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct ScenarioInit {
+    owner_id: AccountId,
+    token_contract_id: AccountId,
+    min_stake_amount: U128,
+}
+
+#[derive(Deserialize, near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct ScenarioStep {
+    at: u64,
+    caller: AccountId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deposit: Option<U128>,
+    method: String,
+    args: Value,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    promise_results: Vec<Value>,
+    #[serde(default)]
+    expect: Value,
+}
+
+// State of an observed account after a step
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct AccountSnapshot {
+    stake: U128,
+    // None for accounts that are not registered agents
+    agent: Option<AgentSnapshot>,
+}
+
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct AgentSnapshot {
+    score: u32,
+    trust_level: TrustLevel,
+    status: AgentStatus,
+    total_interactions: u64,
+    successful_interactions: u64,
+    reliability_score: u32,
+    category_scores: CategoryRatings,
+    violations: u64,
+}
+
+// Deserialize a step's arguments into the method's parameters and call it
+macro_rules! replay_call {
+    ($contract:expr, $args:expr, $method:ident($($arg:ident: $ty:ty),*)) => {{
+        #[derive(Deserialize)]
+        #[serde(crate = "near_sdk::serde")]
+        struct Args {
+            $($arg: $ty,)*
+        }
+        let Args { $($arg),* } = serde_json::from_value($args)
+            .unwrap_or_else(|e| panic!("Invalid arguments for {}: {}", stringify!($method), e));
+        let _ = $contract.$method($($arg),*);
+    }};
+}
+
+fn dispatch(contract: &mut AgentReputationContract, method: &str, args: Value) {
+    match method {
+        // Configuration
+        "set_registry" => replay_call!(contract, args, set_registry(near_ai_registry: AccountId)),
+        "set_intents_processor" => replay_call!(contract, args, set_intents_processor(intents_processor: AccountId)),
+        "set_feedback_expiry" => replay_call!(contract, args, set_feedback_expiry(feedback_expiry_period: u64)),
+        "set_category_scoring" => replay_call!(contract, args, set_category_scoring(configs: Vec<CategoryScoringConfig>)),
+        "set_intent_type" => replay_call!(contract, args, set_intent_type(intent_type: String, metadata: IntentTypeMetadata)),
+
+        // Storage, staking and registration
+        "storage_deposit" => replay_call!(contract, args, storage_deposit(account_id: Option<AccountId>, registration_only: Option<bool>)),
+        "ft_on_transfer" => replay_call!(contract, args, ft_on_transfer(sender_id: AccountId, amount: U128, msg: String)),
+        "on_stake_registration_verified" => replay_call!(
+            contract, args,
+            on_stake_registration_verified(agent_id: AccountId, specializations: Vec<String>, amount: U128)
+        ),
+        "register_agent" => replay_call!(contract, args, register_agent(agent_id: AccountId, specializations: Vec<String>)),
+        "on_agent_verified" => replay_call!(
+            contract, args,
+            on_agent_verified(agent_id: AccountId, specializations: Vec<String>, deposit: U128)
+        ),
+        "unstake_itlx" => replay_call!(contract, args, unstake_itlx(amount: U128)),
+        "undelegate" => replay_call!(contract, args, undelegate(agent_id: AccountId, amount: Option<U128>)),
+
+        // Intents and feedback
+        "record_intent" => replay_call!(
+            contract, args,
+            record_intent(
+                intent_id: String,
+                agent_id: AccountId,
+                intent_type: String,
+                parameters: String,
+                deadline: Option<u64>,
+                referrer_id: Option<AccountId>
+            )
+        ),
+        "record_intent_for_client" => replay_call!(
+            contract, args,
+            record_intent_for_client(
+                intent_id: String,
+                agent_id: AccountId,
+                client_id: AccountId,
+                intent_type: String,
+                parameters: String,
+                deadline: Option<u64>,
+                referrer_id: Option<AccountId>
+            )
+        ),
+        "update_intent_status" => replay_call!(
+            contract, args,
+            update_intent_status(intent_id: String, status: IntentStatus, result: Option<String>, nonce: Option<u64>)
+        ),
+        "finalize_intent" => replay_call!(contract, args, finalize_intent(intent_id: String)),
+        "record_delegation" => replay_call!(
+            contract, args,
+            record_delegation(parent_intent_id: String, sub_agent_id: AccountId)
+        ),
+        "accept_delegation" => replay_call!(contract, args, accept_delegation(parent_intent_id: String)),
+        "decline_delegation" => replay_call!(contract, args, decline_delegation(parent_intent_id: String)),
+        "fail_intent" => replay_call!(
+            contract, args,
+            fail_intent(intent_id: String, category: FailureCategory, result: Option<String>)
+        ),
+        "add_feedback" => replay_call!(
+            contract, args,
+            add_feedback(
+                agent_id: AccountId,
+                intent_id: String,
+                rating: u8,
+                category_ratings: CategoryRatings,
+                message: Option<String>,
+                anonymous: Option<bool>
+            )
+        ),
+        "update_feedback" => replay_call!(
+            contract, args,
+            update_feedback(
+                agent_id: AccountId,
+                feedback_index: u64,
+                rating: u8,
+                category_ratings: CategoryRatings,
+                message: Option<String>
+            )
+        ),
+        "revoke_feedback" => replay_call!(contract, args, revoke_feedback(agent_id: AccountId, feedback_index: u64)),
+        "rate_client" => replay_call!(contract, args, rate_client(intent_id: String, rating: u8)),
+        "release_blind_ratings" => replay_call!(contract, args, release_blind_ratings(intent_id: String)),
+
+        // Violations
+        "report_violation" => replay_call!(
+            contract, args,
+            report_violation(agent_id: AccountId, violation_type: ViolationType, description: String, evidence: Vec<Evidence>)
+        ),
+        "appeal_violation" => replay_call!(contract, args, appeal_violation(violation_index: u64, justification: String)),
+        "resolve_appeal" => replay_call!(
+            contract, args,
+            resolve_appeal(agent_id: AccountId, violation_index: u64, overturn: bool, note: Option<String>)
+        ),
+        "submit_report" => replay_call!(
+            contract, args,
+            submit_report(agent_id: AccountId, violation_type: ViolationType, description: String, evidence: Vec<Evidence>)
+        ),
+        "resolve_report" => replay_call!(contract, args, resolve_report(report_id: u64, ruling: ReportStatus, ruling_note: Option<String>)),
+        "suspend_agent" => replay_call!(contract, args, suspend_agent(agent_id: AccountId, reason: String)),
+        "ban_agent" => replay_call!(contract, args, ban_agent(agent_id: AccountId, reason: String)),
+        "reinstate_agent" => replay_call!(contract, args, reinstate_agent(agent_id: AccountId, reason: String)),
+
+        _ => panic!("Method {} is not supported by scenario replay", method),
+    }
+}
+
+fn set_context(step: &ScenarioStep) {
+    let context = VMContextBuilder::new()
+        .current_account_id(AccountId::new_unchecked(CONTRACT_ACCOUNT.to_string()))
+        .predecessor_account_id(step.caller.clone())
+        .signer_account_id(step.caller.clone())
+        .block_timestamp(step.at)
+        .attached_deposit(step.deposit.map_or(0, |deposit| deposit.0))
+        .build();
+    let promise_results = step
+        .promise_results
+        .iter()
+        .map(|result| PromiseResult::Successful(result.to_string().into_bytes()))
+        .collect();
+    testing_env!(context, VMConfig::test(), RuntimeFeesConfig::test(), Default::default(), promise_results);
+}
+
+fn snapshot(contract: &AgentReputationContract, observe: &[AccountId]) -> BTreeMap<String, AccountSnapshot> {
+    observe
+        .iter()
+        .map(|account_id| {
+            let agent = contract.agent_reputations.get(account_id).map(|agent_rep| AgentSnapshot {
+                score: agent_rep.score,
+                trust_level: contract.get_trust_level(agent_rep.score),
+                status: contract.internal_agent_status(account_id),
+                total_interactions: agent_rep.total_interactions,
+                successful_interactions: agent_rep.successful_interactions,
+                reliability_score: agent_rep.reliability_score,
+                category_scores: agent_rep.category_scores.clone(),
+                violations: agent_rep.violation_history.len() as u64,
+            });
+            let snapshot = AccountSnapshot {
+                stake: U128(contract.agent_stakes.get(account_id).unwrap_or(0)),
+                agent,
+            };
+            (account_id.to_string(), snapshot)
+        })
+        .collect()
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(|message| message.to_string()))
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+// Whether every value in `expected` is present and equal in `actual`
+fn is_subset(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::Null, _) => true,
+        (Value::Object(expected), Value::Object(actual)) => expected
+            .iter()
+            .all(|(key, value)| actual.get(key).is_some_and(|actual| is_subset(value, actual))),
+        _ => expected == actual,
+    }
+}
+
+// Replay a scenario, returning the outcome of every step that ran
+fn replay(scenario: &Scenario) -> Vec<Value> {
+    let first = scenario.steps.first().expect("Scenario has no steps");
+    set_context(&ScenarioStep {
+        at: first.at,
+        caller: AccountId::new_unchecked(CONTRACT_ACCOUNT.to_string()),
+        deposit: None,
+        method: "new".to_string(),
+        args: Value::Null,
+        promise_results: Vec::new(),
+        expect: Value::Null,
+    });
+    // Start from empty storage: the mocked blockchain otherwise carries the previous scenario's
+    // state over to this one
+    near_sdk::mock::with_mocked_blockchain(|blockchain| drop(blockchain.take_storage()));
+    let mut contract = AgentReputationContract::new(
+        scenario.init.owner_id.clone(),
+        scenario.init.token_contract_id.clone(),
+        scenario.init.min_stake_amount.0,
+    );
+
+    let mut outcomes = Vec::new();
+    for step in &scenario.steps {
+        set_context(step);
+        let result = catch_unwind(AssertUnwindSafe(|| dispatch(&mut contract, &step.method, step.args.clone())));
+        match result {
+            Ok(()) => outcomes.push(json!({ "agents": snapshot(&contract, &scenario.observe) })),
+            Err(payload) => {
+                // State after a panic is not what the chain would keep, so nothing after it is replayed
+                outcomes.push(json!({ "panic": panic_message(payload) }));
+                break;
+            }
+        }
+    }
+    outcomes
+}
+
+fn scenario_paths() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("scenarios");
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("Cannot read {}: {}", dir.display(), e))
+        .map(|entry| entry.expect("Cannot read scenario entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn recorded_scenarios_replay_unchanged() {
+    let bless = std::env::var("REPLAY_BLESS").is_ok_and(|value| value == "1");
+    let mut failures = Vec::new();
+
+    for path in scenario_paths() {
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        let mut scenario: Scenario = serde_json::from_str(
+            &std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Cannot read {}: {}", name, e)),
+        )
+        .unwrap_or_else(|e| panic!("Invalid scenario {}: {}", name, e));
+        let outcomes = replay(&scenario);
+
+        if bless {
+            for (step, outcome) in scenario.steps.iter_mut().zip(outcomes) {
+                step.expect = outcome;
+            }
+            std::fs::write(&path, serde_json::to_string_pretty(&scenario).unwrap() + "\n")
+                .unwrap_or_else(|e| panic!("Cannot write {}: {}", name, e));
+            continue;
+        }
+
+        for (i, step) in scenario.steps.iter().enumerate() {
+            match outcomes.get(i) {
+                Some(outcome) if is_subset(&step.expect, outcome) => {},
+                Some(outcome) => failures.push(format!(
+                    "{} step {} ({}): expected {}, got {}",
+                    name, i, step.method, step.expect, outcome
+                )),
+                None => {
+                    failures.push(format!("{} step {} ({}): not reached after an earlier panic", name, i, step.method));
+                    break;
+                },
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "Scenario outcomes changed (rerun with REPLAY_BLESS=1 if intended):\n{}",
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,244 @@
+// Scenario recorder: turns a trace of calls the deployed contract received into a replay
+// fixture. Every account is replaced by a stable pseudonym and free-text arguments are masked
+// before anything is written, so fixtures can be committed without identifying anyone.
+//
+// A trace is the contract's function-call actions, oldest first, as exported from an indexer
+// with their arguments decoded from base64 JSON:
+//
+//   {
+//     "description": "...",
+//     "contract_id": "reputation.near",
+//     "init": { "owner_id": "...", "token_contract_id": "...", "min_stake_amount": "..." },
+//     "calls": [
+//       {
+//         "block_timestamp": 1000000000,
+//         "predecessor_id": "alice.near",
+//         "deposit": "0",                    // optional
+//         "method_name": "add_feedback",
+//         "args": { ... },
+//         "promise_results": [true]          // for callbacks, optional
+//       }
+//     ]
+//   }
+//
+// To record one, run
+//
+//   REPLAY_RECORD=path/to/trace.json cargo test -p reputation record_trace -- --ignored
+//
+// which writes tests/scenarios/<trace name>.json with the outcomes of the current code pinned
+// as its expectations. Review the fixture before committing it: the anonymizer only knows the
+// argument shapes listed here.
+
+use super::{replay, Scenario, ScenarioInit, ScenarioStep, CONTRACT_ACCOUNT};
+use crate::*;
+use near_sdk::serde::Deserialize;
+use near_sdk::serde_json::{self, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// Pseudonyms of the accounts a scenario's init refers to
+const OWNER_ACCOUNT: &str = "owner.test";
+const TOKEN_ACCOUNT: &str = "itlx.test";
+
+// Arguments holding free text. Their content is replaced with a mask of the same length, so
+// the storage a call uses, and with it the call's outcome, does not change.
+const FREE_TEXT_ARGS: &[&str] = &[
+    "description",
+    "justification",
+    "message",
+    "note",
+    "parameters",
+    "reason",
+    "result",
+    "ruling_note",
+];
+
+#[derive(Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub(super) struct RecordedTrace {
+    description: String,
+    // Account the contract was deployed to; replayed as CONTRACT_ACCOUNT
+    contract_id: AccountId,
+    init: ScenarioInit,
+    calls: Vec<RecordedCall>,
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+struct RecordedCall {
+    block_timestamp: u64,
+    predecessor_id: AccountId,
+    #[serde(default)]
+    deposit: Option<U128>,
+    method_name: String,
+    args: Value,
+    #[serde(default)]
+    promise_results: Vec<Value>,
+}
+
+// Maps every account in a trace to a stable pseudonym, in order of first appearance
+pub(super) struct Anonymizer {
+    accounts: BTreeMap<String, String>,
+    next: usize,
+}
+
+impl Anonymizer {
+    pub(super) fn new(contract_id: &AccountId, owner_id: &AccountId, token_contract_id: &AccountId) -> Self {
+        let mut accounts = BTreeMap::new();
+        accounts.insert(contract_id.to_string(), CONTRACT_ACCOUNT.to_string());
+        accounts.insert(owner_id.to_string(), OWNER_ACCOUNT.to_string());
+        accounts.insert(token_contract_id.to_string(), TOKEN_ACCOUNT.to_string());
+        Self { accounts, next: 1 }
+    }
+
+    pub(super) fn account(&mut self, account_id: &str) -> AccountId {
+        let pseudonym = match self.accounts.get(account_id) {
+            Some(pseudonym) => pseudonym.clone(),
+            None => {
+                let pseudonym = format!("account-{}.test", self.next);
+                self.next += 1;
+                self.accounts.insert(account_id.to_string(), pseudonym.clone());
+                pseudonym
+            },
+        };
+        AccountId::new_unchecked(pseudonym)
+    }
+
+    // Pseudonyms handed out so far, other than the contract's and the token's
+    fn observed(&self) -> Vec<AccountId> {
+        let mut accounts: Vec<AccountId> = self
+            .accounts
+            .values()
+            .filter(|pseudonym| *pseudonym != CONTRACT_ACCOUNT && *pseudonym != TOKEN_ACCOUNT)
+            .map(|pseudonym| AccountId::new_unchecked(pseudonym.clone()))
+            .collect();
+        accounts.sort();
+        accounts
+    }
+
+    // Anonymize a JSON value found under `key`
+    pub(super) fn value(&mut self, key: Option<&str>, value: Value) -> Value {
+        match value {
+            Value::String(text) if key.is_some_and(|key| FREE_TEXT_ARGS.contains(&key)) => {
+                Value::String("x".repeat(text.len()))
+            },
+            Value::String(text) => {
+                // Arguments such as ft_on_transfer's msg carry JSON of their own
+                if let Ok(inner @ (Value::Object(_) | Value::Array(_))) = serde_json::from_str::<Value>(&text) {
+                    return Value::String(self.value(None, inner).to_string());
+                }
+                if is_account_like(&text) {
+                    Value::String(self.account(&text).to_string())
+                } else {
+                    Value::String(text)
+                }
+            },
+            Value::Array(items) => Value::Array(items.into_iter().map(|item| self.value(key, item)).collect()),
+            Value::Object(fields) => Value::Object(
+                fields
+                    .into_iter()
+                    .map(|(field, value)| {
+                        let value = self.value(Some(&field), value);
+                        (field, value)
+                    })
+                    .collect(),
+            ),
+            other => other,
+        }
+    }
+}
+
+// Whether a string is a named account under a top-level account (alice.near) or an implicit
+// account. Intent ids, tags and other identifiers without a dot are left alone.
+fn is_account_like(text: &str) -> bool {
+    if text.parse::<AccountId>().is_err() {
+        return false;
+    }
+    let implicit = text.len() == 64 && text.bytes().all(|byte| byte.is_ascii_hexdigit());
+    let named = text
+        .rsplit_once('.')
+        .is_some_and(|(_, top_level)| top_level.bytes().all(|byte| byte.is_ascii_lowercase()));
+    implicit || named
+}
+
+// Anonymize a trace and pin the current code's outcomes as the scenario's expectations
+pub(super) fn record(trace: RecordedTrace) -> Scenario {
+    let mut anonymizer = Anonymizer::new(&trace.contract_id, &trace.init.owner_id, &trace.init.token_contract_id);
+    let init = ScenarioInit {
+        owner_id: anonymizer.account(trace.init.owner_id.as_str()),
+        token_contract_id: anonymizer.account(trace.init.token_contract_id.as_str()),
+        min_stake_amount: trace.init.min_stake_amount,
+    };
+    let steps = trace
+        .calls
+        .into_iter()
+        .map(|call| ScenarioStep {
+            at: call.block_timestamp,
+            caller: anonymizer.account(call.predecessor_id.as_str()),
+            deposit: call.deposit,
+            method: call.method_name,
+            args: anonymizer.value(None, call.args),
+            promise_results: call.promise_results,
+            expect: Value::Null,
+        })
+        .collect();
+
+    let mut scenario = Scenario {
+        description: trace.description,
+        init,
+        observe: anonymizer.observed(),
+        steps,
+    };
+    let outcomes = replay(&scenario);
+    for (step, outcome) in scenario.steps.iter_mut().zip(outcomes) {
+        step.expect = outcome;
+    }
+    scenario
+}
+
+fn read_trace(path: &Path) -> RecordedTrace {
+    serde_json::from_str(
+        &std::fs::read_to_string(path).unwrap_or_else(|e| panic!("Cannot read {}: {}", path.display(), e)),
+    )
+    .unwrap_or_else(|e| panic!("Invalid trace {}: {}", path.display(), e))
+}
+
+#[test]
+#[ignore = "records the trace named by REPLAY_RECORD into tests/scenarios"]
+fn record_trace() {
+    let path = std::env::var("REPLAY_RECORD").expect("Set REPLAY_RECORD to the trace to record");
+    let path = Path::new(&path);
+    let scenario = record(read_trace(path));
+
+    let name = path.file_name().expect("Trace path has no file name");
+    let target = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("scenarios").join(name);
+    std::fs::write(&target, serde_json::to_string_pretty(&scenario).unwrap() + "\n")
+        .unwrap_or_else(|e| panic!("Cannot write {}: {}", target.display(), e));
+}
+
+#[test]
+fn recorded_trace_is_anonymized_and_replays() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("traces").join("example.json");
+    let trace_text = std::fs::read_to_string(&path).unwrap();
+    let scenario = record(read_trace(&path));
+    let fixture = serde_json::to_string(&scenario).unwrap();
+
+    // No account or free text from the trace survives
+    for original in ["alice.near", "bob.near", "reputation.near", "Summarize this contract"] {
+        assert!(trace_text.contains(original));
+        assert!(!fixture.contains(original), "{} leaked into the fixture", original);
+    }
+    // The same account gets the same pseudonym wherever it appears
+    assert_eq!(scenario.steps[0].caller.as_str(), "account-1.test");
+    for step in scenario.steps.iter().filter(|step| step.args.get("agent_id").is_some()) {
+        assert_eq!(step.args["agent_id"], "account-1.test");
+    }
+    let mut anonymizer = Anonymizer::new(&"reputation.near".parse().unwrap(), &"owner.near".parse().unwrap(), &"itlx.near".parse().unwrap());
+    assert_eq!(anonymizer.account("alice.near").as_str(), "account-1.test");
+    assert_eq!(
+        anonymizer.value(None, json!({ "msg": r#"{"receiver_id":"alice.near","intent_id":"intent-7"}"# })),
+        json!({ "msg": r#"{"intent_id":"intent-7","receiver_id":"account-1.test"}"# })
+    );
+    assert!(scenario.steps.iter().all(|step| step.expect.get("panic").is_none()));
+    assert_eq!(replay(&scenario), scenario.steps.iter().map(|step| step.expect.clone()).collect::<Vec<_>>());
+}
@@ -0,0 +1,99 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Upper bound on entries archived per write, keeping writes constant-cost after the policy
+// is tightened; the remainder is archived by later writes or prune_expired_feedback
+const MAX_ARCHIVED_PER_CALL: u64 = 50;
+
+// How much feedback and violation history is kept in state per agent. Older entries are
+// emitted as archival events and deleted.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RetentionConfig {
+    // Most recent feedback entries kept per agent
+    pub max_retained_feedback: u64,
+    // Violations are kept for this long after being recorded (nanoseconds)
+    pub violation_retention_period: u64,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            max_retained_feedback: 500,
+            violation_retention_period: 2 * 365 * 24 * 60 * 60 * 1_000_000_000, // 2 years in nanoseconds
+        }
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Get the per-agent history retention policy
+    pub fn get_retention_config(&self) -> RetentionConfig {
+        self.retention_config.clone()
+    }
+
+    // Archive the agent's feedback and violations beyond the retention policy and save the
+    // record if anything changed. Call after charging storage for the current write, so the
+    // released storage is credited to the original payers rather than netted against it.
+    pub(crate) fn internal_apply_retention(&mut self, agent_id: &AccountId, agent_rep: &mut AgentReputation) {
+        let retained_until = agent_rep
+            .feedback_count
+            .saturating_sub(self.retention_config.max_retained_feedback);
+        let feedback_archived =
+            self.internal_remove_oldest_feedback(agent_id, agent_rep, retained_until, MAX_ARCHIVED_PER_CALL);
+        let violations_archived = self.internal_archive_violations(agent_id, agent_rep);
+
+        if feedback_archived > 0 {
+            // Archived feedback may still have been inside the expiry window
+            self.recalculate_reputation_with_categories(agent_id, agent_rep);
+        }
+        if feedback_archived > 0 || violations_archived > 0 {
+            self.internal_save_agent(agent_id, agent_rep);
+        }
+        if feedback_archived > 0 {
+            self.internal_update_shadow_score(agent_id, agent_rep);
+        }
+    }
+
+    // Delete violations older than the retention period, oldest first, emitting each as an
+    // archival event and releasing its reporter's storage. Stops at a violation whose appeal
+    // is still pending.
+    fn internal_archive_violations(&mut self, agent_id: &AccountId, agent_rep: &mut AgentReputation) -> u64 {
+        let now = env::block_timestamp();
+        let retention_period = self.retention_config.violation_retention_period;
+
+        let mut archived = 0;
+        while archived < MAX_ARCHIVED_PER_CALL {
+            match agent_rep.violation_history.first() {
+                Some(violation) if now - violation.timestamp > retention_period && !violation.appeal_pending() => {},
+                _ => break,
+            }
+
+            // The agent record only shrinks when saved, so release the violation's encoded size
+            let violation = agent_rep.violation_history.remove(0);
+            let freed = violation.try_to_vec().map_or(0, |bytes| bytes.len() as u64);
+            self.internal_release_storage(&violation.reporter, freed);
+
+            emit_event(
+                "violation_archived",
+                json!({
+                    "agent_id": agent_id,
+                    "index": agent_rep.violations_archived,
+                    "violation_type": violation.violation_type,
+                    "reporter": violation.reporter,
+                    "description": violation.description,
+                    "evidence": violation.evidence,
+                    "timestamp": violation.timestamp,
+                    "penalty_applied": violation.penalty_applied,
+                    "tokens_slashed": U128(violation.tokens_slashed),
+                    "appeal": violation.appeal,
+                }),
+            );
+
+            agent_rep.violations_archived += 1;
+            archived += 1;
+        }
+
+        archived
+    }
+}
@@ -0,0 +1,278 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Gas for the ft_transfer of claimed rewards
+const GAS_FOR_REWARD_FT_TRANSFER: Gas = Gas(10_000_000_000_000);
+
+// Gas reserved for the claim callback
+const GAS_FOR_REWARD_CALLBACK: Gas = Gas(5_000_000_000_000);
+
+// Reward weight is counted in whole ITLX so stake × accumulator products stay within u128
+const REWARD_WEIGHT_UNIT: Balance = 10u128.pow(18);
+
+// Fixed-point scale of the per-weight reward accumulator
+const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+// Shortest allowed reward epoch (1 hour)
+pub(crate) const MIN_REWARD_EPOCH_LENGTH: u64 = 60 * 60 * 1_000_000_000;
+
+// Reward weight per staked ITLX by trust level (basis points); Novices earn nothing
+fn trust_level_multiplier_bps(level: TrustLevel) -> u32 {
+    match level {
+        TrustLevel::Novice => 0,
+        TrustLevel::Apprentice => 5_000,
+        TrustLevel::Trusted => 10_000,
+        TrustLevel::Expert => 15_000,
+        TrustLevel::Master => 20_000,
+    }
+}
+
+// How fast the reward pool is paid out
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardsConfig {
+    // Length of a reward epoch (nanoseconds)
+    pub epoch_length: u64,
+    // ITLX paid out per epoch while the pool lasts
+    pub reward_per_epoch: U128,
+}
+
+impl Default for RewardsConfig {
+    fn default() -> Self {
+        Self {
+            epoch_length: 24 * 60 * 60 * 1_000_000_000, // 1 day in nanoseconds
+            reward_per_epoch: U128(0),
+        }
+    }
+}
+
+// Reward pool accounting. Each epoch's rewards are spread over the total weight by raising a
+// per-weight accumulator, and each agent's share is settled lazily from the accumulator when
+// its weight changes or it claims, so no call ever iterates agents.
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct RewardPool {
+    // Funded ITLX not yet distributed to any epoch
+    undistributed: Balance,
+    // Sum of all agents' reward weights
+    total_weight: u128,
+    // Rewards per unit of weight since the pool started, scaled by REWARD_PRECISION
+    reward_per_weight: u128,
+    // Last epoch whose rewards were distributed
+    last_epoch: u64,
+}
+
+// An agent's stake in the reward pool
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct RewardAccount {
+    // Stake in whole ITLX
+    stake_units: u128,
+    multiplier_bps: u32,
+    // Accumulator value already accounted for in `accrued`
+    reward_per_weight_paid: u128,
+    // Settled rewards not yet claimed
+    accrued: Balance,
+}
+
+impl RewardAccount {
+    fn weight(&self) -> u128 {
+        self.stake_units * self.multiplier_bps as u128 / 10_000
+    }
+
+    // Move rewards earned since the last settlement into `accrued`
+    fn settle(&mut self, reward_per_weight: u128) {
+        self.accrued += self.weight() * (reward_per_weight - self.reward_per_weight_paid) / REWARD_PRECISION;
+        self.reward_per_weight_paid = reward_per_weight;
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Claim the caller's accrued staking rewards
+    pub fn claim_rewards(&mut self) -> Promise {
+        let agent_id = env::predecessor_account_id();
+        self.internal_distribute_rewards();
+
+        let mut account = self.reward_accounts.get(&agent_id).expect("No rewards to claim");
+        account.settle(self.reward_pool.reward_per_weight);
+        let amount = account.accrued;
+        assert!(amount > 0, "No rewards to claim");
+
+        account.accrued = 0;
+        self.reward_accounts.insert(&agent_id, &account);
+        self.stats.itlx_rewards = self.stats.itlx_rewards.saturating_sub(amount);
+
+        emit_event(
+            "rewards_claimed",
+            json!({
+                "agent_id": agent_id,
+                "amount": U128(amount),
+            }),
+        );
+
+        Promise::new(self.token_contract_id.clone())
+            .function_call(
+                "ft_transfer".to_string(),
+                json!({
+                    "receiver_id": agent_id,
+                    "amount": U128(amount),
+                    "memo": "Staking rewards",
+                }).to_string().into_bytes(),
+                1, // 1 yoctoNEAR
+                GAS_FOR_REWARD_FT_TRANSFER,
+            )
+            .then(
+                Promise::new(env::current_account_id())
+                    .function_call(
+                        "on_rewards_claimed".to_string(),
+                        json!({
+                            "agent_id": agent_id,
+                            "amount": U128(amount),
+                        }).to_string().into_bytes(),
+                        0,
+                        GAS_FOR_REWARD_CALLBACK,
+                    )
+            )
+    }
+
+    // Callback after transferring claimed rewards
+    #[private]
+    pub fn on_rewards_claimed(&mut self, agent_id: AccountId, amount: U128) {
+        if let PromiseResult::Successful(_) = env::promise_result(0) {
+            return;
+        }
+
+        // Transfer failed: the tokens never left, so they are accrued to the agent again
+        let mut account = self.reward_accounts.get(&agent_id).unwrap_or_default();
+        account.accrued += amount.0;
+        self.reward_accounts.insert(&agent_id, &account);
+        self.stats.itlx_rewards += amount.0;
+
+        emit_event(
+            "rewards_claim_rolled_back",
+            json!({
+                "agent_id": agent_id,
+                "amount": amount,
+            }),
+        );
+    }
+
+    // Rewards the agent could claim now, including epochs not yet distributed
+    pub fn get_pending_rewards(&self, agent_id: AccountId) -> U128 {
+        let account = match self.reward_accounts.get(&agent_id) {
+            Some(account) => account,
+            None => return U128(0),
+        };
+        let (_, reward_per_weight) = self.internal_rewards_due();
+        U128(account.accrued + account.weight() * (reward_per_weight - account.reward_per_weight_paid) / REWARD_PRECISION)
+    }
+
+    // Get the reward pool's balance, weight and epoch
+    pub fn get_reward_pool(&self) -> RewardPoolView {
+        let (distributed, _) = self.internal_rewards_due();
+        RewardPoolView {
+            undistributed: U128(self.reward_pool.undistributed - distributed),
+            total_weight: U128(self.reward_pool.total_weight),
+            current_epoch: env::block_timestamp() / self.rewards_config.epoch_length,
+            config: self.rewards_config.clone(),
+        }
+    }
+
+    // Add ITLX received via ft_transfer_call to the reward pool (owner or treasury)
+    pub(crate) fn internal_fund_rewards(&mut self, sender_id: &AccountId, amount: Balance) {
+        assert!(
+            *sender_id == self.owner_id || *sender_id == self.treasury_id,
+            "Only the owner or treasury can fund rewards"
+        );
+        self.internal_distribute_rewards();
+        self.reward_pool.undistributed += amount;
+        self.stats.itlx_rewards += amount;
+
+        emit_event(
+            "reward_pool_funded",
+            json!({
+                "funded_by": sender_id,
+                "amount": U128(amount),
+            }),
+        );
+    }
+
+    // Distribute the rewards of every epoch finished since the last distribution
+    pub(crate) fn internal_distribute_rewards(&mut self) {
+        let (distributed, reward_per_weight) = self.internal_rewards_due();
+        self.reward_pool.undistributed -= distributed;
+        self.reward_pool.reward_per_weight = reward_per_weight;
+        self.reward_pool.last_epoch = env::block_timestamp() / self.rewards_config.epoch_length;
+    }
+
+    // Restart epoch counting after the epoch length changed; call after distributing under
+    // the old length
+    pub(crate) fn internal_reset_reward_epoch(&mut self) {
+        self.reward_pool.last_epoch = env::block_timestamp() / self.rewards_config.epoch_length;
+    }
+
+    // Track an agent's stake change in its reward weight
+    pub(crate) fn internal_sync_reward_stake(&mut self, agent_id: &AccountId, stake: Balance) {
+        if let Some(account) = self.reward_accounts.get(agent_id) {
+            let multiplier_bps = account.multiplier_bps;
+            self.internal_set_reward_weight(agent_id, account, stake / REWARD_WEIGHT_UNIT, multiplier_bps);
+        }
+    }
+
+    // Track an agent's trust level change in its reward weight. Agents get a reward account
+    // once they first reach a rewarded trust level.
+    pub(crate) fn internal_sync_reward_trust_level(&mut self, agent_id: &AccountId, level: TrustLevel) {
+        let multiplier_bps = trust_level_multiplier_bps(level);
+        let account = match self.reward_accounts.get(agent_id) {
+            Some(account) => account,
+            None if multiplier_bps > 0 => RewardAccount::default(),
+            None => return,
+        };
+        let stake_units = self.agent_stakes.get(agent_id).unwrap_or(0) / REWARD_WEIGHT_UNIT;
+        self.internal_set_reward_weight(agent_id, account, stake_units, multiplier_bps);
+    }
+
+    fn internal_set_reward_weight(
+        &mut self,
+        agent_id: &AccountId,
+        mut account: RewardAccount,
+        stake_units: u128,
+        multiplier_bps: u32,
+    ) {
+        self.internal_distribute_rewards();
+        account.settle(self.reward_pool.reward_per_weight);
+
+        self.reward_pool.total_weight -= account.weight();
+        account.stake_units = stake_units;
+        account.multiplier_bps = multiplier_bps;
+        self.reward_pool.total_weight += account.weight();
+
+        self.reward_accounts.insert(agent_id, &account);
+    }
+
+    // Rewards owed for finished epochs and the accumulator after paying them out. Epochs
+    // nobody held weight in pay nothing, leaving their rewards in the pool.
+    fn internal_rewards_due(&self) -> (Balance, u128) {
+        let pool = &self.reward_pool;
+        let current_epoch = env::block_timestamp() / self.rewards_config.epoch_length;
+        let epochs = current_epoch.saturating_sub(pool.last_epoch) as u128;
+        if epochs == 0 || pool.total_weight == 0 {
+            return (0, pool.reward_per_weight);
+        }
+
+        let distributed = std::cmp::min(pool.undistributed, epochs * self.rewards_config.reward_per_epoch.0);
+        let per_weight = distributed * REWARD_PRECISION / pool.total_weight;
+        // Only what the accumulator can represent leaves the pool; the rounding dust stays
+        let distributed = per_weight * pool.total_weight / REWARD_PRECISION;
+        (distributed, pool.reward_per_weight + per_weight)
+    }
+}
+
+// View struct for the reward pool
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardPoolView {
+    undistributed: U128,
+    total_weight: U128,
+    current_epoch: u64,
+    config: RewardsConfig,
+}
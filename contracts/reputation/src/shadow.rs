@@ -0,0 +1,225 @@
+use crate::*;
+use crate::fixed_point::to_points;
+use crate::events::emit_event;
+
+// Most feedback entries a shadow score moves the feedback window over to reach the trial's
+// expiry period
+const MAX_SHADOW_WINDOW_SHIFT: u64 = 50;
+
+// Tunable inputs of the score calculation
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ScoringParams {
+    // Feedback older than this is ignored (nanoseconds)
+    pub feedback_expiry_period: u64,
+    // Whether more recent feedback weighs more
+    pub recency_weighting: bool,
+    // Weight of the success rate against the rating average (basis points)
+    pub success_rate_weight_bps: u32,
+    // Maximum points the stake bonus can add
+    pub max_stake_bonus: u32,
+    // Prior the feedback score is shrunk toward while feedback is scarce
+    pub bayesian_prior: BayesianPrior,
+    // Weight of each failure cause in the success rate
+    pub failure_weights: FailureWeights,
+    // Weight of oracle-reported performance against the feedback score (basis points)
+    #[serde(default)]
+    pub performance_weight_bps: u32,
+}
+
+impl ScoringParams {
+    // Parameters the live score is computed with
+    pub fn live(contract: &AgentReputationContract) -> Self {
+        Self {
+            feedback_expiry_period: contract.feedback_expiry_period,
+            recency_weighting: true,
+            success_rate_weight_bps: 5_000,
+            max_stake_bonus: 15,
+            bayesian_prior: contract.bayesian_prior.clone(),
+            failure_weights: contract.failure_weights.clone(),
+            performance_weight_bps: contract.performance_config.weight_bps,
+        }
+    }
+}
+
+// An active shadow-scoring trial and its running comparison against live scores
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ShadowScoring {
+    params: ScoringParams,
+    started_at: u64,
+    ends_at: u64,
+    // Number of score recalculations compared
+    samples: u64,
+    // Sums over all samples, used for aggregate comparison
+    total_live: u64,
+    total_shadow: u64,
+    total_abs_diff: u64,
+    max_abs_diff: u32,
+    // Recalculations left out because the trial's feedback window was too far from the live
+    // one to shift in a single call
+    skipped: u64,
+}
+
+impl ShadowScoring {
+    fn is_active(&self) -> bool {
+        env::block_timestamp() < self.ends_at
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Start trialling a candidate scoring configuration for `trial_period` nanoseconds
+    pub fn enable_shadow_scoring(&mut self, params: ScoringParams, trial_period: u64) {
+        self.assert_owner_or_governance();
+        assert!(trial_period > 0, "Trial period must be greater than zero");
+        assert!(params.success_rate_weight_bps <= 10_000, "Success rate weight cannot exceed 10000 basis points");
+        assert!(params.max_stake_bonus <= 100, "Stake bonus cannot exceed 100 points");
+        assert!(params.bayesian_prior.prior_mean <= 100, "Prior mean must be between 0 and 100");
+        assert!(params.feedback_expiry_period > 0, "Feedback expiry period must be greater than zero");
+        params.failure_weights.assert_valid();
+
+        let now = env::block_timestamp();
+        self.shadow_scoring = Some(ShadowScoring {
+            params: params.clone(),
+            started_at: now,
+            ends_at: now + trial_period,
+            samples: 0,
+            total_live: 0,
+            total_shadow: 0,
+            total_abs_diff: 0,
+            max_abs_diff: 0,
+            skipped: 0,
+        });
+
+        emit_event(
+            "shadow_scoring_enabled",
+            json!({ "params": params, "started_at": now, "ends_at": now + trial_period }),
+        );
+    }
+
+    // Stop the current shadow-scoring trial
+    pub fn disable_shadow_scoring(&mut self) {
+        self.assert_owner_or_governance();
+        assert!(self.shadow_scoring.take().is_some(), "Shadow scoring is not enabled");
+
+        emit_event("shadow_scoring_disabled", json!({ "disabled_by": env::predecessor_account_id() }));
+    }
+
+    // Get an agent's live score next to its shadow score from the current trial
+    pub fn get_shadow_score(&self, agent_id: AccountId) -> Option<ShadowScoreView> {
+        let shadow = self.shadow_scoring.as_ref()?;
+        let agent_rep = self.agent_reputations.get(&agent_id)?;
+
+        let shadow_score = self
+            .shadow_scores
+            .get(&agent_id)
+            .filter(|(_, computed_at)| *computed_at >= shadow.started_at)
+            .map(|(score, _)| score);
+
+        Some(ShadowScoreView {
+            agent_id,
+            live_score: agent_rep.score,
+            shadow_score,
+        })
+    }
+
+    // Get the aggregate comparison of shadow and live scores for the current trial
+    pub fn get_shadow_comparison(&self) -> Option<ShadowComparisonView> {
+        self.shadow_scoring.as_ref().map(|shadow| {
+            let average = |total: u64| total.checked_div(shadow.samples).unwrap_or(0);
+
+            ShadowComparisonView {
+                params: shadow.params.clone(),
+                active: shadow.is_active(),
+                started_at: shadow.started_at,
+                ends_at: shadow.ends_at,
+                samples: shadow.samples,
+                average_live_score: average(shadow.total_live),
+                average_shadow_score: average(shadow.total_shadow),
+                average_abs_diff: average(shadow.total_abs_diff),
+                max_abs_diff: shadow.max_abs_diff,
+                skipped: shadow.skipped,
+            }
+        })
+    }
+
+    // Recompute an agent's shadow score after its live score changed
+    pub(crate) fn internal_update_shadow_score(&mut self, agent_id: &AccountId, agent_rep: &mut AgentReputation) {
+        let params = match self.shadow_scoring.as_ref() {
+            Some(shadow) if shadow.is_active() => shadow.params.clone(),
+            _ => return,
+        };
+
+        let shadow_score = match self.internal_shadow_score_bps(agent_id, agent_rep, &params) {
+            Ok(Some(score_bps)) => to_points(score_bps),
+            Ok(None) => return,
+            Err(()) => {
+                self.shadow_scoring.as_mut().expect("Shadow scoring not enabled").skipped += 1;
+                return;
+            },
+        };
+        self.shadow_scores.insert(agent_id, &(shadow_score, env::block_timestamp()));
+
+        let shadow = self.shadow_scoring.as_mut().expect("Shadow scoring not enabled");
+        let diff = (agent_rep.score as i64 - shadow_score as i64).unsigned_abs() as u32;
+        shadow.samples += 1;
+        shadow.total_live += agent_rep.score as u64;
+        shadow.total_shadow += shadow_score as u64;
+        shadow.total_abs_diff += diff as u64;
+        shadow.max_abs_diff = std::cmp::max(shadow.max_abs_diff, diff);
+    }
+
+    // Score an agent under the trial's parameters. The live feedback aggregates cover the live
+    // expiry window; under a different trial period they are shifted to the trial's window for
+    // the calculation and put back afterwards. Errs if the windows are more than
+    // MAX_SHADOW_WINDOW_SHIFT entries apart, or the trial's window reaches feedback already
+    // pruned.
+    fn internal_shadow_score_bps(
+        &self,
+        agent_id: &AccountId,
+        agent_rep: &mut AgentReputation,
+        params: &ScoringParams,
+    ) -> Result<Option<u32>, ()> {
+        if params.feedback_expiry_period == self.feedback_expiry_period {
+            return Ok(self.compute_score_bps(agent_id, agent_rep, params));
+        }
+
+        let live = agent_rep.feedback_aggregates.clone();
+        let periods = self.internal_category_expiry_periods_within(params.feedback_expiry_period);
+        let shifted = if params.feedback_expiry_period < self.feedback_expiry_period {
+            agent_rep.expire_feedback(params.feedback_expiry_period, periods, MAX_SHADOW_WINDOW_SHIFT)
+        } else {
+            agent_rep.restore_feedback(params.feedback_expiry_period, periods, MAX_SHADOW_WINDOW_SHIFT)
+                && !agent_rep.window_reaches_pruned_feedback()
+        };
+        let score_bps = shifted.then(|| self.compute_score_bps(agent_id, agent_rep, params));
+        agent_rep.feedback_aggregates = live;
+        score_bps.ok_or(())
+    }
+}
+
+// View struct comparing an agent's live and shadow scores
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ShadowScoreView {
+    agent_id: AccountId,
+    live_score: u32,
+    shadow_score: Option<u32>,
+}
+
+// View struct for the aggregate shadow-scoring comparison
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ShadowComparisonView {
+    params: ScoringParams,
+    active: bool,
+    started_at: u64,
+    ends_at: u64,
+    samples: u64,
+    average_live_score: u64,
+    average_shadow_score: u64,
+    average_abs_diff: u64,
+    max_abs_diff: u32,
+    // Recalculations not compared; see ShadowScoring
+    skipped: u64,
+}
@@ -0,0 +1,300 @@
+use crate::*;
+use crate::events::emit_event;
+use crate::fixed_point::{blend_bps, linear_share, rating_score_bps, to_points, SCORE_SCALE_BPS};
+use near_sdk::collections::UnorderedSet;
+
+// Upper bound on results returned by specialization search
+const MAX_SEARCH_LIMIT: u32 = 100;
+
+// Limits on the specializations an agent can list
+const MAX_SPECIALIZATIONS: usize = 20;
+const MAX_SPECIALIZATION_LENGTH: usize = 64;
+
+// Most specializations tracked per agent; outcomes in further intent types are not scoped
+const MAX_TRACKED_SPECIALIZATIONS: usize = 32;
+
+// Completed intents of a type that verify an agent's listed specialization in it
+const MIN_VERIFIED_SPECIALIZATION_COMPLETIONS: u64 = 5;
+
+// An agent's record in one specialization, fed by intents of that type and their feedback
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+pub struct SpecializationRecord {
+    // Normalized specialization tag
+    tag: String,
+    // Finalized intents of the type the agent completed or failed
+    total_interactions: u64,
+    successful_interactions: u64,
+    // Reviewer-weighted sum of 0-5 star ratings and the weight behind it
+    weighted_rating_total: u128,
+    rating_weight_sum: u128,
+    last_update: u64,
+}
+
+impl SpecializationRecord {
+    fn new(tag: String) -> Self {
+        Self {
+            tag,
+            total_interactions: 0,
+            successful_interactions: 0,
+            weighted_rating_total: 0,
+            rating_weight_sum: 0,
+            last_update: env::block_timestamp(),
+        }
+    }
+
+    // Score (basis points) within the specialization: ratings blended with the success rate as
+    // for the overall score, shrunk toward the prior while the record is short. Each outcome
+    // counts as evidence of one review at 1x weight.
+    fn score_bps(&self, params: &ScoringParams) -> u32 {
+        let rating = (self.rating_weight_sum > 0)
+            .then(|| rating_score_bps(self.weighted_rating_total, self.rating_weight_sum));
+        let success_rate = (self.total_interactions > 0).then(|| {
+            linear_share(SCORE_SCALE_BPS, self.successful_interactions as u128, self.total_interactions as u128)
+        });
+        let raw_score = match (rating, success_rate) {
+            (Some(rating), Some(success_rate)) => blend_bps(rating, success_rate, params.success_rate_weight_bps),
+            (rating, success_rate) => rating.or(success_rate).unwrap_or(0),
+        };
+        let evidence_weight = std::cmp::max(self.rating_weight_sum, self.total_interactions as u128 * 10_000);
+        params.bayesian_prior.apply(raw_score, evidence_weight)
+    }
+}
+
+// Normalized form of a specialization tag used as the index key
+pub(crate) fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Replace the calling agent's specializations, keeping the search index in sync. The
+    // agent pays for any storage the new list adds.
+    pub fn update_specializations(&mut self, specializations: Vec<String>) {
+        let agent_id = env::predecessor_account_id();
+        let mut agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+        assert!(specializations.len() <= MAX_SPECIALIZATIONS, "Too many specializations");
+        let mut tags = Vec::new();
+        for specialization in &specializations {
+            let tag = normalize_tag(specialization);
+            assert!(!tag.is_empty(), "Specializations cannot be empty");
+            assert!(specialization.len() <= MAX_SPECIALIZATION_LENGTH, "Specialization too long");
+            assert!(!tags.contains(&tag), "Duplicate specialization {}", specialization);
+            tags.push(tag);
+        }
+
+        let initial_storage = env::storage_usage();
+        agent_rep.specializations = specializations;
+        self.internal_save_agent(&agent_id, &agent_rep);
+        self.internal_charge_storage(&agent_id, initial_storage);
+
+        emit_event(
+            "specializations_updated",
+            json!({
+                "agent_id": agent_id,
+                "specializations": agent_rep.specializations,
+            }),
+        );
+    }
+
+    // Find agents offering a specialization with at least `min_score` reputation, optionally
+    // only those holding an unexpired audited badge
+    pub fn find_agents_by_specialization(
+        &self,
+        tag: String,
+        min_score: Option<u32>,
+        audited_only: Option<bool>,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<RankedAgentView> {
+        let agents = match self.specialization_index.get(&normalize_tag(&tag)) {
+            Some(agents) => agents,
+            None => return Vec::new(),
+        };
+        let min_score = min_score.unwrap_or(0);
+        let audited_only = audited_only.unwrap_or(false);
+
+        agents
+            .iter()
+            .filter_map(|agent_id| {
+                self.agent_reputations
+                    .get(&agent_id)
+                    .filter(|rep| rep.score >= min_score)
+                    .filter(|_| !audited_only || self.is_audited(&agent_id))
+                    .map(|rep| self.ranked_agent_view(agent_id, rep.score))
+            })
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(std::cmp::min(limit.unwrap_or(MAX_SEARCH_LIMIT), MAX_SEARCH_LIMIT) as usize)
+            .collect()
+    }
+
+    // Number of agents listing a specialization (noised when privacy noise is enabled)
+    pub fn get_specialization_agent_count(&self, tag: String) -> u64 {
+        let tag = normalize_tag(&tag);
+        let count = self.specialization_index
+            .get(&tag)
+            .map(|agents| agents.len())
+            .unwrap_or(0);
+
+        self.internal_private_count(&format!("specialization_agent_count:{}", tag), count)
+    }
+
+    // Get an agent's scores and trust levels in each specialization it has worked in
+    pub fn get_specialization_scores(&self, agent_id: AccountId) -> Vec<SpecializationScoreView> {
+        let params = ScoringParams::live(self);
+        self.specialization_records
+            .get(&agent_id)
+            .unwrap_or_default()
+            .iter()
+            .map(|record| self.specialization_score_view(record, &params))
+            .collect()
+    }
+
+    // Get an agent's score and trust level in one specialization, or None if it has not
+    // worked in it
+    pub fn get_specialization_score(&self, agent_id: AccountId, tag: String) -> Option<SpecializationScoreView> {
+        let tag = normalize_tag(&tag);
+        self.specialization_records
+            .get(&agent_id)?
+            .iter()
+            .find(|record| record.tag == tag)
+            .map(|record| self.specialization_score_view(record, &ScoringParams::live(self)))
+    }
+
+    // The agent's score (basis points) in a specialization; the prior's score if it has not
+    // worked in it
+    pub(crate) fn internal_specialization_score_bps(&self, agent_id: &AccountId, tag: &str) -> u32 {
+        let tag = normalize_tag(tag);
+        let params = ScoringParams::live(self);
+        self.specialization_records
+            .get(agent_id)
+            .unwrap_or_default()
+            .iter()
+            .find(|record| record.tag == tag)
+            .map_or_else(|| params.bayesian_prior.apply(0, 0), |record| record.score_bps(&params))
+    }
+
+    // Whether the agent lists the specialization and has backed it up, either by completing
+    // enough intents of that type or by holding an unexpired audited badge. Listing a tag
+    // alone is self-assigned and proves nothing.
+    pub(crate) fn internal_has_verified_specialization(&self, agent_id: &AccountId, agent_rep: &AgentReputation, tag: &str) -> bool {
+        let tag = normalize_tag(tag);
+        if !agent_rep.specializations.iter().any(|specialization| normalize_tag(specialization) == tag) {
+            return false;
+        }
+        self.is_audited(agent_id)
+            || self
+                .specialization_records
+                .get(agent_id)
+                .unwrap_or_default()
+                .iter()
+                .any(|record| record.tag == tag && record.successful_interactions >= MIN_VERIFIED_SPECIALIZATION_COMPLETIONS)
+    }
+
+    // Count a finalized intent toward the agent's record in the intent's type
+    pub(crate) fn internal_record_specialization_outcome(&mut self, agent_id: &AccountId, intent_type: &str, successful: bool) {
+        self.internal_update_specialization_record(agent_id, intent_type, |record| {
+            record.total_interactions += 1;
+            if successful {
+                record.successful_interactions += 1;
+            }
+        });
+    }
+
+    // Count feedback on an intent toward the agent's record in the intent's type
+    pub(crate) fn internal_record_specialization_rating(&mut self, agent_id: &AccountId, intent_id: &str, rating: u8, reviewer_weight: u32) {
+        let intent_type = match self.intents.get(&intent_id.to_string()) {
+            Some(intent) => intent.intent_type,
+            None => return,
+        };
+        self.internal_update_specialization_record(agent_id, &intent_type, |record| {
+            record.weighted_rating_total += rating as u128 * reviewer_weight as u128;
+            record.rating_weight_sum += reviewer_weight as u128;
+        });
+    }
+
+    fn internal_update_specialization_record<F: FnOnce(&mut SpecializationRecord)>(
+        &mut self,
+        agent_id: &AccountId,
+        intent_type: &str,
+        update: F,
+    ) {
+        let tag = normalize_tag(intent_type);
+        if tag.is_empty() {
+            return;
+        }
+        let mut records = self.specialization_records.get(agent_id).unwrap_or_default();
+        let index = match records.iter().position(|record| record.tag == tag) {
+            Some(index) => index,
+            None if records.len() < MAX_TRACKED_SPECIALIZATIONS => {
+                records.push(SpecializationRecord::new(tag));
+                records.len() - 1
+            },
+            None => return,
+        };
+        update(&mut records[index]);
+        records[index].last_update = env::block_timestamp();
+        self.specialization_records.insert(agent_id, &records);
+    }
+
+    fn specialization_score_view(&self, record: &SpecializationRecord, params: &ScoringParams) -> SpecializationScoreView {
+        let score_bps = record.score_bps(params);
+        SpecializationScoreView {
+            tag: record.tag.clone(),
+            score: to_points(score_bps),
+            score_bps,
+            trust_level: self.get_trust_level(to_points(score_bps)),
+            total_interactions: record.total_interactions,
+            successful_interactions: record.successful_interactions,
+            average_rating_bps: (record.rating_weight_sum > 0)
+                .then(|| rating_score_bps(record.weighted_rating_total, record.rating_weight_sum)),
+            last_update: record.last_update,
+        }
+    }
+
+    // Add and remove inverted index entries for changed specializations
+    pub(crate) fn internal_update_specialization_index(
+        &mut self,
+        agent_id: &AccountId,
+        previous: Option<&AgentReputation>,
+        current: &AgentReputation,
+    ) {
+        let old_tags: Vec<String> = previous
+            .map(|p| p.specializations.iter().map(|t| normalize_tag(t)).collect())
+            .unwrap_or_default();
+        let new_tags: Vec<String> = current.specializations.iter().map(|t| normalize_tag(t)).collect();
+
+        for tag in old_tags.iter().filter(|t| !new_tags.contains(t)) {
+            if let Some(mut agents) = self.specialization_index.get(tag) {
+                agents.remove(agent_id);
+                if agents.is_empty() {
+                    self.specialization_index.remove(tag);
+                } else {
+                    self.specialization_index.insert(tag, &agents);
+                }
+            }
+        }
+
+        for tag in new_tags.iter().filter(|t| !old_tags.contains(t)) {
+            let mut agents = self.specialization_index.get(tag).unwrap_or_else(|| {
+                UnorderedSet::new([b"t".as_slice(), &env::sha256(tag.as_bytes())].concat())
+            });
+            agents.insert(agent_id);
+            self.specialization_index.insert(tag, &agents);
+        }
+    }
+}
+
+// View struct for an agent's standing in one specialization
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SpecializationScoreView {
+    tag: String,
+    score: u32,
+    score_bps: u32,
+    trust_level: TrustLevel,
+    total_interactions: u64,
+    successful_interactions: u64,
+    // Reviewer-weighted average rating on the score scale; None without feedback
+    average_rating_bps: Option<u32>,
+    last_update: u64,
+}
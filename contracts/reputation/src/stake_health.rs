@@ -0,0 +1,128 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Upper bound on the top-up grace period (30 days)
+pub(crate) const MAX_STAKE_GRACE_PERIOD: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+// Reputation points deducted when the grace period runs out with the stake still short
+const STAKE_SHORTFALL_PENALTY: u32 = 5;
+
+// A registered agent's stake falling below the minimum
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct StakeShortfall {
+    since: u64,
+    // Whether the penalty for letting the grace period run out has been applied
+    penalized: bool,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Penalize an agent whose stake is still below the minimum after the grace period, putting
+    // it on probation until the stake is topped up (callable by anyone)
+    pub fn enforce_stake_minimum(&mut self, agent_id: AccountId) {
+        self.internal_update_stake_shortfall(&agent_id);
+        let mut shortfall = self.stake_shortfalls.get(&agent_id).expect("Stake is not below the minimum");
+        assert!(!shortfall.penalized, "Stake shortfall already penalized");
+        let grace_ends_at = shortfall.since + self.stake_grace_period;
+        assert!(env::block_timestamp() >= grace_ends_at, "Grace period ends at {}", grace_ends_at);
+
+        shortfall.penalized = true;
+        self.stake_shortfalls.insert(&agent_id, &shortfall);
+
+        let mut agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+        agent_rep.deduct_score_points(STAKE_SHORTFALL_PENALTY);
+        self.internal_save_agent(&agent_id, &agent_rep);
+        if self.internal_agent_status(&agent_id) == AgentStatus::Active {
+            self.internal_set_agent_status(
+                &agent_id,
+                AgentStatus::Probation,
+                Some("stake below minimum after grace period".to_string()),
+            );
+        }
+
+        emit_event(
+            "stake_shortfall_penalized",
+            json!({
+                "agent_id": agent_id,
+                "penalty": STAKE_SHORTFALL_PENALTY,
+                "new_score": agent_rep.score,
+            }),
+        );
+    }
+
+    // Get how an agent's stake compares to the minimum and how long it has left to top up
+    pub fn get_stake_health(&self, agent_id: AccountId) -> Option<StakeHealthView> {
+        self.agent_reputations.get(&agent_id)?;
+        let stake = self.agent_stakes.get(&agent_id).unwrap_or(0);
+        let shortfall = self.stake_shortfalls.get(&agent_id);
+        let grace_ends_at = shortfall.as_ref().map(|shortfall| shortfall.since + self.stake_grace_period);
+
+        Some(StakeHealthView {
+            stake: U128(stake),
+            min_stake: U128(self.min_stake_amount),
+            below_minimum: stake < self.min_stake_amount,
+            shortfall_since: shortfall.as_ref().map(|shortfall| shortfall.since),
+            grace_ends_at,
+            grace_remaining: grace_ends_at.map(|ends_at| ends_at.saturating_sub(env::block_timestamp())),
+            penalized: shortfall.is_some_and(|shortfall| shortfall.penalized),
+        })
+    }
+
+    // Start the grace period when a registered agent's stake falls below the minimum, and end
+    // it, lifting any probation it caused, once the stake is back
+    pub(crate) fn internal_update_stake_shortfall(&mut self, agent_id: &AccountId) {
+        let agent_rep = match self.agent_reputations.get(agent_id) {
+            Some(agent_rep) => agent_rep,
+            None => return,
+        };
+        let stake = self.agent_stakes.get(agent_id).unwrap_or(0);
+        let shortfall = self.stake_shortfalls.get(agent_id);
+
+        if stake < self.min_stake_amount {
+            if shortfall.is_none() {
+                let now = env::block_timestamp();
+                self.stake_shortfalls.insert(agent_id, &StakeShortfall { since: now, penalized: false });
+
+                emit_event(
+                    "stake_shortfall_started",
+                    json!({
+                        "agent_id": agent_id,
+                        "stake": U128(stake),
+                        "min_stake": U128(self.min_stake_amount),
+                        "grace_ends_at": now + self.stake_grace_period,
+                    }),
+                );
+            }
+        } else if let Some(shortfall) = shortfall {
+            self.stake_shortfalls.remove(agent_id);
+            if shortfall.penalized
+                && self.internal_agent_status(agent_id) == AgentStatus::Probation
+                && agent_rep.score >= self.agent_status_config.probation_score_threshold
+            {
+                self.internal_set_agent_status(agent_id, AgentStatus::Active, Some("stake restored".to_string()));
+            }
+
+            emit_event("stake_shortfall_cured", json!({ "agent_id": agent_id, "stake": U128(stake) }));
+        }
+    }
+
+    // Whether the agent is on probation for letting a stake shortfall run past the grace period
+    pub(crate) fn internal_stake_penalized(&self, agent_id: &AccountId) -> bool {
+        self.stake_shortfalls.get(agent_id).is_some_and(|shortfall| shortfall.penalized)
+    }
+}
+
+// View struct for an agent's stake against the minimum
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StakeHealthView {
+    stake: U128,
+    min_stake: U128,
+    below_minimum: bool,
+    // When the stake fell below the minimum, if it still is
+    shortfall_since: Option<u64>,
+    grace_ends_at: Option<u64>,
+    // Nanoseconds left to top up before the penalty can be applied
+    grace_remaining: Option<u64>,
+    penalized: bool,
+}
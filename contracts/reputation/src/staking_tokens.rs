@@ -0,0 +1,281 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Decimals of ITLX, the unit token weights are expressed against
+const ITLX_DECIMALS: u8 = 18;
+
+// Most tokens accepted for staking besides ITLX
+const MAX_STAKING_TOKENS: usize = 10;
+
+// Highest conversion weight: one whole token worth 100 ITLX
+const MAX_TOKEN_WEIGHT_BPS: u32 = 1_000_000;
+
+// Gas for the ft_transfer returning unstaked tokens
+const GAS_FOR_TOKEN_UNSTAKE_TRANSFER: Gas = Gas(10_000_000_000_000);
+
+// Gas reserved for on_token_unstake_complete
+const GAS_FOR_TOKEN_UNSTAKE_CALLBACK: Gas = Gas(10_000_000_000_000);
+
+// A token accepted for staking besides ITLX. Stakes in it count toward the stake bonus at
+// their ITLX-equivalent value, on top of an ITLX stake of at least the minimum; the minimum
+// stake, slashing and delegation stay in ITLX.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StakingToken {
+    // Decimals of the token's NEP-141 metadata
+    pub decimals: u8,
+    // ITLX-equivalent value of one whole token (basis points of one ITLX)
+    pub weight_bps: u32,
+    // Whether new stakes are accepted; existing stakes keep counting and can be withdrawn
+    pub accepting: bool,
+}
+
+impl StakingToken {
+    // ITLX-equivalent value of an amount of the token
+    pub(crate) fn itlx_equivalent(&self, amount: Balance) -> Balance {
+        let normalized = if self.decimals >= ITLX_DECIMALS {
+            amount / 10u128.pow((self.decimals - ITLX_DECIMALS) as u32)
+        } else {
+            amount.saturating_mul(10u128.pow((ITLX_DECIMALS - self.decimals) as u32))
+        };
+        normalized.saturating_mul(self.weight_bps as u128) / 10_000
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Accept a token for staking, or update its settings (owner or governance)
+    pub fn set_staking_token(&mut self, token_id: AccountId, token: StakingToken) {
+        self.assert_owner_or_governance();
+        assert_ne!(token_id, self.token_contract_id, "ITLX is always accepted for staking");
+        assert!(token.decimals <= 36, "Token decimals out of range");
+        assert!(token.weight_bps <= MAX_TOKEN_WEIGHT_BPS, "Token weight exceeds maximum");
+        assert!(
+            self.staking_tokens.get(&token_id).is_some() || self.staking_tokens.len() < MAX_STAKING_TOKENS as u64,
+            "Too many staking tokens"
+        );
+
+        let old_value = self.staking_tokens.insert(&token_id, &token);
+
+        self.record_config_change(&format!("staking_token.{}", token_id), json!(old_value), json!(token));
+    }
+
+    // Update a staking token's conversion weight (the staking rate oracle, owner or
+    // governance). Stake bonuses pick it up the next time each agent is rescored.
+    pub fn set_staking_token_weight(&mut self, token_id: AccountId, weight_bps: u32) {
+        let caller = env::predecessor_account_id();
+        assert!(
+            self.staking_rate_oracle.as_ref() == Some(&caller)
+                || caller == self.owner_id
+                || self.is_governance_member(caller),
+            "Unauthorized: only the staking rate oracle, owner or governance can set token weights"
+        );
+        assert!(weight_bps <= MAX_TOKEN_WEIGHT_BPS, "Token weight exceeds maximum");
+
+        let mut token = self.staking_tokens.get(&token_id).expect("Not a staking token");
+        let old_value = token.weight_bps;
+        token.weight_bps = weight_bps;
+        self.staking_tokens.insert(&token_id, &token);
+
+        emit_event(
+            "staking_token_weight_updated",
+            json!({
+                "token_id": token_id,
+                "old_weight_bps": old_value,
+                "weight_bps": weight_bps,
+            }),
+        );
+    }
+
+    // Get every token accepted for staking, ITLX first
+    pub fn get_staking_tokens(&self) -> Vec<StakingTokenView> {
+        std::iter::once(StakingTokenView {
+            token_id: self.token_contract_id.clone(),
+            decimals: ITLX_DECIMALS,
+            weight_bps: 10_000,
+            accepting: true,
+        })
+        .chain(self.staking_tokens.iter().map(|(token_id, token)| StakingTokenView {
+            token_id,
+            decimals: token.decimals,
+            weight_bps: token.weight_bps,
+            accepting: token.accepting,
+        }))
+        .collect()
+    }
+
+    // Get an agent's stakes in each token with their ITLX-equivalent values
+    pub fn get_agent_stakes(&self, agent_id: AccountId) -> AgentStakesView {
+        let itlx = self.agent_stakes.get(&agent_id).unwrap_or(0);
+        let tokens: Vec<TokenStakeView> = self
+            .token_stakes
+            .get(&agent_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(token_id, amount)| TokenStakeView {
+                itlx_equivalent: U128(self.internal_itlx_equivalent(&token_id, amount)),
+                token_id,
+                amount: U128(amount),
+            })
+            .collect();
+        let weighted_total = itlx + tokens.iter().map(|stake| stake.itlx_equivalent.0).sum::<Balance>();
+
+        AgentStakesView {
+            agent_id,
+            itlx: U128(itlx),
+            tokens,
+            weighted_total: U128(weighted_total),
+        }
+    }
+
+    // Withdraw staked tokens of a non-ITLX staking token, restored if the transfer fails
+    pub fn unstake_token(&mut self, token_id: AccountId, amount: U128) -> Promise {
+        self.assert_not_paused(PausableFeature::Unstaking);
+        let agent_id = env::predecessor_account_id();
+
+        let current = self.internal_token_stake(&agent_id, &token_id);
+        assert!(amount.0 > 0 && current >= amount.0, "Not enough staked tokens");
+        let initial_storage = env::storage_usage();
+        self.internal_set_token_stake(&agent_id, &token_id, current - amount.0);
+        self.internal_track_storage(&agent_id, initial_storage);
+        self.internal_record_activity(&agent_id);
+        self.update_reputation_on_stake_change(agent_id.clone());
+
+        Promise::new(token_id.clone())
+            .function_call(
+                "ft_transfer".to_string(),
+                json!({
+                    "receiver_id": agent_id,
+                    "amount": amount,
+                }).to_string().into_bytes(),
+                1, // 1 yoctoNEAR
+                GAS_FOR_TOKEN_UNSTAKE_TRANSFER,
+            )
+            .then(
+                Promise::new(env::current_account_id())
+                    .function_call(
+                        "on_token_unstake_complete".to_string(),
+                        json!({
+                            "agent_id": agent_id,
+                            "token_id": token_id,
+                            "amount": amount,
+                        }).to_string().into_bytes(),
+                        0,
+                        GAS_FOR_TOKEN_UNSTAKE_CALLBACK,
+                    )
+            )
+    }
+
+    // Callback after returning unstaked tokens
+    #[private]
+    pub fn on_token_unstake_complete(&mut self, agent_id: AccountId, token_id: AccountId, amount: U128) {
+        if let PromiseResult::Successful(_) = env::promise_result(0) {
+            return;
+        }
+
+        // Transfer failed: the tokens never left, so they go back to the stake
+        let initial_storage = env::storage_usage();
+        let current = self.internal_token_stake(&agent_id, &token_id);
+        self.internal_set_token_stake(&agent_id, &token_id, current + amount.0);
+        self.internal_track_storage(&agent_id, initial_storage);
+
+        emit_event(
+            "token_unstake_rolled_back",
+            json!({
+                "agent_id": agent_id,
+                "token_id": token_id,
+                "amount": amount,
+            }),
+        );
+    }
+
+    // Credit a transfer of a non-ITLX staking token to the sender's stake. Only registered
+    // agents can stake other tokens, and they pay for the storage of a new stake.
+    pub(crate) fn internal_stake_token(&mut self, token_id: &AccountId, sender_id: &AccountId, amount: Balance) {
+        self.assert_not_paused(PausableFeature::Staking);
+        let token = self.staking_tokens.get(token_id).expect("Token not accepted for staking");
+        assert!(token.accepting, "Token not accepting new stakes");
+        assert!(self.agent_reputations.get(sender_id).is_some(), "Agent not registered");
+
+        let initial_storage = env::storage_usage();
+        let current = self.internal_token_stake(sender_id, token_id);
+        self.internal_set_token_stake(sender_id, token_id, current + amount);
+        self.internal_charge_storage(sender_id, initial_storage);
+        self.internal_record_activity(sender_id);
+        self.update_reputation_on_stake_change(sender_id.clone());
+
+        emit_event(
+            "token_staked",
+            json!({
+                "agent_id": sender_id,
+                "token_id": token_id,
+                "amount": U128(amount),
+            }),
+        );
+    }
+
+    // ITLX-equivalent value of the agent's stakes in other tokens
+    pub(crate) fn internal_weighted_token_stake(&self, agent_id: &AccountId) -> Balance {
+        self.token_stakes
+            .get(agent_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(token_id, amount)| self.internal_itlx_equivalent(&token_id, amount))
+            .sum()
+    }
+
+    fn internal_itlx_equivalent(&self, token_id: &AccountId, amount: Balance) -> Balance {
+        self.staking_tokens.get(token_id).map_or(0, |token| token.itlx_equivalent(amount))
+    }
+
+    fn internal_token_stake(&self, agent_id: &AccountId, token_id: &AccountId) -> Balance {
+        self.token_stakes
+            .get(agent_id)
+            .and_then(|stakes| stakes.into_iter().find(|(id, _)| id == token_id))
+            .map_or(0, |(_, amount)| amount)
+    }
+
+    fn internal_set_token_stake(&mut self, agent_id: &AccountId, token_id: &AccountId, amount: Balance) {
+        let mut stakes = self.token_stakes.get(agent_id).unwrap_or_default();
+        stakes.retain(|(id, _)| id != token_id);
+        if amount > 0 {
+            stakes.push((token_id.clone(), amount));
+        }
+        if stakes.is_empty() {
+            self.token_stakes.remove(agent_id);
+        } else {
+            self.token_stakes.insert(agent_id, &stakes);
+        }
+    }
+}
+
+// View struct for a token accepted for staking
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StakingTokenView {
+    token_id: AccountId,
+    decimals: u8,
+    weight_bps: u32,
+    accepting: bool,
+}
+
+// View struct for an agent's stake in one non-ITLX token
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenStakeView {
+    token_id: AccountId,
+    amount: U128,
+    itlx_equivalent: U128,
+}
+
+// View struct for an agent's stakes across tokens
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentStakesView {
+    agent_id: AccountId,
+    itlx: U128,
+    tokens: Vec<TokenStakeView>,
+    // ITLX plus the ITLX-equivalent value of the other tokens, as counted for the stake bonus
+    // once the ITLX stake reaches the minimum
+    weighted_total: U128,
+}
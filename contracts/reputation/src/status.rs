@@ -0,0 +1,215 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Upper bound on the length of a status change reason
+const MAX_STATUS_REASON_LENGTH: usize = 500;
+
+// Standing of an agent between good health and removal. Probation follows the score
+// automatically; suspension follows repeated violations or a governance decision, and only
+// governance lifts it. Suspended and banned agents cannot take new work.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum AgentStatus {
+    Active,
+    // Score fell below the probation threshold; the agent can still work
+    Probation,
+    Suspended,
+    Banned,
+}
+
+impl AgentStatus {
+    // Whether the agent can be given new work and act on the registry's authorization
+    pub fn in_good_standing(&self) -> bool {
+        matches!(self, AgentStatus::Active | AgentStatus::Probation)
+    }
+}
+
+// Rules moving agents between statuses automatically
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentStatusConfig {
+    // Active agents scoring below this go on probation
+    pub probation_score_threshold: u32,
+    // Agents on probation return to active at or above this score
+    pub probation_exit_score: u32,
+    // Violations within the window that suspend an agent
+    pub suspension_violation_count: u32,
+    // Window violations are counted over (nanoseconds)
+    pub suspension_violation_window: u64,
+}
+
+impl Default for AgentStatusConfig {
+    fn default() -> Self {
+        Self {
+            probation_score_threshold: 25,
+            probation_exit_score: 35,
+            suspension_violation_count: 3,
+            suspension_violation_window: 180 * 24 * 60 * 60 * 1_000_000_000, // 180 days in nanoseconds
+        }
+    }
+}
+
+// An agent's status other than the default of active since registration
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct AgentStatusRecord {
+    status: AgentStatus,
+    since: u64,
+    reason: Option<String>,
+    // Violations before the last reinstatement no longer count toward suspension
+    reinstated_at: Option<u64>,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Suspend an agent (the governance DAO if configured, otherwise the owner)
+    pub fn suspend_agent(&mut self, agent_id: AccountId, reason: String) {
+        self.assert_violation_authority();
+        let current = self.internal_agent_status(&agent_id);
+        assert!(current != AgentStatus::Banned, "Agent is banned");
+
+        self.internal_set_agent_status(&agent_id, AgentStatus::Suspended, Some(reason));
+    }
+
+    // Ban an agent (the governance DAO if configured, otherwise the owner)
+    pub fn ban_agent(&mut self, agent_id: AccountId, reason: String) {
+        self.assert_violation_authority();
+
+        self.internal_set_agent_status(&agent_id, AgentStatus::Banned, Some(reason));
+    }
+
+    // Lift a suspension or ban (the governance DAO if configured, otherwise the owner). The
+    // agent returns on probation if its score is below the probation threshold, and earlier
+    // violations no longer count toward suspension.
+    pub fn reinstate_agent(&mut self, agent_id: AccountId, reason: String) {
+        self.assert_violation_authority();
+        let agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+        assert!(
+            !self.internal_agent_status(&agent_id).in_good_standing(),
+            "Agent is not suspended or banned"
+        );
+
+        let status = if agent_rep.score < self.agent_status_config.probation_score_threshold {
+            AgentStatus::Probation
+        } else {
+            AgentStatus::Active
+        };
+        self.internal_set_agent_status(&agent_id, status, Some(reason));
+        if let Some(mut record) = self.agent_statuses.get(&agent_id) {
+            record.reinstated_at = Some(env::block_timestamp());
+            self.agent_statuses.insert(&agent_id, &record);
+        }
+    }
+
+    // Get an agent's status, or None if it is not registered
+    pub fn get_agent_status(&self, agent_id: AccountId) -> Option<AgentStatusView> {
+        let agent_rep = self.agent_reputations.get(&agent_id)?;
+        Some(match self.agent_statuses.get(&agent_id) {
+            Some(record) => AgentStatusView {
+                status: record.status,
+                since: record.since,
+                reason: record.reason,
+            },
+            None => AgentStatusView {
+                status: AgentStatus::Active,
+                since: agent_rep.registered_at,
+                reason: None,
+            },
+        })
+    }
+
+    // Get the rules for automatic probation and suspension
+    pub fn get_agent_status_config(&self) -> AgentStatusConfig {
+        self.agent_status_config.clone()
+    }
+
+    pub(crate) fn internal_agent_status(&self, agent_id: &AccountId) -> AgentStatus {
+        self.agent_statuses.get(agent_id).map_or(AgentStatus::Active, |record| record.status)
+    }
+
+    // Panic unless the agent is registered and may take new work
+    pub(crate) fn assert_agent_in_good_standing(&self, agent_id: &AccountId) {
+        assert!(
+            self.internal_agent_status(agent_id).in_good_standing(),
+            "Agent is suspended or banned"
+        );
+    }
+
+    // Move an agent onto or off probation as its score crosses the thresholds
+    pub(crate) fn internal_update_probation(&mut self, agent_id: &AccountId, score: u32) {
+        let config = &self.agent_status_config;
+        let status = match self.internal_agent_status(agent_id) {
+            AgentStatus::Active if score < config.probation_score_threshold => AgentStatus::Probation,
+            AgentStatus::Probation if score >= config.probation_exit_score && !self.internal_stake_penalized(agent_id) => {
+                AgentStatus::Active
+            },
+            _ => return,
+        };
+        self.internal_set_agent_status(agent_id, status, None);
+    }
+
+    // Suspend an agent in good standing once its violations since the window start, or since
+    // its last reinstatement, reach the suspension count. Violations overturned on appeal do
+    // not count.
+    pub(crate) fn internal_check_violation_suspension(&mut self, agent_id: &AccountId, agent_rep: &AgentReputation) {
+        let record = self.agent_statuses.get(agent_id);
+        if record.as_ref().is_some_and(|record| !record.status.in_good_standing()) {
+            return;
+        }
+
+        let config = &self.agent_status_config;
+        let window_start = env::block_timestamp().saturating_sub(config.suspension_violation_window);
+        let counted_from = std::cmp::max(window_start, record.and_then(|record| record.reinstated_at).unwrap_or(0));
+        let recent = agent_rep
+            .violation_history
+            .iter()
+            .filter(|violation| violation.timestamp >= counted_from && !violation.overturned())
+            .count() as u32;
+
+        if recent >= config.suspension_violation_count {
+            let reason = format!("{} violations within the suspension window", recent);
+            self.internal_set_agent_status(agent_id, AgentStatus::Suspended, Some(reason));
+        }
+    }
+
+    pub(crate) fn internal_set_agent_status(&mut self, agent_id: &AccountId, status: AgentStatus, reason: Option<String>) {
+        assert!(self.agent_reputations.get(agent_id).is_some(), "Agent not registered");
+        if let Some(reason) = &reason {
+            assert!(reason.len() <= MAX_STATUS_REASON_LENGTH, "Status reason too long");
+        }
+
+        let previous = self.agent_statuses.get(agent_id);
+        let old_status = previous.as_ref().map_or(AgentStatus::Active, |record| record.status);
+        if old_status == status {
+            return;
+        }
+
+        self.agent_statuses.insert(
+            agent_id,
+            &AgentStatusRecord {
+                status,
+                since: env::block_timestamp(),
+                reason: reason.clone(),
+                reinstated_at: previous.and_then(|record| record.reinstated_at),
+            },
+        );
+
+        emit_event(
+            "agent_status_changed",
+            json!({
+                "agent_id": agent_id,
+                "old_status": old_status,
+                "new_status": status,
+                "reason": reason,
+            }),
+        );
+    }
+}
+
+// View struct for an agent's status
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AgentStatusView {
+    status: AgentStatus,
+    since: u64,
+    reason: Option<String>,
+}
@@ -0,0 +1,214 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Minimum storage balance, covering an agent registration (0.01 NEAR, about 1 KB)
+const STORAGE_MIN_BALANCE: Balance = 10_000_000_000_000_000_000_000;
+
+// NEAR deposited by an account for the storage its writes use
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct StorageAccount {
+    deposit: Balance,
+    // Contract storage attributed to the account's writes, in bytes
+    used_bytes: u64,
+}
+
+impl StorageAccount {
+    fn used_balance(&self) -> Balance {
+        self.used_bytes as Balance * env::storage_byte_cost()
+    }
+
+    fn available(&self) -> Balance {
+        self.deposit.saturating_sub(self.used_balance())
+    }
+
+    fn balance(&self) -> StorageBalance {
+        StorageBalance {
+            total: U128(self.deposit),
+            available: U128(self.available()),
+        }
+    }
+}
+
+// NEP-145 storage balance
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalance {
+    total: U128,
+    available: U128,
+}
+
+// NEP-145 storage balance bounds
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalanceBounds {
+    min: U128,
+    max: Option<U128>,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // NEP-145: deposit NEAR for the storage of `account_id` (default: the caller). Agents,
+    // reviewers, clients and violation reporters pay for the data they add.
+    #[payable]
+    pub fn storage_deposit(&mut self, account_id: Option<AccountId>, registration_only: Option<bool>) -> StorageBalance {
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let amount = env::attached_deposit();
+        let mut account = self.storage_accounts.get(&account_id);
+
+        if registration_only.unwrap_or(false) {
+            // Registering only takes the minimum balance and refunds the rest
+            if account.is_some() {
+                if amount > 0 {
+                    Promise::new(env::predecessor_account_id()).transfer(amount);
+                }
+            } else {
+                assert!(amount >= STORAGE_MIN_BALANCE, "Deposit below the minimum storage balance");
+                if amount > STORAGE_MIN_BALANCE {
+                    Promise::new(env::predecessor_account_id()).transfer(amount - STORAGE_MIN_BALANCE);
+                }
+                account = Some(StorageAccount {
+                    deposit: STORAGE_MIN_BALANCE,
+                    used_bytes: 0,
+                });
+            }
+        } else {
+            let mut existing = account.unwrap_or_default();
+            assert!(
+                existing.deposit + amount >= STORAGE_MIN_BALANCE,
+                "Deposit below the minimum storage balance"
+            );
+            existing.deposit += amount;
+            account = Some(existing);
+        }
+
+        let account = account.unwrap();
+        self.storage_accounts.insert(&account_id, &account);
+
+        emit_event(
+            "storage_deposit",
+            json!({
+                "account_id": account_id,
+                "amount": U128(amount),
+            }),
+        );
+
+        account.balance()
+    }
+
+    // NEP-145: withdraw NEAR not needed for the caller's storage (all available if `amount` is None)
+    #[payable]
+    pub fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        assert_eq!(env::attached_deposit(), 1, "Requires attached deposit of exactly 1 yoctoNEAR");
+        let account_id = env::predecessor_account_id();
+        let mut account = self.storage_accounts.get(&account_id).expect("Account not registered for storage");
+
+        let available = account.available();
+        let amount = amount.map_or(available, |amount| amount.0);
+        assert!(amount <= available, "Withdrawal exceeds available storage balance");
+
+        if amount > 0 {
+            account.deposit -= amount;
+            self.storage_accounts.insert(&account_id, &account);
+            Promise::new(account_id).transfer(amount);
+        }
+
+        account.balance()
+    }
+
+    // NEP-145: close the caller's storage account and refund its deposit. Only possible while
+    // none of the contract's storage is attributed to the account; `force` is not supported.
+    #[payable]
+    pub fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        assert_eq!(env::attached_deposit(), 1, "Requires attached deposit of exactly 1 yoctoNEAR");
+        assert!(!force.unwrap_or(false), "Forced storage unregistration is not supported");
+        let account_id = env::predecessor_account_id();
+
+        match self.storage_accounts.get(&account_id) {
+            Some(account) => {
+                assert_eq!(account.used_bytes, 0, "Account still uses storage");
+                self.storage_accounts.remove(&account_id);
+                if account.deposit > 0 {
+                    Promise::new(account_id).transfer(account.deposit);
+                }
+                true
+            },
+            None => false,
+        }
+    }
+
+    // NEP-145
+    pub fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds {
+            min: U128(STORAGE_MIN_BALANCE),
+            max: None,
+        }
+    }
+
+    // NEP-145
+    pub fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        self.storage_accounts.get(&account_id).map(|account| account.balance())
+    }
+
+    // Panic unless the account's storage balance, plus `extra_deposit` about to be credited,
+    // covers at least the minimum balance
+    pub(crate) fn assert_storage_registered(&self, account_id: &AccountId, extra_deposit: Balance) {
+        let available = self.storage_accounts.get(account_id).map_or(0, |account| account.available());
+        assert!(
+            available + extra_deposit >= STORAGE_MIN_BALANCE,
+            "Insufficient storage balance; call storage_deposit first"
+        );
+    }
+
+    // Add NEAR to an account's storage deposit
+    pub(crate) fn internal_credit_storage(&mut self, account_id: &AccountId, amount: Balance) {
+        let mut account = self.storage_accounts.get(account_id).unwrap_or_default();
+        account.deposit += amount;
+        self.storage_accounts.insert(account_id, &account);
+    }
+
+    // Take up to `amount` of an account's unused storage balance back out of its deposit,
+    // returning how much was taken
+    #[cfg(feature = "escrow")]
+    pub(crate) fn internal_take_available_storage(&mut self, account_id: &AccountId, amount: Balance) -> Balance {
+        let mut account = match self.storage_accounts.get(account_id) {
+            Some(account) => account,
+            None => return 0,
+        };
+        let taken = std::cmp::min(amount, account.available());
+        account.deposit -= taken;
+        self.storage_accounts.insert(account_id, &account);
+        taken
+    }
+
+    // Attribute the storage change since `initial_usage` to the account. Returns false if the
+    // account's deposit no longer covers its usage.
+    pub(crate) fn internal_track_storage(&mut self, account_id: &AccountId, initial_usage: u64) -> bool {
+        let mut account = self.storage_accounts.get(account_id).unwrap_or_default();
+        let current_usage = env::storage_usage();
+        if current_usage >= initial_usage {
+            account.used_bytes += current_usage - initial_usage;
+        } else {
+            account.used_bytes = account.used_bytes.saturating_sub(initial_usage - current_usage);
+        }
+        let covered = account.deposit >= account.used_balance();
+        self.storage_accounts.insert(account_id, &account);
+        covered
+    }
+
+    // Release storage attributed to an account, e.g. when the data it paid for is deleted
+    pub(crate) fn internal_release_storage(&mut self, account_id: &AccountId, freed_bytes: u64) {
+        if let Some(mut account) = self.storage_accounts.get(account_id) {
+            account.used_bytes = account.used_bytes.saturating_sub(freed_bytes);
+            self.storage_accounts.insert(account_id, &account);
+        }
+    }
+
+    // Attribute the storage change since `initial_usage` to the account, panicking (and so
+    // reverting the write) if the account's deposit does not cover it
+    pub(crate) fn internal_charge_storage(&mut self, account_id: &AccountId, initial_usage: u64) {
+        assert!(
+            self.internal_track_storage(account_id, initial_usage),
+            "Insufficient storage balance; call storage_deposit first"
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use crate::*;
+use crate::authorization::authorization_message;
+use crate::events::emit_event;
+
+// Upper bound on the governance-set delegation depth
+pub(crate) const MAX_TASK_DELEGATION_DEPTH: u8 = 10;
+
+// Sub-agents one intent's delegation tree can hold
+const MAX_SUB_AGENTS_PER_INTENT: usize = 20;
+
+// One hop in an intent's delegation tree: `delegator` handed part of the work to `sub_agent`
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+pub struct TaskDelegation {
+    delegator: AccountId,
+    sub_agent: AccountId,
+    // Hops from the intent's agent (1 for its direct sub-agents)
+    depth: u8,
+    delegated_at: u64,
+    // Sub-agent's share of the outcome (basis points), fixed when the intent is finalized
+    outcome_share_bps: Option<u32>,
+    // When the sub-agent accepted the work; until then it takes no share of the outcome
+    accepted_at: Option<u64>,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Delegate part of an accepted intent to another agent. Callable by the intent's agent or
+    // by a sub-agent that has accepted its own part; the delegator must hold the delegation
+    // capability and the sub-agent must be cleared for the intent's escrowed value. The
+    // sub-agent shares in the outcome only once it accepts with accept_delegation. The
+    // delegator pays for the storage.
+    pub fn record_delegation(&mut self, parent_intent_id: String, sub_agent_id: AccountId) {
+        let delegator = env::predecessor_account_id();
+        let intent = self.intents.get(&parent_intent_id).expect("Intent not found");
+        assert!(
+            matches!(intent.status, IntentStatus::Accepted | IntentStatus::InProgress),
+            "Only accepted or in-progress intents can be delegated"
+        );
+
+        let initial_storage = env::storage_usage();
+        let mut tree = self.task_delegations.get(&parent_intent_id).unwrap_or_default();
+        let depth = if delegator == intent.agent_id {
+            1
+        } else {
+            tree.iter()
+                .find(|link| link.sub_agent == delegator && link.accepted_at.is_some())
+                .map(|link| link.depth + 1)
+                .expect("Only the intent's agent or its accepted sub-agents can delegate")
+        };
+        assert!(depth <= self.max_task_delegation_depth, "Delegation depth limit reached");
+        assert!(tree.len() < MAX_SUB_AGENTS_PER_INTENT, "Intent has reached the sub-agent limit");
+        assert!(
+            sub_agent_id != intent.agent_id && !tree.iter().any(|link| link.sub_agent == sub_agent_id),
+            "Agent is already working on this intent"
+        );
+
+        let delegator_decision = self.evaluate_authorization(&delegator, "delegation", None);
+        assert!(delegator_decision.allowed(), "Delegator: {}", authorization_message(delegator_decision.rule));
+        let sub_agent_decision = self.evaluate_authorization(&sub_agent_id, "transaction", Some(intent.escrow_amount));
+        assert!(sub_agent_decision.allowed(), "Sub-agent: {}", authorization_message(sub_agent_decision.rule));
+
+        self.internal_record_activity(&delegator);
+        tree.push(TaskDelegation {
+            delegator: delegator.clone(),
+            sub_agent: sub_agent_id.clone(),
+            depth,
+            delegated_at: env::block_timestamp(),
+            outcome_share_bps: None,
+            accepted_at: None,
+        });
+        self.task_delegations.insert(&parent_intent_id, &tree);
+        self.internal_charge_storage(&delegator, initial_storage);
+
+        emit_event(
+            "task_delegated",
+            json!({
+                "intent_id": parent_intent_id,
+                "delegator": delegator,
+                "sub_agent": sub_agent_id,
+                "depth": depth,
+            }),
+        );
+    }
+
+    // Accept work delegated to the caller on an open intent, making it share in the outcome
+    pub fn accept_delegation(&mut self, parent_intent_id: String) {
+        let sub_agent_id = env::predecessor_account_id();
+        let intent = self.intents.get(&parent_intent_id).expect("Intent not found");
+        assert!(!intent.status.is_terminal(), "Intent is already finished");
+
+        let mut tree = self.task_delegations.get(&parent_intent_id).unwrap_or_default();
+        let link = tree
+            .iter_mut()
+            .find(|link| link.sub_agent == sub_agent_id)
+            .expect("No delegation to this agent");
+        assert!(link.accepted_at.is_none(), "Delegation already accepted");
+        link.accepted_at = Some(env::block_timestamp());
+        let delegator = link.delegator.clone();
+        self.task_delegations.insert(&parent_intent_id, &tree);
+        self.internal_record_activity(&sub_agent_id);
+
+        emit_event(
+            "task_delegation_accepted",
+            json!({
+                "intent_id": parent_intent_id,
+                "delegator": delegator,
+                "sub_agent": sub_agent_id,
+            }),
+        );
+    }
+
+    // Decline work delegated to the caller that it has not accepted, returning the storage to
+    // the delegator
+    pub fn decline_delegation(&mut self, parent_intent_id: String) {
+        let sub_agent_id = env::predecessor_account_id();
+        let mut tree = self.task_delegations.get(&parent_intent_id).expect("No delegation to this agent");
+        let index = tree
+            .iter()
+            .position(|link| link.sub_agent == sub_agent_id)
+            .expect("No delegation to this agent");
+        assert!(tree[index].accepted_at.is_none(), "Delegation already accepted");
+
+        let initial_storage = env::storage_usage();
+        let link = tree.remove(index);
+        if tree.is_empty() {
+            self.task_delegations.remove(&parent_intent_id);
+        } else {
+            self.task_delegations.insert(&parent_intent_id, &tree);
+        }
+        self.internal_release_storage(&link.delegator, initial_storage.saturating_sub(env::storage_usage()));
+
+        emit_event(
+            "task_delegation_declined",
+            json!({
+                "intent_id": parent_intent_id,
+                "delegator": link.delegator,
+                "sub_agent": sub_agent_id,
+            }),
+        );
+    }
+
+    // Get an intent's delegation tree, with each sub-agent's share of the outcome: fixed once
+    // the intent is finalized, otherwise what it would be at current scores
+    pub fn get_delegation_tree(&self, intent_id: String) -> Vec<TaskDelegationView> {
+        let intent = match self.intents.get(&intent_id) {
+            Some(intent) => intent,
+            None => return Vec::new(),
+        };
+        let tree = self.task_delegations.get(&intent_id).unwrap_or_default();
+        let shares = self.internal_delegation_shares(&intent.agent_id, &tree);
+
+        tree.into_iter()
+            .zip(shares)
+            .map(|(link, share)| TaskDelegationView {
+                outcome_share_bps: link.outcome_share_bps.unwrap_or(share),
+                finalized: link.outcome_share_bps.is_some(),
+                delegator: link.delegator,
+                sub_agent: link.sub_agent,
+                depth: link.depth,
+                delegated_at: link.delegated_at,
+                accepted_at: link.accepted_at,
+            })
+            .collect()
+    }
+
+    // Fix each sub-agent's share of a finalized intent's outcome, and count the outcome as an
+    // interaction for every sub-agent with a share, successful if the intent completed. The
+    // intent's agent answers to the client and keeps its own interaction record.
+    pub(crate) fn internal_attribute_delegated_outcome(&mut self, intent: &IntentData) {
+        let mut tree = match self.task_delegations.get(&intent.intent_id) {
+            Some(tree) => tree,
+            None => return,
+        };
+        let completed = intent.status == IntentStatus::Completed;
+        let shares = self.internal_delegation_shares(&intent.agent_id, &tree);
+
+        for (link, share) in tree.iter_mut().zip(shares) {
+            link.outcome_share_bps = Some(share);
+
+            if share == 0 {
+                continue;
+            }
+            let mut sub_rep = match self.agent_reputations.get(&link.sub_agent) {
+                Some(sub_rep) => sub_rep,
+                None => continue,
+            };
+            sub_rep.total_interactions += 1;
+            self.internal_record_decay_activity(&link.sub_agent, &mut sub_rep);
+            if completed {
+                sub_rep.successful_interactions += 1;
+            }
+            self.internal_save_agent(&link.sub_agent, &sub_rep);
+
+            emit_event(
+                "delegated_outcome_attributed",
+                json!({
+                    "intent_id": intent.intent_id,
+                    "sub_agent": link.sub_agent,
+                    "status": intent.status,
+                    "outcome_share_bps": share,
+                    "total_interactions": sub_rep.total_interactions,
+                }),
+            );
+        }
+        self.task_delegations.insert(&intent.intent_id, &tree);
+    }
+
+    // Each sub-agent's share of the outcome (basis points), in tree order. The intent's agent
+    // starts with the whole outcome; every delegator splits its share with its direct
+    // sub-agents that have accepted, in proportion to their scores.
+    fn internal_delegation_shares(&self, agent_id: &AccountId, tree: &[TaskDelegation]) -> Vec<u32> {
+        let score_of = |account_id: &AccountId| {
+            self.agent_reputations.get(account_id).map_or(0, |agent_rep| agent_rep.score) as u64
+        };
+        let mut shares = vec![0u32; tree.len()];
+        let mut delegators = vec![(agent_id.clone(), 10_000u64)];
+
+        while let Some((delegator, delegator_share)) = delegators.pop() {
+            let children: Vec<usize> = (0..tree.len())
+                .filter(|&i| tree[i].delegator == delegator && tree[i].accepted_at.is_some())
+                .collect();
+            let children_scores: Vec<u64> = children.iter().map(|&i| score_of(&tree[i].sub_agent)).collect();
+            let total = score_of(&delegator) + children_scores.iter().sum::<u64>();
+            if total == 0 {
+                continue;
+            }
+
+            for (&i, child_score) in children.iter().zip(children_scores) {
+                let share = delegator_share * child_score / total;
+                shares[i] = share as u32;
+                delegators.push((tree[i].sub_agent.clone(), share));
+            }
+        }
+
+        shares
+    }
+}
+
+// View struct for one hop in an intent's delegation tree
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TaskDelegationView {
+    delegator: AccountId,
+    sub_agent: AccountId,
+    depth: u8,
+    delegated_at: u64,
+    // None until the sub-agent accepts
+    accepted_at: Option<u64>,
+    outcome_share_bps: u32,
+    // Whether the share is final
+    finalized: bool,
+}
@@ -0,0 +1,98 @@
+// Helpers shared by the unit tests: mocked call contexts and a contract with an intent type,
+// storage balances and registered agents
+use crate::*;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::serde_json::Value;
+use near_sdk::{testing_env, RuntimeFeesConfig, VMConfig};
+
+pub(crate) const CONTRACT: &str = "reputation.test";
+pub(crate) const OWNER: &str = "owner.test";
+pub(crate) const TOKEN: &str = "itlx.test";
+pub(crate) const MIN_STAKE: Balance = 1_000;
+pub(crate) const INTENT_TYPE: &str = "swap";
+// Block timestamp the helpers start at
+pub(crate) const START: u64 = 1_700_000_000_000_000_000;
+// Storage deposit made for every account the helpers set up
+const STORAGE_DEPOSIT: Balance = 1_000_000_000_000_000_000_000_000;
+
+pub(crate) fn account(name: &str) -> AccountId {
+    name.parse().unwrap()
+}
+
+// Call the contract as `predecessor` at `at` with `deposit` attached
+pub(crate) fn set_context(predecessor: &str, at: u64, deposit: Balance) {
+    testing_env!(VMContextBuilder::new()
+        .current_account_id(account(CONTRACT))
+        .predecessor_account_id(account(predecessor))
+        .signer_account_id(account(predecessor))
+        .block_timestamp(at)
+        .attached_deposit(deposit)
+        .build());
+}
+
+// Call a callback of the contract at `at` with the given promise results
+pub(crate) fn set_callback_context(at: u64, promise_results: Vec<PromiseResult>) {
+    let context = VMContextBuilder::new()
+        .current_account_id(account(CONTRACT))
+        .predecessor_account_id(account(CONTRACT))
+        .block_timestamp(at)
+        .build();
+    testing_env!(context, VMConfig::test(), RuntimeFeesConfig::test(), Default::default(), promise_results);
+}
+
+// Register the test intent type in the taxonomy
+pub(crate) fn register_intent_type(contract: &mut AgentReputationContract) {
+    set_context(OWNER, START, 0);
+    contract.set_intent_type(
+        INTENT_TYPE.to_string(),
+        IntentTypeMetadata {
+            description: "Token swap".to_string(),
+            complexity: 1,
+            required_trust_level: TrustLevel::Novice,
+            default_deadline_period: None,
+        },
+    );
+}
+
+// Fresh contract with the test intent type registered, on empty mocked storage
+pub(crate) fn setup() -> AgentReputationContract {
+    set_context(OWNER, START, 0);
+    near_sdk::mock::with_mocked_blockchain(|blockchain| drop(blockchain.take_storage()));
+    let mut contract = AgentReputationContract::new(account(OWNER), account(TOKEN), MIN_STAKE);
+    register_intent_type(&mut contract);
+    contract
+}
+
+// Give `account_id` a storage balance
+pub(crate) fn deposit_storage(contract: &mut AgentReputationContract, account_id: &str) {
+    set_context(account_id, START, STORAGE_DEPOSIT);
+    contract.storage_deposit(None, None);
+}
+
+// Register `agent_id` with the minimum stake, as a verified stake-and-register transfer would
+pub(crate) fn register_agent(contract: &mut AgentReputationContract, agent_id: &str) {
+    deposit_storage(contract, agent_id);
+    set_callback_context(START, vec![PromiseResult::Successful(b"true".to_vec())]);
+    contract.on_stake_registration_verified(account(agent_id), vec![INTENT_TYPE.to_string()], U128(MIN_STAKE));
+}
+
+// Record an intent from `client_id` to `agent_id` with `escrow` attached and start it
+pub(crate) fn start_intent(contract: &mut AgentReputationContract, intent_id: &str, agent_id: &str, client_id: &str, escrow: Balance) {
+    set_context(client_id, START, escrow);
+    contract.record_intent(intent_id.to_string(), account(agent_id), INTENT_TYPE.to_string(), String::new(), None, None);
+    set_context(agent_id, START, 0);
+    contract.accept_intent(intent_id.to_string());
+    contract.update_intent_status(intent_id.to_string(), IntentStatus::InProgress, None, None);
+}
+
+// Data of the latest event named `name` logged by the current call
+pub(crate) fn last_event(name: &str) -> Value {
+    near_sdk::test_utils::get_logs()
+        .iter()
+        .rev()
+        .filter_map(|log| log.strip_prefix("EVENT_JSON:"))
+        .map(|payload| near_sdk::serde_json::from_str::<Value>(payload).unwrap())
+        .find(|event| event["event"] == name)
+        .map(|event| event["data"][0].clone())
+        .unwrap_or_else(|| panic!("No {} event logged", name))
+}
@@ -0,0 +1,179 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Bounds on the timelock delay (1 hour to 30 days)
+const MIN_TIMELOCK_DELAY: u64 = 60 * 60 * 1_000_000_000;
+const MAX_TIMELOCK_DELAY: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+// Upper bound on actions waiting in the queue
+const MAX_PENDING_ACTIONS: u64 = 50;
+
+// Sensitive configuration changes that only take effect after the timelock delay
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde", tag = "action", rename_all = "snake_case")]
+pub enum TimelockedAction {
+    SetMinStake { min_stake_amount: U128 },
+    SetTreasury { treasury_id: AccountId },
+    SetSlashBurnBps { slash_burn_bps: u16 },
+    SetTimelockDelay { delay: u64 },
+    SetGuardian { guardian_id: Option<AccountId> },
+    SetViolationPolicy { policy: Vec<ViolationPolicyEntry> },
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PendingAction {
+    action: TimelockedAction,
+    proposed_by: AccountId,
+    proposed_at: u64,
+    // Earliest time the action can be executed
+    executable_at: u64,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Carry out a queued action once its delay has passed (anyone may call)
+    pub fn execute_action(&mut self, action_id: u64) {
+        let pending = self.pending_actions.get(&action_id).expect("Pending action not found");
+        assert!(env::block_timestamp() >= pending.executable_at, "Timelock has not passed");
+
+        self.pending_actions.remove(&action_id);
+        self.internal_apply_action(pending.action.clone());
+
+        emit_event(
+            "action_executed",
+            json!({
+                "action_id": action_id,
+                "action": pending.action,
+                "executed_by": env::predecessor_account_id(),
+            }),
+        );
+    }
+
+    // Drop a queued action before it executes (owner, governance or guardian; the guardian
+    // cannot cancel a change of guardian)
+    pub fn cancel_action(&mut self, action_id: u64) {
+        let caller = env::predecessor_account_id();
+        let owner_or_governance = caller == self.owner_id || self.is_governance_member(caller.clone());
+        assert!(
+            owner_or_governance || self.guardian_id.as_ref() == Some(&caller),
+            "Unauthorized: only owner, governance or guardian can cancel actions"
+        );
+
+        let pending = self.pending_actions.remove(&action_id).expect("Pending action not found");
+        if !owner_or_governance {
+            assert!(
+                !matches!(pending.action, TimelockedAction::SetGuardian { .. }),
+                "The guardian cannot cancel a change of guardian"
+            );
+        }
+
+        emit_event(
+            "action_cancelled",
+            json!({
+                "action_id": action_id,
+                "action": pending.action,
+                "cancelled_by": caller,
+            }),
+        );
+    }
+
+    // List queued actions with when they become executable
+    pub fn get_pending_actions(&self) -> Vec<PendingActionView> {
+        self.pending_actions
+            .iter()
+            .map(|(action_id, pending)| PendingActionView {
+                action_id,
+                action: pending.action,
+                proposed_by: pending.proposed_by,
+                proposed_at: pending.proposed_at,
+                executable_at: pending.executable_at,
+            })
+            .collect()
+    }
+
+    // Get the delay between proposing and executing a sensitive action (nanoseconds)
+    pub fn get_timelock_delay(&self) -> u64 {
+        self.timelock_delay
+    }
+
+    // Propose changing the timelock delay itself; the change is timelocked under the current delay
+    pub fn set_timelock_delay(&mut self, delay: u64) -> u64 {
+        self.assert_owner_or_governance();
+        assert!(
+            (MIN_TIMELOCK_DELAY..=MAX_TIMELOCK_DELAY).contains(&delay),
+            "Timelock delay out of range"
+        );
+
+        self.internal_queue_action(TimelockedAction::SetTimelockDelay { delay })
+    }
+
+    // Queue a validated action to run after the timelock delay; returns its ID
+    pub(crate) fn internal_queue_action(&mut self, action: TimelockedAction) -> u64 {
+        assert!(self.pending_actions.len() < MAX_PENDING_ACTIONS, "Too many pending actions");
+
+        let action_id = self.next_action_id;
+        self.next_action_id += 1;
+
+        let now = env::block_timestamp();
+        let pending = PendingAction {
+            action,
+            proposed_by: env::predecessor_account_id(),
+            proposed_at: now,
+            executable_at: now + self.timelock_delay,
+        };
+        self.pending_actions.insert(&action_id, &pending);
+
+        emit_event(
+            "action_queued",
+            json!({
+                "action_id": action_id,
+                "action": pending.action,
+                "proposed_by": pending.proposed_by,
+                "executable_at": pending.executable_at,
+            }),
+        );
+
+        action_id
+    }
+
+    fn internal_apply_action(&mut self, action: TimelockedAction) {
+        match action {
+            TimelockedAction::SetMinStake { min_stake_amount } => {
+                let old_value = std::mem::replace(&mut self.min_stake_amount, min_stake_amount.0);
+                self.record_config_change("min_stake_amount", json!(U128(old_value)), json!(min_stake_amount));
+            },
+            TimelockedAction::SetTreasury { treasury_id } => {
+                let old_value = std::mem::replace(&mut self.treasury_id, treasury_id.clone());
+                self.record_config_change("treasury_id", json!(old_value), json!(treasury_id));
+            },
+            TimelockedAction::SetSlashBurnBps { slash_burn_bps } => {
+                let old_value = std::mem::replace(&mut self.slash_burn_bps, slash_burn_bps);
+                self.record_config_change("slash_burn_bps", json!(old_value), json!(slash_burn_bps));
+            },
+            TimelockedAction::SetTimelockDelay { delay } => {
+                let old_value = std::mem::replace(&mut self.timelock_delay, delay);
+                self.record_config_change("timelock_delay", json!(old_value), json!(delay));
+            },
+            TimelockedAction::SetGuardian { guardian_id } => {
+                self.guardian_id = guardian_id.clone();
+                self.config_nonce += 1;
+                emit_event("guardian_updated", json!({ "guardian_id": guardian_id }));
+            },
+            TimelockedAction::SetViolationPolicy { policy } => {
+                let old_value = std::mem::replace(&mut self.violation_policy, policy.clone());
+                self.record_config_change("violation_policy", json!(old_value), json!(policy));
+            },
+        }
+    }
+}
+
+// View struct for a queued action
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingActionView {
+    action_id: u64,
+    action: TimelockedAction,
+    proposed_by: AccountId,
+    proposed_at: u64,
+    executable_at: u64,
+}
@@ -1,89 +1,181 @@
-use near_sdk::{env, Promise, PromiseResult};
-use near_sdk::json_types::U128;
-use near_sdk::serde_json::json;
+use crate::*;
+use crate::events::emit_event;
+use near_sdk::PromiseOrValue;
 
+// Gas for the ft_transfer returning unstaked tokens
+const GAS_FOR_UNSTAKE_FT_TRANSFER: Gas = Gas(10_000_000_000_000);
+
+// Gas reserved for on_unstake_complete
+const GAS_FOR_UNSTAKE_CALLBACK: Gas = Gas(10_000_000_000_000);
+
+// Message accepted by ft_on_transfer, e.g. {"action":"register","specializations":["defi-routing"]}
+#[derive(near_sdk::serde::Deserialize)]
+#[serde(crate = "near_sdk::serde", tag = "action", rename_all = "snake_case")]
+enum TransferMessage {
+    // Add the transferred tokens to the sender's stake
+    Stake,
+    // Stake the transferred tokens and earn recovery points (agents with reputation below 50)
+    RecoveryBoost,
+    // Register the sender as an agent, staking the transferred tokens
+    Register { specializations: Vec<String> },
+    // Record an intent with the transferred tokens held in escrow
+    RecordIntent {
+        intent_id: String,
+        agent_id: AccountId,
+        intent_type: String,
+        parameters: String,
+        deadline: Option<u64>,
+        referrer_id: Option<AccountId>,
+    },
+    // Add the transferred tokens to the sender's prepaid query credit
+    DepositQueryCredit,
+    // Post the transferred tokens as a bounty for auditing the sending agent
+    PostAuditBounty { scope: String },
+    // Back an agent with the transferred tokens as delegated stake
+    DelegateStake { agent_id: AccountId },
+    // Add the transferred tokens to the staking reward pool (owner or treasury)
+    FundRewards,
+}
+
+#[near_bindgen]
 impl AgentReputationContract {
-    // Handle token staking via cross-contract call
-    pub fn stake_itlx(&mut self, amount: U128) -> Promise {
-        let agent_id = env::predecessor_account_id();
-        
-        // Cross-contract call to transfer tokens from user to this contract
-        let transfer_call = Promise::new(self.token_contract_id.clone())
-            .function_call(
-                "ft_transfer_call".to_string(),
-                json!({
-                    "receiver_id": env::current_account_id(),
-                    "amount": amount,
-                    "msg": "stake"
-                }).to_string().into_bytes(),
-                1, // 1 yoctoNEAR
-                env::prepaid_gas() / 3
-            );
-            
-        // After transfer, update staking record
-        transfer_call.then(
-            Promise::new(env::current_account_id())
-                .function_call(
-                    "on_stake_complete".to_string(),
-                    json!({
-                        "agent_id": agent_id,
-                        "amount": amount
-                    }).to_string().into_bytes(),
-                    0,
-                    env::prepaid_gas() / 3
+    // NEP-141 receiver and the only way to stake: agents call ft_transfer_call on the token
+    // contract with msg "stake" (or {"action":"stake"}) and the tokens are credited here once
+    // they have arrived.
+    // Besides staking it can boost recovery by staking, stake and register atomically, escrow ITLX for an intent,
+    // prepay query credit, post an audit bounty, delegate stake to an agent or fund staking
+    // rewards. Other accepted staking tokens can only be staked. Returns the amount to refund.
+    pub fn ft_on_transfer(&mut self, sender_id: AccountId, amount: U128, msg: String) -> PromiseOrValue<U128> {
+        let message = if msg == "stake" {
+            TransferMessage::Stake
+        } else {
+            near_sdk::serde_json::from_str(&msg).expect("Invalid transfer message")
+        };
+
+        let token_id = env::predecessor_account_id();
+        if token_id != self.token_contract_id {
+            assert!(matches!(message, TransferMessage::Stake), "Only staking is supported for this token");
+            self.internal_stake_token(&token_id, &sender_id, amount.0);
+            return PromiseOrValue::Value(U128(0));
+        }
+
+        match message {
+            TransferMessage::Stake => {
+                self.assert_not_paused(PausableFeature::Staking);
+                let current_stake = self.agent_stakes.get(&sender_id).unwrap_or(0);
+                self.internal_set_stake(&sender_id, current_stake + amount.0);
+                self.internal_record_activity(&sender_id);
+                self.update_reputation_on_stake_change(sender_id);
+                PromiseOrValue::Value(U128(0))
+            },
+            TransferMessage::RecoveryBoost => {
+                self.assert_not_paused(PausableFeature::Staking);
+                self.internal_boost_recovery(&sender_id, amount.0);
+                PromiseOrValue::Value(U128(0))
+            },
+            TransferMessage::Register { specializations } => {
+                self.assert_not_paused(PausableFeature::Staking);
+                assert!(self.agent_reputations.get(&sender_id).is_none(), "Agent already registered");
+                let total_stake = self.agent_stakes.get(&sender_id).unwrap_or(0) + amount.0;
+                assert!(total_stake >= self.min_stake_amount, "Transferred amount below minimum stake");
+                let near_ai_registry = self.near_ai_registry.clone().expect("NEAR AI registry not configured");
+                self.assert_storage_registered(&sender_id, 0);
+
+                // Verify the agent with the registry before crediting the stake
+                PromiseOrValue::Promise(
+                    Promise::new(near_ai_registry)
+                        .function_call(
+                            "has_agent".to_string(),
+                            json!({ "agent_id": sender_id }).to_string().into_bytes(),
+                            0,
+                            GAS_FOR_REGISTRY_CHECK
+                        )
+                        .then(
+                            Promise::new(env::current_account_id())
+                                .function_call(
+                                    "on_stake_registration_verified".to_string(),
+                                    json!({
+                                        "agent_id": sender_id,
+                                        "specializations": specializations,
+                                        "amount": amount,
+                                    }).to_string().into_bytes(),
+                                    0,
+                                    GAS_FOR_REGISTRATION_CALLBACK
+                                )
+                        )
                 )
-        )
-    }
-    
-    // Callback after staking
-    pub fn on_stake_complete(&mut self, agent_id: AccountId, amount: U128) {
-        // Verify callback is from previous cross-contract call
-        assert_eq!(env::predecessor_account_id(), env::current_account_id(), "Unauthorized");
-        
-        // Check if the transfer was successful
-        match env::promise_result(0) {
-            PromiseResult::Successful(_) => {
-                // Update agent stake
-                let current_stake = self.agent_stakes.get(&agent_id).unwrap_or(0);
-                self.agent_stakes.insert(&agent_id, &(current_stake + amount.0));
-                
-                // Boost reputation if this is a significant stake
-                if self.agent_reputations.contains_key(&agent_id) {
-                    let mut agent_rep = self.agent_reputations.get(&agent_id).unwrap();
-                    // Apply stake-based reputation boost (optional)
-                    // For example, give small boosts for maintaining significant stake
-                    self.agent_reputations.insert(&agent_id, &agent_rep);
-                }
             },
-            _ => {
-                // Handle failure case
-                env::log_str("Token staking failed");
-            }
+            TransferMessage::RecordIntent { intent_id, agent_id, intent_type, parameters, deadline, referrer_id } => {
+                self.internal_record_intent(
+                    intent_id,
+                    agent_id,
+                    sender_id.clone(),
+                    intent_type,
+                    parameters,
+                    deadline,
+                    amount.0,
+                    EscrowAsset::Itlx,
+                    referrer_id,
+                    Some(&sender_id),
+                );
+                PromiseOrValue::Value(U128(0))
+            },
+            TransferMessage::DepositQueryCredit => {
+                self.internal_deposit_query_credit(&sender_id, amount.0);
+                PromiseOrValue::Value(U128(0))
+            },
+            TransferMessage::PostAuditBounty { scope } => {
+                self.internal_post_audit_bounty(&sender_id, amount.0, scope);
+                PromiseOrValue::Value(U128(0))
+            },
+            TransferMessage::DelegateStake { agent_id } => {
+                self.internal_delegate_stake(&sender_id, &agent_id, amount.0);
+                PromiseOrValue::Value(U128(0))
+            },
+            TransferMessage::FundRewards => {
+                self.internal_fund_rewards(&sender_id, amount.0);
+                PromiseOrValue::Value(U128(0))
+            },
         }
     }
-    
-    // Unstake tokens (with potential reputation penalty)
+
+    // Callback finalizing a stake-and-register transfer; returns the amount to refund
+    #[private]
+    pub fn on_stake_registration_verified(&mut self, agent_id: AccountId, specializations: Vec<String>, amount: U128) -> U128 {
+        let verified = match env::promise_result(0) {
+            PromiseResult::Successful(result) => near_sdk::serde_json::from_slice::<bool>(&result).unwrap_or(false),
+            _ => false,
+        };
+
+        if !verified || self.agent_reputations.get(&agent_id).is_some() {
+            env::log_str(&format!("Stake registration of agent {} aborted; refunding {}", agent_id, amount.0));
+            return amount;
+        }
+
+        let current_stake = self.agent_stakes.get(&agent_id).unwrap_or(0);
+        self.internal_set_stake(&agent_id, current_stake + amount.0);
+        self.internal_record_activity(&agent_id);
+        self.internal_register_agent(&agent_id, specializations);
+        self.update_reputation_on_stake_change(agent_id);
+
+        U128(0)
+    }
+
+    // Unstake tokens; agents left below the minimum stake have a grace period to top up
     pub fn unstake_itlx(&mut self, amount: U128) -> Promise {
+        self.assert_not_paused(PausableFeature::Unstaking);
+        
         let agent_id = env::predecessor_account_id();
         
         // Check if agent has enough staked
         let current_stake = self.agent_stakes.get(&agent_id).unwrap_or(0);
         assert!(current_stake >= amount.0, "Not enough staked tokens");
         
-        // Update stake amount first
-        self.agent_stakes.insert(&agent_id, &(current_stake - amount.0));
+        // Update stake amount first. Falling below the minimum starts the top-up grace period.
+        self.internal_set_stake(&agent_id, current_stake - amount.0);
+        self.internal_record_activity(&agent_id);
         
-        // Check if remaining stake is below minimum and agent is registered
-        if current_stake - amount.0 < self.min_stake_amount && self.agent_reputations.contains_key(&agent_id) {
-            // Apply reputation penalty for going below minimum stake
-            let mut agent_rep = self.agent_reputations.get(&agent_id).unwrap();
-            if agent_rep.score > 5 {
-                agent_rep.score -= 5; // Penalty for unstaking below minimum
-            }
-            self.agent_reputations.insert(&agent_id, &agent_rep);
-        }
-        
-        // Transfer tokens back to agent
+        // Transfer tokens back to agent, restoring the stake if the transfer fails
         Promise::new(self.token_contract_id.clone())
             .function_call(
                 "ft_transfer".to_string(),
@@ -92,7 +184,84 @@ impl AgentReputationContract {
                     "amount": amount,
                 }).to_string().into_bytes(),
                 1, // 1 yoctoNEAR
-                env::prepaid_gas() / 2
+                GAS_FOR_UNSTAKE_FT_TRANSFER
+            )
+            .then(
+                Promise::new(env::current_account_id())
+                    .function_call(
+                        "on_unstake_complete".to_string(),
+                        json!({
+                            "agent_id": agent_id,
+                            "amount": amount,
+                        }).to_string().into_bytes(),
+                        0,
+                        GAS_FOR_UNSTAKE_CALLBACK
+                    )
             )
     }
-} 
\ No newline at end of file
+
+    // Callback after returning unstaked tokens
+    #[private]
+    pub fn on_unstake_complete(&mut self, agent_id: AccountId, amount: U128) {
+        if let PromiseResult::Successful(_) = env::promise_result(0) {
+            return;
+        }
+
+        // Transfer failed: the tokens never left, so they go back to the stake, which also ends
+        // any grace period the unstake started
+        let current_stake = self.agent_stakes.get(&agent_id).unwrap_or(0);
+        self.internal_set_stake(&agent_id, current_stake + amount.0);
+
+        emit_event(
+            "unstake_rolled_back",
+            json!({
+                "agent_id": agent_id,
+                "amount": amount,
+            }),
+        );
+    }
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    // Contract with a registered agent whose reputation has dropped to `score`
+    fn setup_agent(score: u32) -> AgentReputationContract {
+        let mut contract = setup();
+        register_agent(&mut contract, "agent.test");
+        let agent_id = account("agent.test");
+        let mut agent_rep = contract.agent_reputations.get(&agent_id).unwrap();
+        agent_rep.set_score(score);
+        contract.internal_save_agent(&agent_id, &agent_rep);
+        contract
+    }
+
+    fn transfer(contract: &mut AgentReputationContract, amount: Balance, msg: &str) {
+        set_context(TOKEN, START, 0);
+        contract.ft_on_transfer(account("agent.test"), U128(amount), msg.to_string());
+    }
+
+    #[test]
+    fn stake_transfer_adds_to_stake() {
+        let mut contract = setup_agent(50);
+        transfer(&mut contract, MIN_STAKE, "stake");
+        assert_eq!(contract.agent_stakes.get(&account("agent.test")), Some(2 * MIN_STAKE));
+    }
+
+    #[test]
+    fn recovery_boost_transfer_stakes_and_logs_recovery_points() {
+        let mut contract = setup_agent(30);
+        transfer(&mut contract, MIN_STAKE, r#"{"action":"recovery_boost"}"#);
+        assert_eq!(contract.agent_stakes.get(&account("agent.test")), Some(2 * MIN_STAKE));
+        assert!(near_sdk::test_utils::get_logs()
+            .contains(&"Agent agent.test recovered 10 reputation points through additional staking".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Recovery boost only available for agents with reputation below 50")]
+    fn recovery_boost_requires_low_reputation() {
+        let mut contract = setup_agent(50);
+        transfer(&mut contract, MIN_STAKE, r#"{"action":"recovery_boost"}"#);
+    }
+}
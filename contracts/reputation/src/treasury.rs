@@ -0,0 +1,121 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Gas for the ft_transfer of slashed tokens to the treasury
+const GAS_FOR_TREASURY_TRANSFER: Gas = Gas(10_000_000_000_000);
+
+// Gas reserved for the slashing transfer callback
+const GAS_FOR_SLASH_CALLBACK: Gas = Gas(10_000_000_000_000);
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Route slashed tokens: pay the insurance pool its share, burn the configured share of the
+    // rest and transfer the remainder to the treasury. `delegated` marks tokens slashed from
+    // the agent's delegators rather than its own stake.
+    pub(crate) fn route_slashed_tokens(&mut self, agent_id: &AccountId, amount: Balance, delegated: bool) {
+        let amount = self.internal_fund_insurance(agent_id, amount);
+        let burn_amount = amount * self.slash_burn_bps as u128 / 10_000;
+        let treasury_amount = amount - burn_amount;
+
+        // NEP-141 has no standard burn, so the burned share is locked in this contract for good
+        if burn_amount > 0 {
+            self.total_slashed_burned += burn_amount;
+        }
+
+        if treasury_amount > 0 {
+            Promise::new(self.token_contract_id.clone())
+                .function_call(
+                    "ft_transfer".to_string(),
+                    json!({
+                        "receiver_id": self.treasury_id,
+                        "amount": U128(treasury_amount),
+                        "memo": format!("Slashed stake of {}", agent_id),
+                    }).to_string().into_bytes(),
+                    1, // 1 yoctoNEAR
+                    GAS_FOR_TREASURY_TRANSFER,
+                )
+                .then(
+                    Promise::new(env::current_account_id())
+                        .function_call(
+                            "on_slash_transfer_complete".to_string(),
+                            json!({
+                                "agent_id": agent_id,
+                                "treasury_amount": U128(treasury_amount),
+                                "burn_amount": U128(burn_amount),
+                                "delegated": delegated,
+                            }).to_string().into_bytes(),
+                            0,
+                            GAS_FOR_SLASH_CALLBACK,
+                        )
+                );
+        } else {
+            emit_slashing_settled(agent_id, 0, burn_amount);
+        }
+    }
+
+    // Callback after transferring slashed tokens to the treasury
+    #[private]
+    pub fn on_slash_transfer_complete(
+        &mut self,
+        agent_id: AccountId,
+        treasury_amount: U128,
+        burn_amount: U128,
+        delegated: bool,
+    ) {
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => {
+                self.total_slashed_to_treasury += treasury_amount.0;
+                emit_slashing_settled(&agent_id, treasury_amount.0, burn_amount.0);
+            },
+            _ => {
+                // Transfer failed: roll back the treasury share of the slash so the recorded
+                // stakes keep matching the tokens this contract holds
+                if delegated {
+                    self.internal_restore_delegations(&agent_id, treasury_amount.0);
+                } else {
+                    let current_stake = self.agent_stakes.get(&agent_id).unwrap_or(0);
+                    self.internal_set_stake(&agent_id, current_stake + treasury_amount.0);
+                }
+
+                emit_event(
+                    "slashing_rolled_back",
+                    json!({
+                        "agent_id": agent_id,
+                        "amount": treasury_amount,
+                        "treasury_id": self.treasury_id,
+                        "delegated": delegated,
+                    }),
+                );
+            }
+        }
+    }
+
+    // Get lifetime totals of slashed tokens sent to the treasury and burned
+    pub fn get_slashing_totals(&self) -> SlashingTotalsView {
+        SlashingTotalsView {
+            treasury_id: self.treasury_id.clone(),
+            total_slashed_to_treasury: U128(self.total_slashed_to_treasury),
+            total_slashed_burned: U128(self.total_slashed_burned),
+        }
+    }
+}
+
+fn emit_slashing_settled(agent_id: &AccountId, treasury_amount: Balance, burn_amount: Balance) {
+    emit_event(
+        "slashing_settled",
+        json!({
+            "agent_id": agent_id,
+            "treasury_amount": U128(treasury_amount),
+            "burn_amount": U128(burn_amount),
+        }),
+    );
+}
+
+// View struct for slashing totals
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SlashingTotalsView {
+    treasury_id: AccountId,
+    total_slashed_to_treasury: U128,
+    total_slashed_burned: U128,
+}
@@ -0,0 +1,95 @@
+use crate::*;
+
+// Upper bound on governance-defined violation types
+const MAX_CUSTOM_VIOLATION_TYPES: usize = 20;
+
+// Upper bound on the length of a custom violation type's name
+const MAX_VIOLATION_TYPE_NAME_LENGTH: usize = 32;
+
+// Penalty applied when a violation of a given type is recorded
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ViolationPenalty {
+    // Reputation points deducted
+    pub reputation_penalty: u32,
+    // Share of the agent's stake, and of the stake delegated to it, that is slashed (basis points)
+    pub slash_bps: u32,
+    // Whether a single violation of this type suspends the agent
+    pub suspend: bool,
+}
+
+// A violation type with the penalty for it
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ViolationPolicyEntry {
+    pub violation_type: ViolationType,
+    pub penalty: ViolationPenalty,
+}
+
+// The built-in violation types with their original penalties
+pub(crate) fn default_violation_policy() -> Vec<ViolationPolicyEntry> {
+    let penalties = [
+        (5, 100),    // Minor infraction: 5 points, 1% of stake
+        (15, 500),   // Major infraction: 15 points, 5% of stake
+        (25, 1_000), // Terms violation: 25 points, 10% of stake
+        (40, 2_500), // Ethical violation: 40 points, 25% of stake
+        (60, 5_000), // Security breach: 60 points, 50% of stake
+    ];
+    ViolationType::ALL
+        .into_iter()
+        .zip(penalties)
+        .map(|(violation_type, (reputation_penalty, slash_bps))| ViolationPolicyEntry {
+            violation_type,
+            penalty: ViolationPenalty { reputation_penalty, slash_bps, suspend: false },
+        })
+        .collect()
+}
+
+// Check a replacement violation policy: every built-in type exactly once, custom types
+// uniquely and sensibly named, and penalties within range
+pub(crate) fn assert_valid_violation_policy(policy: &[ViolationPolicyEntry]) {
+    for (i, entry) in policy.iter().enumerate() {
+        assert!(
+            policy[..i].iter().all(|other| other.violation_type != entry.violation_type),
+            "Duplicate violation type"
+        );
+        assert!(entry.penalty.reputation_penalty <= 100, "Reputation penalty cannot exceed 100 points");
+        assert!(entry.penalty.slash_bps <= 10_000, "Slash cannot exceed 10000 basis points");
+        if let ViolationType::Custom(name) = &entry.violation_type {
+            assert!(
+                !name.is_empty()
+                    && name.len() <= MAX_VIOLATION_TYPE_NAME_LENGTH
+                    && name.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_' || b == b'-'),
+                "Custom violation type names must be 1-{} lowercase letters, digits, '_' or '-'",
+                MAX_VIOLATION_TYPE_NAME_LENGTH
+            );
+        }
+    }
+    assert!(
+        ViolationType::ALL
+            .iter()
+            .all(|builtin| policy.iter().any(|entry| entry.violation_type == *builtin)),
+        "Every built-in violation type needs a penalty"
+    );
+    assert!(
+        policy.len() - ViolationType::ALL.len() <= MAX_CUSTOM_VIOLATION_TYPES,
+        "Too many custom violation types"
+    );
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Get every violation type with its penalty
+    pub fn get_violation_policy(&self) -> Vec<ViolationPolicyEntry> {
+        self.violation_policy.clone()
+    }
+
+    // Penalty for a violation type; custom types must be defined in the policy
+    pub(crate) fn internal_violation_penalty(&self, violation_type: &ViolationType) -> ViolationPenalty {
+        self.violation_policy
+            .iter()
+            .find(|entry| entry.violation_type == *violation_type)
+            .map(|entry| entry.penalty.clone())
+            .expect("Unknown violation type")
+    }
+}
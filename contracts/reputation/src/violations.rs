@@ -0,0 +1,187 @@
+use crate::*;
+use crate::events::emit_event;
+use near_sdk::json_types::Base64VecU8;
+
+// Length of an evidence content hash (SHA-256)
+const EVIDENCE_HASH_LENGTH: usize = 32;
+
+// Maximum number of evidence records attached to a single violation
+const MAX_EVIDENCE_PER_VIOLATION: usize = 10;
+
+// Maximum length of an evidence URI
+const MAX_EVIDENCE_URI_LENGTH: usize = 512;
+
+// Maximum length of an evidence media type
+const MAX_EVIDENCE_MEDIA_TYPE_LENGTH: usize = 127;
+
+// Upper bound on violations returned per page
+const MAX_VIOLATIONS_PAGE: u32 = 50;
+
+// Maximum length of an appeal justification or ruling note
+pub(crate) const MAX_APPEAL_TEXT_LENGTH: usize = 1_000;
+
+// Pointer to off-chain evidence. Only the content hash is authoritative; the blob itself lives
+// at the URI (IPFS, Arweave, HTTPS, ...) and is verified against the hash by arbitration tooling.
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Evidence {
+    // SHA-256 of the evidence content
+    pub hash: Base64VecU8,
+    // Where the content can be fetched
+    pub uri: String,
+    // MIME type of the content, e.g. "application/json"
+    pub media_type: String,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, PartialEq, Clone, Copy)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum AppealStatus {
+    Pending,
+    // The violation stands
+    Upheld,
+    // The violation was wrongly recorded; its reputation penalty was restored
+    Overturned,
+}
+
+// An agent's appeal against a recorded violation
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ViolationAppeal {
+    justification: String,
+    filed_at: u64,
+    status: AppealStatus,
+    resolved_at: Option<u64>,
+    ruling_note: Option<String>,
+}
+
+impl ViolationAppeal {
+    pub(crate) fn new(justification: String) -> Self {
+        Self {
+            justification,
+            filed_at: env::block_timestamp(),
+            status: AppealStatus::Pending,
+            resolved_at: None,
+            ruling_note: None,
+        }
+    }
+}
+
+impl ViolationRecord {
+    // Whether the agent's appeal against this violation awaits a ruling
+    pub(crate) fn appeal_pending(&self) -> bool {
+        self.appeal.as_ref().is_some_and(|appeal| appeal.status == AppealStatus::Pending)
+    }
+
+    // Whether the violation was overturned on appeal, and so no longer counts against the agent
+    pub(crate) fn overturned(&self) -> bool {
+        self.appeal.as_ref().is_some_and(|appeal| appeal.status == AppealStatus::Overturned)
+    }
+}
+
+impl AgentReputation {
+    // Position in violation_history of the violation with this (archival-stable) index
+    pub(crate) fn violation_position(&self, violation_index: u64) -> Option<usize> {
+        violation_index
+            .checked_sub(self.violations_archived)
+            .filter(|position| *position < self.violation_history.len() as u64)
+            .map(|position| position as usize)
+    }
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Rule on an agent's pending appeal (the governance DAO if configured, otherwise the owner).
+    // Overturning restores the part of the violation's reputation penalty not already forgiven
+    // by decay; slashed tokens have already left the stake and are not returned.
+    pub fn resolve_appeal(&mut self, agent_id: AccountId, violation_index: u64, overturn: bool, note: Option<String>) {
+        self.assert_violation_authority();
+        if let Some(note) = &note {
+            assert!(note.len() <= MAX_APPEAL_TEXT_LENGTH, "Ruling note too long");
+        }
+
+        let initial_storage = env::storage_usage();
+        let mut agent_rep = self.agent_reputations.get(&agent_id).expect("Agent not registered");
+        let position = agent_rep.violation_position(violation_index).expect("Invalid violation index");
+        assert!(agent_rep.violation_history[position].appeal_pending(), "No pending appeal for this violation");
+
+        // Bring decay up to date so an overturn restores only what is still outstanding
+        self.internal_apply_penalty_decay(&agent_id, &mut agent_rep);
+        let violation = &mut agent_rep.violation_history[position];
+        let restored_bps = if overturn { violation.outstanding_penalty_bps() } else { 0 };
+        violation.penalty_forgiven_bps += restored_bps;
+        if let Some(appeal) = violation.appeal.as_mut() {
+            appeal.status = if overturn { AppealStatus::Overturned } else { AppealStatus::Upheld };
+            appeal.resolved_at = Some(env::block_timestamp());
+            appeal.ruling_note = note.clone();
+        }
+        agent_rep.set_score_bps(agent_rep.score_bps.saturating_add(restored_bps));
+
+        self.internal_save_agent(&agent_id, &agent_rep);
+        self.internal_charge_storage(&env::predecessor_account_id(), initial_storage);
+
+        emit_event(
+            "violation_appeal_resolved",
+            json!({
+                "agent_id": agent_id,
+                "violation_index": violation_index,
+                "overturned": overturn,
+                "restored_bps": restored_bps,
+                "note": note,
+            }),
+        );
+    }
+
+    // Get a page of an agent's violations, including their evidence records
+    pub fn get_violations(&self, agent_id: AccountId, from_index: u32, limit: u32) -> Vec<ViolationView> {
+        let agent_rep = match self.agent_reputations.get(&agent_id) {
+            Some(agent_rep) => agent_rep,
+            None => return Vec::new(),
+        };
+
+        agent_rep
+            .violation_history
+            .iter()
+            .enumerate()
+            .skip((from_index as u64).saturating_sub(agent_rep.violations_archived) as usize)
+            .take(std::cmp::min(limit, MAX_VIOLATIONS_PAGE) as usize)
+            .map(|(position, violation)| ViolationView {
+                // Indexes count archived violations, so they stay stable
+                index: agent_rep.violations_archived + position as u64,
+                violation_type: violation.violation_type.clone(),
+                reporter: violation.reporter.clone(),
+                description: violation.description.clone(),
+                evidence: violation.evidence.clone(),
+                timestamp: violation.timestamp,
+                penalty_applied: violation.penalty_applied,
+                tokens_slashed: U128(violation.tokens_slashed),
+                appeal: violation.appeal.clone(),
+            })
+            .collect()
+    }
+}
+
+// Panic unless the evidence list is within limits and every record is well-formed
+pub(crate) fn assert_valid_evidence(evidence: &[Evidence]) {
+    assert!(evidence.len() <= MAX_EVIDENCE_PER_VIOLATION, "Too many evidence records");
+    for record in evidence {
+        assert_eq!(record.hash.0.len(), EVIDENCE_HASH_LENGTH, "Evidence hash must be a 32-byte SHA-256 digest");
+        assert!(!record.uri.is_empty(), "Evidence URI cannot be empty");
+        assert!(record.uri.len() <= MAX_EVIDENCE_URI_LENGTH, "Evidence URI too long");
+        assert!(record.media_type.len() <= MAX_EVIDENCE_MEDIA_TYPE_LENGTH, "Evidence media type too long");
+    }
+}
+
+// View struct for a single violation record
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ViolationView {
+    index: u64,
+    violation_type: ViolationType,
+    reporter: AccountId,
+    description: String,
+    evidence: Vec<Evidence>,
+    timestamp: u64,
+    penalty_applied: u32,
+    tokens_slashed: U128,
+    appeal: Option<ViolationAppeal>,
+}
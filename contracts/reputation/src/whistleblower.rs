@@ -0,0 +1,248 @@
+use crate::*;
+use crate::events::emit_event;
+
+// Bond anyone must attach to report a violation (1 NEAR)
+const WHISTLEBLOWER_REPORT_BOND: Balance = 1_000_000_000_000_000_000_000_000;
+
+// Share of the stake slashed over a confirmed report paid to its reporter (basis points)
+pub(crate) const WHISTLEBLOWER_REWARD_BPS: u32 = 2_000;
+
+// Maximum length of a report description or ruling note
+const MAX_REPORT_TEXT_LENGTH: usize = 1_000;
+
+// Upper bound on pending reports returned per page
+const MAX_REPORTS_PAGE: u32 = 50;
+
+// Gas for the ft_transfer paying a whistleblower's reward
+const GAS_FOR_REWARD_TRANSFER: Gas = Gas(10_000_000_000_000);
+
+// Gas reserved for the reward transfer callback, which may route the reward as a slash
+const GAS_FOR_REWARD_CALLBACK: Gas = Gas(30_000_000_000_000);
+
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize, near_sdk::serde::Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde", rename_all = "snake_case")]
+pub enum ReportStatus {
+    // Awaiting triage
+    Pending,
+    // The violation was recorded; bond returned and the reporter rewarded from the slash
+    Confirmed,
+    // Not substantiated but made in good faith; bond returned
+    Dismissed,
+    // Made without basis; bond forfeited to the treasury
+    Frivolous,
+}
+
+// A violation reported by someone outside governance
+#[derive(BorshDeserialize, BorshSerialize, near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct WhistleblowerReport {
+    pub(crate) reporter: AccountId,
+    pub(crate) agent_id: AccountId,
+    pub(crate) violation_type: ViolationType,
+    pub(crate) description: String,
+    pub(crate) evidence: Vec<Evidence>,
+    pub(crate) bond: U128,
+    pub(crate) status: ReportStatus,
+    pub(crate) submitted_at: u64,
+    pub(crate) resolved_at: Option<u64>,
+    pub(crate) ruling_note: Option<String>,
+}
+
+#[near_bindgen]
+impl AgentReputationContract {
+    // Report an agent's violation for governance to triage. Requires the report bond as deposit;
+    // the reporter pays for the storage of the report. Returns the report ID.
+    #[payable]
+    pub fn submit_report(
+        &mut self,
+        agent_id: AccountId,
+        violation_type: ViolationType,
+        description: String,
+        evidence: Vec<Evidence>,
+    ) -> u64 {
+        self.assert_not_paused(PausableFeature::ViolationReporting);
+        assert_eq!(env::attached_deposit(), WHISTLEBLOWER_REPORT_BOND, "Attach exactly the report bond");
+
+        let reporter = env::predecessor_account_id();
+        assert_ne!(reporter, agent_id, "Agents cannot report themselves");
+        assert!(self.agent_reputations.get(&agent_id).is_some(), "Agent not registered");
+        assert!(description.len() <= MAX_REPORT_TEXT_LENGTH, "Report description too long");
+        violations::assert_valid_evidence(&evidence);
+        // Only types with a penalty in the policy can be confirmed
+        self.internal_violation_penalty(&violation_type);
+
+        let initial_storage = env::storage_usage();
+        let report_id = self.next_report_id;
+        self.next_report_id += 1;
+        self.whistleblower_reports.insert(
+            &report_id,
+            &WhistleblowerReport {
+                reporter: reporter.clone(),
+                agent_id: agent_id.clone(),
+                violation_type: violation_type.clone(),
+                description,
+                evidence,
+                bond: U128(WHISTLEBLOWER_REPORT_BOND),
+                status: ReportStatus::Pending,
+                submitted_at: env::block_timestamp(),
+                resolved_at: None,
+                ruling_note: None,
+            },
+        );
+        self.pending_reports.insert(&report_id);
+        self.internal_charge_storage(&reporter, initial_storage);
+
+        emit_event(
+            "violation_reported",
+            json!({
+                "report_id": report_id,
+                "reporter": reporter,
+                "agent_id": agent_id,
+                "violation_type": violation_type,
+            }),
+        );
+
+        report_id
+    }
+
+    // Triage a pending report (the governance DAO if configured, otherwise the owner).
+    // Confirming records the violation with the reporter as its reporter, slashing the agent
+    // and paying the reporter a share of the slashed stake; confirmed and dismissed reports
+    // return the bond, frivolous ones forfeit it to the treasury.
+    pub fn resolve_report(&mut self, report_id: u64, ruling: ReportStatus, ruling_note: Option<String>) -> Promise {
+        self.assert_violation_authority();
+        assert!(ruling != ReportStatus::Pending, "Ruling must resolve the report");
+        if let Some(note) = &ruling_note {
+            assert!(note.len() <= MAX_REPORT_TEXT_LENGTH, "Ruling note too long");
+        }
+
+        let mut report = self.whistleblower_reports.get(&report_id).expect("Report not found");
+        assert!(report.status == ReportStatus::Pending, "Report already resolved");
+
+        let initial_storage = env::storage_usage();
+        if ruling == ReportStatus::Confirmed {
+            self.internal_record_violation(
+                report.agent_id.clone(),
+                report.violation_type.clone(),
+                report.reporter.clone(),
+                report.description.clone(),
+                report.evidence.clone(),
+                true,
+            );
+        }
+
+        report.status = ruling;
+        report.resolved_at = Some(env::block_timestamp());
+        report.ruling_note = ruling_note;
+        self.whistleblower_reports.insert(&report_id, &report);
+        self.pending_reports.remove(&report_id);
+
+        // The resolving member pays for the storage of the violation record
+        self.internal_charge_storage(&env::predecessor_account_id(), initial_storage);
+
+        if ruling == ReportStatus::Confirmed {
+            let mut agent_rep = self.agent_reputations.get(&report.agent_id).expect("Agent not registered");
+            self.internal_apply_retention(&report.agent_id, &mut agent_rep);
+        }
+
+        emit_event(
+            "violation_report_resolved",
+            json!({
+                "report_id": report_id,
+                "agent_id": report.agent_id,
+                "status": ruling,
+            }),
+        );
+
+        let bond_recipient = if ruling == ReportStatus::Frivolous { self.treasury_id.clone() } else { report.reporter };
+        Promise::new(bond_recipient).transfer(report.bond.0)
+    }
+
+    // Get a whistleblower report by ID
+    pub fn get_report(&self, report_id: u64) -> Option<WhistleblowerReport> {
+        self.whistleblower_reports.get(&report_id)
+    }
+
+    // Get a page of the IDs of reports awaiting triage
+    pub fn get_pending_reports(&self, from_index: u64, limit: u32) -> Vec<u64> {
+        self.pending_reports
+            .iter()
+            .skip(from_index as usize)
+            .take(std::cmp::min(limit, MAX_REPORTS_PAGE) as usize)
+            .collect()
+    }
+
+    // Get the report bond and the share of a confirmed report's slash paid to its reporter
+    pub fn get_report_terms(&self) -> ReportTermsView {
+        ReportTermsView {
+            bond: U128(WHISTLEBLOWER_REPORT_BOND),
+            reward_bps: WHISTLEBLOWER_REWARD_BPS,
+        }
+    }
+
+    // Pay a whistleblower their share of a slash. If the transfer fails, the reward is routed
+    // like the rest of the slash.
+    pub(crate) fn internal_pay_whistleblower_reward(&mut self, agent_id: &AccountId, reporter: &AccountId, amount: Balance) {
+        Promise::new(self.token_contract_id.clone())
+            .function_call(
+                "ft_transfer".to_string(),
+                json!({
+                    "receiver_id": reporter,
+                    "amount": U128(amount),
+                    "memo": format!("Whistleblower reward for reporting {}", agent_id),
+                }).to_string().into_bytes(),
+                1, // 1 yoctoNEAR
+                GAS_FOR_REWARD_TRANSFER,
+            )
+            .then(
+                Promise::new(env::current_account_id())
+                    .function_call(
+                        "on_whistleblower_reward_complete".to_string(),
+                        json!({
+                            "agent_id": agent_id,
+                            "reporter": reporter,
+                            "amount": U128(amount),
+                        }).to_string().into_bytes(),
+                        0,
+                        GAS_FOR_REWARD_CALLBACK,
+                    )
+            );
+    }
+
+    // Callback after paying a whistleblower's reward
+    #[private]
+    pub fn on_whistleblower_reward_complete(&mut self, agent_id: AccountId, reporter: AccountId, amount: U128) {
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => {
+                emit_event(
+                    "whistleblower_rewarded",
+                    json!({
+                        "agent_id": agent_id,
+                        "reporter": reporter,
+                        "amount": amount,
+                    }),
+                );
+            },
+            _ => {
+                // The tokens never left; they go where the rest of the slash went
+                self.route_slashed_tokens(&agent_id, amount.0, false);
+                emit_event(
+                    "whistleblower_reward_rolled_back",
+                    json!({
+                        "agent_id": agent_id,
+                        "reporter": reporter,
+                        "amount": amount,
+                    }),
+                );
+            }
+        }
+    }
+}
+
+// View struct for the terms of whistleblower reports
+#[derive(near_sdk::serde::Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReportTermsView {
+    bond: U128,
+    reward_bps: u32,
+}